@@ -82,6 +82,7 @@
 #![feature(staged_api)]
 #![feature(unboxed_closures)]
 #![feature(question_mark)]
+#![cfg_attr(not(stage0), feature(i128_type))]
 
 #[macro_use]
 mod macros;
@@ -104,11 +105,18 @@ mod uint_macros;
 #[path = "num/i32.rs"]  pub mod i32;
 #[path = "num/i64.rs"]  pub mod i64;
 
+// FIXME(stage0): the snapshot compiler does not know about 128-bit integers
+// yet, so all of their impls are cfg'd out while bootstrapping.
+#[cfg(not(stage0))]
+#[path = "num/i128.rs"] pub mod i128;
+
 #[path = "num/usize.rs"] pub mod usize;
 #[path = "num/u8.rs"]   pub mod u8;
 #[path = "num/u16.rs"]  pub mod u16;
 #[path = "num/u32.rs"]  pub mod u32;
 #[path = "num/u64.rs"]  pub mod u64;
+#[cfg(not(stage0))]
+#[path = "num/u128.rs"] pub mod u128;
 
 #[path = "num/f32.rs"]   pub mod f32;
 #[path = "num/f64.rs"]   pub mod f64;
@@ -0,0 +1,148 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fallbacks for the 128-bit integer routines LLVM emits calls to.
+//!
+//! compiler-rt only builds these on 64-bit targets, so on everything else
+//! they are provided here instead. None of the code below may itself use
+//! 128-bit multiplication, division or remainder, as that would be lowered
+//! right back into a call to one of these functions.
+
+const MASK_32: u64 = 0xffff_ffff;
+
+#[inline]
+fn hi(a: u128) -> u64 {
+    (a >> 64) as u64
+}
+
+#[inline]
+fn lo(a: u128) -> u64 {
+    a as u64
+}
+
+#[inline]
+fn from_parts(hi: u64, lo: u64) -> u128 {
+    (hi as u128) << 64 | lo as u128
+}
+
+/// Full 64x64 -> 128 bit multiplication, returned as `(high, low)`.
+fn mul_64(a: u64, b: u64) -> (u64, u64) {
+    let (ah, al) = (a >> 32, a & MASK_32);
+    let (bh, bl) = (b >> 32, b & MASK_32);
+
+    let ll = al * bl;
+    let lh = al * bh;
+    let hl = ah * bl;
+    let hh = ah * bh;
+
+    let mid = (ll >> 32) + (lh & MASK_32) + (hl & MASK_32);
+    let low = (mid << 32) | (ll & MASK_32);
+    let high = hh + (lh >> 32) + (hl >> 32) + (mid >> 32);
+    (high, low)
+}
+
+fn mul_128(a: u128, b: u128) -> u128 {
+    let (high, low) = mul_64(lo(a), lo(b));
+    let high = high.wrapping_add(hi(a).wrapping_mul(lo(b)))
+                   .wrapping_add(lo(a).wrapping_mul(hi(b)));
+    from_parts(high, low)
+}
+
+/// Returns `(n / d, n % d)` using plain shift-and-subtract long division.
+fn udivmod_128(n: u128, d: u128) -> (u128, u128) {
+    let (nh, nl) = (hi(n), lo(n));
+    let (dh, dl) = (hi(d), lo(d));
+    if nh == 0 && dh == 0 {
+        return ((nl / dl) as u128, (nl % dl) as u128);
+    }
+
+    let (mut qh, mut ql) = (0u64, 0u64);
+    let (mut rh, mut rl) = (0u64, 0u64);
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        let bit = if i >= 64 { (nh >> (i - 64)) & 1 } else { (nl >> i) & 1 };
+
+        // The remainder is always below `d`, so after shifting it left the
+        // bit falling off the top means it is certainly at least `d` now.
+        let carry = rh >> 63;
+        rh = rh << 1 | rl >> 63;
+        rl = rl << 1 | bit;
+
+        if carry != 0 || rh > dh || (rh == dh && rl >= dl) {
+            let (new_rl, borrow) = rl.overflowing_sub(dl);
+            rh = rh.wrapping_sub(dh).wrapping_sub(borrow as u64);
+            rl = new_rl;
+            if i >= 64 {
+                qh |= 1 << (i - 64);
+            } else {
+                ql |= 1 << i;
+            }
+        }
+    }
+    (from_parts(qh, ql), from_parts(rh, rl))
+}
+
+fn sdivmod_128(a: i128, b: i128) -> (i128, i128) {
+    let ua = if a < 0 { (a as u128).wrapping_neg() } else { a as u128 };
+    let ub = if b < 0 { (b as u128).wrapping_neg() } else { b as u128 };
+    let (q, r) = udivmod_128(ua, ub);
+    let q = if (a < 0) != (b < 0) { q.wrapping_neg() } else { q };
+    let r = if a < 0 { r.wrapping_neg() } else { r };
+    (q as i128, r as i128)
+}
+
+#[no_mangle]
+pub extern "C" fn __udivti3(a: u128, b: u128) -> u128 {
+    udivmod_128(a, b).0
+}
+
+#[no_mangle]
+pub extern "C" fn __umodti3(a: u128, b: u128) -> u128 {
+    udivmod_128(a, b).1
+}
+
+#[no_mangle]
+pub extern "C" fn __divti3(a: i128, b: i128) -> i128 {
+    sdivmod_128(a, b).0
+}
+
+#[no_mangle]
+pub extern "C" fn __modti3(a: i128, b: i128) -> i128 {
+    sdivmod_128(a, b).1
+}
+
+#[no_mangle]
+pub extern "C" fn __multi3(a: i128, b: i128) -> i128 {
+    mul_128(a as u128, b as u128) as i128
+}
+
+#[no_mangle]
+pub extern "C" fn __muloti4(a: i128, b: i128, overflow: &mut i32) -> i128 {
+    let negative = (a < 0) != (b < 0);
+    let ua = if a < 0 { (a as u128).wrapping_neg() } else { a as u128 };
+    let ub = if b < 0 { (b as u128).wrapping_neg() } else { b as u128 };
+
+    // Work out the magnitude of the full product, noting whether it needs
+    // more than 128 bits.
+    let (small, big) = if hi(ua) == 0 { (lo(ua), ub) } else { (lo(ub), ua) };
+    let mut overflowed = hi(ua) != 0 && hi(ub) != 0;
+    let (p1h, p1l) = mul_64(small, lo(big));
+    let (p2h, p2l) = mul_64(small, hi(big));
+    let (high, carry) = p1h.overflowing_add(p2l);
+    overflowed |= p2h != 0 || carry;
+
+    let magnitude = from_parts(high, p1l);
+    let limit = 1u128 << 127;
+    overflowed |= if negative { magnitude > limit } else { magnitude >= limit };
+
+    *overflow = overflowed as i32;
+    mul_128(a as u128, b as u128) as i128
+}
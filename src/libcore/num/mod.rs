@@ -68,6 +68,11 @@ impl<T: fmt::Display> fmt::Display for Wrapping<T> {
 
 mod wrapping;
 
+// compiler-rt only provides the 128-bit division and overflow routines on
+// 64-bit targets.
+#[cfg(all(not(stage0), not(target_pointer_width = "64")))]
+mod compiler_rt;
+
 // All these modules are technically private and only exposed for libcoretest:
 pub mod flt2dec;
 pub mod dec2flt;
@@ -117,6 +122,8 @@ macro_rules! zero_one_impl {
     )*)
 }
 zero_one_impl! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+zero_one_impl! { u128 i128 }
 
 macro_rules! zero_one_impl_float {
     ($($t:ty)*) => ($(
@@ -1176,6 +1183,15 @@ impl i64 {
         intrinsics::mul_with_overflow }
 }
 
+#[cfg(not(stage0))]
+#[lang = "i128"]
+impl i128 {
+    int_impl! { i128, u128, 128,
+        intrinsics::add_with_overflow,
+        intrinsics::sub_with_overflow,
+        intrinsics::mul_with_overflow }
+}
+
 #[cfg(target_pointer_width = "32")]
 #[lang = "isize"]
 impl isize {
@@ -2188,6 +2204,19 @@ impl u64 {
         intrinsics::mul_with_overflow }
 }
 
+#[cfg(not(stage0))]
+#[lang = "u128"]
+impl u128 {
+    uint_impl! { u128, 128,
+        intrinsics::ctpop,
+        intrinsics::ctlz,
+        intrinsics::cttz,
+        intrinsics::bswap,
+        intrinsics::add_with_overflow,
+        intrinsics::sub_with_overflow,
+        intrinsics::mul_with_overflow }
+}
+
 #[cfg(target_pointer_width = "32")]
 #[lang = "usize"]
 impl usize {
@@ -2351,6 +2380,8 @@ macro_rules! from_str_radix_int_impl {
     )*}
 }
 from_str_radix_int_impl! { isize i8 i16 i32 i64 usize u8 u16 u32 u64 }
+#[cfg(not(stage0))]
+from_str_radix_int_impl! { i128 u128 }
 
 /// The error type returned when a checked integral type conversion fails.
 #[unstable(feature = "try_from", issue = "33417")]
@@ -2470,6 +2501,8 @@ macro_rules! doit {
     })*)
 }
 doit! { i8 i16 i32 i64 isize u8 u16 u32 u64 usize }
+#[cfg(not(stage0))]
+doit! { i128 u128 }
 
 fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32)
                                          -> Result<T, ParseIntError> {
@@ -2606,6 +2639,10 @@ impl_from! { u8, usize }
 impl_from! { u16, u32 }
 impl_from! { u16, u64 }
 impl_from! { u32, u64 }
+#[cfg(not(stage0))] impl_from! { u8, u128 }
+#[cfg(not(stage0))] impl_from! { u16, u128 }
+#[cfg(not(stage0))] impl_from! { u32, u128 }
+#[cfg(not(stage0))] impl_from! { u64, u128 }
 
 // Signed -> Signed
 impl_from! { i8, i16 }
@@ -2615,6 +2652,10 @@ impl_from! { i8, isize }
 impl_from! { i16, i32 }
 impl_from! { i16, i64 }
 impl_from! { i32, i64 }
+#[cfg(not(stage0))] impl_from! { i8, i128 }
+#[cfg(not(stage0))] impl_from! { i16, i128 }
+#[cfg(not(stage0))] impl_from! { i32, i128 }
+#[cfg(not(stage0))] impl_from! { i64, i128 }
 
 // Unsigned -> Signed
 impl_from! { u8, i16 }
@@ -2623,6 +2664,10 @@ impl_from! { u8, i64 }
 impl_from! { u16, i32 }
 impl_from! { u16, i64 }
 impl_from! { u32, i64 }
+#[cfg(not(stage0))] impl_from! { u8, i128 }
+#[cfg(not(stage0))] impl_from! { u16, i128 }
+#[cfg(not(stage0))] impl_from! { u32, i128 }
+#[cfg(not(stage0))] impl_from! { u64, i128 }
 
 // Note: integers can only be represented with full precision in a float if
 // they fit in the significand, which is 24 bits in f32 and 53 bits in f64.
@@ -10,12 +10,14 @@
 
 #![doc(hidden)]
 
-macro_rules! uint_module { ($T:ident, $bits:expr) => (
+macro_rules! uint_module {
+    ($T:ident, $bits:expr) => (uint_module!($T, $bits, #[stable(feature = "rust1", since = "1.0.0")]););
+    ($T:ident, $bits:expr, #[$attr:meta]) => (
 
-#[stable(feature = "rust1", since = "1.0.0")]
+#[$attr]
 #[allow(missing_docs)]
 pub const MIN: $T = $T::min_value();
-#[stable(feature = "rust1", since = "1.0.0")]
+#[$attr]
 #[allow(missing_docs)]
 pub const MAX: $T = $T::max_value();
 
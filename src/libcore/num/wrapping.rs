@@ -118,6 +118,8 @@ macro_rules! sh_impl_all {
 }
 
 sh_impl_all! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+sh_impl_all! { u128 i128 }
 
 // FIXME(30524): impl Op<T> for Wrapping<T>, impl OpAssign<T> for Wrapping<T>
 macro_rules! wrapping_impl {
@@ -288,6 +290,8 @@ macro_rules! wrapping_impl {
 }
 
 wrapping_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+wrapping_impl! { u128 i128 }
 
 mod shift_max {
     #![allow(non_upper_case_globals)]
@@ -308,11 +312,15 @@ mod shift_max {
     pub const i16: u32 = (1 << 4) - 1;
     pub const i32: u32 = (1 << 5) - 1;
     pub const i64: u32 = (1 << 6) - 1;
+    #[cfg(not(stage0))]
+    pub const i128: u32 = (1 << 7) - 1;
     pub use self::platform::isize;
 
     pub const  u8: u32 = i8;
     pub const u16: u32 = i16;
     pub const u32: u32 = i32;
     pub const u64: u32 = i64;
+    #[cfg(not(stage0))]
+    pub const u128: u32 = i128;
     pub use self::platform::usize;
 }
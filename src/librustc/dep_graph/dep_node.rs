@@ -44,6 +44,7 @@ pub enum DepNode<D: Clone + Debug> {
     RegionResolveCrate,
     CheckLoops,
     PluginRegistrar,
+    DeriveRegistrar,
     StabilityIndex,
     CollectItem(D),
     Coherence,
@@ -137,6 +138,7 @@ impl<D: Clone + Debug> DepNode<D> {
             TransCrateItem,
             TypeckItemType,
             TypeckItemBody,
+            MirMapConstruction,
             ImplOrTraitItems,
             ItemSignature,
             FieldTy,
@@ -162,6 +164,7 @@ impl<D: Clone + Debug> DepNode<D> {
             RegionResolveCrate => Some(RegionResolveCrate),
             CheckLoops => Some(CheckLoops),
             PluginRegistrar => Some(PluginRegistrar),
+            DeriveRegistrar => Some(DeriveRegistrar),
             StabilityIndex => Some(StabilityIndex),
             Coherence => Some(Coherence),
             EffectCheck => Some(EffectCheck),
@@ -9,6 +9,8 @@
 // except according to those terms.
 
 use hir::def_id::DefId;
+use rustc_data_structures::fnv::FnvHashSet;
+use std::cell::RefCell;
 use std::rc::Rc;
 
 use super::dep_node::DepNode;
@@ -18,13 +20,18 @@ use super::thread::{DepGraphThreadData, DepMessage};
 
 #[derive(Clone)]
 pub struct DepGraph {
-    data: Rc<DepGraphThreadData>
+    data: Rc<DepGraphThreadData>,
+
+    /// Nodes whose result was carried over from the previous
+    /// compilation session. See `mark_green`.
+    green_nodes: Rc<RefCell<FnvHashSet<DepNode<DefId>>>>,
 }
 
 impl DepGraph {
     pub fn new(enabled: bool) -> DepGraph {
         DepGraph {
-            data: Rc::new(DepGraphThreadData::new(enabled))
+            data: Rc::new(DepGraphThreadData::new(enabled)),
+            green_nodes: Rc::new(RefCell::new(FnvHashSet())),
         }
     }
 
@@ -68,4 +75,19 @@ impl DepGraph {
     pub fn write(&self, v: DepNode<DefId>) {
         self.data.enqueue(DepMessage::Write(v));
     }
+
+    /// Records that all the inputs of `node` are unchanged since the
+    /// previous compilation session and that whatever `node` computed
+    /// then has been restored, so the work need not be done again.
+    /// This is done by `rustc_incremental` when the dep-graph is
+    /// loaded; the node and its edges are carried over into the new
+    /// graph at the same time.
+    pub fn mark_green(&self, node: DepNode<DefId>) {
+        self.green_nodes.borrow_mut().insert(node);
+    }
+
+    /// True if the work for `node` can be skipped; see `mark_green`.
+    pub fn is_green(&self, node: &DepNode<DefId>) -> bool {
+        self.green_nodes.borrow().contains(node)
+    }
 }
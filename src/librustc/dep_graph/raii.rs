@@ -11,17 +11,21 @@
 use hir::def_id::DefId;
 use super::DepNode;
 use super::thread::{DepGraphThreadData, DepMessage};
+use util::profiling;
 
 pub struct DepTask<'graph> {
     data: &'graph DepGraphThreadData,
     key: DepNode<DefId>,
+    // Timing of the task for `-Z self-profile`.
+    _activity: Option<profiling::Activity>,
 }
 
 impl<'graph> DepTask<'graph> {
     pub fn new(data: &'graph DepGraphThreadData, key: DepNode<DefId>)
                -> DepTask<'graph> {
+        let activity = profiling::start_task(&key);
         data.enqueue(DepMessage::PushTask(key));
-        DepTask { data: data, key: key }
+        DepTask { data: data, key: key, _activity: activity }
     }
 }
 
@@ -19,6 +19,7 @@ use syntax::visit::Visitor;
 enum Target {
     Fn,
     Struct,
+    Union,
     Enum,
     Other,
 }
@@ -28,6 +29,7 @@ impl Target {
         match item.node {
             ast::ItemKind::Fn(..) => Target::Fn,
             ast::ItemKind::Struct(..) => Target::Struct,
+            ast::ItemKind::Union(..) => Target::Union,
             ast::ItemKind::Enum(..) => Target::Enum,
             _ => Target::Other,
         }
@@ -56,13 +58,21 @@ impl<'a> CheckAttrVisitor<'a> {
             let word: &str = &word.name();
             let message = match word {
                 "C" => {
-                    if target != Target::Struct && target != Target::Enum {
-                            "attribute should be applied to struct or enum"
+                    if target != Target::Struct &&
+                            target != Target::Union &&
+                            target != Target::Enum {
+                            "attribute should be applied to struct, enum or union"
+                    } else {
+                        continue
+                    }
+                }
+                "packed" => {
+                    if target != Target::Struct && target != Target::Union {
+                        "attribute should be applied to struct or union"
                     } else {
                         continue
                     }
                 }
-                "packed" |
                 "simd" => {
                     if target != Target::Struct {
                         "attribute should be applied to struct"
@@ -72,7 +82,7 @@ impl<'a> CheckAttrVisitor<'a> {
                 }
                 "i8" | "u8" | "i16" | "u16" |
                 "i32" | "u32" | "i64" | "u64" |
-                "isize" | "usize" => {
+                "i128" | "u128" | "isize" | "usize" => {
                     if target != Target::Enum {
                             "attribute should be applied to enum"
                     } else {
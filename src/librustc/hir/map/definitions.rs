@@ -177,6 +177,8 @@ pub enum DefPathData {
     Initializer,
     /// Pattern binding
     Binding(ast::Name),
+    /// An `impl Trait` type node.
+    ImplTrait,
 }
 
 impl Definitions {
@@ -326,6 +328,10 @@ impl DefPathData {
             Initializer => {
                 InternedString::new("{{initializer}}")
             }
+
+            ImplTrait => {
+                InternedString::new("{{impl-Trait}}")
+            }
         }
     }
 
@@ -242,7 +242,7 @@ impl<'a, 'gcx, 'tcx> CombineFields<'a, 'gcx, 'tcx> {
                     self.infcx.type_variables
                         .borrow_mut()
                         .instantiate_and_push(
-                            b_vid, generalized_ty, &mut stack);
+                            b_vid, generalized_ty, self.trace.origin.span(), &mut stack);
                     generalized_ty
                 }
             };
@@ -70,6 +70,7 @@ use super::region_inference::GenericKind;
 use super::region_inference::ProcessedErrors;
 use super::region_inference::ProcessedErrorOrigin;
 use super::region_inference::SameRegions;
+use super::type_variable::TraceStep;
 
 use std::collections::HashSet;
 
@@ -91,7 +92,7 @@ use std::char::from_u32;
 use std::fmt;
 use syntax::ast;
 use syntax::errors::{DiagnosticBuilder, check_old_skool};
-use syntax::codemap::{self, Pos, Span};
+use syntax::codemap::{self, Pos, Span, DUMMY_SP};
 use syntax::parse::token;
 use syntax::ptr::P;
 
@@ -106,6 +107,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
                 hir::ItemImpl(..) => "impl",
                 hir::ItemStruct(..) => "struct",
                 hir::ItemEnum(..) => "enum",
+                hir::ItemUnion(..) => "union",
                 hir::ItemTrait(..) => "trait",
                 hir::ItemFn(..) => "function body",
                 _ => "item"
@@ -482,13 +484,31 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                                        trace.origin);
 
         if !is_simple_error || check_old_skool() {
-            err.note_expected_found(&"type", &expected, &found);
+            match trace.values {
+                infer::Types(ref exp_found) => {
+                    // Large types are easier to compare with what they
+                    // have in common left out.
+                    let (expected_styled, found_styled) =
+                        self.tcx.highlight_type_differences(
+                            self.resolve_type_vars_if_possible(&exp_found.expected),
+                            self.resolve_type_vars_if_possible(&exp_found.found));
+                    err.note_expected_found_styled(&"type", &expected, &found,
+                                                   expected_styled, found_styled);
+                }
+                infer::TraitRefs(..) | infer::PolyTraitRefs(..) => {
+                    err.note_expected_found(&"type", &expected, &found);
+                }
+            }
         }
 
         err.span_label(trace.origin.span(), &terr);
 
         self.check_and_note_conflicting_crates(&mut err, terr, trace.origin.span());
 
+        if self.tcx.sess.opts.debugging_opts.type_trace {
+            self.note_type_trace(&mut err, &trace);
+        }
+
         match trace.origin {
             TypeOrigin::MatchExpressionArm(_, arm_span, source) => match source {
                 hir::MatchSource::IfLetDesugar{..} => {
@@ -546,6 +566,100 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
+    /// Adds notes explaining how the type variables in the mismatched
+    /// types came to have their types: where each variable was created,
+    /// the variables it was unified with, and where one of them was
+    /// instantiated, in turn explaining the variables of that type.
+    fn note_type_trace(&self, err: &mut DiagnosticBuilder, trace: &TypeTrace<'tcx>) {
+        let exp_found = match trace.values {
+            infer::Types(ref exp_found) => exp_found,
+            infer::TraitRefs(..) | infer::PolyTraitRefs(..) => return,
+        };
+
+        let mut seen = HashSet::new();
+        let mut notes = 0;
+        for &ty in &[exp_found.expected, exp_found.found] {
+            let mut vars = type_vars_in(ty);
+            if vars.is_empty() {
+                // The type was resolved before it was compared; fall back
+                // to the most recent variable that was inferred to be it.
+                let ty = self.resolve_type_vars_if_possible(&ty);
+                if ty.is_primitive() || ty.references_error() {
+                    continue;
+                }
+                vars.extend(self.last_var_inferred_as(ty));
+            }
+            for vid in vars {
+                self.note_var_trace(err, vid, &mut seen, &mut notes);
+            }
+        }
+    }
+
+    fn last_var_inferred_as(&self, ty: Ty<'tcx>) -> Option<ty::TyVid> {
+        let num_vars = self.type_variables.borrow().num_vars();
+        (0..num_vars).rev().map(|index| ty::TyVid { index: index as u32 }).find(|&vid| {
+            let origin = self.type_variables.borrow().origin(vid);
+            origin != DUMMY_SP && self.resolve_type_vars_if_possible(&self.tcx.mk_var(vid)) == ty
+        })
+    }
+
+    fn note_var_trace(&self,
+                      err: &mut DiagnosticBuilder,
+                      vid: ty::TyVid,
+                      seen: &mut HashSet<ty::TyVid>,
+                      notes: &mut usize) {
+        // Long chains explain little more than their first few steps.
+        const MAX_TYPE_TRACE_NOTES: usize = 16;
+
+        if !seen.insert(vid) {
+            return;
+        }
+        let chain = self.type_variables.borrow().inference_chain(vid);
+        if chain.is_empty() {
+            return;
+        }
+
+        let mut instantiated_with = None;
+        {
+            // A variable is often instantiated by the code it was created
+            // for; saying so twice does not help.
+            let mut last_span = DUMMY_SP;
+            let mut note = |err: &mut DiagnosticBuilder, span: Span, msg: &str| {
+                if span != DUMMY_SP && span != last_span && *notes < MAX_TYPE_TRACE_NOTES {
+                    err.span_note(span, msg);
+                    last_span = span;
+                    *notes += 1;
+                }
+            };
+
+            let origin = self.type_variables.borrow().origin(vid);
+            let ty = self.resolve_type_vars_if_possible(&self.tcx.mk_var(vid));
+            note(err, origin, &format!("the type `{}` was inferred for this", ty));
+
+            for &(_, step) in &chain {
+                match step {
+                    TraceStep::Related(_, span) => {
+                        note(err, span, "...because it was unified with another \
+                                         inferred type here");
+                    }
+                    TraceStep::Instantiated(ty, span) => {
+                        let resolved = self.resolve_type_vars_if_possible(&ty);
+                        note(err, span, &format!("...because it was required to be `{}` here",
+                                                 resolved));
+                        instantiated_with = Some(ty);
+                    }
+                }
+            }
+        }
+
+        // The type may itself contain variables whose types conflict.
+        if let Some(ty) = instantiated_with {
+            for vid in type_vars_in(ty) {
+                self.note_var_trace(err, vid, seen, notes);
+            }
+        }
+    }
+
     pub fn report_and_explain_type_error(&self,
                                          trace: TypeTrace<'tcx>,
                                          terr: &TypeError<'tcx>)
@@ -1293,7 +1407,6 @@ impl<'a, 'gcx, 'tcx> Rebuilder<'a, 'gcx, 'tcx> {
                 self.rebuild_arg_ty_or_output(&ret_ty, lifetime, anon_nums, region_names)
             ),
             hir::DefaultReturn(span) => hir::DefaultReturn(span),
-            hir::NoReturn(span) => hir::NoReturn(span)
         }
     }
 
@@ -1788,6 +1901,13 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
     }
 }
 
+fn type_vars_in<'tcx>(ty: Ty<'tcx>) -> Vec<ty::TyVid> {
+    ty.walk().filter_map(|t| match t.sty {
+        ty::TyInfer(ty::TyVar(vid)) => Some(vid),
+        _ => None,
+    }).collect()
+}
+
 pub trait Resolvable<'tcx> {
     fn resolve<'a, 'gcx>(&self, infcx: &InferCtxt<'a, 'gcx, 'tcx>) -> Self;
 }
@@ -17,6 +17,8 @@ use ty::{self, Ty, TyCtxt};
 use ty::relate::{Relate, RelateResult, TypeRelation};
 use traits::PredicateObligations;
 
+use syntax::codemap::Span;
+
 /// "Greatest lower bound" (common subtype)
 pub struct Glb<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     fields: CombineFields<'a, 'gcx, 'tcx>
@@ -80,6 +82,10 @@ impl<'a, 'gcx, 'tcx> LatticeDir<'a, 'gcx, 'tcx> for Glb<'a, 'gcx, 'tcx> {
         self.fields.infcx
     }
 
+    fn span(&self) -> Span {
+        self.fields.trace.origin.span()
+    }
+
     fn relate_bound(&self, v: Ty<'tcx>, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, ()> {
         let mut sub = self.fields.sub();
         sub.relate(&v, &a)?;
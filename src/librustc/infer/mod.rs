@@ -470,7 +470,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'gcx> {
         InferCtxt {
             tcx: self,
             tables: InferTables::Global(&self.tables),
-            type_variables: RefCell::new(type_variable::TypeVariableTable::new()),
+            type_variables: RefCell::new(type_variable::TypeVariableTable::new(false)),
             int_unification_table: RefCell::new(UnificationTable::new()),
             float_unification_table: RefCell::new(UnificationTable::new()),
             region_vars: RegionVarBindings::new(self),
@@ -507,10 +507,11 @@ impl<'a, 'gcx, 'tcx> InferCtxtBuilder<'a, 'gcx, 'tcx> {
         let param_env = param_env.take().unwrap_or_else(|| {
             global_tcx.empty_parameter_environment()
         });
+        let type_trace = global_tcx.sess.opts.debugging_opts.type_trace;
         global_tcx.enter_local(arenas, |tcx| f(InferCtxt {
             tcx: tcx,
             tables: tables,
-            type_variables: RefCell::new(type_variable::TypeVariableTable::new()),
+            type_variables: RefCell::new(type_variable::TypeVariableTable::new(type_trace)),
             int_unification_table: RefCell::new(UnificationTable::new()),
             float_unification_table: RefCell::new(UnificationTable::new()),
             region_vars: RegionVarBindings::new(tcx),
@@ -575,7 +576,6 @@ impl_trans_normalize!('gcx,
     Ty<'gcx>,
     &'gcx Substs<'gcx>,
     ty::FnSig<'gcx>,
-    ty::FnOutput<'gcx>,
     &'gcx ty::BareFnTy<'gcx>,
     ty::ClosureSubsts<'gcx>,
     ty::PolyTraitRef<'gcx>
@@ -1094,31 +1094,34 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         })
     }
 
-    pub fn next_ty_var_id(&self, diverging: bool) -> TyVid {
+    /// Creates a type variable for the type of the code at `origin`, which
+    /// is where `-Z type-trace` says the type was inferred.
+    pub fn next_ty_var_id(&self, diverging: bool, origin: Span) -> TyVid {
         self.type_variables
             .borrow_mut()
-            .new_var(diverging, None)
+            .new_var(diverging, origin, None)
     }
 
-    pub fn next_ty_var(&self) -> Ty<'tcx> {
-        self.tcx.mk_var(self.next_ty_var_id(false))
+    pub fn next_ty_var(&self, origin: Span) -> Ty<'tcx> {
+        self.tcx.mk_var(self.next_ty_var_id(false, origin))
     }
 
     pub fn next_ty_var_with_default(&self,
+                                    origin: Span,
                                     default: Option<type_variable::Default<'tcx>>) -> Ty<'tcx> {
         let ty_var_id = self.type_variables
                             .borrow_mut()
-                            .new_var(false, default);
+                            .new_var(false, origin, default);
 
         self.tcx.mk_var(ty_var_id)
     }
 
-    pub fn next_diverging_ty_var(&self) -> Ty<'tcx> {
-        self.tcx.mk_var(self.next_ty_var_id(true))
+    pub fn next_diverging_ty_var(&self, origin: Span) -> Ty<'tcx> {
+        self.tcx.mk_var(self.next_ty_var_id(true, origin))
     }
 
-    pub fn next_ty_vars(&self, n: usize) -> Vec<Ty<'tcx>> {
-        (0..n).map(|_i| self.next_ty_var()).collect()
+    pub fn next_ty_vars(&self, n: usize, origin: Span) -> Vec<Ty<'tcx>> {
+        (0..n).map(|_i| self.next_ty_var(origin)).collect()
     }
 
     pub fn next_int_var_id(&self) -> IntVid {
@@ -1163,7 +1166,7 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                 }
             });
 
-            let ty_var = self.next_ty_var_with_default(default);
+            let ty_var = self.next_ty_var_with_default(span, default);
             substs.types.push(space, ty_var);
         }
     }
@@ -1525,6 +1528,17 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                                    expected: Ty<'tcx>,
                                    actual: Ty<'tcx>,
                                    err: TypeError<'tcx>) {
+        self.struct_mismatched_types(origin, expected, actual, err).emit();
+    }
+
+    /// Like `report_mismatched_types`, but leaves emitting the error to
+    /// the caller, which can add to it first.
+    pub fn struct_mismatched_types(&self,
+                                   origin: TypeOrigin,
+                                   expected: Ty<'tcx>,
+                                   actual: Ty<'tcx>,
+                                   err: TypeError<'tcx>)
+                                   -> DiagnosticBuilder<'tcx> {
         let trace = TypeTrace {
             origin: origin,
             values: Types(ExpectedFound {
@@ -1532,7 +1546,7 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                 found: actual
             })
         };
-        self.report_and_explain_type_error(trace, &err).emit();
+        self.report_and_explain_type_error(trace, &err)
     }
 
     pub fn report_conflicting_default_types(&self,
@@ -75,7 +75,7 @@ impl<'a, 'gcx, 'tcx> TypeRelation<'a, 'gcx, 'tcx> for Sub<'a, 'gcx, 'tcx> {
             (&ty::TyInfer(TyVar(a_id)), &ty::TyInfer(TyVar(b_id))) => {
                 infcx.type_variables
                     .borrow_mut()
-                    .relate_vars(a_id, SubtypeOf, b_id);
+                    .relate_vars(a_id, SubtypeOf, b_id, self.fields.trace.origin.span());
                 Ok(a)
             }
             (&ty::TyInfer(TyVar(a_id)), _) => {
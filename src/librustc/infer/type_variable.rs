@@ -16,20 +16,29 @@ use ty::{self, Ty};
 use syntax::codemap::Span;
 
 use std::cmp::min;
+use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::mem;
 use std::u32;
 use rustc_data_structures::snapshot_vec as sv;
 use rustc_data_structures::unify as ut;
+use util::nodemap::FnvHashMap;
 
 pub struct TypeVariableTable<'tcx> {
     values: sv::SnapshotVec<Delegate<'tcx>>,
     eq_relations: ut::UnificationTable<ty::TyVid>,
+    /// Whether the steps of inference are recorded, which only
+    /// `-Z type-trace` uses.
+    trace: bool,
 }
 
 struct TypeVariableData<'tcx> {
     value: TypeVariableValue<'tcx>,
-    diverging: bool
+    diverging: bool,
+    /// The expression or pattern whose type the variable stands for.
+    origin: Span,
+    /// How the variable came to have its type, oldest step first.
+    steps: Vec<TraceStep<'tcx>>,
 }
 
 enum TypeVariableValue<'tcx> {
@@ -51,6 +60,18 @@ pub struct Default<'tcx> {
     pub def_id: DefId
 }
 
+/// A step in the inference of a type variable, kept to explain type
+/// mismatches with `-Z type-trace`.
+#[derive(Copy, Clone, Debug)]
+pub enum TraceStep<'tcx> {
+    /// The variable was related to another variable while checking the
+    /// code at the span.
+    Related(ty::TyVid, Span),
+    /// The variable was instantiated with a type while checking the code
+    /// at the span.
+    Instantiated(Ty<'tcx>, Span),
+}
+
 pub struct Snapshot {
     snapshot: sv::Snapshot,
     eq_snapshot: ut::Snapshot<ty::TyVid>,
@@ -61,6 +82,8 @@ enum UndoEntry<'tcx> {
     SpecifyVar(ty::TyVid, Vec<Relation>, Option<Default<'tcx>>),
     Relate(ty::TyVid, ty::TyVid),
     RelateRange(ty::TyVid, usize),
+    // A trace step was recorded for the var.
+    AddStep(ty::TyVid),
 }
 
 struct Delegate<'tcx>(PhantomData<&'tcx ()>);
@@ -84,10 +107,11 @@ impl RelationDir {
 }
 
 impl<'tcx> TypeVariableTable<'tcx> {
-    pub fn new() -> TypeVariableTable<'tcx> {
+    pub fn new(trace: bool) -> TypeVariableTable<'tcx> {
         TypeVariableTable {
             values: sv::SnapshotVec::new(),
             eq_relations: ut::UnificationTable::new(),
+            trace: trace,
         }
     }
 
@@ -106,13 +130,32 @@ impl<'tcx> TypeVariableTable<'tcx> {
         self.values.get(vid.index as usize).diverging
     }
 
-    /// Records that `a <: b`, `a :> b`, or `a == b`, depending on `dir`.
+    pub fn origin(&self, vid: ty::TyVid) -> Span {
+        self.values.get(vid.index as usize).origin
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.values.len()
+    }
+
+    fn add_step(&mut self, vid: ty::TyVid, step: TraceStep<'tcx>) {
+        if !self.trace {
+            return;
+        }
+        self.values.get_mut(vid.index as usize).steps.push(step);
+        self.values.record(AddStep(vid));
+    }
+
+    /// Records that `a <: b`, `a :> b`, or `a == b`, depending on `dir`,
+    /// as required by the code at `span`.
     ///
     /// Precondition: neither `a` nor `b` are known.
-    pub fn relate_vars(&mut self, a: ty::TyVid, dir: RelationDir, b: ty::TyVid) {
+    pub fn relate_vars(&mut self, a: ty::TyVid, dir: RelationDir, b: ty::TyVid, span: Span) {
         let a = self.root_var(a);
         let b = self.root_var(b);
         if a != b {
+            self.add_step(a, TraceStep::Related(b, span));
+            self.add_step(b, TraceStep::Related(a, span));
             if dir == EqTo {
                 // a and b must be equal which we mark in the unification table
                 let root = self.eq_relations.union(a, b);
@@ -140,15 +183,16 @@ impl<'tcx> TypeVariableTable<'tcx> {
         }
     }
 
-    /// Instantiates `vid` with the type `ty` and then pushes an entry onto `stack` for each of the
-    /// relations of `vid` to other variables. The relations will have the form `(ty, dir, vid1)`
-    /// where `vid1` is some other variable id.
+    /// Instantiates `vid` with the type `ty`, as required by the code at `span`, and then pushes
+    /// an entry onto `stack` for each of the relations of `vid` to other variables. The relations
+    /// will have the form `(ty, dir, vid1)` where `vid1` is some other variable id.
     ///
     /// Precondition: `vid` must be a root in the unification table
     pub fn instantiate_and_push(
         &mut self,
         vid: ty::TyVid,
         ty: Ty<'tcx>,
+        span: Span,
         stack: &mut Vec<(Ty<'tcx>, RelationDir, ty::TyVid)>)
     {
         debug_assert!(self.root_var(vid) == vid);
@@ -168,19 +212,63 @@ impl<'tcx> TypeVariableTable<'tcx> {
         }
 
         self.values.record(SpecifyVar(vid, relations, default));
+        self.add_step(vid, TraceStep::Instantiated(ty, span));
     }
 
+    /// Creates a variable for the type of the code at `origin`.
     pub fn new_var(&mut self,
                    diverging: bool,
+                   origin: Span,
                    default: Option<Default<'tcx>>) -> ty::TyVid {
         self.eq_relations.new_key(());
         let index = self.values.push(TypeVariableData {
             value: Bounded { relations: vec![], default: default },
-            diverging: diverging
+            diverging: diverging,
+            origin: origin,
+            steps: vec![],
         });
         ty::TyVid { index: index as u32 }
     }
 
+    /// The shortest chain of steps by which `vid` came to have its type:
+    /// each variable on the way paired with the step that leads to the
+    /// next one, the last being the step that instantiated a variable.
+    /// Empty if no variable related to `vid` has been instantiated.
+    pub fn inference_chain(&self, vid: ty::TyVid) -> Vec<(ty::TyVid, TraceStep<'tcx>)> {
+        let mut reached_from = FnvHashMap();
+        let mut queue = VecDeque::new();
+        reached_from.insert(vid, None);
+        queue.push_back(vid);
+
+        while let Some(current) = queue.pop_front() {
+            let steps = &self.values.get(current.index as usize).steps;
+            let instantiated = steps.iter().find(|step| match **step {
+                TraceStep::Instantiated(..) => true,
+                TraceStep::Related(..) => false,
+            });
+            if let Some(&step) = instantiated {
+                let mut chain = vec![(current, step)];
+                let mut next = current;
+                while let Some(&Some((previous, step))) = reached_from.get(&next) {
+                    chain.push((previous, step));
+                    next = previous;
+                }
+                chain.reverse();
+                return chain;
+            }
+            for &step in steps {
+                if let TraceStep::Related(other, _) = step {
+                    if !reached_from.contains_key(&other) {
+                        reached_from.insert(other, Some((current, step)));
+                        queue.push_back(other);
+                    }
+                }
+            }
+        }
+
+        vec![]
+    }
+
     pub fn root_var(&mut self, vid: ty::TyVid) -> ty::TyVid {
         self.eq_relations.find(vid)
     }
@@ -312,6 +400,10 @@ impl<'tcx> sv::SnapshotVecDelegate for Delegate<'tcx> {
                     relations.pop();
                 }
             }
+
+            AddStep(vid) => {
+                values[vid.index as usize].steps.pop();
+            }
         }
     }
 }
@@ -97,6 +97,7 @@ pub mod middle {
     pub mod region;
     pub mod recursion_limit;
     pub mod resolve_lifetime;
+    pub mod size_limits;
     pub mod stability;
     pub mod weak_lang_items;
 }
@@ -121,6 +122,7 @@ pub mod util {
     pub mod nodemap;
     pub mod num;
     pub mod fs;
+    pub mod profiling;
 }
 
 // A private module so that macro-expanded idents like
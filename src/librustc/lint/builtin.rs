@@ -204,6 +204,18 @@ declare_lint! {
     "object-unsafe non-principal fragments in object types were erroneously allowed"
 }
 
+declare_lint! {
+    pub LARGE_STACK_FRAMES,
+    Warn,
+    "detects functions whose locals exceed the `#![stack_frame_size_limit]`"
+}
+
+declare_lint! {
+    pub LARGE_MOVE,
+    Warn,
+    "detects values moved or copied by value that exceed the `#![move_size_limit]`"
+}
+
 /// Does nothing as a lint pass, but registers some `Lint`s
 /// which are used by other parts of the compiler.
 #[derive(Copy, Clone)]
@@ -242,7 +254,9 @@ impl LintPass for HardwiredLints {
             SUPER_OR_SELF_IN_GLOBAL_PATH,
             UNSIZED_IN_TUPLE,
             OBJECT_UNSAFE_FRAGMENT,
-            HR_LIFETIME_IN_ASSOC_TYPE
+            HR_LIFETIME_IN_ASSOC_TYPE,
+            LARGE_STACK_FRAMES,
+            LARGE_MOVE
         )
     }
 }
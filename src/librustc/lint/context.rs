@@ -240,6 +240,14 @@ impl LintStore {
         self.by_name.insert(name.into(), Removed(reason.into()));
     }
 
+    /// Get the lint registered under `lint_name`, following renames.
+    pub fn lint_by_name(&self, lint_name: &str) -> Option<LintId> {
+        match self.by_name.get(lint_name) {
+            Some(&Id(lint_id)) | Some(&Renamed(_, lint_id)) => Some(lint_id),
+            Some(&Removed(_)) | None => None
+        }
+    }
+
     #[allow(unused_variables)]
     fn find_lint(&self, lint_name: &str, sess: &Session, span: Option<Span>)
                  -> Result<LintId, FindLintError>
@@ -1103,6 +1111,19 @@ impl LateLintPass for GatherNodeLevels {
             _ => { }
         }
     }
+
+    fn check_fn(&mut self, cx: &LateContext, _: hir_visit::FnKind, _: &hir::FnDecl,
+                _: &hir::Block, _: Span, id: ast::NodeId) {
+        // The sizes of the frames and moves of functions are only known
+        // once they are monomorphized in trans.
+        for &lint in &[builtin::LARGE_STACK_FRAMES, builtin::LARGE_MOVE] {
+            let lint_id = LintId::of(lint);
+            let lvlsrc = cx.lints.get_level_source(lint_id);
+            if lvlsrc.0 != Allow {
+                cx.node_levels.borrow_mut().insert((id, lint_id), lvlsrc);
+            }
+        }
+    }
 }
 
 enum CheckLintNameResult {
@@ -263,6 +263,11 @@ impl LintId {
         }
     }
 
+    /// Get the `Lint` this identifies.
+    pub fn lint(&self) -> &'static Lint {
+        self.lint
+    }
+
     /// Get the name of the lint.
     pub fn as_str(&self) -> String {
         self.lint.name_lower()
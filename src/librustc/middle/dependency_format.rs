@@ -135,7 +135,8 @@ fn calculate_type(sess: &session::Session,
         // Generating a dylib without `-C prefer-dynamic` means that we're going
         // to try to eagerly statically link all dependencies. This is normally
         // done for end-product dylibs, not intermediate products.
-        config::CrateTypeDylib if !sess.opts.cg.prefer_dynamic => {
+        config::CrateTypeDylib |
+        config::CrateTypeProcMacro if !sess.opts.cg.prefer_dynamic => {
             match attempt_static(sess) {
                 Some(v) => return v,
                 None => {}
@@ -143,7 +144,9 @@ fn calculate_type(sess: &session::Session,
         }
 
         // Everything else falls through below
-        config::CrateTypeExecutable | config::CrateTypeDylib => {},
+        config::CrateTypeExecutable |
+        config::CrateTypeDylib |
+        config::CrateTypeProcMacro => {},
     }
 
     let mut formats = FnvHashMap();
@@ -19,7 +19,7 @@ use ty::MethodCall;
 
 use syntax::ast;
 use syntax::codemap::Span;
-use hir;
+use hir::{self, PatKind};
 use hir::intravisit;
 use hir::intravisit::{FnKind, Visitor};
 
@@ -176,11 +176,37 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
                     self.require_unsafe(expr.span, "use of mutable static");
                 }
             }
+            hir::ExprField(ref base, field) => {
+                if self.tcx.expr_ty_adjusted(base).is_union() {
+                    self.require_unsafe(field.span, "access to union field");
+                }
+            }
+            hir::ExprAssign(ref lhs, ref rhs) => {
+                // Writing to a union field is safe, as the previous value
+                // is never dropped; only reads have to be in unsafe code.
+                if let hir::ExprField(ref base, _) = lhs.node {
+                    if self.tcx.expr_ty_adjusted(base).is_union() {
+                        self.visit_expr(base);
+                        self.visit_expr(rhs);
+                        return;
+                    }
+                }
+            }
             _ => {}
         }
 
         intravisit::walk_expr(self, expr);
     }
+
+    fn visit_pat(&mut self, pat: &hir::Pat) {
+        if let PatKind::Struct(..) = pat.node {
+            if self.tcx.node_id_to_type(pat.id).is_union() {
+                self.require_unsafe(pat.span, "matching on union field");
+            }
+        }
+
+        intravisit::walk_pat(self, pat);
+    }
 }
 
 pub fn check_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
@@ -161,10 +161,9 @@ impl<'a, 'gcx, 'tcx, 'v> Visitor<'v> for ExprVisitor<'a, 'gcx, 'tcx> {
                     let typ = self.infcx.tcx.node_id_to_type(expr.id);
                     match typ.sty {
                         ty::TyFnDef(_, _, ref bare_fn_ty) if bare_fn_ty.abi == RustIntrinsic => {
-                            if let ty::FnConverging(to) = bare_fn_ty.sig.0.output {
-                                let from = bare_fn_ty.sig.0.inputs[0];
-                                self.check_transmute(expr.span, from, to, expr.id);
-                            }
+                            let from = bare_fn_ty.sig.0.inputs[0];
+                            let to = bare_fn_ty.sig.0.output;
+                            self.check_transmute(expr.span, from, to, expr.id);
                         }
                         _ => {
                             span_bug!(expr.span, "transmute wasn't a bare fn?!");
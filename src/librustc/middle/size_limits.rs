@@ -0,0 +1,46 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Size limits.
+//
+// The `large_stack_frames` and `large_move` lints compare the sizes of the
+// locals of a function and of the values it moves against limits in bytes
+// set on the crate, like `#![stack_frame_size_limit="8192"]` and
+// `#![move_size_limit="1024"]`. This pass just peeks and looks for those
+// attributes.
+
+use session::Session;
+use syntax::ast;
+use syntax::attr::AttrMetaMethods;
+
+use std::cell::Cell;
+
+pub fn update_size_limits(sess: &Session, krate: &ast::Crate) {
+    update_limit(sess, krate, &sess.stack_frame_size_limit, "stack_frame_size_limit");
+    update_limit(sess, krate, &sess.move_size_limit, "move_size_limit");
+}
+
+fn update_limit(sess: &Session, krate: &ast::Crate, limit: &Cell<Option<u64>>, name: &str) {
+    for attr in &krate.attrs {
+        if !attr.check_name(name) {
+            continue;
+        }
+
+        if let Some(s) = attr.value_str() {
+            if let Some(n) = s.parse().ok() {
+                limit.set(Some(n));
+                return;
+            }
+        }
+
+        sess.span_err(attr.span, &format!("malformed {} attribute, expected #![{}=\"N\"]",
+                                          name.replace('_', " "), name));
+    }
+}
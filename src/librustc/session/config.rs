@@ -74,6 +74,8 @@ pub enum OutputType {
 pub enum ErrorOutputType {
     HumanReadable(ColorConfig),
     Json,
+    /// One line per diagnostic, without snippets or notes.
+    Short(ColorConfig),
 }
 
 impl Default for ErrorOutputType {
@@ -165,6 +167,10 @@ pub enum PrintRequest {
     CrateName,
     Cfg,
     TargetList,
+    TargetSpec,
+    TargetCPUs,
+    TargetFeatures,
+    NativeStaticLibs,
 }
 
 pub enum Input {
@@ -301,6 +307,7 @@ pub enum CrateType {
     CrateTypeRlib,
     CrateTypeStaticlib,
     CrateTypeCdylib,
+    CrateTypeProcMacro,
 }
 
 #[derive(Clone)]
@@ -628,6 +635,8 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "skip LLVM verification"),
     borrowck_stats: bool = (false, parse_bool,
         "gather borrowck statistics"),
+    borrowck_mir: bool = (false, parse_bool,
+        "run the MIR-based borrow checker alongside the AST one"),
     no_landing_pads: bool = (false, parse_bool,
         "omit landing pads for unwinding"),
     debug_llvm: bool = (false, parse_bool,
@@ -687,12 +696,15 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "adds unstable command line options to rustc interface"),
     print_enum_sizes: bool = (false, parse_bool,
           "print the size of enums and their variants"),
+    print_type_sizes: bool = (false, parse_bool,
+          "print the size, alignment and field offsets of each translated type"),
     force_overflow_checks: Option<bool> = (None, parse_opt_bool,
           "force overflow checks on or off"),
     force_dropflag_checks: Option<bool> = (None, parse_opt_bool,
           "force drop flag checks on or off"),
     trace_macros: bool = (false, parse_bool,
-          "for every macro invocation, print its name and arguments"),
+          "for every macro invocation, report its arguments, the arm that matched \
+           and what it expands to"),
     enable_nonzeroing_move_hints: bool = (false, parse_bool,
           "force nonzeroing move optimization on"),
     keep_mtwt_tables: bool = (false, parse_bool,
@@ -709,6 +721,16 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "dump MIR state at various points in translation"),
     orbit: bool = (false, parse_bool,
           "get MIR where it belongs - everywhere; most importantly, in orbit"),
+    miri: bool = (false, parse_bool,
+          "evaluate statics, and the constants of MIR-translated functions, \
+           with the MIR interpreter"),
+    apply_suggestions: bool = (false, parse_bool,
+          "rewrite the source files with the suggestions that are known to be correct"),
+    type_trace: bool = (false, parse_bool,
+          "explain type mismatches with where the inferred types involved came from"),
+    self_profile: bool = (false, parse_bool,
+          "write a trace of the time spent in each pass, item and codegen unit, \
+           and print the slowest items to typecheck, borrowck and translate"),
 }
 
 pub fn default_lib_output() -> CrateType {
@@ -940,7 +962,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                              assumed.", "[KIND=]NAME"),
         opt::multi_s("", "crate-type", "Comma separated list of types of crates
                                     for the compiler to emit",
-                   "[bin|lib|rlib|dylib|staticlib]"),
+                   "[bin|lib|rlib|dylib|cdylib|staticlib|proc-macro]"),
         opt::opt_s("", "crate-name", "Specify the name of the crate being built",
                "NAME"),
         opt::multi_s("", "emit", "Comma separated list of types of output for \
@@ -948,7 +970,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                  "[asm|llvm-bc|llvm-ir|obj|link|dep-info]"),
         opt::multi_s("", "print", "Comma separated list of compiler information to \
                                print on stdout",
-                 "[crate-name|file-names|sysroot|cfg|target-list]"),
+                 "[crate-name|file-names|sysroot|cfg|target-list|target-spec-json|\
+                  target-cpus|target-features|native-static-libs]"),
         opt::flagmulti_s("g",  "",  "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
         opt::opt_s("o", "", "Write output to <filename>", "FILENAME"),
@@ -983,7 +1006,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
         opt::multi_ubnr("Z", "", "Set internal debugging options", "FLAG"),
         opt::opt_ubnr("", "error-format",
                       "How errors and other messages are produced",
-                      "human|json"),
+                      "human|json|short"),
         opt::opt_s("", "color", "Configure coloring of output:
                                  auto   = colorize, if output goes to a tty (default);
                                  always = always colorize output;
@@ -1000,8 +1023,11 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
                            valid types are any of the types for `--pretty`, as well as:
                            `flowgraph=<nodeid>` (graphviz formatted flowgraph for node),
                            `everybody_loops` (all function bodies replaced with `loop {}`),
-                           `hir` (the HIR), `hir,identified`, or
-                           `hir,typed` (HIR with types for each node).",
+                           `hir` (the HIR), `hir,identified`,
+                           `hir,typed` (HIR with types for each node),
+                           `mir` (the MIR), or `mir-cfg` (graphviz formatted MIR).
+                           `hir` and `mir` variants can be restricted to the items
+                           matching a path suffix with `=<path>`, e.g. `mir=mymod::foo`.",
                           "TYPE"),
 
         // new options here should **not** use the `_ubnr` functions, all new
@@ -1057,13 +1083,14 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         match matches.opt_str("error-format").as_ref().map(|s| &s[..]) {
             Some("human")   => ErrorOutputType::HumanReadable(color),
             Some("json") => ErrorOutputType::Json,
+            Some("short") => ErrorOutputType::Short(color),
 
             None => ErrorOutputType::HumanReadable(color),
 
             Some(arg) => {
                 early_error(ErrorOutputType::HumanReadable(color),
-                            &format!("argument for --error-format must be human or json (instead \
-                                      was `{}`)",
+                            &format!("argument for --error-format must be human, json or short \
+                                      (instead was `{}`)",
                                      arg))
             }
         }
@@ -1239,6 +1266,18 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
             "sysroot" => PrintRequest::Sysroot,
             "cfg" => PrintRequest::Cfg,
             "target-list" => PrintRequest::TargetList,
+            "target-spec-json" => {
+                if nightly_options::is_unstable_enabled(matches) {
+                    PrintRequest::TargetSpec
+                } else {
+                    early_error(error_format,
+                                "the `-Z unstable-options` flag must also be passed to \
+                                 enable the target-spec-json print option");
+                }
+            }
+            "target-cpus" => PrintRequest::TargetCPUs,
+            "target-features" => PrintRequest::TargetFeatures,
+            "native-static-libs" => PrintRequest::NativeStaticLibs,
             req => {
                 early_error(error_format, &format!("unknown print request `{}`", req))
             }
@@ -1329,6 +1368,7 @@ pub fn parse_crate_types_from_list(list_list: Vec<String>) -> Result<Vec<CrateTy
                 "dylib"     => CrateTypeDylib,
                 "cdylib"    => CrateTypeCdylib,
                 "bin"       => CrateTypeExecutable,
+                "proc-macro" => CrateTypeProcMacro,
                 _ => {
                     return Err(format!("unknown crate type: `{}`",
                                        part));
@@ -1417,6 +1457,7 @@ impl fmt::Display for CrateType {
             CrateTypeRlib => "rlib".fmt(f),
             CrateTypeStaticlib => "staticlib".fmt(f),
             CrateTypeCdylib => "cdylib".fmt(f),
+            CrateTypeProcMacro => "proc-macro".fmt(f),
         }
     }
 }
@@ -23,7 +23,7 @@ use mir::transform as mir_pass;
 use syntax::ast::{NodeId, NodeIdAssigner, Name};
 use syntax::codemap::{Span, MultiSpan};
 use syntax::errors::{self, DiagnosticBuilder};
-use syntax::errors::emitter::{Emitter, BasicEmitter, EmitterWriter};
+use syntax::errors::emitter::{Emitter, BasicEmitter, EmitterWriter, ShortEmitter};
 use syntax::errors::json::JsonEmitter;
 use syntax::diagnostics;
 use syntax::feature_gate;
@@ -62,6 +62,7 @@ pub struct Session {
     pub entry_fn: RefCell<Option<(NodeId, Span)>>,
     pub entry_type: Cell<Option<config::EntryFnType>>,
     pub plugin_registrar_fn: Cell<Option<ast::NodeId>>,
+    pub derive_registrar_fn: Cell<Option<ast::NodeId>>,
     pub default_sysroot: Option<PathBuf>,
     // The name of the root source file of the crate, in the local file system.
     // The path is always expected to be absolute. `None` means that there is no
@@ -70,6 +71,11 @@ pub struct Session {
     pub working_dir: PathBuf,
     pub lint_store: RefCell<lint::LintStore>,
     pub lints: RefCell<NodeMap<Vec<(lint::LintId, Span, String)>>>,
+    /// The lints added while analyzing each item, which incremental
+    /// compilation saves and adds again when it reuses the results of
+    /// the item; see `analyzing_item`.
+    pub item_lints: RefCell<NodeMap<Vec<(NodeId, lint::LintId, Span, String)>>>,
+    analyzed_item: Cell<Option<NodeId>>,
     pub plugin_llvm_passes: RefCell<Vec<String>>,
     pub mir_passes: RefCell<mir_pass::Passes>,
     pub plugin_attributes: RefCell<Vec<(String, AttributeType)>>,
@@ -87,6 +93,13 @@ pub struct Session {
     /// operations such as auto-dereference and monomorphization.
     pub recursion_limit: Cell<usize>,
 
+    /// The limits on the size of the locals of a function and of the values
+    /// it moves, set by `#![stack_frame_size_limit]` and `#![move_size_limit]`.
+    /// The `large_stack_frames` and `large_move` lints only check a function
+    /// when the corresponding limit is set.
+    pub stack_frame_size_limit: Cell<Option<u64>>,
+    pub move_size_limit: Cell<Option<u64>>,
+
     /// The metadata::creader module may inject an allocator/panic_runtime
     /// dependency if it didn't already find one, and this tracks what was
     /// injected.
@@ -250,6 +263,14 @@ impl Session {
                     sp: Span,
                     msg: String) {
         let lint_id = lint::LintId::of(lint);
+        if let Some(item) = self.analyzed_item.get() {
+            let mut item_lints = self.item_lints.borrow_mut();
+            let item_lints = item_lints.entry(item).or_insert(vec![]);
+            let tuple = (id, lint_id, sp, msg.clone());
+            if !item_lints.contains(&tuple) {
+                item_lints.push(tuple);
+            }
+        }
         let mut lints = self.lints.borrow_mut();
         match lints.get_mut(&id) {
             Some(arr) => {
@@ -263,6 +284,20 @@ impl Session {
         }
         lints.insert(id, vec!((lint_id, sp, msg)));
     }
+    /// Runs `f`, which analyzes the item `item`, recording the lints it
+    /// adds in `item_lints` when compiling incrementally.
+    pub fn analyzing_item<F, R>(&self, item: ast::NodeId, f: F) -> R
+        where F: FnOnce() -> R
+    {
+        if self.opts.incremental.is_none() {
+            return f();
+        }
+        let outer = self.analyzed_item.get();
+        self.analyzed_item.set(Some(item));
+        let result = f();
+        self.analyzed_item.set(outer);
+        result
+    }
     pub fn reserve_node_ids(&self, count: ast::NodeId) -> ast::NodeId {
         let id = self.next_node_id.get();
 
@@ -311,6 +346,9 @@ impl Session {
     pub fn print_enum_sizes(&self) -> bool {
         self.opts.debugging_opts.print_enum_sizes
     }
+    pub fn print_type_sizes(&self) -> bool {
+        self.opts.debugging_opts.print_type_sizes
+    }
     pub fn nonzeroing_move_hints(&self) -> bool {
         self.opts.debugging_opts.enable_nonzeroing_move_hints
     }
@@ -327,6 +365,13 @@ impl Session {
         format!("__rustc_plugin_registrar__{}_{}", svh, index.as_usize())
     }
 
+    /// Returns the symbol name for the registrar function of a `proc-macro`
+    /// crate, given the crate Svh and the function DefIndex.
+    pub fn generate_derive_registrar_symbol(&self, svh: &Svh, index: DefIndex)
+                                            -> String {
+        format!("__rustc_derive_registrar__{}_{}", svh, index.as_usize())
+    }
+
     pub fn sysroot<'a>(&'a self) -> &'a Path {
         match self.opts.maybe_sysroot {
             Some (ref sysroot) => sysroot,
@@ -456,19 +501,13 @@ pub fn build_session_with_codemap(sopts: config::Options,
         .unwrap_or(true);
     let treat_err_as_bug = sopts.treat_err_as_bug;
 
-    let emitter: Box<Emitter> = match sopts.error_format {
-        config::ErrorOutputType::HumanReadable(color_config) => {
-            Box::new(EmitterWriter::stderr(color_config, Some(registry), codemap.clone()))
-        }
-        config::ErrorOutputType::Json => {
-            Box::new(JsonEmitter::stderr(Some(registry), codemap.clone()))
-        }
-    };
-
     let diagnostic_handler =
         errors::Handler::with_emitter(can_print_warnings,
                                       treat_err_as_bug,
-                                      emitter);
+                                      emitter(sopts.error_format,
+                                              Some(registry),
+                                              codemap.clone()));
+    diagnostic_handler.set_collect_suggestions(sopts.debugging_opts.apply_suggestions);
 
     build_session_(sopts,
                    dep_graph,
@@ -478,6 +517,24 @@ pub fn build_session_with_codemap(sopts: config::Options,
                    cstore)
 }
 
+/// The emitter that prints diagnostics in the given format to stderr.
+pub fn emitter(error_format: config::ErrorOutputType,
+               registry: Option<diagnostics::registry::Registry>,
+               codemap: Rc<codemap::CodeMap>)
+               -> Box<Emitter> {
+    match error_format {
+        config::ErrorOutputType::HumanReadable(color_config) => {
+            Box::new(EmitterWriter::stderr(color_config, registry, codemap))
+        }
+        config::ErrorOutputType::Json => {
+            Box::new(JsonEmitter::stderr(registry, codemap))
+        }
+        config::ErrorOutputType::Short(color_config) => {
+            Box::new(ShortEmitter::stderr(color_config, codemap))
+        }
+    }
+}
+
 pub fn build_session_(sopts: config::Options,
                       dep_graph: &DepGraph,
                       local_crate_source_file: Option<PathBuf>,
@@ -518,11 +575,14 @@ pub fn build_session_(sopts: config::Options,
         entry_fn: RefCell::new(None),
         entry_type: Cell::new(None),
         plugin_registrar_fn: Cell::new(None),
+        derive_registrar_fn: Cell::new(None),
         default_sysroot: default_sysroot,
         local_crate_source_file: local_crate_source_file,
         working_dir: env::current_dir().unwrap(),
         lint_store: RefCell::new(lint::LintStore::new()),
         lints: RefCell::new(NodeMap()),
+        item_lints: RefCell::new(NodeMap()),
+        analyzed_item: Cell::new(None),
         plugin_llvm_passes: RefCell::new(Vec::new()),
         mir_passes: RefCell::new(mir_pass::Passes::new()),
         plugin_attributes: RefCell::new(Vec::new()),
@@ -531,6 +591,8 @@ pub fn build_session_(sopts: config::Options,
         crate_disambiguator: Cell::new(token::intern("")),
         features: RefCell::new(feature_gate::Features::new()),
         recursion_limit: Cell::new(64),
+        stack_frame_size_limit: Cell::new(None),
+        move_size_limit: Cell::new(None),
         next_node_id: Cell::new(1),
         injected_allocator: Cell::new(None),
         injected_panic_runtime: Cell::new(None),
@@ -594,7 +656,8 @@ unsafe fn configure_llvm(sess: &Session) {
 
 pub fn early_error(output: config::ErrorOutputType, msg: &str) -> ! {
     let mut emitter: Box<Emitter> = match output {
-        config::ErrorOutputType::HumanReadable(color_config) => {
+        config::ErrorOutputType::HumanReadable(color_config) |
+        config::ErrorOutputType::Short(color_config) => {
             Box::new(BasicEmitter::stderr(color_config))
         }
         config::ErrorOutputType::Json => Box::new(JsonEmitter::basic()),
@@ -605,7 +668,8 @@ pub fn early_error(output: config::ErrorOutputType, msg: &str) -> ! {
 
 pub fn early_warn(output: config::ErrorOutputType, msg: &str) {
     let mut emitter: Box<Emitter> = match output {
-        config::ErrorOutputType::HumanReadable(color_config) => {
+        config::ErrorOutputType::HumanReadable(color_config) |
+        config::ErrorOutputType::Short(color_config) => {
             Box::new(BasicEmitter::stderr(color_config))
         }
         config::ErrorOutputType::Json => Box::new(JsonEmitter::basic()),
@@ -247,6 +247,7 @@ fn ty_is_local_constructor(tcx: TyCtxt, ty: Ty, infer_is_local: InferIsLocal)->
         ty::TyUint(..) |
         ty::TyFloat(..) |
         ty::TyStr |
+        ty::TyNever |
         ty::TyFnDef(..) |
         ty::TyFnPtr(_) |
         ty::TyArray(..) |
@@ -255,7 +256,8 @@ fn ty_is_local_constructor(tcx: TyCtxt, ty: Ty, infer_is_local: InferIsLocal)->
         ty::TyRef(..) |
         ty::TyTuple(..) |
         ty::TyParam(..) |
-        ty::TyProjection(..) => {
+        ty::TyProjection(..) |
+        ty::TyAnon(..) => {
             false
         }
 
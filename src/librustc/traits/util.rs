@@ -515,7 +515,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             def_id: fn_trait_def_id,
             substs: self.mk_substs(trait_substs),
         };
-        ty::Binder((trait_ref, sig.0.output.unwrap_or(self.mk_nil())))
+        ty::Binder((trait_ref, sig.0.output))
     }
 }
 
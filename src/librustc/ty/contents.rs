@@ -187,7 +187,7 @@ impl<'a, 'tcx> ty::TyS<'tcx> {
                 // Scalar and unique types are sendable, and durable
                 ty::TyInfer(ty::FreshIntTy(_)) | ty::TyInfer(ty::FreshFloatTy(_)) |
                 ty::TyBool | ty::TyInt(_) | ty::TyUint(_) | ty::TyFloat(_) |
-                ty::TyFnDef(..) | ty::TyFnPtr(_) | ty::TyChar => {
+                ty::TyFnDef(..) | ty::TyFnPtr(_) | ty::TyChar | ty::TyNever => {
                     TC::None
                 }
 
@@ -233,6 +233,12 @@ impl<'a, 'tcx> ty::TyS<'tcx> {
                             })
                         });
 
+                    // The fields of a union are never dropped, so only a
+                    // destructor on the union itself requires drop glue.
+                    if def.is_union() {
+                        res = res - TC::OwnsAll;
+                    }
+
                     if def.has_dtor() {
                         res = res | TC::OwnsDtor;
                     }
@@ -241,7 +247,8 @@ impl<'a, 'tcx> ty::TyS<'tcx> {
                 }
 
                 ty::TyProjection(..) |
-                ty::TyParam(_) => {
+                ty::TyParam(_) |
+                ty::TyAnon(..) => {
                     TC::All
                 }
 
@@ -13,11 +13,13 @@ use ty::subst;
 use infer::type_variable;
 use ty::{self, BoundRegion, Region, Ty, TyCtxt};
 
+use std::cmp;
 use std::fmt;
 use syntax::abi;
 use syntax::ast::{self, Name};
 use syntax::codemap::Span;
-use syntax::errors::DiagnosticBuilder;
+use syntax::errors::{DiagnosticBuilder, DiagnosticStyledString};
+use util::ppaux;
 
 use hir;
 
@@ -55,7 +57,6 @@ pub enum TypeError<'tcx> {
     BuiltinBoundsMismatch(ExpectedFound<ty::BuiltinBounds>),
     VariadicMismatch(ExpectedFound<bool>),
     CyclicTy,
-    ConvergenceMismatch(ExpectedFound<bool>),
     ProjectionNameMismatched(ExpectedFound<Name>),
     ProjectionBoundsLength(ExpectedFound<usize>),
     TyParamDefaultMismatch(ExpectedFound<type_variable::Default<'tcx>>)
@@ -186,11 +187,6 @@ impl<'tcx> fmt::Display for TypeError<'tcx> {
                        if values.expected { "variadic" } else { "non-variadic" },
                        if values.found { "variadic" } else { "non-variadic" })
             }
-            ConvergenceMismatch(ref values) => {
-                write!(f, "expected {} fn, found {} function",
-                       if values.expected { "converging" } else { "diverging" },
-                       if values.found { "converging" } else { "diverging" })
-            }
             ProjectionNameMismatched(ref values) => {
                 write!(f, "expected {}, found {}",
                        values.expected,
@@ -214,7 +210,7 @@ impl<'a, 'gcx, 'lcx, 'tcx> ty::TyS<'tcx> {
     fn sort_string(&self, tcx: TyCtxt<'a, 'gcx, 'lcx>) -> String {
         match self.sty {
             ty::TyBool | ty::TyChar | ty::TyInt(_) |
-            ty::TyUint(_) | ty::TyFloat(_) | ty::TyStr => self.to_string(),
+            ty::TyUint(_) | ty::TyFloat(_) | ty::TyStr | ty::TyNever => self.to_string(),
             ty::TyTuple(ref tys) if tys.is_empty() => self.to_string(),
 
             ty::TyEnum(def, _) => format!("enum `{}`", tcx.item_path_str(def.did)),
@@ -240,6 +236,7 @@ impl<'a, 'gcx, 'lcx, 'tcx> ty::TyS<'tcx> {
             ty::TyInfer(ty::FreshIntTy(_)) => "skolemized integral type".to_string(),
             ty::TyInfer(ty::FreshFloatTy(_)) => "skolemized floating-point type".to_string(),
             ty::TyProjection(_) => "associated type".to_string(),
+            ty::TyAnon(..) => "anonymized type".to_string(),
             ty::TyParam(ref p) => {
                 if p.space == subst::SelfSpace {
                     "Self".to_string()
@@ -340,4 +337,137 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             _ => {}
         }
     }
+
+    /// Renders the expected and found types of a mismatch for the human
+    /// emitter: the components they have in common are elided, and the
+    /// outermost ones that differ are highlighted.
+    pub fn highlight_type_differences(self, expected: Ty<'tcx>, found: Ty<'tcx>)
+                                      -> (DiagnosticStyledString, DiagnosticStyledString) {
+        let mut styled = (DiagnosticStyledString::new(), DiagnosticStyledString::new());
+        self.push_type_differences(expected, found, &mut styled);
+        styled
+    }
+
+    fn push_type_differences(self,
+                             a: Ty<'tcx>,
+                             b: Ty<'tcx>,
+                             styled: &mut (DiagnosticStyledString, DiagnosticStyledString)) {
+        fn push_both(styled: &mut (DiagnosticStyledString, DiagnosticStyledString), text: &str) {
+            styled.0.push_normal(text);
+            styled.1.push_normal(text);
+        }
+
+        if a == b {
+            styled.0.push_elided(a.to_string());
+            styled.1.push_elided(b.to_string());
+            return;
+        }
+
+        match (&a.sty, &b.sty) {
+            (&ty::TyStruct(def_a, substs_a), &ty::TyStruct(def_b, substs_b)) |
+            (&ty::TyEnum(def_a, substs_a), &ty::TyEnum(def_b, substs_b)) if def_a == def_b => {
+                push_both(styled, &self.item_path_str(def_a.did));
+
+                // Leave out the type parameters that have their default on
+                // both sides, as the types themselves are printed.
+                let num_defaults = |substs| {
+                    ppaux::number_of_supplied_defaults(self, substs, subst::TypeSpace, |tcx| {
+                        tcx.lookup_item_type(def_a.did).generics
+                    })
+                };
+                let num_defaults = cmp::min(num_defaults(substs_a), num_defaults(substs_b));
+                let regions_a = substs_a.regions.get_slice(subst::TypeSpace);
+                let regions_b = substs_b.regions.get_slice(subst::TypeSpace);
+                let types_a = substs_a.types.get_slice(subst::TypeSpace);
+                let types_b = substs_b.types.get_slice(subst::TypeSpace);
+                let types_a = &types_a[..types_a.len() - num_defaults];
+                let types_b = &types_b[..types_b.len() - num_defaults];
+
+                let mut params = 0;
+                for (region_a, region_b) in regions_a.iter().zip(regions_b) {
+                    push_both(styled, if params == 0 { "<" } else { ", " });
+                    params += 1;
+                    let (region_a, region_b) = (region_string(region_a),
+                                                region_string(region_b));
+                    if region_a == region_b {
+                        styled.0.push_elided(region_a);
+                        styled.1.push_elided(region_b);
+                    } else {
+                        styled.0.push_highlighted(region_a);
+                        styled.1.push_highlighted(region_b);
+                    }
+                }
+                for (&ty_a, &ty_b) in types_a.iter().zip(types_b) {
+                    push_both(styled, if params == 0 { "<" } else { ", " });
+                    params += 1;
+                    self.push_type_differences(ty_a, ty_b, styled);
+                }
+                if params > 0 {
+                    push_both(styled, ">");
+                }
+            }
+            (&ty::TyBox(a), &ty::TyBox(b)) => {
+                push_both(styled, "Box<");
+                self.push_type_differences(a, b, styled);
+                push_both(styled, ">");
+            }
+            (&ty::TyRef(region_a, mt_a), &ty::TyRef(region_b, mt_b))
+                    if mt_a.mutbl == mt_b.mutbl => {
+                push_both(styled, "&");
+                let (region_a, region_b) = (region_a.to_string(), region_b.to_string());
+                if region_a == region_b {
+                    if !region_a.is_empty() {
+                        push_both(styled, &format!("{} ", region_a));
+                    }
+                } else {
+                    styled.0.push_highlighted(format!("{} ", region_a));
+                    styled.1.push_highlighted(format!("{} ", region_b));
+                }
+                if mt_a.mutbl == hir::MutMutable {
+                    push_both(styled, "mut ");
+                }
+                self.push_type_differences(mt_a.ty, mt_b.ty, styled);
+            }
+            (&ty::TyRawPtr(mt_a), &ty::TyRawPtr(mt_b)) if mt_a.mutbl == mt_b.mutbl => {
+                push_both(styled, match mt_a.mutbl {
+                    hir::MutMutable => "*mut ",
+                    hir::MutImmutable => "*const ",
+                });
+                self.push_type_differences(mt_a.ty, mt_b.ty, styled);
+            }
+            (&ty::TySlice(a), &ty::TySlice(b)) => {
+                push_both(styled, "[");
+                self.push_type_differences(a, b, styled);
+                push_both(styled, "]");
+            }
+            (&ty::TyArray(a, len_a), &ty::TyArray(b, len_b)) if len_a == len_b => {
+                push_both(styled, "[");
+                self.push_type_differences(a, b, styled);
+                push_both(styled, &format!("; {}]", len_a));
+            }
+            (&ty::TyTuple(tys_a), &ty::TyTuple(tys_b)) if tys_a.len() == tys_b.len() => {
+                push_both(styled, "(");
+                for (i, (&ty_a, &ty_b)) in tys_a.iter().zip(tys_b).enumerate() {
+                    if i > 0 {
+                        push_both(styled, ", ");
+                    }
+                    self.push_type_differences(ty_a, ty_b, styled);
+                }
+                if tys_a.len() == 1 {
+                    push_both(styled, ",");
+                }
+                push_both(styled, ")");
+            }
+            _ => {
+                styled.0.push_highlighted(a.to_string());
+                styled.1.push_highlighted(b.to_string());
+            }
+        }
+    }
+}
+
+/// A region as it is printed among the parameters of a type.
+fn region_string(region: &Region) -> String {
+    let s = region.to_string();
+    if s.is_empty() { "'_".to_string() } else { s }
 }
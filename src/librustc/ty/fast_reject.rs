@@ -25,6 +25,7 @@ pub enum SimplifiedType {
     EnumSimplifiedType(DefId),
     StrSimplifiedType,
     VecSimplifiedType,
+    NeverSimplifiedType,
     PtrSimplifiedType,
     TupleSimplifiedType(usize),
     TraitSimplifiedType(DefId),
@@ -32,6 +33,7 @@ pub enum SimplifiedType {
     ClosureSimplifiedType(DefId),
     FunctionSimplifiedType(usize),
     ParameterSimplifiedType,
+    AnonSimplifiedType(DefId),
 }
 
 /// Tries to simplify a type by dropping type parameters, deref'ing away any reference types, etc.
@@ -57,6 +59,7 @@ pub fn simplify_type<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
         ty::TyEnum(def, _) => Some(EnumSimplifiedType(def.did)),
         ty::TyStr => Some(StrSimplifiedType),
         ty::TyArray(..) | ty::TySlice(_) => Some(VecSimplifiedType),
+        ty::TyNever => Some(NeverSimplifiedType),
         ty::TyRawPtr(_) => Some(PtrSimplifiedType),
         ty::TyTrait(ref trait_info) => {
             Some(TraitSimplifiedType(trait_info.principal_def_id()))
@@ -98,6 +101,9 @@ pub fn simplify_type<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
                 None
             }
         }
+        ty::TyAnon(def_id, _) => {
+            Some(AnonSimplifiedType(def_id))
+        }
         ty::TyInfer(_) | ty::TyError => None,
     }
 }
@@ -160,12 +160,6 @@ pub trait TypeFolder<'gcx: 'tcx, 'tcx> : Sized {
         sig.super_fold_with(self)
     }
 
-    fn fold_output(&mut self,
-                      output: &ty::FnOutput<'tcx>)
-                      -> ty::FnOutput<'tcx> {
-        output.super_fold_with(self)
-    }
-
     fn fold_bare_fn_ty(&mut self,
                        fty: &'tcx ty::BareFnTy<'tcx>)
                        -> &'tcx ty::BareFnTy<'tcx>
@@ -696,7 +690,8 @@ impl<'tcx> TypeVisitor<'tcx> for LateBoundRegionsCollector {
         // in the normalized form
         if self.just_constrained {
             match t.sty {
-                ty::TyProjection(..) => { return false; }
+                ty::TyProjection(..) |
+                ty::TyAnon(..) => { return false; }
                 _ => { }
             }
         }
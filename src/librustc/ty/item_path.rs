@@ -184,6 +184,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             data @ DefPathData::Field(..) |
             data @ DefPathData::StructCtor |
             data @ DefPathData::Initializer |
+            data @ DefPathData::ImplTrait |
             data @ DefPathData::MacroDef(..) |
             data @ DefPathData::ClosureExpr |
             data @ DefPathData::Binding(..) => {
@@ -273,6 +274,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             ty::TyInt(_) |
             ty::TyUint(_) |
             ty::TyFloat(_) |
+            ty::TyNever |
             ty::TyStr => {
                 buffer.push(&format!("{}", self_ty));
             }
@@ -340,9 +342,11 @@ pub fn characteristic_def_id_of_type(ty: Ty) -> Option<DefId> {
         ty::TyInt(_) |
         ty::TyUint(_) |
         ty::TyStr |
+        ty::TyNever |
         ty::TyFnPtr(_) |
         ty::TyProjection(_) |
         ty::TyParam(_) |
+        ty::TyAnon(..) |
         ty::TyInfer(_) |
         ty::TyError |
         ty::TyFloat(_) => None,
@@ -34,6 +34,7 @@ pub struct TargetDataLayout {
     pub i16_align: Align,
     pub i32_align: Align,
     pub i64_align: Align,
+    pub i128_align: Align,
     pub f32_align: Align,
     pub f64_align: Align,
     pub pointer_size: Size,
@@ -53,6 +54,7 @@ impl Default for TargetDataLayout {
             i16_align: Align::from_bits(16, 16).unwrap(),
             i32_align: Align::from_bits(32, 32).unwrap(),
             i64_align: Align::from_bits(32, 64).unwrap(),
+            i128_align: Align::from_bits(32, 64).unwrap(),
             f32_align: Align::from_bits(32, 32).unwrap(),
             f64_align: Align::from_bits(64, 64).unwrap(),
             pointer_size: Size::from_bits(64),
@@ -115,6 +117,7 @@ impl TargetDataLayout {
                         Ok(16) => &mut dl.i16_align,
                         Ok(32) => &mut dl.i32_align,
                         Ok(64) => &mut dl.i64_align,
+                        Ok(128) => &mut dl.i128_align,
                         Ok(_) => continue,
                         Err(_) => {
                             size(&s[1..], "i"); // For the user error.
@@ -321,7 +324,8 @@ pub enum Integer {
     I8,
     I16,
     I32,
-    I64
+    I64,
+    I128
 }
 
 impl Integer {
@@ -354,6 +358,7 @@ impl Integer {
             attr::SignedInt(IntTy::I16) | attr::UnsignedInt(UintTy::U16) => I16,
             attr::SignedInt(IntTy::I32) | attr::UnsignedInt(UintTy::U32) => I32,
             attr::SignedInt(IntTy::I64) | attr::UnsignedInt(UintTy::U64) => I64,
+            attr::SignedInt(IntTy::I128) | attr::UnsignedInt(UintTy::U128) => I128,
             attr::SignedInt(IntTy::Is) | attr::UnsignedInt(UintTy::Us) => {
                 dl.ptr_sized_integer()
             }
@@ -426,6 +431,7 @@ impl Primitive {
             Int(I16) => Size::from_bits(16),
             Int(I32) | F32 => Size::from_bits(32),
             Int(I64) | F64 => Size::from_bits(64),
+            Int(I128) => Size::from_bits(128),
             Pointer => dl.pointer_size
         }
     }
@@ -437,6 +443,7 @@ impl Primitive {
             Int(I16) => dl.i16_align,
             Int(I32) => dl.i32_align,
             Int(I64) => dl.i64_align,
+            Int(I128) => dl.i128_align,
             F32 => dl.f32_align,
             F64 => dl.f64_align,
             Pointer => dl.pointer_align
@@ -516,6 +523,16 @@ impl<'a, 'gcx, 'tcx> Struct {
         Ok(())
     }
 
+    /// Get the offset of the field at `index`.
+    pub fn offset_of_field(&self, index: usize) -> Size {
+        assert!(index < self.offset_after_field.len());
+        if index == 0 {
+            Size::from_bytes(0)
+        } else {
+            self.offset_after_field[index - 1]
+        }
+    }
+
     /// Get the size without trailing alignment padding.
     pub fn min_size(&self) -> Size {
         self.offset_after_field.last().map_or(Size::from_bytes(0), |&last| last)
@@ -570,6 +587,10 @@ impl<'a, 'gcx, 'tcx> Struct {
                 }
             }
 
+            // The fields of a union overlap, so none of them can be relied
+            // upon to be non-zero.
+            (_, &ty::TyStruct(def, _)) if def.is_union() => Ok(None),
+
             // Perhaps one of the fields of this struct is non-zero
             // let's recurse and find out
             (_, &ty::TyStruct(def, substs)) => {
@@ -614,6 +635,54 @@ impl<'a, 'gcx, 'tcx> Struct {
     }
 }
 
+/// An untagged union.
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct Union {
+    pub align: Align,
+
+    pub min_size: Size,
+
+    /// If true, no alignment padding is used.
+    pub packed: bool,
+}
+
+impl<'a, 'gcx, 'tcx> Union {
+    pub fn new(dl: &TargetDataLayout, packed: bool) -> Union {
+        Union {
+            align: if packed { dl.i8_align } else { dl.aggregate_align },
+            min_size: Size::from_bytes(0),
+            packed: packed,
+        }
+    }
+
+    /// Extend the Union with more fields, which all start at offset 0.
+    pub fn extend<I>(&mut self, dl: &TargetDataLayout,
+                     fields: I,
+                     scapegoat: Ty<'gcx>)
+                     -> Result<(), LayoutError<'gcx>>
+    where I: Iterator<Item=Result<&'a Layout, LayoutError<'gcx>>> {
+        for (index, field) in fields.enumerate() {
+            let field = field?;
+            if field.is_unsized() {
+                bug!("Union::extend: field #{} of `{}` is unsized",
+                     index, scapegoat);
+            }
+
+            if !self.packed {
+                self.align = self.align.max(field.align(dl));
+            }
+            self.min_size = cmp::max(self.min_size, field.size(dl));
+        }
+
+        Ok(())
+    }
+
+    /// Get the size with trailing aligment padding.
+    pub fn stride(&self) -> Size {
+        self.min_size.abi_align(self.align)
+    }
+}
+
 /// The first half of a fat pointer.
 /// - For a trait object, this is the address of the box.
 /// - For a slice, this is the base address.
@@ -681,6 +750,11 @@ pub enum Layout {
         non_zero: bool
     },
 
+    /// Untagged unions.
+    UntaggedUnion {
+        variants: Union,
+    },
+
     /// General-case enums: for each case there is a struct, and they
     /// all start with a field for the discriminant.
     General {
@@ -831,7 +905,7 @@ impl<'a, 'gcx, 'tcx> Layout {
             }
 
             // Odd unit types.
-            ty::TyFnDef(..) => {
+            ty::TyFnDef(..) | ty::TyNever => {
                 Univariant {
                     variant: Struct::new(dl, false),
                     non_zero: false
@@ -875,6 +949,11 @@ impl<'a, 'gcx, 'tcx> Layout {
                         .layout(infcx)
                 });
                 let packed = tcx.lookup_packed(def.did);
+                if def.is_union() {
+                    let mut un = Union::new(dl, packed);
+                    un.extend(dl, fields, ty)?;
+                    return Ok(UntaggedUnion { variants: un });
+                }
                 let mut st = Struct::new(dl, packed);
                 st.extend(dl, fields, ty)?;
 
@@ -1106,6 +1185,16 @@ impl<'a, 'gcx, 'tcx> Layout {
             ty::TyProjection(_) | ty::TyParam(_) => {
                 return Err(LayoutError::Unknown(ty));
             }
+
+            // `impl Trait` has the layout of its concrete type,
+            // but only once it's been revealed (i.e. in trans).
+            ty::TyAnon(..) => {
+                let normalized = normalize_associated_type(infcx, ty);
+                if ty == normalized {
+                    return Err(LayoutError::Unknown(ty));
+                }
+                return Layout::compute_uncached(normalized, infcx);
+            }
             ty::TyInfer(_) | ty::TyError => {
                 bug!("Layout::compute: unexpected type `{}`", ty)
             }
@@ -1118,7 +1207,7 @@ impl<'a, 'gcx, 'tcx> Layout {
     pub fn is_unsized(&self) -> bool {
         match *self {
             Scalar {..} | Vector {..} | FatPointer {..} |
-            CEnum {..} | General {..} |
+            CEnum {..} | General {..} | UntaggedUnion {..} |
             RawNullablePointer {..} |
             StructWrappedNullablePointer {..} => false,
 
@@ -1157,6 +1246,8 @@ impl<'a, 'gcx, 'tcx> Layout {
             StructWrappedNullablePointer { nonnull: ref variant, .. } => {
                 variant.stride()
             }
+
+            UntaggedUnion { ref variants } => variants.stride()
         }
     }
 
@@ -1196,6 +1287,8 @@ impl<'a, 'gcx, 'tcx> Layout {
             StructWrappedNullablePointer { nonnull: ref variant, .. } => {
                 variant.align
             }
+
+            UntaggedUnion { ref variants } => variants.align
         }
     }
 }
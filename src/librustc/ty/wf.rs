@@ -320,6 +320,7 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
                 ty::TyUint(..) |
                 ty::TyFloat(..) |
                 ty::TyError |
+                ty::TyNever |
                 ty::TyStr |
                 ty::TyParam(_) => {
                     // WfScalar, WfParameter, etc
@@ -348,6 +349,12 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
                     self.compute_projection(data);
                 }
 
+                ty::TyAnon(..) => {
+                    // all of the requirements on type parameters
+                    // should've been checked by the instantiation
+                    // of whatever returned this exact `impl Trait`.
+                }
+
                 ty::TyEnum(def, substs) |
                 ty::TyStruct(def, substs) => {
                     // WfNominalType
@@ -22,6 +22,7 @@ use std::time::Instant;
 use hir;
 use hir::intravisit;
 use hir::intravisit::Visitor;
+use util::profiling;
 
 // The name of the associated type for `Fn` return types
 pub const FN_OUTPUT_NAME: &'static str = "Output";
@@ -35,6 +36,7 @@ pub fn time<T, F>(do_it: bool, what: &str, f: F) -> T where
     F: FnOnce() -> T,
 {
     thread_local!(static DEPTH: Cell<usize> = Cell::new(0));
+    let _activity = profiling::start_activity("phase", || what.to_string());
     if !do_it { return f(); }
 
     let old = DEPTH.with(|slot| {
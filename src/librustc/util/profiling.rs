@@ -0,0 +1,229 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The event trace of `-Z self-profile`.
+//!
+//! While a profiler is installed on a thread, the phases timed with
+//! `util::common::time`, the dep-graph tasks and the LLVM passes run on
+//! each codegen unit are recorded as events. Once the compilation is done,
+//! the events are written out in the trace event format read by
+//! `chrome://tracing`, and the items that were slowest to typecheck,
+//! borrowck and translate are summarized.
+
+use dep_graph::DepNode;
+use hir::def_id::DefId;
+use ty;
+
+use serialize::json;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+thread_local!(static PROFILER: RefCell<Option<SelfProfiler>> = RefCell::new(None));
+
+/// The kinds of dep-graph tasks that are summarized, with how they are
+/// described in the summary.
+const SUMMARIZED_TASKS: &'static [(&'static str, &'static str)] = &[
+    ("TypeckItemBody", "typecheck"),
+    ("BorrowCheck", "borrowck"),
+    ("TransCrateItem", "translate"),
+];
+
+/// The number of items listed for each kind of task in the summary.
+const SUMMARY_LEN: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct ProfileEvent {
+    /// `phase` for the phases timed with `time`, `llvm` for LLVM passes,
+    /// and the kind of the dep-graph node for tasks, e.g. `BorrowCheck`.
+    pub category: String,
+    /// The name of the phase, the path of the item of a task, or the LLVM
+    /// passes and the codegen unit they ran on.
+    pub label: String,
+    /// The name of the thread the activity ran on.
+    pub thread: String,
+    /// When the activity started, relative to the start of the profile.
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// A handle to the events of a compilation, shared by all of its threads.
+#[derive(Clone)]
+pub struct SelfProfiler {
+    start: Instant,
+    events: Arc<Mutex<Vec<ProfileEvent>>>,
+}
+
+impl SelfProfiler {
+    pub fn new() -> SelfProfiler {
+        SelfProfiler {
+            start: Instant::now(),
+            events: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// The events recorded so far, in the order in which they ended.
+    pub fn events(&self) -> Vec<ProfileEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Writes the events in the trace event format.
+    pub fn write_trace(&self, w: &mut Write) -> io::Result<()> {
+        let events = self.events();
+
+        let mut threads: Vec<&str> = vec![];
+        for event in &events {
+            if !threads.contains(&&event.thread[..]) {
+                threads.push(&event.thread);
+            }
+        }
+
+        writeln!(w, "{{\"traceEvents\":[")?;
+        for (tid, thread) in threads.iter().enumerate() {
+            writeln!(w, "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":{},\
+                         \"args\":{{\"name\":{}}}}},",
+                     tid, json::as_json(thread))?;
+        }
+        for (i, event) in events.iter().enumerate() {
+            let tid = threads.iter().position(|t| *t == event.thread).unwrap();
+            write!(w, "{{\"name\":{},\"cat\":{},\"ph\":\"X\",\"ts\":{},\"dur\":{},\
+                        \"pid\":0,\"tid\":{}}}",
+                   json::as_json(&event.label),
+                   json::as_json(&event.category),
+                   micros(event.start),
+                   micros(event.duration),
+                   tid)?;
+            writeln!(w, "{}", if i + 1 < events.len() { "," } else { "" })?;
+        }
+        writeln!(w, "]}}")
+    }
+
+    /// Prints the items that took the longest for each of the summarized
+    /// kinds of tasks. The times of the tasks for the same item are added up.
+    pub fn print_summary(&self) {
+        let events = self.events();
+        for &(category, description) in SUMMARIZED_TASKS {
+            let mut totals: HashMap<&str, Duration> = HashMap::new();
+            for event in events.iter().filter(|e| e.category == category) {
+                let total = totals.entry(&event.label).or_insert(Duration::new(0, 0));
+                *total = *total + event.duration;
+            }
+            if totals.is_empty() {
+                continue;
+            }
+
+            let mut totals: Vec<_> = totals.into_iter().collect();
+            // Longest first, ties by path.
+            totals.sort_by(|a, b| (b.1, a.0).cmp(&(a.1, b.0)));
+            println!("slowest items to {}:", description);
+            for &(item, duration) in totals.iter().take(SUMMARY_LEN) {
+                println!("  time: {:.3}\t{}", secs(duration), item);
+            }
+        }
+    }
+
+    fn record(&self, category: String, label: String, start: Instant) {
+        let event = ProfileEvent {
+            category: category,
+            label: label,
+            thread: thread::current().name().unwrap_or("<unnamed>").to_string(),
+            start: start.duration_since(self.start),
+            duration: start.elapsed(),
+        };
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+/// Installs `profiler` on the current thread, returning the profiler that
+/// was installed before.
+pub fn install(profiler: Option<SelfProfiler>) -> Option<SelfProfiler> {
+    PROFILER.with(|slot| ::std::mem::replace(&mut *slot.borrow_mut(), profiler))
+}
+
+/// The profiler installed on the current thread, e.g. to install it on the
+/// threads spawned for codegen as well.
+pub fn current() -> Option<SelfProfiler> {
+    PROFILER.with(|slot| slot.borrow().clone())
+}
+
+/// A running activity, recorded as an event when dropped.
+pub struct Activity {
+    profiler: SelfProfiler,
+    category: String,
+    label: String,
+    start: Instant,
+}
+
+impl Drop for Activity {
+    fn drop(&mut self) {
+        let category = ::std::mem::replace(&mut self.category, String::new());
+        let label = ::std::mem::replace(&mut self.label, String::new());
+        self.profiler.record(category, label, self.start);
+    }
+}
+
+/// Starts timing an activity, if a profiler is installed on the current
+/// thread. The label is only computed in that case.
+pub fn start_activity<F>(category: &str, label: F) -> Option<Activity>
+    where F: FnOnce() -> String
+{
+    current().map(|profiler| {
+        Activity {
+            profiler: profiler,
+            category: category.to_string(),
+            label: label(),
+            start: Instant::now(),
+        }
+    })
+}
+
+/// Starts timing a dep-graph task, labelled with the path of its item.
+pub fn start_task(key: &DepNode<DefId>) -> Option<Activity> {
+    if PROFILER.with(|slot| slot.borrow().is_none()) {
+        return None;
+    }
+
+    // The `Debug` output of the node without its def-id, e.g.
+    // `TypeckItemBody(())`, starts with the kind of the node.
+    let kind = format!("{:?}", key.map_def(|_| Some(())).unwrap());
+    let kind = kind.split('(').next().unwrap();
+
+    let mut def_id = None;
+    key.map_def(|&d| {
+        def_id = Some(d);
+        None::<DefId>
+    });
+
+    start_activity(kind, || {
+        match def_id {
+            Some(def_id) => ty::tls::with_opt(|tcx| {
+                match tcx {
+                    // Looking up the path must not show up as a read
+                    // of the task.
+                    Some(tcx) => tcx.dep_graph.with_ignore(|| tcx.item_path_str(def_id)),
+                    None => format!("{:?}", def_id),
+                }
+            }),
+            None => String::new(),
+        }
+    })
+}
+
+fn secs(d: Duration) -> f64 {
+    const NANOS_PER_SEC: f64 = 1_000_000_000.0;
+    d.as_secs() as f64 + d.subsec_nanos() as f64 / NANOS_PER_SEC
+}
+
+fn micros(d: Duration) -> u64 {
+    d.as_secs() * 1_000_000 + (d.subsec_nanos() / 1_000) as u64
+}
@@ -99,7 +99,9 @@ impl<'a, 'tcx> RestrictionsContext<'a, 'tcx> {
                     Categorization::Downcast(_, variant_id) => Some(variant_id),
                     _ => None
                 };
+                let base_ty = cmt_base.ty;
                 let result = self.restrict(cmt_base);
+                let result = self.restrict_union_siblings(result, base_ty, &cmt, i.cleaned());
                 self.extend(result, &cmt, LpInterior(opt_variant_id, i.cleaned()))
             }
 
@@ -154,6 +156,34 @@ impl<'a, 'tcx> RestrictionsContext<'a, 'tcx> {
         }
     }
 
+    /// The fields of a union all overlap, so borrowing one of them also
+    /// restricts all of its siblings.
+    fn restrict_union_siblings(&self,
+                               result: RestrictionResult<'tcx>,
+                               base_ty: ty::Ty<'tcx>,
+                               cmt: &mc::cmt<'tcx>,
+                               interior: InteriorKind) -> RestrictionResult<'tcx> {
+        let (adt_def, substs) = match base_ty.sty {
+            ty::TyStruct(adt_def, substs) if adt_def.is_union() => (adt_def, substs),
+            _ => return result,
+        };
+        match result {
+            RestrictionResult::Safe => RestrictionResult::Safe,
+            RestrictionResult::SafeIf(base_lp, mut base_vec) => {
+                for field in &adt_def.struct_variant().fields {
+                    let sibling = InteriorKind::InteriorField(mc::NamedField(field.name));
+                    if sibling == interior {
+                        continue;
+                    }
+                    let v = LpExtend(base_lp.clone(), cmt.mutbl, LpInterior(None, sibling));
+                    let field_ty = field.ty(self.bccx.tcx, substs);
+                    base_vec.push(Rc::new(LoanPath::new(v, field_ty)));
+                }
+                RestrictionResult::SafeIf(base_lp, base_vec)
+            }
+        }
+    }
+
     fn extend(&self,
               result: RestrictionResult<'tcx>,
               cmt: &mc::cmt<'tcx>,
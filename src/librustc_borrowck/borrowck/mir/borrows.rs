@@ -0,0 +1,309 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Gathers the borrows (`&lv` and `&mut lv` rvalues) of a MIR body and
+//! computes the region of each one: the set of points at which the
+//! borrow is still in force.
+//!
+//! Rather than using the lexical regions inferred by typeck, a borrow is
+//! considered in force exactly where some local that may hold the
+//! resulting reference is live. We track which locals may hold the
+//! reference by following it forward through assignments and calls, so a
+//! borrow ends at the last use of the reference, and a path on which the
+//! reference is overwritten or never stored does not extend it.
+
+use rustc::hir;
+use rustc::mir::repr::{BasicBlock, BorrowKind, Lvalue, Mir, Operand};
+use rustc::mir::repr::{ProjectionElem, Rvalue, StatementKind, TerminatorKind};
+use rustc::mir::visit::{LvalueContext, Visitor};
+use rustc::ty::{self, Ty, TyCtxt, TypeFoldable};
+use syntax::codemap::Span;
+
+use indexed_set::{Idx, IdxSet, IdxSetBuf};
+
+use super::gather_moves::Location;
+use super::liveness::{LocalIndex, Locals, Liveness, PointIndex, Points};
+
+pub struct BorrowData<'tcx> {
+    pub location: Location,
+    pub kind: BorrowKind,
+    pub lvalue: Lvalue<'tcx>,
+    pub span: Span,
+    /// The points at which the borrow is still in force.
+    pub region: IdxSetBuf<PointIndex>,
+}
+
+pub fn gather_borrows<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                mir: &Mir<'tcx>,
+                                locals: &Locals,
+                                points: &Points,
+                                liveness: &Liveness)
+                                -> Vec<BorrowData<'tcx>> {
+    let mut flow = BorrowFlow {
+        tcx: tcx,
+        mir: mir,
+        locals: locals,
+        ref_targets: vec![None; locals.len()],
+    };
+    flow.compute_ref_targets();
+
+    let mut borrows = vec![];
+    for bb in mir.all_basic_blocks() {
+        let data = mir.basic_block_data(bb);
+        for (index, statement) in data.statements.iter().enumerate() {
+            let (dest, rvalue) = match statement.kind {
+                StatementKind::Assign(ref dest, ref rvalue) => (dest, rvalue),
+                _ => continue,
+            };
+            if let Rvalue::Ref(_, kind, ref lvalue) = *rvalue {
+                let location = Location { block: bb, index: index };
+                let mut holders = IdxSetBuf::new_empty(locals.len());
+                holders.add(&flow.owner(dest));
+                let region = flow.region(points, liveness, location, holders);
+                debug!("gather_borrows: {:?} = {:?} at {:?}", dest, rvalue, location);
+                borrows.push(BorrowData {
+                    location: location,
+                    kind: kind,
+                    lvalue: lvalue.clone(),
+                    span: statement.span,
+                    region: region,
+                });
+            }
+        }
+    }
+    borrows
+}
+
+struct BorrowFlow<'b, 'a: 'b, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    mir: &'b Mir<'tcx>,
+    locals: &'b Locals,
+    /// For locals holding a `&mut` borrow, the local owning the borrowed
+    /// data. A reference passed to a call may be stored into that data.
+    ref_targets: Vec<Option<LocalIndex>>,
+}
+
+/// The bare locals mentioned anywhere in an rvalue or operand.
+struct Mentions<'b> {
+    locals: &'b Locals,
+    found: Vec<LocalIndex>,
+}
+
+impl<'b, 'tcx> Visitor<'tcx> for Mentions<'b> {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        if let Some(local) = self.locals.local(lvalue) {
+            self.found.push(local);
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+impl<'b, 'a: 'b, 'tcx: 'a> BorrowFlow<'b, 'a, 'tcx> {
+    fn compute_ref_targets(&mut self) {
+        let escaped = self.locals.escaped();
+        // Chains of reborrows need more than one pass; the number of
+        // locals bounds how long such a chain can be.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for data in &self.mir.basic_blocks {
+                for statement in &data.statements {
+                    let (dest, rvalue) = match statement.kind {
+                        StatementKind::Assign(ref dest, ref rvalue) => (dest, rvalue),
+                        _ => continue,
+                    };
+                    let dest = match self.locals.local(dest) {
+                        Some(dest) => dest,
+                        None => continue,
+                    };
+                    let target = match *rvalue {
+                        Rvalue::Ref(_, BorrowKind::Mut, ref lvalue) |
+                        Rvalue::Ref(_, BorrowKind::Unique, ref lvalue) => self.owner(lvalue),
+                        _ => continue,
+                    };
+                    let merged = match self.ref_targets[dest.idx()] {
+                        None => target,
+                        Some(previous) if previous == target => continue,
+                        Some(_) => escaped,
+                    };
+                    if self.ref_targets[dest.idx()] != Some(merged) {
+                        self.ref_targets[dest.idx()] = Some(merged);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The local whose storage `lvalue` lives in. Anything reached through
+    /// a reference we cannot trace back to a local, through a raw pointer,
+    /// or in a static is owned by the `escaped` pseudo-local.
+    fn owner(&self, lvalue: &Lvalue<'tcx>) -> LocalIndex {
+        let escaped = self.locals.escaped();
+        match *lvalue {
+            Lvalue::Static(_) => escaped,
+            Lvalue::Projection(ref proj) => {
+                let base = self.owner(&proj.base);
+                if let ProjectionElem::Deref = proj.elem {
+                    let base_ty = self.mir.lvalue_ty(self.tcx, &proj.base).to_ty(self.tcx);
+                    if !base_ty.is_unique() {
+                        return match self.locals.local(&proj.base) {
+                            Some(local) => self.ref_targets[local.idx()].unwrap_or(escaped),
+                            None => escaped,
+                        };
+                    }
+                }
+                base
+            }
+            _ => self.locals.local(lvalue).unwrap(),
+        }
+    }
+
+    fn mentions_holder<F>(&self, holders: &IdxSet<LocalIndex>, walk: F) -> bool
+        where F: FnOnce(&mut Mentions<'b>)
+    {
+        let mut mentions = Mentions { locals: self.locals, found: vec![] };
+        walk(&mut mentions);
+        mentions.found.iter().any(|local| holders.contains(local))
+    }
+
+    /// Records that the value written to `dest` may hold the borrow if
+    /// `flows` is set; a bare local that is overwritten otherwise no
+    /// longer holds it.
+    fn assign(&self, holders: &mut IdxSet<LocalIndex>, dest: &Lvalue<'tcx>, flows: bool) {
+        if let Some(local) = self.locals.local(dest) {
+            holders.remove(&local);
+        }
+        if flows && may_hold_borrow(self.mir.lvalue_ty(self.tcx, dest).to_ty(self.tcx)) {
+            holders.add(&self.owner(dest));
+        }
+    }
+
+    fn statement_effect(&self, holders: &mut IdxSet<LocalIndex>, bb: BasicBlock, index: usize) {
+        let statement = &self.mir.basic_block_data(bb).statements[index];
+        match statement.kind {
+            StatementKind::Assign(ref dest, ref rvalue) => {
+                let flows = self.mentions_holder(holders, |m| m.visit_rvalue(rvalue));
+                self.assign(holders, dest, flows);
+            }
+            // A reference does not outlive the storage it is kept in.
+            StatementKind::StorageLive(ref lvalue) |
+            StatementKind::StorageDead(ref lvalue) => {
+                if let Some(local) = self.locals.local(lvalue) {
+                    holders.remove(&local);
+                }
+            }
+        }
+    }
+
+    /// The effect of a terminator along the edge to `target`.
+    fn terminator_effect(&self,
+                         holders: &mut IdxSet<LocalIndex>,
+                         bb: BasicBlock,
+                         target: BasicBlock) {
+        let terminator = self.mir.basic_block_data(bb).terminator();
+        if let TerminatorKind::Call { ref func, ref args, destination: Some((ref dest, dest_bb)), .. }
+                = terminator.kind {
+            if target != dest_bb {
+                return;
+            }
+            let flows = self.mentions_holder(holders, |m| {
+                m.visit_operand(func);
+                for arg in args {
+                    m.visit_operand(arg);
+                }
+            });
+            self.assign(holders, dest, flows);
+            if !flows {
+                return;
+            }
+
+            // The callee may store the borrow into anything it was
+            // handed a mutable reference to.
+            for arg in args {
+                let lvalue = match *arg {
+                    Operand::Consume(ref lvalue) => lvalue,
+                    Operand::Constant(_) => continue,
+                };
+                let arg_ty = self.mir.lvalue_ty(self.tcx, lvalue).to_ty(self.tcx);
+                if let ty::TyRef(_, ty::TypeAndMut { ty, mutbl: hir::MutMutable }) =
+                        arg_ty.sty {
+                    if may_hold_borrow(ty) {
+                        let target = self.locals.local(lvalue)
+                                                .and_then(|l| self.ref_targets[l.idx()])
+                                                .unwrap_or(self.locals.escaped());
+                        holders.add(&target);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Follows the holders of a borrow forward from the point at which
+    /// it is created, collecting every point at which one of them is
+    /// live.
+    fn region(&self,
+              points: &Points,
+              liveness: &Liveness,
+              start: Location,
+              holders: IdxSetBuf<LocalIndex>)
+              -> IdxSetBuf<PointIndex> {
+        let mut region = IdxSetBuf::new_empty(points.len());
+        let mut on_entry: Vec<Option<IdxSetBuf<LocalIndex>>> =
+            vec![None; self.mir.basic_blocks.len()];
+        let mut worklist = vec![(start.block, start.index + 1, holders)];
+
+        while let Some((bb, first, mut holders)) = worklist.pop() {
+            let data = self.mir.basic_block_data(bb);
+            let mut reached_end = true;
+            for index in first..data.statements.len() + 1 {
+                let point = points.index(Location { block: bb, index: index });
+                let live = liveness.live_at(point);
+                if !live.words().iter().zip(holders.words()).any(|(l, h)| l & h != 0) {
+                    reached_end = false;
+                    break;
+                }
+                region.add(&point);
+                if index < data.statements.len() {
+                    self.statement_effect(&mut holders, bb, index);
+                }
+            }
+            if !reached_end {
+                continue;
+            }
+
+            for &succ in data.terminator().successors().iter() {
+                let mut out = holders.clone();
+                self.terminator_effect(&mut out, bb, succ);
+                let changed = match on_entry[succ.index()] {
+                    Some(ref mut entry) => entry.union(&out),
+                    None => true,
+                };
+                if changed {
+                    if on_entry[succ.index()].is_none() {
+                        on_entry[succ.index()] = Some(out);
+                    }
+                    let entry = on_entry[succ.index()].clone().unwrap();
+                    worklist.push((succ, 0, entry));
+                }
+            }
+        }
+        region
+    }
+}
+
+/// Whether a value of type `ty` may contain a reference, and so carry a
+/// borrow along with it.
+fn may_hold_borrow<'tcx>(ty: Ty<'tcx>) -> bool {
+    ty.has_erasable_regions() || ty.walk().any(|t| match t.sty {
+        ty::TyRef(..) => true,
+        _ => false,
+    })
+}
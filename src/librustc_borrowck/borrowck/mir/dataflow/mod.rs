@@ -200,6 +200,43 @@ impl<'a, 'tcx: 'a, O> DataflowAnalysis<'a, 'tcx, O>
 
 pub struct DataflowResults<O>(DataflowState<O>) where O: BitDenotation;
 
+impl<O: BitDenotation> DataflowResults<O> {
+    pub fn sets(&self) -> &AllSets<O::Idx> {
+        &self.0.sets
+    }
+
+    /// Applies the effect of the statement at `idx` in `bb` (or of the
+    /// terminator, when `idx` is the number of statements) to `state`.
+    ///
+    /// Starting from the entry set of a block, this reconstructs the
+    /// flow state in front of each of its statements in turn.
+    pub fn apply_effect(&self,
+                        ctxt: &O::Ctxt,
+                        mir: &Mir,
+                        state: &mut IdxSet<O::Idx>,
+                        bb: repr::BasicBlock,
+                        idx: usize) {
+        let bits_per_block = self.0.sets.bits_per_block();
+        let mut on_entry = state.to_owned();
+        let mut gen_set = IdxSetBuf::new_empty(bits_per_block);
+        let mut kill_set = IdxSetBuf::new_empty(bits_per_block);
+        {
+            let mut sets = BlockSets {
+                on_entry: &mut *on_entry,
+                gen_set: &mut *gen_set,
+                kill_set: &mut *kill_set,
+            };
+            if idx < mir.basic_block_data(bb).statements.len() {
+                self.0.operator.statement_effect(ctxt, &mut sets, bb, idx);
+            } else {
+                self.0.operator.terminator_effect(ctxt, &mut sets, bb, idx);
+            }
+        }
+        state.union(&gen_set);
+        state.subtract(&kill_set);
+    }
+}
+
 // FIXME: This type shouldn't be public, but the graphviz::MirWithFlowState trait
 // references it in a method signature. Look into using `pub(crate)` to address this.
 pub struct DataflowState<O: BitDenotation>
@@ -105,6 +105,12 @@ fn each_block<'a, 'tcx, O>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             repr::StatementKind::Assign(ref lvalue, ref rvalue) => {
                 (lvalue, rvalue)
             }
+            repr::StatementKind::StorageLive(_) |
+            repr::StatementKind::StorageDead(_) => {
+                debug!("rustc_peek: computing effect of storage marker: {:?}", stmt);
+                apply_statement_effect(ctxt, results, &mut sets, bb, j);
+                continue;
+            }
         };
 
         if lvalue == peek_arg_lval {
@@ -134,12 +140,7 @@ fn each_block<'a, 'tcx, O>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
         debug!("rustc_peek: computing effect on lvalue: {:?} ({:?}) in stmt: {:?}",
                lvalue, lhs_mpi, stmt);
-        // reset GEN and KILL sets before emulating their effect.
-        for e in sets.gen_set.words_mut() { *e = 0; }
-        for e in sets.kill_set.words_mut() { *e = 0; }
-        results.0.operator.statement_effect(ctxt, &mut sets, bb, j);
-        sets.on_entry.union(sets.gen_set);
-        sets.on_entry.subtract(sets.kill_set);
+        apply_statement_effect(ctxt, results, &mut sets, bb, j);
     }
 
     tcx.sess.span_err(span, &format!("rustc_peek: MIR did not match \
@@ -148,6 +149,22 @@ fn each_block<'a, 'tcx, O>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                       form `&expr`"));
 }
 
+/// Emulates the effect of statement `idx` of `bb` on `sets.on_entry`.
+fn apply_statement_effect<'tcx, O>(ctxt: &O::Ctxt,
+                                   results: &DataflowResults<O>,
+                                   sets: &mut super::BlockSets<MovePathIndex>,
+                                   bb: repr::BasicBlock,
+                                   idx: usize) where
+    O: BitDenotation<Ctxt=MoveDataParamEnv<'tcx>, Idx=MovePathIndex>
+{
+    // reset GEN and KILL sets before emulating their effect.
+    for e in sets.gen_set.words_mut() { *e = 0; }
+    for e in sets.kill_set.words_mut() { *e = 0; }
+    results.0.operator.statement_effect(ctxt, sets, bb, idx);
+    sets.on_entry.union(sets.gen_set);
+    sets.on_entry.subtract(sets.kill_set);
+}
+
 fn is_rustc_peek<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                            terminator: &'a Option<repr::Terminator<'tcx>>)
                            -> Option<(&'a [repr::Operand<'tcx>], Span)> {
@@ -9,7 +9,7 @@
 // except according to those terms.
 
 
-use rustc::ty::{FnOutput, TyCtxt};
+use rustc::ty::TyCtxt;
 use rustc::mir::repr::*;
 use rustc::util::nodemap::FnvHashMap;
 
@@ -415,6 +415,25 @@ impl<'tcx> MovePathLookup<'tcx> {
             }
         }
     }
+
+    /// Like `find`, but returns the move path of the longest prefix of
+    /// `lval` that has one, and `None` if not even its base does.
+    pub fn find_closest(&self, lval: &Lvalue<'tcx>) -> Option<MovePathIndex> {
+        match *lval {
+            Lvalue::Var(var_idx) => self.vars.get(var_idx as usize).and_then(|i| *i),
+            Lvalue::Temp(temp_idx) => self.temps.get(temp_idx as usize).and_then(|i| *i),
+            Lvalue::Arg(arg_idx) => self.args.get(arg_idx as usize).and_then(|i| *i),
+            Lvalue::Static(ref _def_id) => self.statics,
+            Lvalue::ReturnPointer => self.return_ptr,
+            Lvalue::Projection(ref proj) => {
+                self.find_closest(&proj.base).map(|base_index| {
+                    self.projections.get(base_index.idx())
+                                    .and_then(|map| map.get(&proj.elem.lift()))
+                                    .map_or(base_index, |i| *i)
+                })
+            }
+        }
+    }
 }
 
 impl<'a, 'tcx> MovePathDataBuilder<'a, 'tcx> {
@@ -637,6 +656,9 @@ fn gather_moves<'a, 'tcx>(mir: &Mir<'tcx>, tcx: TyCtxt<'a, 'tcx, 'tcx>) -> MoveD
                         }
                     }
                 }
+                // Every var and temp already has a move path.
+                StatementKind::StorageLive(_) |
+                StatementKind::StorageDead(_) => {}
             }
         }
 
@@ -647,7 +669,7 @@ fn gather_moves<'a, 'tcx>(mir: &Mir<'tcx>, tcx: TyCtxt<'a, 'tcx, 'tcx>) -> MoveD
             TerminatorKind::Return => {
                 let source = Location { block: bb,
                                         index: bb_data.statements.len() };
-                if let FnOutput::FnConverging(_) = bb_ctxt.builder.mir.return_ty {
+                if !bb_ctxt.builder.mir.return_ty.is_never() {
                     debug!("gather_moves Return on_move_out_lval return {:?}", source);
                     bb_ctxt.on_move_out_lval(SK::Return, &Lvalue::ReturnPointer, source);
                 } else {
@@ -0,0 +1,230 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Liveness of the locals (vars, temps, args and the return pointer)
+//! of a MIR body. A local is live at a point if the value it holds
+//! there may be read at that point or later.
+
+use rustc::mir::repr::{BasicBlock, Lvalue, Mir, TerminatorKind};
+use rustc::mir::visit::{LvalueContext, Visitor};
+
+use indexed_set::{Idx, IdxSet, IdxSetBuf};
+
+use super::gather_moves::Location;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LocalIndex(usize);
+
+impl Idx for LocalIndex {
+    fn new(idx: usize) -> Self { LocalIndex(idx) }
+    fn idx(&self) -> usize { self.0 }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PointIndex(usize);
+
+impl Idx for PointIndex {
+    fn new(idx: usize) -> Self { PointIndex(idx) }
+    fn idx(&self) -> usize { self.0 }
+}
+
+/// Dense numbering of the locals of a MIR body.
+///
+/// One extra pseudo-local, `escaped`, stands for any storage outside of
+/// the function's own locals (statics, or memory reached through a
+/// reference). It is considered live everywhere.
+pub struct Locals {
+    num_vars: usize,
+    num_temps: usize,
+    num_args: usize,
+}
+
+impl Locals {
+    pub fn new(mir: &Mir) -> Self {
+        Locals {
+            num_vars: mir.var_decls.len(),
+            num_temps: mir.temp_decls.len(),
+            num_args: mir.arg_decls.len(),
+        }
+    }
+
+    /// Number of locals, including the `escaped` pseudo-local.
+    pub fn len(&self) -> usize {
+        self.num_vars + self.num_temps + self.num_args + 2
+    }
+
+    pub fn escaped(&self) -> LocalIndex {
+        LocalIndex(self.len() - 1)
+    }
+
+    /// The local `lvalue` names, if it is a bare local.
+    pub fn local(&self, lvalue: &Lvalue) -> Option<LocalIndex> {
+        match *lvalue {
+            Lvalue::Var(i) => Some(LocalIndex(i as usize)),
+            Lvalue::Temp(i) => Some(LocalIndex(self.num_vars + i as usize)),
+            Lvalue::Arg(i) => {
+                Some(LocalIndex(self.num_vars + self.num_temps + i as usize))
+            }
+            Lvalue::ReturnPointer => {
+                Some(LocalIndex(self.num_vars + self.num_temps + self.num_args))
+            }
+            Lvalue::Static(_) |
+            Lvalue::Projection(_) => None,
+        }
+    }
+}
+
+/// Dense numbering of the statements and terminators of a MIR body.
+pub struct Points {
+    block_start: Vec<usize>,
+    num_points: usize,
+}
+
+impl Points {
+    pub fn new(mir: &Mir) -> Self {
+        let mut block_start = Vec::with_capacity(mir.basic_blocks.len());
+        let mut num_points = 0;
+        for data in &mir.basic_blocks {
+            block_start.push(num_points);
+            num_points += data.statements.len() + 1;
+        }
+        Points { block_start: block_start, num_points: num_points }
+    }
+
+    pub fn len(&self) -> usize {
+        self.num_points
+    }
+
+    pub fn index(&self, loc: Location) -> PointIndex {
+        PointIndex(self.block_start[loc.block.index()] + loc.index)
+    }
+}
+
+/// The locals read or (fully) overwritten by a single statement or
+/// terminator.
+struct DefsUses<'a> {
+    locals: &'a Locals,
+    defs: Vec<LocalIndex>,
+    uses: Vec<LocalIndex>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DefsUses<'a> {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        if let Some(local) = self.locals.local(lvalue) {
+            match context {
+                // Nothing stored in a local survives the end of its
+                // storage, so that ends its live range like a write.
+                LvalueContext::Store |
+                LvalueContext::Call |
+                LvalueContext::StorageDead => self.defs.push(local),
+                LvalueContext::StorageLive => {}
+                _ => self.uses.push(local),
+            }
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+impl<'a> DefsUses<'a> {
+    fn new(locals: &'a Locals) -> Self {
+        DefsUses { locals: locals, defs: vec![], uses: vec![] }
+    }
+
+    /// Turns the set of locals live after this point into the set of
+    /// locals live before it.
+    fn apply(&self, live: &mut IdxSet<LocalIndex>) {
+        for def in &self.defs {
+            live.remove(def);
+        }
+        for use_ in &self.uses {
+            live.add(use_);
+        }
+    }
+}
+
+pub struct Liveness {
+    /// For each point, the locals live on entry to it.
+    live: Vec<IdxSetBuf<LocalIndex>>,
+}
+
+impl Liveness {
+    pub fn compute(mir: &Mir, locals: &Locals, points: &Points) -> Self {
+        let blocks = mir.all_basic_blocks();
+        let mut entry: Vec<_> = blocks.iter()
+                                      .map(|_| IdxSetBuf::new_empty(locals.len()))
+                                      .collect();
+
+        // Iterate to a fixed point; visiting blocks in reverse order
+        // makes this converge quickly for the usual forward-ordered CFG.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &bb in blocks.iter().rev() {
+                let live = walk_block(mir, locals, bb, &entry, |_, _| {});
+                if live.words() != entry[bb.index()].words() {
+                    entry[bb.index()] = live;
+                    changed = true;
+                }
+            }
+        }
+
+        let mut live = vec![IdxSetBuf::new_empty(locals.len()); points.len()];
+        for &bb in &blocks {
+            walk_block(mir, locals, bb, &entry, |index, set| {
+                let point = points.index(Location { block: bb, index: index });
+                live[point.idx()] = set.to_owned();
+            });
+        }
+        for set in &mut live {
+            set.add(&locals.escaped());
+        }
+        Liveness { live: live }
+    }
+
+    pub fn live_at(&self, point: PointIndex) -> &IdxSet<LocalIndex> {
+        &self.live[point.idx()]
+    }
+}
+
+/// Walks `bb` backwards, starting from the union of the entry sets of its
+/// successors, and calls `each_point` with the locals live on entry to
+/// each statement and the terminator. Returns the set live on entry to
+/// the block.
+fn walk_block<F>(mir: &Mir,
+                 locals: &Locals,
+                 bb: BasicBlock,
+                 entry: &[IdxSetBuf<LocalIndex>],
+                 mut each_point: F)
+                 -> IdxSetBuf<LocalIndex>
+    where F: FnMut(usize, &IdxSet<LocalIndex>)
+{
+    let data = mir.basic_block_data(bb);
+    let mut live = IdxSetBuf::new_empty(locals.len());
+    for succ in data.terminator().successors().iter() {
+        live.union(&entry[succ.index()]);
+    }
+
+    let terminator = data.terminator();
+    let mut du = DefsUses::new(locals);
+    du.visit_terminator(bb, terminator);
+    if let TerminatorKind::Return = terminator.kind {
+        du.uses.push(locals.local(&Lvalue::ReturnPointer).unwrap());
+    }
+    du.apply(&mut live);
+    each_point(data.statements.len(), &live);
+
+    for (index, statement) in data.statements.iter().enumerate().rev() {
+        let mut du = DefsUses::new(locals);
+        du.visit_statement(bb, statement);
+        du.apply(&mut live);
+        each_point(index, &live);
+    }
+    live
+}
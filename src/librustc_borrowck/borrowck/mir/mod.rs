@@ -19,13 +19,23 @@ use rustc::hir;
 use rustc::hir::intravisit::{FnKind};
 
 use rustc::mir::repr;
-use rustc::mir::repr::{BasicBlock, BasicBlockData, Mir, Statement, Terminator};
+use rustc::mir::repr::{BasicBlock, BasicBlockData, BorrowKind, Lvalue, Mir};
+use rustc::mir::repr::{ProjectionElem, Statement, Terminator, TerminatorKind};
+use rustc::mir::tcx::LvalueTy;
+use rustc::mir::visit::{LvalueContext, Visitor};
 use rustc::session::Session;
 use rustc::ty::{self, TyCtxt};
+use rustc::util::nodemap::FnvHashSet;
+
+use std::cmp;
+
+use indexed_set::{Idx, IdxSetBuf};
 
 mod abs_domain;
+mod borrows;
 mod dataflow;
 mod gather_moves;
+mod liveness;
 // mod graphviz;
 
 use self::dataflow::{BitDenotation};
@@ -35,6 +45,8 @@ use self::dataflow::{MaybeInitializedLvals, MaybeUninitializedLvals};
 use self::dataflow::{DefinitelyInitializedLvals};
 use self::gather_moves::{MoveData, MovePathIndex, Location};
 use self::gather_moves::{MovePathContent};
+use self::borrows::BorrowData;
+use self::liveness::{Liveness, Locals, PointIndex, Points};
 
 fn has_rustc_mir_with(attrs: &[ast::Attribute], name: &str) -> Option<P<MetaItem>> {
     for attr in attrs {
@@ -67,10 +79,10 @@ pub fn borrowck_mir<'a, 'tcx: 'a>(
     match fk {
         FnKind::ItemFn(name, _, _, _, _, _, _) |
         FnKind::Method(name, _, _, _) => {
-            debug!("borrowck_mir({}) UNIMPLEMENTED", name);
+            debug!("borrowck_mir({})", name);
         }
         FnKind::Closure(_) => {
-            debug!("borrowck_mir closure (body.id={}) UNIMPLEMENTED", body.id);
+            debug!("borrowck_mir closure (body.id={})", body.id);
         }
     }
 
@@ -100,13 +112,22 @@ pub fn borrowck_mir<'a, 'tcx: 'a>(
         bcx.tcx.sess.fatal("stop_after_dataflow ended compilation");
     }
 
+    let locals = Locals::new(mir);
+    let points = Points::new(mir);
+    let liveness = Liveness::compute(mir, &locals, &points);
+    let borrows = borrows::gather_borrows(tcx, mir, &locals, &points, &liveness);
+
     let mut mbcx = MirBorrowckCtxt {
         bcx: bcx,
         mir: mir,
         node_id: id,
-        move_data: mdpe.move_data,
+        mdpe: mdpe,
         flow_inits: flow_inits,
         flow_uninits: flow_uninits,
+        flow_def_inits: flow_def_inits,
+        points: points,
+        borrows: borrows,
+        reported: FnvHashSet(),
     };
 
     for bb in mir.all_basic_blocks() {
@@ -171,29 +192,609 @@ pub struct MirBorrowckCtxt<'b, 'a: 'b, 'tcx: 'a> {
     bcx: &'b mut BorrowckCtxt<'a, 'tcx>,
     mir: &'b Mir<'tcx>,
     node_id: ast::NodeId,
-    move_data: MoveData<'tcx>,
+    mdpe: MoveDataParamEnv<'tcx>,
     flow_inits: DataflowResults<MaybeInitializedLvals<'a, 'tcx>>,
-    flow_uninits: DataflowResults<MaybeUninitializedLvals<'a, 'tcx>>
+    flow_uninits: DataflowResults<MaybeUninitializedLvals<'a, 'tcx>>,
+    flow_def_inits: DataflowResults<DefinitelyInitializedLvals<'a, 'tcx>>,
+    points: Points,
+    borrows: Vec<BorrowData<'tcx>>,
+    /// Spans that already have an error reported against them; the
+    /// same source expression often shows up more than once in MIR.
+    reported: FnvHashSet<Span>,
+}
+
+/// The flow state in front of a single statement or terminator.
+struct FlowState {
+    uninits: IdxSetBuf<MovePathIndex>,
+    def_inits: IdxSetBuf<MovePathIndex>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum AccessKind {
+    /// A copy, or an inspection such as a length or discriminant read.
+    Read,
+    /// A shared borrow.
+    ReadBorrow,
+    Move,
+    MutBorrow,
+    /// An overwrite of the lvalue itself; anything reached through a
+    /// pointer stored in it is unaffected.
+    Assign,
+    /// A drop that is immediately followed by an assignment of a new value.
+    Replace,
+    Drop,
+    /// The end of the storage of a var or temp, whether or not it still
+    /// holds a value.
+    StorageDead,
+}
+
+impl AccessKind {
+    fn is_read(self) -> bool {
+        match self {
+            AccessKind::Read | AccessKind::ReadBorrow => true,
+            _ => false,
+        }
+    }
+}
+
+/// Collects the lvalues a statement or terminator accesses, and how.
+struct Accesses<'c, 'a: 'c, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    mir: &'c Mir<'tcx>,
+    param_env: &'c ty::ParameterEnvironment<'tcx>,
+    span: Span,
+    found: Vec<(Lvalue<'tcx>, AccessKind)>,
+}
+
+impl<'c, 'a: 'c, 'tcx: 'a> Visitor<'tcx> for Accesses<'c, 'a, 'tcx> {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        let kind = match context {
+            LvalueContext::Store | LvalueContext::Call => AccessKind::Assign,
+            LvalueContext::Drop => AccessKind::Drop,
+            LvalueContext::StorageDead => AccessKind::StorageDead,
+            LvalueContext::Inspect | LvalueContext::Slice { .. } => AccessKind::Read,
+            LvalueContext::Borrow { kind: BorrowKind::Shared, .. } => AccessKind::ReadBorrow,
+            LvalueContext::Borrow { .. } => AccessKind::MutBorrow,
+            LvalueContext::Consume => {
+                let ty = self.mir.lvalue_ty(self.tcx, lvalue).to_ty(self.tcx);
+                if ty.moves_by_default(self.tcx, self.param_env, self.span) {
+                    AccessKind::Move
+                } else {
+                    AccessKind::Read
+                }
+            }
+            LvalueContext::Projection |
+            LvalueContext::StorageLive => {
+                return self.super_lvalue(lvalue, context);
+            }
+        };
+        self.found.push((lvalue.clone(), kind));
+        self.super_lvalue(lvalue, context);
+    }
 }
 
 impl<'b, 'a: 'b, 'tcx: 'a> MirBorrowckCtxt<'b, 'a, 'tcx> {
     fn process_basic_block(&mut self, bb: BasicBlock) {
-        let &BasicBlockData { ref statements, ref terminator, is_cleanup: _ } =
-            self.mir.basic_block_data(bb);
-        for stmt in statements {
-            self.process_statement(bb, stmt);
+        let mir = self.mir;
+        let &BasicBlockData { ref statements, ref terminator, is_cleanup } =
+            mir.basic_block_data(bb);
+        // Cleanup blocks only drop what is still around while unwinding;
+        // any borrow still in force at that point died with the panic.
+        if is_cleanup {
+            return;
+        }
+
+        let mut state = FlowState {
+            uninits: self.flow_uninits.sets().on_entry_set_for(bb.index()).to_owned(),
+            def_inits: self.flow_def_inits.sets().on_entry_set_for(bb.index()).to_owned(),
+        };
+        for (index, stmt) in statements.iter().enumerate() {
+            self.process_statement(Location { block: bb, index: index }, stmt, &state);
+            self.flow_uninits.apply_effect(&self.mdpe, mir, &mut state.uninits, bb, index);
+            self.flow_def_inits.apply_effect(&self.mdpe, mir, &mut state.def_inits, bb, index);
         }
 
-        self.process_terminator(bb, terminator);
+        let location = Location { block: bb, index: statements.len() };
+        self.process_terminator(location, terminator.as_ref().unwrap(), &state);
     }
 
-    fn process_statement(&mut self, bb: BasicBlock, stmt: &Statement<'tcx>) {
-        debug!("MirBorrowckCtxt::process_statement({:?}, {:?}", bb, stmt);
+    fn process_statement(&mut self,
+                         location: Location,
+                         stmt: &Statement<'tcx>,
+                         state: &FlowState) {
+        debug!("MirBorrowckCtxt::process_statement({:?}, {:?})", location, stmt);
+        if let repr::StatementKind::Assign(Lvalue::Var(index), _) = stmt.kind {
+            self.check_reassignment(index, stmt.span, state);
+        }
+
+        let accesses = {
+            let mut accesses = self.accesses(stmt.span);
+            accesses.visit_statement(location.block, stmt);
+            accesses.found
+        };
+        let point = self.points.index(location);
+        for (lvalue, kind) in accesses {
+            self.check_access(point, &lvalue, kind, stmt.span, state);
+        }
     }
 
-    fn process_terminator(&mut self, bb: BasicBlock, term: &Option<Terminator<'tcx>>) {
-        debug!("MirBorrowckCtxt::process_terminator({:?}, {:?})", bb, term);
+    fn process_terminator(&mut self,
+                          location: Location,
+                          term: &Terminator<'tcx>,
+                          state: &FlowState) {
+        debug!("MirBorrowckCtxt::process_terminator({:?}, {:?})", location, term);
+        let mir = self.mir;
+        let point = self.points.index(location);
+        let accesses = {
+            let mut accesses = self.accesses(term.span);
+            accesses.visit_terminator(location.block, term);
+            accesses.found
+        };
+        for (lvalue, kind) in accesses {
+            match (&term.kind, kind) {
+                (&TerminatorKind::Call { destination: Some((_, dest_bb)), .. },
+                 AccessKind::Assign) => {
+                    // The destination is only written once the call
+                    // returns, when the arguments are no longer in use.
+                    let point = self.points.index(Location { block: dest_bb, index: 0 });
+                    self.check_access(point, &lvalue, kind, term.span, state);
+                }
+                (&TerminatorKind::Drop { target, .. }, AccessKind::Drop) => {
+                    // An assignment to a value that needs dropping is
+                    // built as a drop of the old value followed by a
+                    // plain assignment at the start of the target block.
+                    let replaced = match mir.basic_block_data(target).statements.first() {
+                        Some(&Statement {
+                            kind: repr::StatementKind::Assign(ref dest, _), ..
+                        }) => *dest == lvalue,
+                        _ => false,
+                    };
+                    let kind = if replaced { AccessKind::Replace } else { AccessKind::Drop };
+                    self.check_access(point, &lvalue, kind, term.span, state);
+                }
+                _ => self.check_access(point, &lvalue, kind, term.span, state),
+            }
+        }
+
+        if let TerminatorKind::Return = term.kind {
+            self.check_return(point, term.span);
+        }
+    }
+
+    fn accesses<'c>(&'c self, span: Span) -> Accesses<'c, 'a, 'tcx> {
+        Accesses {
+            tcx: self.bcx.tcx,
+            mir: self.mir,
+            param_env: &self.mdpe.param_env,
+            span: span,
+            found: vec![],
+        }
+    }
+
+    fn check_access(&mut self,
+                    point: PointIndex,
+                    lvalue: &Lvalue<'tcx>,
+                    kind: AccessKind,
+                    span: Span,
+                    state: &FlowState) {
+        match kind {
+            AccessKind::Assign | AccessKind::Replace |
+            AccessKind::Drop | AccessKind::StorageDead => {}
+            _ => self.check_if_initialized(lvalue, kind, span, state),
+        }
+
+        let conflict = self.borrows.iter().position(|borrow| {
+            if !borrow.region.contains(&point) {
+                return false;
+            }
+            if borrow.kind == BorrowKind::Shared && kind.is_read() {
+                return false;
+            }
+            self.places_conflict(&borrow.lvalue, lvalue, kind)
+        });
+        if let Some(index) = conflict {
+            self.report_conflict(index, lvalue, kind, span);
+        }
+    }
+
+    /// Whether an access to `access` may touch memory covered by a borrow
+    /// of `borrowed`. An assignment only writes the lvalue itself, and the
+    /// end of its storage only invalidates the memory it owns.
+    fn places_conflict(&self,
+                       borrowed: &Lvalue<'tcx>,
+                       access: &Lvalue<'tcx>,
+                       kind: AccessKind)
+                       -> bool {
+        let tcx = self.bcx.tcx;
+        let shallow = kind == AccessKind::Assign;
+        let borrowed = lvalue_prefixes(borrowed);
+        let access = lvalue_prefixes(access);
+        if borrowed[0] != access[0] {
+            return false;
+        }
+
+        let common = cmp::min(borrowed.len(), access.len());
+        for i in 1..common {
+            let (b, a) = match (borrowed[i], access[i]) {
+                (&Lvalue::Projection(ref b), &Lvalue::Projection(ref a)) => (b, a),
+                _ => bug!("lvalue prefix is not a projection"),
+            };
+            match (&b.elem, &a.elem) {
+                (&ProjectionElem::Field(f1, _), &ProjectionElem::Field(f2, _)) if f1 != f2 => {
+                    // The fields of a union all overlap.
+                    let is_union = match self.mir.lvalue_ty(tcx, &b.base).to_ty(tcx).sty {
+                        ty::TyStruct(def, _) => def.is_union(),
+                        _ => false,
+                    };
+                    if !is_union {
+                        return false;
+                    }
+                }
+                (&ProjectionElem::Downcast(_, v1), &ProjectionElem::Downcast(_, v2))
+                        if v1 != v2 => return false,
+                (&ProjectionElem::ConstantIndex { offset: o1, from_end: e1, .. },
+                 &ProjectionElem::ConstantIndex { offset: o2, from_end: e2, .. })
+                        if e1 == e2 && o1 != o2 => return false,
+                // Any other pair, including two indexing operations,
+                // may refer to the same memory.
+                _ => {}
+            }
+        }
+
+        // The access lies within the borrowed lvalue.
+        if access.len() >= borrowed.len() {
+            return true;
+        }
+
+        // The access covers the borrowed lvalue, but does not reach
+        // through a pointer unless it is a deep access and the pointer
+        // owns or uniquely borrows its referent.
+        for i in common..borrowed.len() {
+            if let Lvalue::Projection(ref proj) = *borrowed[i] {
+                if let ProjectionElem::Deref = proj.elem {
+                    if shallow {
+                        return false;
+                    }
+                    match self.mir.lvalue_ty(tcx, &proj.base).to_ty(tcx).sty {
+                        ty::TyRef(_, ty::TypeAndMut { mutbl: hir::MutImmutable, .. }) |
+                        ty::TyRawPtr(..) => return false,
+                        ty::TyRef(..) if kind == AccessKind::StorageDead => return false,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        true
     }
+
+    fn check_if_initialized(&mut self,
+                            lvalue: &Lvalue<'tcx>,
+                            kind: AccessKind,
+                            span: Span,
+                            state: &FlowState) {
+        let tcx = self.bcx.tcx;
+        let mir = self.mir;
+
+        // Only the memory owned by the function is tracked; for anything
+        // reached through a reference, the reference must be initialized.
+        let prefixes = lvalue_prefixes(lvalue);
+        if let Lvalue::Static(_) = *prefixes[0] {
+            return;
+        }
+        let mut owned = prefixes[0];
+        for &prefix in &prefixes[1..] {
+            if let Lvalue::Projection(ref proj) = *prefix {
+                if let ProjectionElem::Deref = proj.elem {
+                    if !mir.lvalue_ty(tcx, &proj.base).to_ty(tcx).is_unique() {
+                        break;
+                    }
+                }
+            }
+            owned = prefix;
+        }
+
+        let uninit = {
+            let move_data = &self.mdpe.move_data;
+            let path = match move_data.rev_lookup.find_closest(owned) {
+                Some(path) => path,
+                None => return,
+            };
+
+            let mut uninit = None;
+            let mut ancestor = Some(path);
+            while let Some(index) = ancestor {
+                if state.uninits.contains(&index) {
+                    uninit = Some(index);
+                    break;
+                }
+                ancestor = move_data.move_paths[index].parent;
+            }
+
+            // A use of the whole also uses any part moved out of it.
+            let exact = match move_data.move_paths[path].content {
+                MovePathContent::Lvalue(ref path_lvalue) => path_lvalue == owned,
+                MovePathContent::Static => false,
+            };
+            if uninit.is_none() && exact {
+                on_all_children_bits(tcx, mir, move_data, path, |child| {
+                    if uninit.is_none() && state.uninits.contains(&child) {
+                        uninit = Some(child);
+                    }
+                });
+            }
+            uninit
+        };
+
+        if let Some(path) = uninit {
+            self.report_use_of_uninit(lvalue, path, kind, span);
+        }
+    }
+
+    fn check_reassignment(&mut self, index: u32, span: Span, state: &FlowState) {
+        let mir = self.mir;
+        let decl = &mir.var_decls[index as usize];
+        if decl.mutability == repr::Mutability::Mut {
+            return;
+        }
+        let path = self.mdpe.move_data.rev_lookup.find(&Lvalue::Var(index));
+        if !state.def_inits.contains(&path) || !self.reported.insert(span) {
+            return;
+        }
+        struct_span_err!(self.bcx.tcx.sess, span, E0384,
+                         "re-assignment of immutable variable `{}` (Mir)",
+                         decl.name)
+            .span_label(span, &format!("re-assignment of immutable variable"))
+            .emit();
+    }
+
+    /// Borrows of memory owned by this function must not be in force once
+    /// it returns.
+    fn check_return(&mut self, point: PointIndex, span: Span) {
+        let tcx = self.bcx.tcx;
+        let mut dangling = vec![];
+        for (index, borrow) in self.borrows.iter().enumerate() {
+            if !borrow.region.contains(&point) {
+                continue;
+            }
+            let prefixes = lvalue_prefixes(&borrow.lvalue);
+            let owned = match *prefixes[0] {
+                Lvalue::Static(_) => false,
+                _ => prefixes[1..].iter().all(|prefix| match **prefix {
+                    Lvalue::Projection(ref proj) => match proj.elem {
+                        ProjectionElem::Deref => {
+                            self.mir.lvalue_ty(tcx, &proj.base).to_ty(tcx).is_unique()
+                        }
+                        _ => true,
+                    },
+                    _ => true,
+                }),
+            };
+            if owned {
+                dangling.push(index);
+            }
+        }
+        for index in dangling {
+            let (lvalue, borrow_span) = {
+                let borrow = &self.borrows[index];
+                (borrow.lvalue.clone(), borrow.span)
+            };
+            self.report_borrowed_value_does_not_live_long_enough(&lvalue, borrow_span, span);
+        }
+    }
+
+    fn report_conflict(&mut self,
+                       index: usize,
+                       lvalue: &Lvalue<'tcx>,
+                       kind: AccessKind,
+                       span: Span) {
+        if !self.reported.insert(span) {
+            return;
+        }
+        let (borrow_kind, borrowed, borrow_span) = {
+            let borrow = &self.borrows[index];
+            (borrow.kind, borrow.lvalue.clone(), borrow.span)
+        };
+        let tcx = self.bcx.tcx;
+        let sess = tcx.sess;
+        let desc = self.describe_lvalue(lvalue);
+        let borrowed_desc = self.describe_lvalue(&borrowed);
+        let also = if desc == borrowed_desc {
+            "it".to_string()
+        } else {
+            format!("`{}`", borrowed_desc)
+        };
+        let mut err = match (kind, borrow_kind) {
+            (AccessKind::Drop, _) | (AccessKind::StorageDead, _) => {
+                return self.report_borrowed_value_does_not_live_long_enough(&borrowed,
+                                                                            borrow_span,
+                                                                            span);
+            }
+            (AccessKind::Read, _) => {
+                struct_span_err!(sess, span, E0503,
+                                 "cannot use `{}` because it was mutably borrowed (Mir)",
+                                 desc)
+            }
+            (AccessKind::ReadBorrow, _) => {
+                struct_span_err!(sess, span, E0502,
+                                 "cannot borrow `{}` as immutable because {} is also \
+                                  borrowed as mutable (Mir)",
+                                 desc, also)
+            }
+            (AccessKind::MutBorrow, BorrowKind::Shared) => {
+                struct_span_err!(sess, span, E0502,
+                                 "cannot borrow `{}` as mutable because {} is also \
+                                  borrowed as immutable (Mir)",
+                                 desc, also)
+            }
+            (AccessKind::MutBorrow, _) => {
+                struct_span_err!(sess, span, E0499,
+                                 "cannot borrow `{}` as mutable more than once at a time (Mir)",
+                                 desc)
+            }
+            (AccessKind::Move, _) => {
+                struct_span_err!(sess, span, E0505,
+                                 "cannot move out of `{}` because it is borrowed (Mir)",
+                                 desc)
+            }
+            (AccessKind::Assign, _) | (AccessKind::Replace, _) => {
+                struct_span_err!(sess, span, E0506,
+                                 "cannot assign to `{}` because it is borrowed (Mir)",
+                                 desc)
+            }
+        };
+        err.span_label(borrow_span, &format!("borrow of `{}` occurs here", borrowed_desc));
+        let label = match kind {
+            AccessKind::Read => format!("use of borrowed `{}`", borrowed_desc),
+            AccessKind::Move => format!("move out of `{}` occurs here", desc),
+            AccessKind::Assign | AccessKind::Replace => {
+                format!("assignment to borrowed `{}` occurs here", desc)
+            }
+            _ => format!("borrow occurs here"),
+        };
+        err.span_label(span, &label);
+        err.emit();
+    }
+
+    fn report_borrowed_value_does_not_live_long_enough(&mut self,
+                                                        borrowed: &Lvalue<'tcx>,
+                                                        borrow_span: Span,
+                                                        span: Span) {
+        if !self.reported.insert(borrow_span) {
+            return;
+        }
+        let desc = self.describe_lvalue(borrowed);
+        self.bcx.tcx.sess.struct_span_err(borrow_span,
+                                          &format!("`{}` does not live long enough (Mir)",
+                                                   desc))
+            .span_label(borrow_span, &format!("borrow occurs here"))
+            .span_label(span, &format!("`{}` dropped here while still borrowed", desc))
+            .emit();
+    }
+
+    fn report_use_of_uninit(&mut self,
+                            lvalue: &Lvalue<'tcx>,
+                            path: MovePathIndex,
+                            kind: AccessKind,
+                            span: Span) {
+        if !self.reported.insert(span) {
+            return;
+        }
+        let verb = match kind {
+            AccessKind::ReadBorrow | AccessKind::MutBorrow => "borrow",
+            _ => "use",
+        };
+        let desc = self.describe_lvalue(lvalue);
+
+        // Find where the value was moved, if it was moved at all rather
+        // than never initialized.
+        let move_span = {
+            let tcx = self.bcx.tcx;
+            let move_data = &self.mdpe.move_data;
+            let moves_by_default = match move_data.move_paths[path].content {
+                MovePathContent::Lvalue(ref lvalue) => {
+                    let ty = self.mir.lvalue_ty(tcx, lvalue).to_ty(tcx);
+                    ty.moves_by_default(tcx, &self.mdpe.param_env, span)
+                }
+                MovePathContent::Static => false,
+            };
+            if moves_by_default {
+                move_data.path_map[path].first().map(|mi| {
+                    self.location_span(move_data.moves[mi.idx()].source)
+                })
+            } else {
+                None
+            }
+        };
+
+        let tcx = self.bcx.tcx;
+        let sess = tcx.sess;
+        match move_span {
+            Some(move_span) => {
+                struct_span_err!(sess, span, E0382,
+                                 "{} of moved value: `{}` (Mir)", verb, desc)
+                    .span_label(move_span, &format!("value moved here"))
+                    .span_label(span, &format!("value used here after move"))
+                    .emit();
+            }
+            None => {
+                struct_span_err!(sess, span, E0381,
+                                 "{} of possibly uninitialized variable: `{}` (Mir)",
+                                 verb, desc)
+                    .span_label(span, &format!("use of possibly uninitialized `{}`", desc))
+                    .emit();
+            }
+        }
+    }
+
+    fn location_span(&self, location: Location) -> Span {
+        let data = self.mir.basic_block_data(location.block);
+        match data.statements.get(location.index) {
+            Some(stmt) => stmt.span,
+            None => data.terminator().span,
+        }
+    }
+
+    /// A user-facing rendering of `lvalue`, in the style of the AST
+    /// borrowck's loan paths.
+    fn describe_lvalue(&self, lvalue: &Lvalue<'tcx>) -> String {
+        let tcx = self.bcx.tcx;
+        match *lvalue {
+            Lvalue::Var(index) => self.mir.var_decls[index as usize].name.to_string(),
+            Lvalue::Arg(index) => self.mir.arg_decls[index as usize].debug_name.to_string(),
+            Lvalue::Temp(_) => "_".to_string(),
+            Lvalue::Static(def_id) => tcx.item_path_str(def_id),
+            Lvalue::ReturnPointer => "(return value)".to_string(),
+            Lvalue::Projection(ref proj) => {
+                match proj.elem {
+                    ProjectionElem::Deref => format!("*{}", self.describe_lvalue(&proj.base)),
+                    ProjectionElem::Field(field, _) => {
+                        // A field reached through a pointer reads like an
+                        // autoderef'd field access: `x.f` rather than `(*x).f`.
+                        let base = match proj.base {
+                            Lvalue::Projection(ref inner) => match inner.elem {
+                                ProjectionElem::Deref => self.describe_lvalue(&inner.base),
+                                _ => self.describe_lvalue(&proj.base),
+                            },
+                            _ => self.describe_lvalue(&proj.base),
+                        };
+                        format!("{}.{}", base, self.describe_field(&proj.base, field))
+                    }
+                    ProjectionElem::Downcast(..) => self.describe_lvalue(&proj.base),
+                    ProjectionElem::Index(_) |
+                    ProjectionElem::ConstantIndex { .. } => {
+                        format!("{}[..]", self.describe_lvalue(&proj.base))
+                    }
+                }
+            }
+        }
+    }
+
+    fn describe_field(&self, base: &Lvalue<'tcx>, field: repr::Field) -> String {
+        let tcx = self.bcx.tcx;
+        match self.mir.lvalue_ty(tcx, base) {
+            LvalueTy::Ty { ty } => match ty.sty {
+                ty::TyStruct(def, _) | ty::TyEnum(def, _) if def.variants.len() == 1 => {
+                    def.struct_variant().fields[field.index()].name.to_string()
+                }
+                _ => field.index().to_string(),
+            },
+            LvalueTy::Downcast { adt_def, variant_index, .. } => {
+                adt_def.variants[variant_index].fields[field.index()].name.to_string()
+            }
+        }
+    }
+}
+
+/// `lvalue` and all the lvalues it is projected from, starting with
+/// the base local or static.
+fn lvalue_prefixes<'c, 'tcx>(mut lvalue: &'c Lvalue<'tcx>) -> Vec<&'c Lvalue<'tcx>> {
+    let mut prefixes = vec![lvalue];
+    while let Lvalue::Projection(ref proj) = *lvalue {
+        lvalue = &proj.base;
+        prefixes.push(lvalue);
+    }
+    prefixes.reverse();
+    prefixes
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -313,6 +914,15 @@ fn drop_flag_effects_for_location<'a, 'tcx, F>(
                                      move_data.rev_lookup.find(lvalue),
                                      |moi| callback(moi, DropFlagState::Present))
             }
+            // A fresh live range starts out uninitialized, and nothing
+            // is left of the value once it ends.
+            repr::StatementKind::StorageLive(ref lvalue) |
+            repr::StatementKind::StorageDead(ref lvalue) => {
+                debug!("drop_flag_effects: storage marker {:?}", stmt);
+                on_all_children_bits(tcx, mir, move_data,
+                                     move_data.rev_lookup.find(lvalue),
+                                     |moi| callback(moi, DropFlagState::Absent))
+            }
         },
         None => {
             // terminator - no move-ins except for function return edge
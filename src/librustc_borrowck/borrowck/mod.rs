@@ -82,7 +82,14 @@ impl<'a, 'tcx, 'v> Visitor<'v> for BorrowckCtxt<'a, 'tcx> {
     }
 
     fn visit_item(&mut self, item: &hir::Item) {
-        borrowck_item(self, item);
+        // Nothing the item depends on has changed since it was last
+        // borrow-checked.
+        let def_id = self.tcx.map.local_def_id(item.id);
+        if self.tcx.dep_graph.is_green(&DepNode::BorrowCheck(def_id)) {
+            return;
+        }
+        let tcx = self.tcx;
+        tcx.sess.analyzing_item(item.id, || borrowck_item(self, item));
     }
 
     fn visit_trait_item(&mut self, ti: &hir::TraitItem) {
@@ -166,11 +173,15 @@ fn borrowck_fn(this: &mut BorrowckCtxt,
                attributes: &[ast::Attribute]) {
     debug!("borrowck_fn(id={})", id);
 
-    if attributes.iter().any(|item| item.check_name("rustc_mir_borrowck")) {
-        let mir = this.mir_map.unwrap().map.get(&id).unwrap();
-        this.with_temp_region_map(id, |this| {
-            mir::borrowck_mir(this, fk, decl, mir, body, sp, id, attributes)
-        });
+    // The MIR borrowck runs in addition to the AST one for now, so that
+    // the two can be compared; its errors are marked with `(Mir)`.
+    if this.tcx.sess.opts.debugging_opts.borrowck_mir ||
+       attributes.iter().any(|item| item.check_name("rustc_mir_borrowck")) {
+        if let Some(mir) = this.mir_map.and_then(|map| map.map.get(&id)) {
+            this.with_temp_region_map(id, |this| {
+                mir::borrowck_mir(this, fk, decl, mir, body, sp, id, attributes)
+            });
+        }
     }
 
     let cfg = cfg::CFG::new(this.tcx, body);
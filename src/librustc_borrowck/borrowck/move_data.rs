@@ -21,7 +21,8 @@ use rustc::middle::dataflow::DataFlowOperator;
 use rustc::middle::dataflow::KillFrom;
 use rustc::middle::expr_use_visitor as euv;
 use rustc::middle::expr_use_visitor::MutateMode;
-use rustc::ty::TyCtxt;
+use rustc::middle::mem_categorization as mc;
+use rustc::ty::{self, TyCtxt};
 use rustc::util::nodemap::{FnvHashMap, NodeSet};
 
 use std::cell::RefCell;
@@ -212,6 +213,32 @@ fn loan_path_is_precise(loan_path: &LoanPath) -> bool {
     }
 }
 
+/// Returns the paths of all union fields overlapping with `loan_path`, that is
+/// the siblings of every union field along the path.
+fn union_sibling_paths<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                 loan_path: &Rc<LoanPath<'tcx>>)
+                                 -> Vec<Rc<LoanPath<'tcx>>> {
+    let mut siblings = vec![];
+    let mut lp = loan_path;
+    while let LpExtend(ref base_lp, mutbl, elem) = lp.kind {
+        if let (&ty::TyStruct(adt_def, substs), LpInterior(opt_variant_id, interior)) =
+                (&base_lp.ty.sty, elem) {
+            if adt_def.is_union() {
+                for field in &adt_def.struct_variant().fields {
+                    let sibling = InteriorKind::InteriorField(mc::NamedField(field.name));
+                    if sibling != interior {
+                        let kind = LpExtend(base_lp.clone(), mutbl,
+                                            LpInterior(opt_variant_id, sibling));
+                        siblings.push(Rc::new(LoanPath::new(kind, field.ty(tcx, substs))));
+                    }
+                }
+            }
+        }
+        lp = base_lp;
+    }
+    siblings
+}
+
 impl<'a, 'tcx> MoveData<'tcx> {
     pub fn new() -> MoveData<'tcx> {
         MoveData {
@@ -367,6 +394,17 @@ impl<'a, 'tcx> MoveData<'tcx> {
                     lp: Rc<LoanPath<'tcx>>,
                     id: ast::NodeId,
                     kind: MoveKind) {
+        // Moving out of a union field moves out of all of its fields.
+        for sibling_lp in union_sibling_paths(tcx, &lp) {
+            self.add_move_helper(tcx, sibling_lp, id, kind);
+        }
+        self.add_move_helper(tcx, lp, id, kind);
+    }
+
+    fn add_move_helper(&self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                       lp: Rc<LoanPath<'tcx>>,
+                       id: ast::NodeId,
+                       kind: MoveKind) {
         debug!("add_move(lp={:?}, id={}, kind={:?})",
                lp,
                id,
@@ -396,6 +434,19 @@ impl<'a, 'tcx> MoveData<'tcx> {
                           span: Span,
                           assignee_id: ast::NodeId,
                           mode: euv::MutateMode) {
+        // Assigning to a union field initializes all of its fields.
+        for sibling_lp in union_sibling_paths(tcx, &lp) {
+            self.add_assignment_helper(tcx, sibling_lp, assign_id, span, assignee_id, mode);
+        }
+        self.add_assignment_helper(tcx, lp, assign_id, span, assignee_id, mode);
+    }
+
+    fn add_assignment_helper(&self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                             lp: Rc<LoanPath<'tcx>>,
+                             assign_id: ast::NodeId,
+                             span: Span,
+                             assignee_id: ast::NodeId,
+                             mode: euv::MutateMode) {
         debug!("add_assignment(lp={:?}, assign_id={}, assignee_id={}",
                lp, assign_id, assignee_id);
 
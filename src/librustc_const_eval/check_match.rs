@@ -208,7 +208,7 @@ fn check_expr(cx: &mut MatchCheckCtxt, ex: &hir::Expr) {
             check_arms(cx, &inlined_arms[..], source);
 
             // Finally, check if the whole match expression is exhaustive.
-            // Check for empty enum, because is_useful only works on inhabited types.
+            // Check for empty enums and `!`, because is_useful only works on inhabited types.
             let pat_ty = cx.tcx.node_id_to_type(scrut.id);
             if inlined_arms.is_empty() {
                 if !pat_ty.is_empty(cx.tcx) {
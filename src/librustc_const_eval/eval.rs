@@ -27,6 +27,7 @@ use rustc::ty::util::IntTypeExt;
 use rustc::traits::ProjectionMode;
 use rustc::util::nodemap::NodeMap;
 use rustc::lint;
+use rustc_i128::{i128, u128};
 
 use graphviz::IntoCow;
 use syntax::ast;
@@ -412,6 +413,8 @@ pub enum ErrKind {
     BadType(ConstVal),
     ErroneousReferencedConstant(Box<ConstEvalErr>),
     CharCast(ConstInt),
+    /// An error reported by the MIR interpreter (`-Z miri`).
+    Miri(String),
 }
 
 impl From<ConstMathErr> for ErrKind {
@@ -475,6 +478,7 @@ impl ConstEvalErr {
             CharCast(ref got) => {
                 format!("only `u8` can be cast as `char`, not `{}`", got.description()).into_cow()
             },
+            Miri(ref msg) => msg.clone().into_cow(),
         }
     }
 }
@@ -559,10 +563,10 @@ pub fn eval_const_expr_partial<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             hir::ExprLit(ref lit) => {
                 use syntax::ast::*;
                 use syntax::ast::LitIntType::*;
-                const I8_OVERFLOW: u64 = ::std::i8::MAX as u64 + 1;
-                const I16_OVERFLOW: u64 = ::std::i16::MAX as u64 + 1;
-                const I32_OVERFLOW: u64 = ::std::i32::MAX as u64 + 1;
-                const I64_OVERFLOW: u64 = ::std::i64::MAX as u64 + 1;
+                const I8_OVERFLOW: u128 = ::std::i8::MAX as u128 + 1;
+                const I16_OVERFLOW: u128 = ::std::i16::MAX as u128 + 1;
+                const I32_OVERFLOW: u128 = ::std::i32::MAX as u128 + 1;
+                const I64_OVERFLOW: u128 = ::std::i64::MAX as u128 + 1;
                 match (&lit.node, ety.map(|t| &t.sty)) {
                     (&LitKind::Int(I8_OVERFLOW, Unsuffixed), Some(&ty::TyInt(IntTy::I8))) |
                     (&LitKind::Int(I8_OVERFLOW, Signed(IntTy::I8)), _) => {
@@ -580,6 +584,12 @@ pub fn eval_const_expr_partial<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     (&LitKind::Int(I64_OVERFLOW, Signed(IntTy::I64)), _) => {
                         return Ok(Integral(I64(::std::i64::MIN)))
                     },
+                    (&LitKind::Int(n, Unsuffixed), Some(&ty::TyInt(IntTy::I128))) |
+                    (&LitKind::Int(n, Signed(IntTy::I128)), _) => {
+                        if n == i128::min_value() as u128 {
+                            return Ok(Integral(I128(i128::min_value())));
+                        }
+                    },
                     (&LitKind::Int(n, Unsuffixed), Some(&ty::TyInt(IntTy::Is))) |
                     (&LitKind::Int(n, Signed(IntTy::Is)), _) => {
                         match tcx.sess.target.int_type {
@@ -945,31 +955,35 @@ fn infer<'a, 'tcx>(i: ConstInt,
         (&ty::TyInt(IntTy::I16), result @ I16(_)) => Ok(result),
         (&ty::TyInt(IntTy::I32), result @ I32(_)) => Ok(result),
         (&ty::TyInt(IntTy::I64), result @ I64(_)) => Ok(result),
+        (&ty::TyInt(IntTy::I128), result @ I128(_)) => Ok(result),
         (&ty::TyInt(IntTy::Is), result @ Isize(_)) => Ok(result),
 
         (&ty::TyUint(UintTy::U8), result @ U8(_)) => Ok(result),
         (&ty::TyUint(UintTy::U16), result @ U16(_)) => Ok(result),
         (&ty::TyUint(UintTy::U32), result @ U32(_)) => Ok(result),
         (&ty::TyUint(UintTy::U64), result @ U64(_)) => Ok(result),
+        (&ty::TyUint(UintTy::U128), result @ U128(_)) => Ok(result),
         (&ty::TyUint(UintTy::Us), result @ Usize(_)) => Ok(result),
 
-        (&ty::TyInt(IntTy::I8), Infer(i)) => Ok(I8(i as i64 as i8)),
-        (&ty::TyInt(IntTy::I16), Infer(i)) => Ok(I16(i as i64 as i16)),
-        (&ty::TyInt(IntTy::I32), Infer(i)) => Ok(I32(i as i64 as i32)),
-        (&ty::TyInt(IntTy::I64), Infer(i)) => Ok(I64(i as i64)),
+        (&ty::TyInt(IntTy::I8), Infer(i)) => Ok(I8(i as i128 as i8)),
+        (&ty::TyInt(IntTy::I16), Infer(i)) => Ok(I16(i as i128 as i16)),
+        (&ty::TyInt(IntTy::I32), Infer(i)) => Ok(I32(i as i128 as i32)),
+        (&ty::TyInt(IntTy::I64), Infer(i)) => Ok(I64(i as i128 as i64)),
+        (&ty::TyInt(IntTy::I128), Infer(i)) => Ok(I128(i as i128)),
         (&ty::TyInt(IntTy::Is), Infer(i)) => {
-            match ConstIsize::new(i as i64, tcx.sess.target.int_type) {
+            match ConstIsize::new(i as i128 as i64, tcx.sess.target.int_type) {
                 Ok(val) => Ok(Isize(val)),
-                Err(_) => Ok(Isize(ConstIsize::Is32(i as i64 as i32))),
+                Err(_) => Ok(Isize(ConstIsize::Is32(i as i128 as i32))),
             }
         },
 
         (&ty::TyInt(IntTy::I8), InferSigned(i)) => Ok(I8(i as i8)),
         (&ty::TyInt(IntTy::I16), InferSigned(i)) => Ok(I16(i as i16)),
         (&ty::TyInt(IntTy::I32), InferSigned(i)) => Ok(I32(i as i32)),
-        (&ty::TyInt(IntTy::I64), InferSigned(i)) => Ok(I64(i)),
+        (&ty::TyInt(IntTy::I64), InferSigned(i)) => Ok(I64(i as i64)),
+        (&ty::TyInt(IntTy::I128), InferSigned(i)) => Ok(I128(i)),
         (&ty::TyInt(IntTy::Is), InferSigned(i)) => {
-            match ConstIsize::new(i, tcx.sess.target.int_type) {
+            match ConstIsize::new(i as i64, tcx.sess.target.int_type) {
                 Ok(val) => Ok(Isize(val)),
                 Err(_) => Ok(Isize(ConstIsize::Is32(i as i32))),
             }
@@ -978,9 +992,10 @@ fn infer<'a, 'tcx>(i: ConstInt,
         (&ty::TyUint(UintTy::U8), Infer(i)) => Ok(U8(i as u8)),
         (&ty::TyUint(UintTy::U16), Infer(i)) => Ok(U16(i as u16)),
         (&ty::TyUint(UintTy::U32), Infer(i)) => Ok(U32(i as u32)),
-        (&ty::TyUint(UintTy::U64), Infer(i)) => Ok(U64(i)),
+        (&ty::TyUint(UintTy::U64), Infer(i)) => Ok(U64(i as u64)),
+        (&ty::TyUint(UintTy::U128), Infer(i)) => Ok(U128(i)),
         (&ty::TyUint(UintTy::Us), Infer(i)) => {
-            match ConstUsize::new(i, tcx.sess.target.uint_type) {
+            match ConstUsize::new(i as u64, tcx.sess.target.uint_type) {
                 Ok(val) => Ok(Usize(val)),
                 Err(_) => Ok(Usize(ConstUsize::Us32(i as u32))),
             }
@@ -1064,6 +1079,7 @@ fn cast_const_int<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, val: ConstInt, ty: ty::
         ty::TyInt(ast::IntTy::I16) => Ok(Integral(I16(v as i64 as i16))),
         ty::TyInt(ast::IntTy::I32) => Ok(Integral(I32(v as i64 as i32))),
         ty::TyInt(ast::IntTy::I64) => Ok(Integral(I64(v as i64))),
+        ty::TyInt(ast::IntTy::I128) => Ok(Integral(I128(val.to_u128_unchecked() as i128))),
         ty::TyInt(ast::IntTy::Is) => {
             match ConstIsize::new(v as i64, tcx.sess.target.int_type) {
                 Ok(val) => Ok(Integral(Isize(val))),
@@ -1074,6 +1090,7 @@ fn cast_const_int<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, val: ConstInt, ty: ty::
         ty::TyUint(ast::UintTy::U16) => Ok(Integral(U16(v as u16))),
         ty::TyUint(ast::UintTy::U32) => Ok(Integral(U32(v as u32))),
         ty::TyUint(ast::UintTy::U64) => Ok(Integral(U64(v))),
+        ty::TyUint(ast::UintTy::U128) => Ok(Integral(U128(val.to_u128_unchecked()))),
         ty::TyUint(ast::UintTy::Us) => {
             match ConstUsize::new(v, tcx.sess.target.uint_type) {
                 Ok(val) => Ok(Integral(Usize(val))),
@@ -1102,9 +1119,11 @@ fn cast_const_int<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, val: ConstInt, ty: ty::
 
 fn cast_const_float<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, f: f64, ty: ty::Ty) -> CastResult {
     match ty.sty {
-        ty::TyInt(_) if f >= 0.0 => cast_const_int(tcx, Infer(f as u64), ty),
-        ty::TyInt(_) => cast_const_int(tcx, InferSigned(f as i64), ty),
-        ty::TyUint(_) if f >= 0.0 => cast_const_int(tcx, Infer(f as u64), ty),
+        ty::TyInt(ast::IntTy::I128) => Ok(Integral(I128(f as i128))),
+        ty::TyUint(ast::UintTy::U128) if f >= 0.0 => Ok(Integral(U128(f as u128))),
+        ty::TyInt(_) if f >= 0.0 => cast_const_int(tcx, Infer(f as u128), ty),
+        ty::TyInt(_) => cast_const_int(tcx, InferSigned(f as i128), ty),
+        ty::TyUint(_) if f >= 0.0 => cast_const_int(tcx, Infer(f as u128), ty),
         ty::TyFloat(ast::FloatTy::F64) => Ok(Float(f)),
         ty::TyFloat(ast::FloatTy::F32) => Ok(Float(f as f32 as f64)),
         _ => Err(CannotCast),
@@ -1114,9 +1133,9 @@ fn cast_const_float<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, f: f64, ty: ty::Ty) -
 fn cast_const<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, val: ConstVal, ty: ty::Ty) -> CastResult {
     match val {
         Integral(i) => cast_const_int(tcx, i, ty),
-        Bool(b) => cast_const_int(tcx, Infer(b as u64), ty),
+        Bool(b) => cast_const_int(tcx, Infer(b as u128), ty),
         Float(f) => cast_const_float(tcx, f, ty),
-        Char(c) => cast_const_int(tcx, Infer(c as u64), ty),
+        Char(c) => cast_const_int(tcx, Infer(c as u128), ty),
         Function(_) => Err(UnimplementedConstVal("casting fn pointers")),
         ByteStr(_) => match ty.sty {
             ty::TyRawPtr(_) => {
@@ -1140,13 +1159,13 @@ fn lit_to_const<'a, 'tcx>(lit: &ast::LitKind,
         LitKind::ByteStr(ref data) => Ok(ByteStr(data.clone())),
         LitKind::Byte(n) => Ok(Integral(U8(n))),
         LitKind::Int(n, Signed(ity)) => {
-            infer(InferSigned(n as i64), tcx, &ty::TyInt(ity)).map(Integral)
+            infer(InferSigned(n as i128), tcx, &ty::TyInt(ity)).map(Integral)
         },
 
         LitKind::Int(n, Unsuffixed) => {
             match ty_hint.map(|t| &t.sty) {
                 Some(&ty::TyInt(ity)) => {
-                    infer(InferSigned(n as i64), tcx, &ty::TyInt(ity)).map(Integral)
+                    infer(InferSigned(n as i128), tcx, &ty::TyInt(ity)).map(Integral)
                 },
                 Some(&ty::TyUint(uty)) => {
                     infer(Infer(n), tcx, &ty::TyUint(uty)).map(Integral)
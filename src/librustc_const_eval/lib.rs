@@ -37,6 +37,7 @@
 #[macro_use] extern crate rustc;
 extern crate rustc_back;
 extern crate rustc_const_math;
+extern crate rustc_i128;
 extern crate graphviz;
 
 extern crate serialize as rustc_serialize; // used by deriving
@@ -11,6 +11,7 @@
 use std::cmp::Ordering;
 use syntax::attr::IntType;
 use syntax::ast::{IntTy, UintTy};
+use rustc_i128::{i128, u128};
 
 use super::is::*;
 use super::us::*;
@@ -22,34 +23,38 @@ pub enum ConstInt {
     I16(i16),
     I32(i32),
     I64(i64),
+    I128(i128),
     Isize(ConstIsize),
     U8(u8),
     U16(u16),
     U32(u32),
     U64(u64),
+    U128(u128),
     Usize(ConstUsize),
-    Infer(u64),
-    InferSigned(i64),
+    Infer(u128),
+    InferSigned(i128),
 }
 pub use self::ConstInt::*;
 
 
 macro_rules! bounds {
     ($($t:ident $min:ident $max:ident)*) => {
-        mod as_u64 {
+        mod as_u128 {
+            use rustc_i128::u128;
             $(
                 #[allow(dead_code)]
-                pub const $min: u64 = ::std::$t::MIN as u64;
+                pub const $min: u128 = ::std::$t::MIN as u128;
                 #[allow(dead_code)]
-                pub const $max: u64 = ::std::$t::MAX as u64;
+                pub const $max: u128 = ::std::$t::MAX as u128;
             )*
         }
-        mod as_i64 {
+        mod as_i128 {
+            use rustc_i128::i128;
             $(
                 #[allow(dead_code)]
-                pub const $min: i64 = ::std::$t::MIN as i64;
+                pub const $min: i128 = ::std::$t::MIN as i128;
                 #[allow(dead_code)]
-                pub const $max: i64 = ::std::$t::MAX as i64;
+                pub const $max: i128 = ::std::$t::MAX as i128;
             )*
         }
     }
@@ -67,41 +72,51 @@ impl ConstInt {
         let inferred = match (self, other) {
             (InferSigned(_), InferSigned(_))
             | (Infer(_), Infer(_)) => self, // no inference possible
-            // kindof wrong, you could have had values > I64MAX during computation of a
-            (Infer(a @ 0...as_u64::I64MAX), InferSigned(_)) => InferSigned(a as i64),
+            // kindof wrong, you could have had values > I128MAX during computation of a
+            (Infer(a), InferSigned(_)) if a <= i128::max_value() as u128 => {
+                InferSigned(a as i128)
+            },
             (Infer(_), InferSigned(_)) => return Err(ConstMathErr::NotInRange),
             (_, InferSigned(_))
             | (_, Infer(_)) => return other.infer(self).map(|(b, a)| (a, b)),
 
-            (Infer(a @ 0...as_u64::I8MAX), I8(_)) => I8(a as i64 as i8),
-            (Infer(a @ 0...as_u64::I16MAX), I16(_)) => I16(a as i64 as i16),
-            (Infer(a @ 0...as_u64::I32MAX), I32(_)) => I32(a as i64 as i32),
-            (Infer(a @ 0...as_u64::I64MAX), I64(_)) => I64(a as i64),
-            (Infer(a @ 0...as_u64::I32MAX), Isize(Is32(_))) => Isize(Is32(a as i64 as i32)),
-            (Infer(a @ 0...as_u64::I64MAX), Isize(Is64(_))) => Isize(Is64(a as i64)),
-            (Infer(a @ 0...as_u64::U8MAX), U8(_)) => U8(a as u8),
-            (Infer(a @ 0...as_u64::U16MAX), U16(_)) => U16(a as u16),
-            (Infer(a @ 0...as_u64::U32MAX), U32(_)) => U32(a as u32),
-            (Infer(a), U64(_)) => U64(a),
-            (Infer(a @ 0...as_u64::U32MAX), Usize(Us32(_))) => Usize(Us32(a as u32)),
-            (Infer(a), Usize(Us64(_))) => Usize(Us64(a)),
+            (Infer(a @ 0...as_u128::I8MAX), I8(_)) => I8(a as i8),
+            (Infer(a @ 0...as_u128::I16MAX), I16(_)) => I16(a as i16),
+            (Infer(a @ 0...as_u128::I32MAX), I32(_)) => I32(a as i32),
+            (Infer(a @ 0...as_u128::I64MAX), I64(_)) => I64(a as i64),
+            (Infer(a), I128(_)) if a <= i128::max_value() as u128 => I128(a as i128),
+            (Infer(a @ 0...as_u128::I32MAX), Isize(Is32(_))) => Isize(Is32(a as i32)),
+            (Infer(a @ 0...as_u128::I64MAX), Isize(Is64(_))) => Isize(Is64(a as i64)),
+            (Infer(a @ 0...as_u128::U8MAX), U8(_)) => U8(a as u8),
+            (Infer(a @ 0...as_u128::U16MAX), U16(_)) => U16(a as u16),
+            (Infer(a @ 0...as_u128::U32MAX), U32(_)) => U32(a as u32),
+            (Infer(a @ 0...as_u128::U64MAX), U64(_)) => U64(a as u64),
+            (Infer(a), U128(_)) => U128(a),
+            (Infer(a @ 0...as_u128::U32MAX), Usize(Us32(_))) => Usize(Us32(a as u32)),
+            (Infer(a @ 0...as_u128::U64MAX), Usize(Us64(_))) => Usize(Us64(a as u64)),
 
             (Infer(_), _) => return Err(ConstMathErr::NotInRange),
 
-            (InferSigned(a @ as_i64::I8MIN...as_i64::I8MAX), I8(_)) => I8(a as i8),
-            (InferSigned(a @ as_i64::I16MIN...as_i64::I16MAX), I16(_)) => I16(a as i16),
-            (InferSigned(a @ as_i64::I32MIN...as_i64::I32MAX), I32(_)) => I32(a as i32),
-            (InferSigned(a), I64(_)) => I64(a),
-            (InferSigned(a @ as_i64::I32MIN...as_i64::I32MAX), Isize(Is32(_))) => {
+            (InferSigned(a @ as_i128::I8MIN...as_i128::I8MAX), I8(_)) => I8(a as i8),
+            (InferSigned(a @ as_i128::I16MIN...as_i128::I16MAX), I16(_)) => I16(a as i16),
+            (InferSigned(a @ as_i128::I32MIN...as_i128::I32MAX), I32(_)) => I32(a as i32),
+            (InferSigned(a @ as_i128::I64MIN...as_i128::I64MAX), I64(_)) => I64(a as i64),
+            (InferSigned(a), I128(_)) => I128(a),
+            (InferSigned(a @ as_i128::I32MIN...as_i128::I32MAX), Isize(Is32(_))) => {
                 Isize(Is32(a as i32))
             },
-            (InferSigned(a), Isize(Is64(_))) => Isize(Is64(a)),
-            (InferSigned(a @ 0...as_i64::U8MAX), U8(_)) => U8(a as u8),
-            (InferSigned(a @ 0...as_i64::U16MAX), U16(_)) => U16(a as u16),
-            (InferSigned(a @ 0...as_i64::U32MAX), U32(_)) => U32(a as u32),
-            (InferSigned(a @ 0...as_i64::I64MAX), U64(_)) => U64(a as u64),
-            (InferSigned(a @ 0...as_i64::U32MAX), Usize(Us32(_))) => Usize(Us32(a as u32)),
-            (InferSigned(a @ 0...as_i64::I64MAX), Usize(Us64(_))) => Usize(Us64(a as u64)),
+            (InferSigned(a @ as_i128::I64MIN...as_i128::I64MAX), Isize(Is64(_))) => {
+                Isize(Is64(a as i64))
+            },
+            (InferSigned(a @ 0...as_i128::U8MAX), U8(_)) => U8(a as u8),
+            (InferSigned(a @ 0...as_i128::U16MAX), U16(_)) => U16(a as u16),
+            (InferSigned(a @ 0...as_i128::U32MAX), U32(_)) => U32(a as u32),
+            (InferSigned(a), U64(_)) if a >= 0 && a as u128 <= as_u128::U64MAX => U64(a as u64),
+            (InferSigned(a), U128(_)) if a >= 0 => U128(a as u128),
+            (InferSigned(a @ 0...as_i128::U32MAX), Usize(Us32(_))) => Usize(Us32(a as u32)),
+            (InferSigned(a), Usize(Us64(_))) if a >= 0 && a as u128 <= as_u128::U64MAX => {
+                Usize(Us64(a as u64))
+            },
             (InferSigned(_), _) => return Err(ConstMathErr::NotInRange),
             _ => self, // already known types
         };
@@ -113,25 +128,28 @@ impl ConstInt {
         match self {
             Infer(i) => Infer(i),
             InferSigned(i) if i < 0 => InferSigned(i),
-            I8(i) if i < 0 => InferSigned(i as i64),
-            I16(i) if i < 0 => InferSigned(i as i64),
-            I32(i) if i < 0 => InferSigned(i as i64),
-            I64(i) if i < 0 => InferSigned(i as i64),
-            Isize(Is32(i)) if i < 0 => InferSigned(i as i64),
-            Isize(Is64(i)) if i < 0 => InferSigned(i as i64),
-            InferSigned(i) => Infer(i as u64),
-            I8(i) => Infer(i as u64),
-            I16(i) => Infer(i as u64),
-            I32(i) => Infer(i as u64),
-            I64(i) => Infer(i as u64),
-            Isize(Is32(i)) => Infer(i as u64),
-            Isize(Is64(i)) => Infer(i as u64),
-            U8(i) => Infer(i as u64),
-            U16(i) => Infer(i as u64),
-            U32(i) => Infer(i as u64),
-            U64(i) => Infer(i as u64),
-            Usize(Us32(i)) => Infer(i as u64),
-            Usize(Us64(i)) => Infer(i),
+            I8(i) if i < 0 => InferSigned(i as i128),
+            I16(i) if i < 0 => InferSigned(i as i128),
+            I32(i) if i < 0 => InferSigned(i as i128),
+            I64(i) if i < 0 => InferSigned(i as i128),
+            I128(i) if i < 0 => InferSigned(i),
+            Isize(Is32(i)) if i < 0 => InferSigned(i as i128),
+            Isize(Is64(i)) if i < 0 => InferSigned(i as i128),
+            InferSigned(i) => Infer(i as u128),
+            I8(i) => Infer(i as u128),
+            I16(i) => Infer(i as u128),
+            I32(i) => Infer(i as u128),
+            I64(i) => Infer(i as u128),
+            I128(i) => Infer(i as u128),
+            Isize(Is32(i)) => Infer(i as u128),
+            Isize(Is64(i)) => Infer(i as u128),
+            U8(i) => Infer(i as u128),
+            U16(i) => Infer(i as u128),
+            U32(i) => Infer(i as u128),
+            U64(i) => Infer(i as u128),
+            U128(i) => Infer(i),
+            Usize(Us32(i)) => Infer(i as u128),
+            Usize(Us64(i)) => Infer(i as u128),
         }
     }
 
@@ -144,11 +162,13 @@ impl ConstInt {
             I16(_) => "i16",
             I32(_) => "i32",
             I64(_) => "i64",
+            I128(_) => "i128",
             Isize(_) => "isize",
             U8(_) => "u8",
             U16(_) => "u16",
             U32(_) => "u32",
             U64(_) => "u64",
+            U128(_) => "u128",
             Usize(_) => "usize",
         }
     }
@@ -156,9 +176,19 @@ impl ConstInt {
     /// Erases the type and returns a u64.
     /// This is not the same as `-5i8 as u64` but as `-5i8 as i64 as u64`
     pub fn to_u64_unchecked(self) -> u64 {
+        match self.erase_type() {
+            ConstInt::Infer(i) => i as u64,
+            ConstInt::InferSigned(i) => i as i64 as u64,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Erases the type and returns a u128.
+    /// This is not the same as `-5i8 as u128` but as `-5i8 as i128 as u128`
+    pub fn to_u128_unchecked(self) -> u128 {
         match self.erase_type() {
             ConstInt::Infer(i) => i,
-            ConstInt::InferSigned(i) => i as u64,
+            ConstInt::InferSigned(i) => i as u128,
             _ => unreachable!(),
         }
     }
@@ -169,16 +199,18 @@ impl ConstInt {
             I8(v) if v >= 0 => Some(v as u32),
             I16(v) if v >= 0 => Some(v as u32),
             I32(v) if v >= 0 => Some(v as u32),
-            InferSigned(v)
-            | Isize(Is64(v))
+            Isize(Is64(v))
             | I64(v) if v >= 0 && v <= ::std::u32::MAX as i64 => Some(v as u32),
+            InferSigned(v)
+            | I128(v) if v >= 0 && v <= ::std::u32::MAX as i128 => Some(v as u32),
             Isize(Is32(v)) if v >= 0 => Some(v as u32),
             U8(v) => Some(v as u32),
             U16(v) => Some(v as u32),
             U32(v) => Some(v),
-            Infer(v)
-            | Usize(Us64(v))
+            Usize(Us64(v))
             | U64(v) if v <= ::std::u32::MAX as u64 => Some(v as u32),
+            Infer(v)
+            | U128(v) if v <= ::std::u32::MAX as u128 => Some(v as u32),
             Usize(Us32(v)) => Some(v),
             _ => None,
         }
@@ -187,18 +219,20 @@ impl ConstInt {
     /// Converts the value to a `u64` if it's >= 0
     pub fn to_u64(&self) -> Option<u64> {
         match *self {
-            Infer(v) => Some(v),
-            InferSigned(v) if v >= 0 => Some(v as u64),
             I8(v) if v >= 0 => Some(v as u64),
             I16(v) if v >= 0 => Some(v as u64),
             I32(v) if v >= 0 => Some(v as u64),
             I64(v) if v >= 0 => Some(v as u64),
+            InferSigned(v)
+            | I128(v) if v >= 0 && v as u128 <= ::std::u64::MAX as u128 => Some(v as u64),
             Isize(Is32(v)) if v >= 0 => Some(v as u64),
             Isize(Is64(v)) if v >= 0 => Some(v as u64),
             U8(v) => Some(v as u64),
             U16(v) => Some(v as u64),
             U32(v) => Some(v as u64),
             U64(v) => Some(v),
+            Infer(v)
+            | U128(v) if v <= ::std::u64::MAX as u128 => Some(v as u64),
             Usize(Us32(v)) => Some(v as u64),
             Usize(Us64(v)) => Some(v),
             _ => None,
@@ -211,6 +245,7 @@ impl ConstInt {
             I16(v) => v < 0,
             I32(v) => v < 0,
             I64(v) => v < 0,
+            I128(v) => v < 0,
             Isize(Is32(v)) => v < 0,
             Isize(Is64(v)) => v < 0,
             InferSigned(v) => v < 0,
@@ -225,12 +260,14 @@ impl ConstInt {
             (I16(a), I16(b)) => Ok(a.cmp(&b)),
             (I32(a), I32(b)) => Ok(a.cmp(&b)),
             (I64(a), I64(b)) => Ok(a.cmp(&b)),
+            (I128(a), I128(b)) => Ok(a.cmp(&b)),
             (Isize(Is32(a)), Isize(Is32(b))) => Ok(a.cmp(&b)),
             (Isize(Is64(a)), Isize(Is64(b))) => Ok(a.cmp(&b)),
             (U8(a), U8(b)) => Ok(a.cmp(&b)),
             (U16(a), U16(b)) => Ok(a.cmp(&b)),
             (U32(a), U32(b)) => Ok(a.cmp(&b)),
             (U64(a), U64(b)) => Ok(a.cmp(&b)),
+            (U128(a), U128(b)) => Ok(a.cmp(&b)),
             (Usize(Us32(a)), Usize(Us32(b))) => Ok(a.cmp(&b)),
             (Usize(Us64(a)), Usize(Us64(b))) => Ok(a.cmp(&b)),
             (Infer(a), Infer(b)) => Ok(a.cmp(&b)),
@@ -249,12 +286,14 @@ impl ConstInt {
             ConstInt::I16(i) => ConstInt::I16(add1!(i)),
             ConstInt::I32(i) => ConstInt::I32(add1!(i)),
             ConstInt::I64(i) => ConstInt::I64(add1!(i)),
+            ConstInt::I128(i) => ConstInt::I128(add1!(i)),
             ConstInt::Isize(ConstIsize::Is32(i)) => ConstInt::Isize(ConstIsize::Is32(add1!(i))),
             ConstInt::Isize(ConstIsize::Is64(i)) => ConstInt::Isize(ConstIsize::Is64(add1!(i))),
             ConstInt::U8(i) => ConstInt::U8(add1!(i)),
             ConstInt::U16(i) => ConstInt::U16(add1!(i)),
             ConstInt::U32(i) => ConstInt::U32(add1!(i)),
             ConstInt::U64(i) => ConstInt::U64(add1!(i)),
+            ConstInt::U128(i) => ConstInt::U128(add1!(i)),
             ConstInt::Usize(ConstUsize::Us32(i)) => ConstInt::Usize(ConstUsize::Us32(add1!(i))),
             ConstInt::Usize(ConstUsize::Us64(i)) => ConstInt::Usize(ConstUsize::Us64(add1!(i))),
             ConstInt::Infer(_) | ConstInt::InferSigned(_) => panic!("no type info for const int"),
@@ -267,11 +306,13 @@ impl ConstInt {
             ConstInt::I16(_) => Some(IntType::SignedInt(IntTy::I16)),
             ConstInt::I32(_) => Some(IntType::SignedInt(IntTy::I32)),
             ConstInt::I64(_) => Some(IntType::SignedInt(IntTy::I64)),
+            ConstInt::I128(_) => Some(IntType::SignedInt(IntTy::I128)),
             ConstInt::Isize(_) => Some(IntType::SignedInt(IntTy::Is)),
             ConstInt::U8(_) => Some(IntType::UnsignedInt(UintTy::U8)),
             ConstInt::U16(_) => Some(IntType::UnsignedInt(UintTy::U16)),
             ConstInt::U32(_) => Some(IntType::UnsignedInt(UintTy::U32)),
             ConstInt::U64(_) => Some(IntType::UnsignedInt(UintTy::U64)),
+            ConstInt::U128(_) => Some(IntType::UnsignedInt(UintTy::U128)),
             ConstInt::Usize(_) => Some(IntType::UnsignedInt(UintTy::Us)),
             _ => None,
         }
@@ -299,12 +340,14 @@ impl ::std::fmt::Display for ConstInt {
             I16(i) => write!(fmt, "{}i16", i),
             I32(i) => write!(fmt, "{}i32", i),
             I64(i) => write!(fmt, "{}i64", i),
+            I128(i) => write!(fmt, "{}i128", i),
             Isize(ConstIsize::Is64(i)) => write!(fmt, "{}isize", i),
             Isize(ConstIsize::Is32(i)) => write!(fmt, "{}isize", i),
             U8(i) => write!(fmt, "{}u8", i),
             U16(i) => write!(fmt, "{}u16", i),
             U32(i) => write!(fmt, "{}u32", i),
             U64(i) => write!(fmt, "{}u64", i),
+            U128(i) => write!(fmt, "{}u128", i),
             Usize(ConstUsize::Us64(i)) => write!(fmt, "{}usize", i),
             Usize(ConstUsize::Us32(i)) => write!(fmt, "{}usize", i),
         }
@@ -331,12 +374,14 @@ macro_rules! impl_binop {
                     (I16(a), I16(b)) => a.$checked_func(b).map(I16),
                     (I32(a), I32(b)) => a.$checked_func(b).map(I32),
                     (I64(a), I64(b)) => a.$checked_func(b).map(I64),
+                    (I128(a), I128(b)) => a.$checked_func(b).map(I128),
                     (Isize(Is32(a)), Isize(Is32(b))) => a.$checked_func(b).map(Is32).map(Isize),
                     (Isize(Is64(a)), Isize(Is64(b))) => a.$checked_func(b).map(Is64).map(Isize),
                     (U8(a), U8(b)) => a.$checked_func(b).map(U8),
                     (U16(a), U16(b)) => a.$checked_func(b).map(U16),
                     (U32(a), U32(b)) => a.$checked_func(b).map(U32),
                     (U64(a), U64(b)) => a.$checked_func(b).map(U64),
+                    (U128(a), U128(b)) => a.$checked_func(b).map(U128),
                     (Usize(Us32(a)), Usize(Us32(b))) => a.$checked_func(b).map(Us32).map(Usize),
                     (Usize(Us64(a)), Usize(Us64(b))) => a.$checked_func(b).map(Us64).map(Usize),
                     (Infer(a), Infer(b)) => a.$checked_func(b).map(Infer),
@@ -358,12 +403,14 @@ macro_rules! derive_binop {
                     (I16(a), I16(b)) => Ok(I16(a.$func(b))),
                     (I32(a), I32(b)) => Ok(I32(a.$func(b))),
                     (I64(a), I64(b)) => Ok(I64(a.$func(b))),
+                    (I128(a), I128(b)) => Ok(I128(a.$func(b))),
                     (Isize(Is32(a)), Isize(Is32(b))) => Ok(Isize(Is32(a.$func(b)))),
                     (Isize(Is64(a)), Isize(Is64(b))) => Ok(Isize(Is64(a.$func(b)))),
                     (U8(a), U8(b)) => Ok(U8(a.$func(b))),
                     (U16(a), U16(b)) => Ok(U16(a.$func(b))),
                     (U32(a), U32(b)) => Ok(U32(a.$func(b))),
                     (U64(a), U64(b)) => Ok(U64(a.$func(b))),
+                    (U128(a), U128(b)) => Ok(U128(a.$func(b))),
                     (Usize(Us32(a)), Usize(Us32(b))) => Ok(Usize(Us32(a.$func(b)))),
                     (Usize(Us64(a)), Usize(Us64(b))) => Ok(Usize(Us64(a.$func(b)))),
                     (Infer(a), Infer(b)) => Ok(Infer(a.$func(b))),
@@ -393,6 +440,7 @@ fn check_division(
         (I16(_), I16(0)) => Err(zerr),
         (I32(_), I32(0)) => Err(zerr),
         (I64(_), I64(0)) => Err(zerr),
+        (I128(_), I128(0)) => Err(zerr),
         (Isize(_), Isize(Is32(0))) => Err(zerr),
         (Isize(_), Isize(Is64(0))) => Err(zerr),
         (InferSigned(_), InferSigned(0)) => Err(zerr),
@@ -401,6 +449,7 @@ fn check_division(
         (U16(_), U16(0)) => Err(zerr),
         (U32(_), U32(0)) => Err(zerr),
         (U64(_), U64(0)) => Err(zerr),
+        (U128(_), U128(0)) => Err(zerr),
         (Usize(_), Usize(Us32(0))) => Err(zerr),
         (Usize(_), Usize(Us64(0))) => Err(zerr),
         (Infer(_), Infer(0)) => Err(zerr),
@@ -409,9 +458,10 @@ fn check_division(
         (I16(::std::i16::MIN), I16(-1)) => Err(Overflow(op)),
         (I32(::std::i32::MIN), I32(-1)) => Err(Overflow(op)),
         (I64(::std::i64::MIN), I64(-1)) => Err(Overflow(op)),
+        (I128(a), I128(-1)) if a == i128::min_value() => Err(Overflow(op)),
         (Isize(Is32(::std::i32::MIN)), Isize(Is32(-1))) => Err(Overflow(op)),
         (Isize(Is64(::std::i64::MIN)), Isize(Is64(-1))) => Err(Overflow(op)),
-        (InferSigned(::std::i64::MIN), InferSigned(-1)) => Err(Overflow(op)),
+        (InferSigned(a), InferSigned(-1)) if a == i128::min_value() => Err(Overflow(op)),
 
         _ => Ok(()),
     }
@@ -427,6 +477,7 @@ impl ::std::ops::Div for ConstInt {
             (I16(a), I16(b)) => Ok(I16(a/b)),
             (I32(a), I32(b)) => Ok(I32(a/b)),
             (I64(a), I64(b)) => Ok(I64(a/b)),
+            (I128(a), I128(b)) => Ok(I128(a/b)),
             (Isize(Is32(a)), Isize(Is32(b))) => Ok(Isize(Is32(a/b))),
             (Isize(Is64(a)), Isize(Is64(b))) => Ok(Isize(Is64(a/b))),
             (InferSigned(a), InferSigned(b)) => Ok(InferSigned(a/b)),
@@ -435,6 +486,7 @@ impl ::std::ops::Div for ConstInt {
             (U16(a), U16(b)) => Ok(U16(a/b)),
             (U32(a), U32(b)) => Ok(U32(a/b)),
             (U64(a), U64(b)) => Ok(U64(a/b)),
+            (U128(a), U128(b)) => Ok(U128(a/b)),
             (Usize(Us32(a)), Usize(Us32(b))) => Ok(Usize(Us32(a/b))),
             (Usize(Us64(a)), Usize(Us64(b))) => Ok(Usize(Us64(a/b))),
             (Infer(a), Infer(b)) => Ok(Infer(a/b)),
@@ -455,6 +507,7 @@ impl ::std::ops::Rem for ConstInt {
             (I16(a), I16(b)) => Ok(I16(a%b)),
             (I32(a), I32(b)) => Ok(I32(a%b)),
             (I64(a), I64(b)) => Ok(I64(a%b)),
+            (I128(a), I128(b)) => Ok(I128(a%b)),
             (Isize(Is32(a)), Isize(Is32(b))) => Ok(Isize(Is32(a%b))),
             (Isize(Is64(a)), Isize(Is64(b))) => Ok(Isize(Is64(a%b))),
             (InferSigned(a), InferSigned(b)) => Ok(InferSigned(a%b)),
@@ -463,6 +516,7 @@ impl ::std::ops::Rem for ConstInt {
             (U16(a), U16(b)) => Ok(U16(a%b)),
             (U32(a), U32(b)) => Ok(U32(a%b)),
             (U64(a), U64(b)) => Ok(U64(a%b)),
+            (U128(a), U128(b)) => Ok(U128(a%b)),
             (Usize(Us32(a)), Usize(Us32(b))) => Ok(Usize(Us32(a%b))),
             (Usize(Us64(a)), Usize(Us64(b))) => Ok(Usize(Us64(a%b))),
             (Infer(a), Infer(b)) => Ok(Infer(a%b)),
@@ -481,12 +535,14 @@ impl ::std::ops::Shl<ConstInt> for ConstInt {
             I16(a) => Ok(I16(overflowing!(a.overflowing_shl(b), Op::Shl))),
             I32(a) => Ok(I32(overflowing!(a.overflowing_shl(b), Op::Shl))),
             I64(a) => Ok(I64(overflowing!(a.overflowing_shl(b), Op::Shl))),
+            I128(a) => Ok(I128(overflowing!(a.overflowing_shl(b), Op::Shl))),
             Isize(Is32(a)) => Ok(Isize(Is32(overflowing!(a.overflowing_shl(b), Op::Shl)))),
             Isize(Is64(a)) => Ok(Isize(Is64(overflowing!(a.overflowing_shl(b), Op::Shl)))),
             U8(a) => Ok(U8(overflowing!(a.overflowing_shl(b), Op::Shl))),
             U16(a) => Ok(U16(overflowing!(a.overflowing_shl(b), Op::Shl))),
             U32(a) => Ok(U32(overflowing!(a.overflowing_shl(b), Op::Shl))),
             U64(a) => Ok(U64(overflowing!(a.overflowing_shl(b), Op::Shl))),
+            U128(a) => Ok(U128(overflowing!(a.overflowing_shl(b), Op::Shl))),
             Usize(Us32(a)) => Ok(Usize(Us32(overflowing!(a.overflowing_shl(b), Op::Shl)))),
             Usize(Us64(a)) => Ok(Usize(Us64(overflowing!(a.overflowing_shl(b), Op::Shl)))),
             Infer(a) => Ok(Infer(overflowing!(a.overflowing_shl(b), Op::Shl))),
@@ -504,12 +560,14 @@ impl ::std::ops::Shr<ConstInt> for ConstInt {
             I16(a) => Ok(I16(overflowing!(a.overflowing_shr(b), Op::Shr))),
             I32(a) => Ok(I32(overflowing!(a.overflowing_shr(b), Op::Shr))),
             I64(a) => Ok(I64(overflowing!(a.overflowing_shr(b), Op::Shr))),
+            I128(a) => Ok(I128(overflowing!(a.overflowing_shr(b), Op::Shr))),
             Isize(Is32(a)) => Ok(Isize(Is32(overflowing!(a.overflowing_shr(b), Op::Shr)))),
             Isize(Is64(a)) => Ok(Isize(Is64(overflowing!(a.overflowing_shr(b), Op::Shr)))),
             U8(a) => Ok(U8(overflowing!(a.overflowing_shr(b), Op::Shr))),
             U16(a) => Ok(U16(overflowing!(a.overflowing_shr(b), Op::Shr))),
             U32(a) => Ok(U32(overflowing!(a.overflowing_shr(b), Op::Shr))),
             U64(a) => Ok(U64(overflowing!(a.overflowing_shr(b), Op::Shr))),
+            U128(a) => Ok(U128(overflowing!(a.overflowing_shr(b), Op::Shr))),
             Usize(Us32(a)) => Ok(Usize(Us32(overflowing!(a.overflowing_shr(b), Op::Shr)))),
             Usize(Us64(a)) => Ok(Usize(Us64(overflowing!(a.overflowing_shr(b), Op::Shr)))),
             Infer(a) => Ok(Infer(overflowing!(a.overflowing_shr(b), Op::Shr))),
@@ -526,20 +584,23 @@ impl ::std::ops::Neg for ConstInt {
             I16(a) => Ok(I16(overflowing!(a.overflowing_neg(), Op::Neg))),
             I32(a) => Ok(I32(overflowing!(a.overflowing_neg(), Op::Neg))),
             I64(a) => Ok(I64(overflowing!(a.overflowing_neg(), Op::Neg))),
+            I128(a) => Ok(I128(overflowing!(a.overflowing_neg(), Op::Neg))),
             Isize(Is32(a)) => Ok(Isize(Is32(overflowing!(a.overflowing_neg(), Op::Neg)))),
             Isize(Is64(a)) => Ok(Isize(Is64(overflowing!(a.overflowing_neg(), Op::Neg)))),
             U8(0) => Ok(U8(0)),
             U16(0) => Ok(U16(0)),
             U32(0) => Ok(U32(0)),
             U64(0) => Ok(U64(0)),
+            U128(0) => Ok(U128(0)),
             Usize(Us32(0)) => Ok(Usize(Us32(0))),
             Usize(Us64(0)) => Ok(Usize(Us64(0))),
             U8(_) => Err(UnsignedNegation),
             U16(_) => Err(UnsignedNegation),
             U32(_) => Err(UnsignedNegation),
             U64(_) => Err(UnsignedNegation),
+            U128(_) => Err(UnsignedNegation),
             Usize(_) => Err(UnsignedNegation),
-            Infer(a @ 0...as_u64::I64MAX) => Ok(InferSigned(-(a as i64))),
+            Infer(a) if a <= i128::max_value() as u128 => Ok(InferSigned(-(a as i128))),
             Infer(_) => Err(Overflow(Op::Neg)),
             InferSigned(a) => Ok(InferSigned(overflowing!(a.overflowing_neg(), Op::Neg))),
         }
@@ -554,12 +615,14 @@ impl ::std::ops::Not for ConstInt {
             I16(a) => Ok(I16(!a)),
             I32(a) => Ok(I32(!a)),
             I64(a) => Ok(I64(!a)),
+            I128(a) => Ok(I128(!a)),
             Isize(Is32(a)) => Ok(Isize(Is32(!a))),
             Isize(Is64(a)) => Ok(Isize(Is64(!a))),
             U8(a) => Ok(U8(!a)),
             U16(a) => Ok(U16(!a)),
             U32(a) => Ok(U32(!a)),
             U64(a) => Ok(U64(!a)),
+            U128(a) => Ok(U128(!a)),
             Usize(Us32(a)) => Ok(Usize(Us32(!a))),
             Usize(Us64(a)) => Ok(Usize(Us64(!a))),
             Infer(a) => Ok(Infer(!a)),
@@ -41,6 +41,7 @@ use rustc::hir::lowering::lower_crate;
 use rustc_passes::{no_asm, loops, consts, rvalues, static_recursion};
 use rustc_const_eval::check_match;
 use super::Compilation;
+use derive_registrar;
 
 use serialize::json;
 
@@ -95,7 +96,7 @@ pub fn compile_input(sess: &Session,
     // large chunks of memory alive and we want to free them as soon as
     // possible to keep the peak memory usage low
     let (outputs, trans) = {
-        let (outputs, expanded_crate, id) = {
+        let (outputs, expanded_crate, macro_scopes, id) = {
             let krate = match phase_1_parse_input(sess, cfg, input) {
                 Ok(krate) => krate,
                 Err(mut parse_error) => {
@@ -118,13 +119,13 @@ pub fn compile_input(sess: &Session,
 
             let outputs = build_output_filenames(input, outdir, output, &krate.attrs, sess);
             let id = link::find_crate_name(Some(sess), &krate.attrs, input);
-            let expanded_crate = phase_2_configure_and_expand(sess,
-                                                              &cstore,
-                                                              krate,
-                                                              &id,
-                                                              addl_plugins)?;
+            let (expanded_crate, macro_scopes) = phase_2_configure_and_expand(sess,
+                                                                              &cstore,
+                                                                              krate,
+                                                                              &id,
+                                                                              addl_plugins)?;
 
-            (outputs, expanded_crate, id)
+            (outputs, expanded_crate, macro_scopes, id)
         };
 
         controller_entry_point!(after_expand,
@@ -167,7 +168,7 @@ pub fn compile_input(sess: &Session,
              || lint::check_ast_crate(sess, &expanded_crate));
 
         let (analysis, resolutions, mut hir_forest) = {
-            lower_and_resolve(sess, &id, &mut defs, &expanded_crate,
+            lower_and_resolve(sess, &id, &mut defs, &expanded_crate, macro_scopes,
                               &sess.dep_graph, control.make_glob_map)
         };
 
@@ -580,13 +581,14 @@ fn count_nodes(krate: &ast::Crate) -> usize {
 /// harness if one is to be provided and injection of a dependency on the
 /// standard library and prelude.
 ///
-/// Returns `None` if we're aborting after handling -W help.
+/// Returns the expanded crate with the scopes of its `macro` items, which
+/// the resolver names them in.
 pub fn phase_2_configure_and_expand(sess: &Session,
                                     cstore: &CStore,
                                     mut krate: ast::Crate,
                                     crate_name: &str,
                                     addl_plugins: Option<Vec<String>>)
-                                    -> Result<ast::Crate, usize> {
+                                    -> Result<(ast::Crate, resolve::MacroScopes), usize> {
     let time_passes = sess.time_passes();
 
     // strip before anything else because crate metadata may use #[cfg_attr]
@@ -613,6 +615,10 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         middle::recursion_limit::update_recursion_limit(sess, &krate);
     });
 
+    time(time_passes, "size limits", || {
+        middle::size_limits::update_size_limits(sess, &krate);
+    });
+
     // these need to be set "early" so that expansion sees `quote` if enabled.
     sess.track_errors(|| {
         *sess.features.borrow_mut() =
@@ -683,6 +689,7 @@ pub fn phase_2_configure_and_expand(sess: &Session,
     }
     sess.track_errors(|| sess.lint_store.borrow_mut().process_command_line(sess))?;
 
+    let mut macro_scopes = resolve::MacroScopes::new();
     krate = time(time_passes, "expansion", || {
         // Windows dlls do not have rpaths, so they don't know how to find their
         // dependencies. It's up to us to tell the system where to find all the
@@ -707,7 +714,8 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         let mut ecx = syntax::ext::base::ExtCtxt::new(&sess.parse_sess,
                                                       krate.config.clone(),
                                                       cfg,
-                                                      &mut feature_gated_cfgs);
+                                                      &mut feature_gated_cfgs,
+                                                      &mut macro_scopes);
         syntax_ext::register_builtins(&mut ecx.syntax_env);
         let (ret, macro_names) = syntax::ext::expand::expand_crate(ecx,
                                                                    macros,
@@ -740,6 +748,18 @@ pub fn phase_2_configure_and_expand(sess: &Session,
                                          sess.diagnostic())
     });
 
+    krate = time(time_passes, "maybe creating a macro crate", || {
+        let crate_types = sess.crate_types.borrow();
+        let num_crate_types = crate_types.len();
+        let is_proc_macro_crate = crate_types.contains(&config::CrateTypeProcMacro);
+        syntax_ext::proc_macro_registrar::modify(&sess.parse_sess,
+                                                 krate,
+                                                 is_proc_macro_crate,
+                                                 num_crate_types,
+                                                 sess.diagnostic(),
+                                                 &sess.features.borrow())
+    });
+
     krate = time(time_passes,
                  "prelude injection",
                  || syntax::std_inject::maybe_inject_prelude(&sess.parse_sess, krate));
@@ -768,7 +788,7 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         println!("Post-expansion node count: {}", count_nodes(&krate));
     }
 
-    Ok(krate)
+    Ok((krate, macro_scopes))
 }
 
 pub fn assign_node_ids(sess: &Session, krate: ast::Crate) -> ast::Crate {
@@ -798,10 +818,11 @@ pub fn lower_and_resolve<'a>(sess: &Session,
                              id: &'a str,
                              defs: &mut hir_map::Definitions,
                              krate: &ast::Crate,
+                             macro_scopes: resolve::MacroScopes,
                              dep_graph: &DepGraph,
                              make_glob_map: resolve::MakeGlobMap)
                              -> (ty::CrateAnalysis<'a>, Resolutions, hir_map::Forest) {
-    resolve::with_resolver(sess, defs, make_glob_map, |mut resolver| {
+    resolve::with_resolver(sess, defs, make_glob_map, macro_scopes, |mut resolver| {
         time(sess.time_passes(), "name resolution", || {
             resolve::resolve_crate(&mut resolver, krate);
         });
@@ -875,6 +896,7 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
     sess.plugin_registrar_fn.set(time(time_passes, "looking for plugin registrar", || {
         plugin::build::find_plugin_registrar(sess.diagnostic(), &hir_map)
     }));
+    sess.derive_registrar_fn.set(derive_registrar::find(&hir_map));
 
     let region_map = time(time_passes,
                           "region resolution",
@@ -903,13 +925,20 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
                              index,
                              name,
                              |tcx| {
-        time(time_passes,
-             "load_dep_graph",
-             || rustc_incremental::load_dep_graph(tcx));
+        let previous_results =
+            time(time_passes,
+                 "load_dep_graph",
+                 || rustc_incremental::load_dep_graph(tcx));
 
         // passes are timed inside typeck
         try_with_f!(typeck::check_crate(tcx, trait_map), (tcx, None, analysis));
 
+        // Type checking skipped the items whose results are restored.
+        let restored_mir =
+            time(time_passes,
+                 "restoring previous results",
+                 || previous_results.restore(tcx));
+
         time(time_passes,
              "const checking",
              || consts::check_crate(tcx));
@@ -952,6 +981,12 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
             time(time_passes,
                  "MIR dump",
                  || mir::mir_map::build_mir_for_crate(tcx));
+        mir_map.map.extend(restored_mir);
+
+        let results =
+            time(time_passes,
+                 "encoding results",
+                 || rustc_incremental::encode_results(tcx, &mir_map));
 
         time(time_passes, "MIR passes", || {
             let mut passes = sess.mir_passes.borrow_mut();
@@ -1007,6 +1042,10 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
             return Ok(f(tcx, Some(mir_map), analysis, Err(sess.err_count())));
         }
 
+        time(time_passes,
+             "saving results",
+             || rustc_incremental::save_results(tcx, results));
+
         Ok(f(tcx, Some(mir_map), analysis, Ok(())))
     })
 }
@@ -1029,6 +1068,13 @@ pub fn phase_4_translate_to_llvm<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         passes.push_pass(box mir::transform::no_landing_pads::NoLandingPads);
         passes.push_pass(box mir::transform::remove_dead_blocks::RemoveDeadBlocks);
         passes.push_pass(box mir::transform::erase_regions::EraseRegions);
+        if tcx.sess.opts.mir_opt_level >= 2 {
+            passes.push_pass(box mir::transform::inline::Inline);
+            passes.push_pass(box mir::transform::const_propagate::ConstPropagate);
+            passes.push_pass(box mir::transform::copy_propagation::CopyPropagation);
+            passes.push_pass(box mir::transform::dead_store_elimination::DeadStoreElimination);
+            passes.push_pass(box mir::transform::simplify_cfg::SimplifyCfg);
+        }
         passes.push_pass(box mir::transform::break_cleanup_edges::BreakCleanupEdges);
         passes.run_passes(tcx, &mut mir_map);
     });
@@ -1178,6 +1224,9 @@ pub fn collect_crate_types(session: &Session, attrs: &[ast::Attribute]) -> Vec<c
                              Some(config::CrateTypeStaticlib)
                          }
                          Some(ref n) if *n == "bin" => Some(config::CrateTypeExecutable),
+                         Some(ref n) if *n == "proc-macro" => {
+                             Some(config::CrateTypeProcMacro)
+                         }
                          Some(_) => {
                              session.add_lint(lint::builtin::UNKNOWN_CRATE_TYPES,
                                               ast::CRATE_NODE_ID,
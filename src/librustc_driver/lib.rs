@@ -76,11 +76,14 @@ use rustc::lint;
 use rustc_metadata::loader;
 use rustc_metadata::cstore::CStore;
 use rustc::util::common::time;
+use rustc::util::profiling;
+use serialize::json::ToJson;
 
 use std::cmp::max;
 use std::cmp::Ordering::Equal;
 use std::default::Default;
 use std::env;
+use std::fs::File;
 use std::io::{self, Read, Write};
 use std::iter::repeat;
 use std::path::PathBuf;
@@ -103,7 +106,9 @@ pub mod test;
 
 pub mod driver;
 pub mod pretty;
+pub mod derive_registrar;
 pub mod target_features;
+mod suggestions;
 
 
 const BUG_REPORT_URL: &'static str = "https://github.com/rust-lang/rust/blob/master/CONTRIBUTING.\
@@ -214,9 +219,37 @@ pub fn run_compiler_with_file_loader<'a, L>(args: &[String],
 
     let plugins = sess.opts.debugging_opts.extra_plugins.clone();
     let control = callbacks.build_controller(&sess, &matches);
-    (driver::compile_input(&sess, &cstore, cfg, &input, &odir, &ofile,
-                           Some(plugins), &control),
-     Some(sess))
+    if sess.opts.debugging_opts.self_profile {
+        profiling::install(Some(profiling::SelfProfiler::new()));
+    }
+    let result = driver::compile_input(&sess, &cstore, cfg, &input, &odir, &ofile,
+                                       Some(plugins), &control);
+    if let Some(profiler) = profiling::install(None) {
+        write_self_profile(&sess, &profiler, &input, &odir);
+    }
+    if sess.opts.debugging_opts.apply_suggestions {
+        suggestions::apply_suggestions(&sess);
+    }
+    (result, Some(sess))
+}
+
+// Write the event trace of `-Z self-profile` next to the outputs, and print
+// the summary of the slowest items.
+fn write_self_profile(sess: &Session,
+                      profiler: &profiling::SelfProfiler,
+                      input: &Input,
+                      odir: &Option<PathBuf>) {
+    let name = format!("{}.self-profile.json", input.filestem());
+    let path = match *odir {
+        Some(ref dir) => dir.join(name),
+        None => PathBuf::from(name),
+    };
+    let written = File::create(&path).and_then(|mut f| profiler.write_trace(&mut f));
+    if let Err(e) = written {
+        sess.err(&format!("could not write self-profile to `{}`: {}", path.display(), e));
+        return;
+    }
+    profiler.print_summary();
 }
 
 // Extract output directory and file from matches.
@@ -371,7 +404,8 @@ fn handle_explain(code: &str,
 fn check_cfg(sopts: &config::Options,
              output: ErrorOutputType) {
     let mut emitter: Box<Emitter> = match output {
-        config::ErrorOutputType::HumanReadable(color_config) => {
+        config::ErrorOutputType::HumanReadable(color_config) |
+        config::ErrorOutputType::Short(color_config) => {
             Box::new(errors::emitter::BasicEmitter::stderr(color_config))
         }
         config::ErrorOutputType::Json => Box::new(errors::json::JsonEmitter::basic()),
@@ -577,7 +611,9 @@ impl RustcDefaultCalls {
                         odir: &Option<PathBuf>,
                         ofile: &Option<PathBuf>)
                         -> Compilation {
-        if sess.opts.prints.is_empty() {
+        // The native libraries of a static library are only known once it
+        // has been linked, so that request is handled by the linking code.
+        if sess.opts.prints.iter().all(|p| *p == PrintRequest::NativeStaticLibs) {
             return Compilation::Continue;
         }
 
@@ -601,6 +637,7 @@ impl RustcDefaultCalls {
                     targets.sort();
                     println!("{}", targets.join("\n"));
                 },
+                PrintRequest::TargetSpec => println!("{}", sess.target.target.to_json().pretty()),
                 PrintRequest::Sysroot => println!("{}", sess.sysroot().display()),
                 PrintRequest::FileNames |
                 PrintRequest::CrateName => {
@@ -654,6 +691,9 @@ impl RustcDefaultCalls {
                         }
                     }
                 }
+                PrintRequest::TargetCPUs => target_features::print_target_cpus(sess),
+                PrintRequest::TargetFeatures => target_features::print_target_features(sess),
+                PrintRequest::NativeStaticLibs => {}
             }
         }
         return Compilation::Stop;
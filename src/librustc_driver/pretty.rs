@@ -42,7 +42,6 @@ use graphviz as dot;
 
 use std::fs::File;
 use std::io::{self, Write};
-use std::iter;
 use std::option;
 use std::path::Path;
 use std::str::FromStr;
@@ -135,7 +134,8 @@ pub fn parse_pretty(sess: &Session,
                 sess.fatal(&format!("argument to `unpretty` must be one of `normal`, \
                                      `expanded`, `flowgraph[,unlabelled]=<nodeid>`, \
                                      `identified`, `expanded,identified`, `everybody_loops`, \
-                                     `hir`, `hir,identified`, `hir,typed`, or `mir`; got {}",
+                                     `hir`, `hir,identified`, `hir,typed`, `mir` or \
+                                     `mir-cfg`; got {}",
                                     name));
             } else {
                 sess.fatal(&format!("argument to `pretty` must be one of `normal`, `expanded`, \
@@ -932,12 +932,22 @@ fn print_with_analysis<'tcx, 'a: 'tcx>(sess: &'a Session,
                                        ppm: PpMode,
                                        uii: Option<UserIdentifiedItem>,
                                        ofile: Option<&Path>) {
-    let nodeid = if let Some(uii) = uii {
-        debug!("pretty printing for {:?}", uii);
-        Some(uii.to_one_node_id("--unpretty", sess, &ast_map))
-    } else {
-        debug!("pretty printing for whole crate");
-        None
+    // A flowgraph is drawn for a single node, while MIR is printed for
+    // every item the path suffix matches.
+    let (nodeid, mir_filter) = match uii {
+        Some(uii) => {
+            debug!("pretty printing for {:?}", uii);
+            if let PpmFlowGraph(_) = ppm {
+                (Some(uii.to_one_node_id("--unpretty", sess, &ast_map)), None)
+            } else {
+                let nodeids: Vec<_> = uii.all_matching_node_ids(&ast_map).collect();
+                (None, Some((uii.reconstructed_input(), nodeids)))
+            }
+        }
+        None => {
+            debug!("pretty printing for whole crate");
+            (None, None)
+        }
     };
 
     let mut out = Vec::new();
@@ -952,24 +962,24 @@ fn print_with_analysis<'tcx, 'a: 'tcx>(sess: &'a Session,
         match ppm {
             PpmMir | PpmMirCFG => {
                 if let Some(mir_map) = mir_map {
-                    if let Some(nodeid) = nodeid {
-                        let mir = mir_map.map.get(&nodeid).unwrap_or_else(|| {
-                            sess.fatal(&format!("no MIR map entry for node {}", nodeid))
-                        });
-                        match ppm {
-                            PpmMir => write_mir_pretty(tcx, iter::once((&nodeid, mir)), &mut out),
-                            PpmMirCFG => {
-                                write_mir_graphviz(tcx, iter::once((&nodeid, mir)), &mut out)
-                            }
-                            _ => unreachable!(),
-                        }?;
-                    } else {
-                        match ppm {
-                            PpmMir => write_mir_pretty(tcx, mir_map.map.iter(), &mut out),
-                            PpmMirCFG => write_mir_graphviz(tcx, mir_map.map.iter(), &mut out),
-                            _ => unreachable!(),
-                        }?;
+                    let mut mirs: Vec<_> = mir_map.map.iter().filter(|&(id, _)| {
+                        mir_filter.as_ref().map_or(true, |&(_, ref nodeids)| {
+                            nodeids.contains(id)
+                        })
+                    }).collect();
+                    if mirs.is_empty() {
+                        if let Some((ref path, _)) = mir_filter {
+                            sess.fatal(&format!("no MIR map entry for `{}`", path));
+                        }
                     }
+                    // Print the items in the order they are defined in, not
+                    // in the order of the map, so that the output is stable.
+                    mirs.sort_by_key(|&(&id, _)| id);
+                    match ppm {
+                        PpmMir => write_mir_pretty(tcx, mirs.into_iter(), &mut out),
+                        PpmMirCFG => write_mir_graphviz(tcx, mirs.into_iter(), &mut out),
+                        _ => unreachable!(),
+                    }?;
                 }
                 Ok(())
             }
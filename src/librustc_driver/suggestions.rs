@@ -0,0 +1,133 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rewriting of the source files with the suggestions of the diagnostics,
+//! for `-Z apply-suggestions`. Only the suggestions marked as
+//! `MachineApplicable` are collected; of those, the ones that touch code
+//! from a macro expansion, whose parts overlap one another, or that
+//! overlap a suggestion applied before them, are left alone.
+
+use rustc::session::Session;
+use syntax::codemap::{CodeMap, FileMap, NO_EXPANSION, Pos};
+use syntax::errors::CodeSuggestion;
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::rc::Rc;
+
+/// A replacement of a byte range of a file.
+#[derive(PartialEq)]
+struct Replacement {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Replacement {
+    fn overlaps(&self, other: &Replacement) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+pub fn apply_suggestions(sess: &Session) {
+    let mut files: BTreeMap<String, (Rc<FileMap>, Vec<Vec<Replacement>>)> = BTreeMap::new();
+    for suggestion in sess.diagnostic().take_suggestions() {
+        if let Some((fm, parts)) = replacements(sess.codemap(), &suggestion) {
+            let suggestions = &mut files.entry(fm.name.clone()).or_insert((fm, vec![])).1;
+            // The same diagnostic may have been reported more than once.
+            if !suggestions.contains(&parts) {
+                suggestions.push(parts);
+            }
+        }
+    }
+
+    for (name, (fm, suggestions)) in files {
+        let src = match fm.src {
+            Some(ref src) => src,
+            None => continue,
+        };
+
+        let mut applied = 0;
+        let mut skipped = 0;
+        let mut accepted: Vec<Replacement> = vec![];
+        for parts in suggestions {
+            if parts.iter().any(|part| accepted.iter().any(|r| part.overlaps(r))) {
+                skipped += 1;
+                continue;
+            }
+            applied += 1;
+            accepted.extend(parts);
+        }
+        // Insertions at the same position stay in the order in which
+        // they were suggested, ahead of a replacement starting there.
+        accepted.sort_by_key(|r| (r.start, r.end));
+
+        let mut result = String::with_capacity(src.len());
+        let mut pos = 0;
+        for r in &accepted {
+            result.push_str(&src[pos..r.start]);
+            result.push_str(&r.text);
+            pos = r.end;
+        }
+        result.push_str(&src[pos..]);
+
+        if let Err(e) = File::create(&name).and_then(|mut f| f.write_all(result.as_bytes())) {
+            sess.err(&format!("could not write suggestions to `{}`: {}", name, e));
+            continue;
+        }
+        sess.note_without_error(&format!("applied {} suggestion{} to `{}`",
+                                         applied,
+                                         if applied == 1 { "" } else { "s" },
+                                         name));
+        if skipped > 0 {
+            sess.note_without_error(&format!("{} overlapping suggestion{} in `{}` \
+                                              could not be applied; compile again to \
+                                              apply {}",
+                                             skipped,
+                                             if skipped == 1 { "" } else { "s" },
+                                             name,
+                                             if skipped == 1 { "it" } else { "them" }));
+        }
+    }
+}
+
+/// The file a suggestion edits and its replacements, or `None` if it
+/// cannot be applied to the source as written.
+fn replacements(codemap: &CodeMap, suggestion: &CodeSuggestion)
+                -> Option<(Rc<FileMap>, Vec<Replacement>)> {
+    let mut file: Option<Rc<FileMap>> = None;
+    let mut parts = vec![];
+    for (span, text) in suggestion.substitution_parts() {
+        if span.expn_id != NO_EXPANSION || span.lo > span.hi {
+            return None;
+        }
+        let fm = codemap.lookup_byte_offset(span.lo).fm;
+        if !fm.is_real_file() || fm.is_imported() || span.hi > fm.end_pos {
+            return None;
+        }
+        if let Some(ref file) = file {
+            if file.name != fm.name {
+                return None;
+            }
+        }
+        let part = Replacement {
+            start: (span.lo - fm.start_pos).to_usize(),
+            end: (span.hi - fm.start_pos).to_usize(),
+            text: text.to_string(),
+        };
+        if parts.iter().any(|p| part.overlaps(p)) {
+            return None;
+        }
+        parts.push(part);
+        file = Some(fm);
+    }
+    file.map(|file| (file, parts))
+}
@@ -9,12 +9,14 @@
 // except according to those terms.
 
 use syntax::{ast, attr};
-use llvm::LLVMRustHasFeature;
+use llvm::{self, LLVMRustHasFeature, LLVMRustDisposeTargetMachine, TargetMachineRef};
 use rustc::session::Session;
 use rustc_trans::back::write::create_target_machine;
 use syntax::parse::token::InternedString;
 use syntax::parse::token::intern_and_get_ident as intern;
-use libc::c_char;
+use libc::{c_char, size_t};
+use std::ffi::CStr;
+use std::ptr;
 
 // WARNING: the features must be known to LLVM or the feature
 // detection code will walk past the end of the feature array,
@@ -60,3 +62,58 @@ pub fn add_configuration(cfg: &mut ast::CrateConfig, sess: &Session) {
         }
     }
 }
+
+/// Print the CPUs LLVM knows for the target, for `--print target-cpus`.
+pub fn print_target_cpus(sess: &Session) {
+    let target_machine = create_target_machine(sess);
+    let cpus = unsafe {
+        let cpus = llvm_table(target_machine,
+                              llvm::LLVMRustGetTargetCPUsCount,
+                              llvm::LLVMRustGetTargetCPU);
+        LLVMRustDisposeTargetMachine(target_machine);
+        cpus
+    };
+    print_table("CPUs", &cpus);
+}
+
+/// Print the features LLVM knows for the target, for `--print target-features`.
+pub fn print_target_features(sess: &Session) {
+    let target_machine = create_target_machine(sess);
+    let features = unsafe {
+        let features = llvm_table(target_machine,
+                                  llvm::LLVMRustGetTargetFeaturesCount,
+                                  llvm::LLVMRustGetTargetFeature);
+        LLVMRustDisposeTargetMachine(target_machine);
+        features
+    };
+    print_table("features", &features);
+}
+
+/// The names and descriptions of the entries of one of the CPU and feature
+/// tables of a target machine.
+unsafe fn llvm_table(target_machine: TargetMachineRef,
+                     count: unsafe extern "C" fn(TargetMachineRef) -> size_t,
+                     entry: unsafe extern "C" fn(TargetMachineRef,
+                                                 size_t,
+                                                 *mut *const c_char,
+                                                 *mut *const c_char))
+                     -> Vec<(String, String)> {
+    (0..count(target_machine)).map(|index| {
+        let mut name = ptr::null();
+        let mut desc = ptr::null();
+        entry(target_machine, index, &mut name, &mut desc);
+        (CStr::from_ptr(name).to_string_lossy().into_owned(),
+         CStr::from_ptr(desc).to_string_lossy().into_owned())
+    }).collect()
+}
+
+/// Print a table in the layout of LLVM's own listing.
+fn print_table(what: &str, entries: &[(String, String)]) {
+    let width = entries.iter().map(|&(ref name, _)| name.len()).max().unwrap_or(0);
+    println!("Available {} for this target:", what);
+    println!("");
+    for &(ref name, ref desc) in entries {
+        println!("  {:<width$} - {}.", name, desc, width = width);
+    }
+    println!("");
+}
@@ -26,6 +26,7 @@ extern crate graphviz;
 extern crate rbml;
 #[macro_use] extern crate rustc;
 extern crate rustc_data_structures;
+extern crate rustc_metadata;
 extern crate serialize as rustc_serialize;
 
 #[macro_use] extern crate log;
@@ -39,3 +40,4 @@ pub use assert_dep_graph::assert_dep_graph;
 pub use calculate_svh::SvhCalculate;
 pub use persist::load_dep_graph;
 pub use persist::save_dep_graph;
+pub use persist::{encode_results, save_results, EncodedResults, PreviousResults};
@@ -11,13 +11,26 @@
 //! The data that we will serialize and deserialize.
 
 use rustc::dep_graph::DepNode;
+use rustc::hir::def::Def;
 use rustc::hir::def_id::DefIndex;
+use rustc::hir::map::DefPath;
+use rustc::mir::repr::Mir;
+use rustc::ty::{self, Ty};
+use rustc::ty::adjustment::AutoAdjustment;
+use rustc::ty::cast::CastKind;
+use syntax::ast;
+use syntax::codemap::Span;
 
 use super::directory::DefPathIndex;
 
 /// Data for use when recompiling the **current crate**.
 #[derive(Debug, RustcEncodable, RustcDecodable)]
 pub struct SerializedDepGraph {
+    /// Hash of the crate this graph was saved for, to tell whether
+    /// the `SerializedResults` saved alongside it are from the same
+    /// compilation; see `results.rs`.
+    pub krate_hash: u64,
+
     pub nodes: Vec<DepNode<DefPathIndex>>,
     pub edges: Vec<SerializedEdge>,
 
@@ -89,3 +102,103 @@ pub struct SerializedMetadataHash {
     /// the hash itself, computed by `calculate_item_hash`
     pub hash: u64,
 }
+
+/// The results of analyzing the items of the **current crate**, for
+/// use when it is recompiled. See `results.rs`.
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub struct SerializedResults {
+    /// Hash of the crate these results were computed for. They are
+    /// saved before compilation is finished, so they are only used if
+    /// the dep-graph was saved with the same hash.
+    pub krate_hash: u64,
+
+    /// The upstream crates. Def-ids from other crates are saved as
+    /// they are, so the results are only used if these are unchanged.
+    pub crates: Vec<SerializedCrate>,
+
+    /// The def-path of each local def-index the results mention.
+    pub local_def_paths: Vec<(DefIndex, DefPath)>,
+
+    /// The trait imports that method calls were resolved through, as
+    /// the `use` item and the index of the import within its node-ids.
+    pub used_trait_imports: Vec<(DefIndex, u32)>,
+
+    pub items: Vec<SerializedItemResults>,
+
+    /// The encoded `SerializedItemData` of each item. Types are encoded
+    /// with the metadata type encoder, whose abbreviations refer to
+    /// positions in this buffer.
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub struct SerializedCrate {
+    pub cnum: ast::CrateNum,
+    pub name: String,
+    pub hash: u64,
+}
+
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub struct SerializedItemResults {
+    pub def_index: DefIndex,
+
+    /// Hash of the source text of the item. The HIR hash does not
+    /// cover spans, but the spans in the MIR must still be correct.
+    pub source_hash: u64,
+
+    /// The number of node-ids in the item; the results refer to them
+    /// by their index.
+    pub node_count: u32,
+
+    /// The local def-ids mentioned in the results, all of which must
+    /// still exist for the results to be used.
+    pub local_def_indices: Vec<DefIndex>,
+
+    /// The lints added while analyzing the item, which are added again
+    /// when its results are restored.
+    pub lints: Vec<SerializedLint>,
+
+    /// The `mut` bindings of the item that borrow checking found to be
+    /// used, by index.
+    pub used_mut_nodes: Vec<u32>,
+
+    /// Where the `SerializedItemData` starts in `SerializedResults::data`.
+    pub position: usize,
+}
+
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub struct SerializedLint {
+    /// The name of the lint, as it is registered in the lint store.
+    pub name: String,
+
+    /// The index of the node the lint is for.
+    pub node: u32,
+
+    /// The span of the lint, relative to the item.
+    pub span: Span,
+
+    pub msg: String,
+}
+
+/// The typeck tables and the MIR of one item. Nodes are referred to by
+/// their index within the item's node-ids.
+#[derive(RustcEncodable, RustcDecodable)]
+pub struct SerializedItemData<'tcx> {
+    pub node_types: Vec<(u32, Ty<'tcx>)>,
+    pub item_substs: Vec<(u32, ty::ItemSubsts<'tcx>)>,
+    pub adjustments: Vec<(u32, AutoAdjustment<'tcx>)>,
+    pub method_map: Vec<(u32, u32, ty::MethodCallee<'tcx>)>,
+    pub upvar_capture_map: Vec<(u32, u32, ty::UpvarCapture)>,
+    pub closure_tys: Vec<(u32, ty::ClosureTy<'tcx>)>,
+    pub closure_kinds: Vec<(u32, ty::ClosureKind)>,
+    pub liberated_fn_sigs: Vec<(u32, ty::FnSig<'tcx>)>,
+    pub fru_field_types: Vec<(u32, Vec<Ty<'tcx>>)>,
+    pub cast_kinds: Vec<(u32, CastKind)>,
+
+    /// The fully resolved paths, of which type checking completes
+    /// those that name associated items.
+    pub path_resolutions: Vec<(u32, Def)>,
+
+    /// The MIR as built, before any passes have run on it.
+    pub mir: Vec<(u32, Mir<'tcx>)>,
+}
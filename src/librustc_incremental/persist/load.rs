@@ -15,7 +15,7 @@ use rbml::opaque::Decoder;
 use rustc::dep_graph::DepNode;
 use rustc::hir::def_id::DefId;
 use rustc::ty::TyCtxt;
-use rustc_data_structures::fnv::FnvHashSet;
+use rustc_data_structures::fnv::{FnvHashMap, FnvHashSet};
 use rustc_serialize::Decodable as RustcDecodable;
 use std::io::Read;
 use std::fs::File;
@@ -25,31 +25,45 @@ use super::data::*;
 use super::directory::*;
 use super::dirty_clean;
 use super::hash::*;
+use super::results::{self, PreviousResults};
 use super::util::*;
 
-type DirtyNodes = FnvHashSet<DepNode<DefId>>;
+pub type GreenNodes = FnvHashSet<DepNode<DefId>>;
 
-type CleanEdges = Vec<(DepNode<DefId>, DepNode<DefId>)>;
+/// What `decode_dep_graph` found out about the previous compilation.
+pub struct PreviousDepGraph {
+    /// The `krate_hash` the dep-graph was saved with.
+    pub krate_hash: u64,
+
+    /// The nodes whose inputs are all unchanged.
+    pub green_nodes: GreenNodes,
+}
 
 /// If we are in incremental mode, and a previous dep-graph exists,
 /// then load up those nodes/edges that are still valid into the
-/// dep-graph for this session. (This is assumed to be running very
-/// early in compilation, before we've really done any work, but
-/// actually it doesn't matter all that much.) See `README.md` for
-/// more general overview.
-pub fn load_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+/// dep-graph for this session, along with the results of analyzing
+/// the items that are unchanged. (This is assumed to be running very
+/// early in compilation, before we've really done any work.) See
+/// `README.md` for more general overview.
+pub fn load_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) -> PreviousResults {
     let _ignore = tcx.dep_graph.in_ignore();
 
+    let mut previous_results = PreviousResults::none();
     if let Some(dep_graph) = dep_graph_path(tcx) {
         // FIXME(#32754) lock file?
-        load_dep_graph_if_exists(tcx, &dep_graph);
+        if let Some(previous) = load_dep_graph_if_exists(tcx, &dep_graph) {
+            previous_results = results::load_results(tcx, &previous);
+        }
         dirty_clean::check_dirty_clean_annotations(tcx);
     }
+    previous_results
 }
 
-pub fn load_dep_graph_if_exists<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, path: &Path) {
+pub fn load_dep_graph_if_exists<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                          path: &Path)
+                                          -> Option<PreviousDepGraph> {
     if !path.exists() {
-        return;
+        return None;
     }
 
     let mut data = vec![];
@@ -62,12 +76,12 @@ pub fn load_dep_graph_if_exists<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, path: &Pa
             tcx.sess.err(
                 &format!("could not load dep-graph from `{}`: {}",
                          path.display(), err));
-            return;
+            return None;
         }
     }
 
     match decode_dep_graph(tcx, &data) {
-        Ok(dirty) => dirty,
+        Ok(previous) => Some(previous),
         Err(err) => {
             bug!("decoding error in dep-graph from `{}`: {}", path.display(), err);
         }
@@ -76,7 +90,7 @@ pub fn load_dep_graph_if_exists<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, path: &Pa
 
 pub fn decode_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                   data: &[u8])
-                                  -> Result<(), Error>
+                                  -> Result<PreviousDepGraph, Error>
 {
     // Deserialize the directory and dep-graph.
     let mut decoder = Decoder::new(data, 0);
@@ -91,125 +105,184 @@ pub fn decode_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
     debug!("decode_dep_graph: retraced = {:#?}", retraced);
 
-    // Compute the set of Hir nodes whose data has changed.
-    let mut dirty_nodes =
-        initial_dirty_nodes(tcx, &serialized_dep_graph.hashes, &retraced);
-
-    debug!("decode_dep_graph: initial dirty_nodes = {:#?}", dirty_nodes);
-
-    // Find all DepNodes reachable from that core set. This loop
-    // iterates repeatedly over the list of edges whose source is not
-    // known to be dirty (`clean_edges`). If it finds an edge whose
-    // source is dirty, it removes it from that list and adds the
-    // target to `dirty_nodes`. It stops when it reaches a fixed
-    // point.
-    let clean_edges = compute_clean_edges(&serialized_dep_graph.edges,
-                                          &retraced,
-                                          &mut dirty_nodes);
-
-    // Add synthetic `foo->foo` edges for each clean node `foo` that
-    // we had before. This is sort of a hack to create clean nodes in
-    // the graph, since the existence of a node is a signal that the
-    // work it represents need not be repeated.
-    let clean_nodes =
+    // Compare the hashes of the inputs (Hir and MetaData nodes) to
+    // their current ones, then decide for every other node whether
+    // all of its inputs are unchanged.
+    let mut marker = GreenMarker::new(tcx,
+                                      &serialized_dep_graph.hashes,
+                                      &serialized_dep_graph.edges,
+                                      &retraced);
+    let green_nodes: GreenNodes =
         serialized_dep_graph.nodes
                             .iter()
                             .filter_map(|&node| retraced.map(node))
-                            .filter(|node| !dirty_nodes.contains(node))
-                            .map(|node| (node, node));
+                            .filter(|&node| marker.is_green(node))
+                            .collect();
+
+    debug!("decode_dep_graph: green_nodes = {:#?}", green_nodes);
 
-    // Add nodes and edges that are not dirty into our main graph.
+    // The edges into green nodes, whose sources are green too.
+    let green_edges =
+        serialized_dep_graph.edges
+                            .iter()
+                            .filter_map(|&(source, target)| {
+                                match (retraced.map(source), retraced.map(target)) {
+                                    (Some(source), Some(target)) => Some((source, target)),
+                                    _ => None,
+                                }
+                            })
+                            .filter(|&(source, target)| {
+                                source != target && green_nodes.contains(&target)
+                            });
+
+    // Add synthetic `foo->foo` edges for each green node `foo`. This
+    // is sort of a hack to create green nodes in the graph, since the
+    // existence of a node is a signal that the work it represents
+    // need not be repeated.
+    let green_self_edges = green_nodes.iter().map(|&node| (node, node));
+
+    // Add the green nodes and edges into our main graph.
     let dep_graph = tcx.dep_graph.clone();
-    for (source, target) in clean_edges.into_iter().chain(clean_nodes) {
+    for (source, target) in green_edges.chain(green_self_edges) {
         let _task = dep_graph.in_task(target);
         dep_graph.read(source);
 
-        debug!("decode_dep_graph: clean edge: {:?} -> {:?}", source, target);
+        debug!("decode_dep_graph: green edge: {:?} -> {:?}", source, target);
     }
 
-    Ok(())
+    // Type checking and building MIR can only be skipped if their
+    // results are restored too; `results::load_results` marks those.
+    for &node in &green_nodes {
+        match node {
+            DepNode::TypeckItemBody(_) | DepNode::MirMapConstruction(_) => {}
+            _ => dep_graph.mark_green(node),
+        }
+    }
+
+    Ok(PreviousDepGraph {
+        krate_hash: serialized_dep_graph.krate_hash,
+        green_nodes: green_nodes,
+    })
 }
 
-fn initial_dirty_nodes<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
-                                 hashes: &[SerializedHash],
-                                 retraced: &RetracedDefIdDirectory)
-                                 -> DirtyNodes {
-    let mut hcx = HashContext::new(tcx);
-    let mut items_removed = false;
-    let mut dirty_nodes = FnvHashSet();
-    for hash in hashes {
-        match hash.node.map_def(|&i| retraced.def_id(i)) {
-            Some(dep_node) => {
-                let current_hash = hcx.hash(dep_node).unwrap();
-                debug!("initial_dirty_nodes: hash of {:?} is {:?}, was {:?}",
-                       dep_node, current_hash, hash.hash);
-                if current_hash != hash.hash {
-                    dirty_nodes.insert(dep_node);
+/// Decides which nodes of the previous dep-graph are green, meaning
+/// that the work they represent need not be repeated. An input is
+/// green if its hash is unchanged; any other node is green if it
+/// still exists and all its predecessors are green.
+struct GreenMarker {
+    predecessors: FnvHashMap<DepNode<DefId>, Vec<DepNode<DefId>>>,
+
+    /// Whether the nodes visited so far are green; `None` while the
+    /// predecessors of a node are being visited.
+    colors: FnvHashMap<DepNode<DefId>, Option<bool>>,
+}
+
+impl GreenMarker {
+    fn new<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                     hashes: &[SerializedHash],
+                     serialized_edges: &[SerializedEdge],
+                     retraced: &RetracedDefIdDirectory)
+                     -> GreenMarker {
+        let mut colors = FnvHashMap();
+        let mut predecessors = FnvHashMap();
+
+        let mut hcx = HashContext::new(tcx);
+        let mut items_changed = false;
+        for hash in hashes {
+            match hash.node.map_def(|&i| retraced.def_id(i)) {
+                Some(dep_node) => {
+                    let current_hash = hcx.hash(dep_node).unwrap();
+                    debug!("GreenMarker: hash of {:?} is {:?}, was {:?}",
+                           dep_node, current_hash, hash.hash);
+                    let unchanged = current_hash == hash.hash;
+                    items_changed |= !unchanged;
+                    colors.insert(dep_node, Some(unchanged));
+                }
+                None => {
+                    items_changed = true;
                 }
             }
-            None => {
-                items_removed = true;
+        }
+
+        // If any of the items in the krate have changed, then we consider
+        // the meta-node `Krate` to be red, since that means something
+        // which (potentially) read the contents of every single item.
+        colors.insert(DepNode::Krate, Some(!items_changed));
+
+        for &(serialized_source, serialized_target) in serialized_edges {
+            if let Some(target) = retraced.map(serialized_target) {
+                if let Some(source) = retraced.map(serialized_source) {
+                    // Ignore the synthetic self-edges from the last
+                    // time the graph was loaded.
+                    if source != target {
+                        predecessors.entry(target).or_insert(vec![]).push(source);
+                    }
+                } else {
+                    // source removed, target must be red
+                    colors.insert(target, Some(false));
+                }
+            } else {
+                // target removed, ignore the edge
             }
         }
-    }
 
-    // If any of the items in the krate have changed, then we consider
-    // the meta-node `Krate` to be dirty, since that means something
-    // which (potentially) read the contents of every single item.
-    if items_removed || !dirty_nodes.is_empty() {
-        dirty_nodes.insert(DepNode::Krate);
+        GreenMarker {
+            predecessors: predecessors,
+            colors: colors,
+        }
     }
 
-    dirty_nodes
-}
+    fn is_green(&mut self, node: DepNode<DefId>) -> bool {
+        if let Some(green) = self.color(&node) {
+            return green;
+        }
 
-fn compute_clean_edges(serialized_edges: &[(SerializedEdge)],
-                       retraced: &RetracedDefIdDirectory,
-                       dirty_nodes: &mut DirtyNodes)
-                       -> CleanEdges {
-    // Build up an initial list of edges. Include an edge (source,
-    // target) if neither node has been removed. If the source has
-    // been removed, add target to the list of dirty nodes.
-    let mut clean_edges = Vec::with_capacity(serialized_edges.len());
-    for &(serialized_source, serialized_target) in serialized_edges {
-        if let Some(target) = retraced.map(serialized_target) {
-            if let Some(source) = retraced.map(serialized_source) {
-                clean_edges.push((source, target))
-            } else {
-                // source removed, target must be dirty
-                dirty_nodes.insert(target);
+        // The dep-graph can be too deep to recurse over, so the nodes
+        // being colored are kept on a stack, each with the predecessors
+        // it has left to check.
+        let mut stack = vec![self.start(node)];
+        let mut finished = None;
+        loop {
+            let green = match finished.take() {
+                // A predecessor is red.
+                Some(false) => false,
+                _ => match stack.last_mut().unwrap().1.pop() {
+                    Some(source) => match self.color(&source) {
+                        Some(true) => continue,
+                        Some(false) => false,
+                        None => {
+                            let started = self.start(source);
+                            stack.push(started);
+                            continue;
+                        }
+                    },
+                    None => true,
+                },
+            };
+
+            let (node, _) = stack.pop().unwrap();
+            debug!("GreenMarker: {:?} is {}", node, if green { "green" } else { "red" });
+            self.colors.insert(node, Some(green));
+            if stack.is_empty() {
+                return green;
             }
-        } else {
-            // target removed, ignore the edge
+            finished = Some(green);
         }
     }
 
-    debug!("compute_clean_edges: dirty_nodes={:#?}", dirty_nodes);
-
-    // Propagate dirty marks by iterating repeatedly over
-    // `clean_edges`. If we find an edge `(source, target)` where
-    // `source` is dirty, add `target` to the list of dirty nodes and
-    // remove it. Keep doing this until we find no more dirty nodes.
-    let mut previous_size = 0;
-    while dirty_nodes.len() > previous_size {
-        debug!("compute_clean_edges: previous_size={}", previous_size);
-        previous_size = dirty_nodes.len();
-        let mut i = 0;
-        while i < clean_edges.len() {
-            if dirty_nodes.contains(&clean_edges[i].0) {
-                let (source, target) = clean_edges.swap_remove(i);
-                debug!("compute_clean_edges: dirty source {:?} -> {:?}",
-                       source, target);
-                dirty_nodes.insert(target);
-            } else if dirty_nodes.contains(&clean_edges[i].1) {
-                let (source, target) = clean_edges.swap_remove(i);
-                debug!("compute_clean_edges: dirty target {:?} -> {:?}",
-                       source, target);
-            } else {
-                i += 1;
-            }
+    fn color(&self, node: &DepNode<DefId>) -> Option<bool> {
+        match self.colors.get(node) {
+            Some(&Some(green)) => Some(green),
+            // A cycle; this should not happen, but is safest treated
+            // as a change.
+            Some(&None) => Some(false),
+            None => None,
         }
     }
 
-    clean_edges
+    fn start(&mut self, node: DepNode<DefId>) -> (DepNode<DefId>, Vec<DepNode<DefId>>) {
+        self.colors.insert(node.clone(), None);
+        let predecessors = self.predecessors.remove(&node).unwrap_or(vec![]);
+        (node, predecessors)
+    }
 }
@@ -17,8 +17,11 @@ mod directory;
 mod dirty_clean;
 mod hash;
 mod load;
+mod results;
 mod save;
+mod tls_context;
 mod util;
 
 pub use self::load::load_dep_graph;
+pub use self::results::{encode_results, save_results, EncodedResults, PreviousResults};
 pub use self::save::save_dep_graph;
@@ -0,0 +1,880 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Code to save the results of analyzing each item, and to restore
+//! those of the unchanged items when the crate is recompiled.
+//!
+//! Right after the MIR is built, the typeck tables and the MIR of
+//! every item are encoded, keyed by the item's def-path; they are
+//! written out once the crate is known to be free of errors. When the
+//! next compilation loads the dep-graph, an item whose
+//! `TypeckItemBody`, `MirMapConstruction` and `BorrowCheck` nodes are
+//! all green gets its results back instead of being type-checked,
+//! having its MIR built and being borrow-checked again.
+//!
+//! Node-ids are not stable from one compilation to the next, so the
+//! results refer to nodes by their index in the order an `IdVisitor`
+//! visits the item, which is the same as long as the item is
+//! unchanged. Regions are erased, as the passes that would need them
+//! are the ones being skipped, and the spans in the MIR are saved
+//! relative to the item.
+//!
+//! The lints the skipped passes would add and the `mut` bindings
+//! borrow checking would find to be used are saved with the results
+//! too, so that restoring an item reports the same warnings as
+//! analyzing it again would.
+
+use rbml::Error;
+use rbml::opaque::{Decoder, Encoder};
+use rustc::dep_graph::DepNode;
+use rustc::hir;
+use rustc::hir::def::{Def, PathResolution};
+use rustc::hir::def_id::{DefId, DefIndex};
+use rustc::hir::intravisit::{IdVisitingOperation, IdVisitor, Visitor};
+use rustc::hir::map as hir_map;
+use rustc::middle::const_val::ConstVal;
+use rustc::middle::cstore::{tls, LOCAL_CRATE};
+use rustc::mir::mir_map::MirMap;
+use rustc::mir::repr::{AggregateKind, BasicBlock, Mir, Rvalue, TerminatorKind};
+use rustc::mir::visit::MutVisitor;
+use rustc::ty::{self, MethodCall, TyCtxt, UpvarId};
+use rustc::ty::adjustment::{AdjustDerefRef, AutoAdjustment, AutoDerefRef, AutoPtr};
+use rustc::util::nodemap::NodeMap;
+use rustc_data_structures::fnv::{FnvHashMap, FnvHashSet};
+use rustc_serialize::{Decodable, Encodable};
+use std::fs::File;
+use std::hash::{Hash, Hasher, SipHasher};
+use std::io::{Cursor, Read};
+use std::mem;
+use syntax::ast::NodeId;
+use syntax::codemap::{mk_sp, BytePos, Span, NO_EXPANSION};
+
+use calculate_svh::SvhCalculate;
+use super::data::*;
+use super::hash::HashContext;
+use super::load::PreviousDepGraph;
+use super::save::save_in;
+use super::tls_context::{self, DecodingContext, DefIdTranslation, EncodingContext};
+use super::util::*;
+
+/// The results of analyzing the current crate, as encoded by
+/// `encode_results`.
+pub struct EncodedResults(Option<SerializedResults>);
+
+/// Encodes the typeck tables and the MIR of every item in the crate.
+/// This must run right after the MIR is built, before any passes have
+/// transformed it.
+pub fn encode_results<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                mir_map: &MirMap<'tcx>)
+                                -> EncodedResults {
+    if tcx.sess.opts.incremental.is_none() {
+        return EncodedResults(None);
+    }
+
+    let _ignore = tcx.dep_graph.in_ignore();
+    let mut encoder = ResultsEncoder::new(tcx, mir_map);
+    tcx.map.krate().visit_all_items(&mut encoder);
+    EncodedResults(Some(encoder.finish()))
+}
+
+/// Writes out the results from `encode_results`. This should only be
+/// done once the crate is known to be free of errors.
+pub fn save_results<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, results: EncodedResults) {
+    if let EncodedResults(Some(mut results)) = results {
+        let _ignore = tcx.dep_graph.in_ignore();
+        add_item_lints(tcx, &mut results);
+        let mut hcx = HashContext::new(tcx);
+        save_in(&mut hcx, results_path(tcx), |_, encoder| results.encode(encoder));
+    }
+}
+
+/// Records the lints added while analyzing each item and the `mut`
+/// bindings found to be used, which are only known once the crate has
+/// been borrow-checked. The results of items with lints that cannot be
+/// saved relative to the item are dropped.
+fn add_item_lints<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, results: &mut SerializedResults) {
+    let item_lints = tcx.sess.item_lints.borrow();
+    let used_mut_nodes = tcx.used_mut_nodes.borrow();
+    let items = mem::replace(&mut results.items, vec![]);
+    results.items = items.into_iter().filter_map(|mut item| {
+        let node_id = tcx.map.as_local_node_id(DefId::local(item.def_index)).unwrap();
+        let hir_item = tcx.map.expect_item(node_id);
+        let node_ids = ItemNodeIds::new(hir_item);
+
+        for &(id, lint_id, span, ref msg) in item_lints.get(&node_id).into_iter().flat_map(|v| v) {
+            let index = match node_ids.index(id) {
+                Some(index) => index,
+                None => return None,
+            };
+            if span.expn_id != NO_EXPANSION ||
+               span.lo < hir_item.span.lo || hir_item.span.hi < span.hi {
+                return None;
+            }
+            item.lints.push(SerializedLint {
+                name: lint_id.as_str(),
+                node: index,
+                span: relative_span(tcx, hir_item.span, span),
+                msg: msg.clone(),
+            });
+        }
+
+        item.used_mut_nodes =
+            node_ids.ids
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, id)| used_mut_nodes.contains(id))
+                    .map(|(index, _)| index as u32)
+                    .collect();
+        Some(item)
+    }).collect();
+}
+
+/// The results saved by the previous compilation for the items that
+/// are unchanged; see `load_dep_graph`.
+pub struct PreviousResults {
+    results: Option<SerializedResults>,
+    translation: DefIdTranslation,
+
+    /// The items to restore, as indices into `results.items`.
+    items: Vec<usize>,
+}
+
+impl PreviousResults {
+    pub fn none() -> PreviousResults {
+        PreviousResults {
+            results: None,
+            translation: DefIdTranslation::new(FnvHashMap()),
+            items: vec![],
+        }
+    }
+
+    /// Restores the typeck tables of the unchanged items and returns
+    /// their MIR. This must run right after type checking, which skips
+    /// these items, because decoding types needs the ADTs it collects.
+    pub fn restore<'a, 'tcx>(self, tcx: TyCtxt<'a, 'tcx, 'tcx>) -> NodeMap<Mir<'tcx>> {
+        let mut mir_map = NodeMap();
+        let results = match self.results {
+            Some(results) => results,
+            None => return mir_map,
+        };
+
+        let _ignore = tcx.dep_graph.in_ignore();
+        for &index in &self.items {
+            let item = &results.items[index];
+            match restore_item(tcx, &results, item, &self.translation, &mut mir_map) {
+                Ok(()) => {}
+                Err(err) => {
+                    bug!("decoding error in results of {:?}: {}", item.def_index, err);
+                }
+            }
+        }
+        mir_map
+    }
+}
+
+/// Loads the results of the previous compilation and decides which
+/// items they can be restored for. Those are marked green in the
+/// dep-graph, so that the passes which would compute the results
+/// skip them.
+pub fn load_results<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                              previous: &PreviousDepGraph)
+                              -> PreviousResults {
+    let path = match results_path(tcx) {
+        Some(path) => path,
+        None => return PreviousResults::none(),
+    };
+    if !path.exists() {
+        return PreviousResults::none();
+    }
+
+    let mut data = vec![];
+    match
+        File::open(&path)
+        .and_then(|mut file| file.read_to_end(&mut data))
+    {
+        Ok(_) => { }
+        Err(err) => {
+            tcx.sess.err(
+                &format!("could not load results from `{}`: {}",
+                         path.display(), err));
+            return PreviousResults::none();
+        }
+    }
+
+    let results = match SerializedResults::decode(&mut Decoder::new(&data, 0)) {
+        Ok(results) => results,
+        Err(err) => {
+            bug!("decoding error in results from `{}`: {}", path.display(), err);
+        }
+    };
+
+    // The results are saved before the dep-graph; if compilation
+    // stopped in between, they are not for the same crate.
+    if results.krate_hash != previous.krate_hash {
+        debug!("load_results: results are from another compilation");
+        return PreviousResults::none();
+    }
+
+    let crates = serialized_crates(tcx);
+    let crates_unchanged = results.crates.iter().all(|saved| {
+        crates.iter().any(|current| {
+            current.cnum == saved.cnum &&
+            current.name == saved.name &&
+            current.hash == saved.hash
+        })
+    });
+    if !crates_unchanged {
+        debug!("load_results: upstream crates have changed");
+        return PreviousResults::none();
+    }
+
+    let translation = DefIdTranslation::new(
+        results.local_def_paths
+               .iter()
+               .filter_map(|&(index, ref path)| {
+                   tcx.retrace_path(path).map(|def_id| (index, def_id))
+               })
+               .collect());
+
+    // Which imports are used is only known after type checking every
+    // item, so restore the imports used by any item of the crate.
+    for &(def_index, index) in &results.used_trait_imports {
+        let def_id = match translation.local_def_id(def_index) {
+            Some(def_id) => def_id,
+            None => continue,
+        };
+        let node_id = tcx.map.as_local_node_id(def_id).unwrap();
+        if let Some(hir_map::NodeItem(item)) = tcx.map.find(node_id) {
+            if let Some(&id) = ItemNodeIds::new(item).ids.get(index as usize) {
+                tcx.used_trait_imports.borrow_mut().insert(id);
+            }
+        }
+    }
+
+    let items: Vec<_> =
+        results.items
+               .iter()
+               .enumerate()
+               .filter(|&(_, item)| is_restorable(tcx, previous, &translation, item))
+               .map(|(index, _)| index)
+               .collect();
+
+    for &index in &items {
+        let def_id = translation.local_def_id(results.items[index].def_index).unwrap();
+        debug!("load_results: restoring results of {:?}", def_id);
+        tcx.dep_graph.mark_green(DepNode::TypeckItemBody(def_id));
+        tcx.dep_graph.mark_green(DepNode::MirMapConstruction(def_id));
+    }
+
+    PreviousResults {
+        results: Some(results),
+        translation: translation,
+        items: items,
+    }
+}
+
+fn is_restorable<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                           previous: &PreviousDepGraph,
+                           translation: &DefIdTranslation,
+                           item: &SerializedItemResults)
+                           -> bool {
+    let def_id = match translation.local_def_id(item.def_index) {
+        Some(def_id) => def_id,
+        None => return false,
+    };
+
+    // Borrow checking must be skipped too, as it would need the
+    // regions erased from the results.
+    let green = |node| previous.green_nodes.contains(&node);
+    if !green(DepNode::TypeckItemBody(def_id)) ||
+       !green(DepNode::MirMapConstruction(def_id)) ||
+       !green(DepNode::BorrowCheck(def_id)) {
+        return false;
+    }
+
+    if !item.local_def_indices.iter().all(|&index| translation.local_def_id(index).is_some()) {
+        return false;
+    }
+
+    // The lints may come from a plugin that is no longer loaded.
+    let lint_store = tcx.sess.lint_store.borrow();
+    if !item.lints.iter().all(|lint| lint_store.lint_by_name(&lint.name).is_some()) {
+        return false;
+    }
+
+    let node_id = tcx.map.as_local_node_id(def_id).unwrap();
+    match tcx.map.find(node_id) {
+        Some(hir_map::NodeItem(hir_item)) => {
+            ItemNodeIds::new(hir_item).ids.len() == item.node_count as usize &&
+            source_hash(tcx, hir_item.span) == item.source_hash
+        }
+        _ => false,
+    }
+}
+
+fn restore_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                          results: &SerializedResults,
+                          item: &SerializedItemResults,
+                          translation: &DefIdTranslation,
+                          mir_map: &mut NodeMap<Mir<'tcx>>)
+                          -> Result<(), Error> {
+    let def_id = translation.local_def_id(item.def_index).unwrap();
+    let hir_item = tcx.map.expect_item(tcx.map.as_local_node_id(def_id).unwrap());
+    let node_ids = ItemNodeIds::new(hir_item);
+    let id = |index: u32| node_ids.ids[index as usize];
+
+    let dcx = DecodingContext {
+        tcx: tcx,
+        data: &results.data,
+        translation: translation,
+    };
+    let mut decoder = Decoder::new(&results.data, item.position);
+    let data: SerializedItemData<'tcx> = try!(
+        tls::enter_decoding_context(&dcx, &mut decoder, |_, decoder| {
+            Decodable::decode(decoder)
+        }));
+
+    {
+        let mut tables = tcx.tables.borrow_mut();
+        for (index, ty) in data.node_types {
+            tables.node_types.insert(id(index), ty);
+        }
+        for (index, item_substs) in data.item_substs {
+            tables.item_substs.insert(id(index), item_substs);
+        }
+        for (index, adjustment) in data.adjustments {
+            tables.adjustments.insert(id(index), adjustment);
+        }
+        for (index, autoderef, callee) in data.method_map {
+            let method_call = MethodCall { expr_id: id(index), autoderef: autoderef };
+            let callee = ty::MethodCallee {
+                def_id: translation.translate(callee.def_id),
+                ..callee
+            };
+            tables.method_map.insert(method_call, callee);
+        }
+        for (var_index, closure_index, capture) in data.upvar_capture_map {
+            let upvar_id = UpvarId { var_id: id(var_index), closure_expr_id: id(closure_index) };
+            tables.upvar_capture_map.insert(upvar_id, capture);
+        }
+        for (index, closure_ty) in data.closure_tys {
+            tables.closure_tys.insert(tcx.map.local_def_id(id(index)), closure_ty);
+        }
+        for (index, closure_kind) in data.closure_kinds {
+            tables.closure_kinds.insert(tcx.map.local_def_id(id(index)), closure_kind);
+        }
+        for (index, fn_sig) in data.liberated_fn_sigs {
+            tables.liberated_fn_sigs.insert(id(index), fn_sig);
+        }
+        for (index, field_types) in data.fru_field_types {
+            tables.fru_field_types.insert(id(index), field_types);
+        }
+    }
+
+    let mut cast_kinds = tcx.cast_kinds.borrow_mut();
+    for (index, cast_kind) in data.cast_kinds {
+        cast_kinds.insert(id(index), cast_kind);
+    }
+
+    // Type checking completes the resolution of paths that name
+    // associated items; only those are taken from the results.
+    let mut def_map = tcx.def_map.borrow_mut();
+    for (index, def) in data.path_resolutions {
+        if let Some(resolution) = def_map.get_mut(&id(index)) {
+            if resolution.depth != 0 {
+                let def = map_def(def, &mut |def_id| translation.translate(def_id)).unwrap();
+                *resolution = PathResolution::new(def, 0);
+            }
+        }
+    }
+
+    let mut translator = MirTranslator {
+        translation: translation,
+        item_span: hir_item.span,
+    };
+    for (index, mut mir) in data.mir {
+        translator.visit_mir(&mut mir);
+        for promoted in &mut mir.promoted {
+            translator.visit_mir(promoted);
+        }
+        mir_map.insert(id(index), mir);
+    }
+
+    // Adding the lints while the item counts as analyzed records them
+    // again, for the next compilation to save.
+    let lint_store = tcx.sess.lint_store.borrow();
+    tcx.sess.analyzing_item(hir_item.id, || {
+        for lint in &item.lints {
+            let lint_id = lint_store.lint_by_name(&lint.name).unwrap();
+            let span = mk_sp(hir_item.span.lo + lint.span.lo, hir_item.span.lo + lint.span.hi);
+            tcx.sess.add_lint(lint_id.lint(), id(lint.node), span, lint.msg.clone());
+        }
+    });
+    tcx.used_mut_nodes.borrow_mut().extend(item.used_mut_nodes.iter().map(|&index| id(index)));
+
+    Ok(())
+}
+
+struct ResultsEncoder<'a, 'tcx: 'a, 'm> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    mir_map: &'m MirMap<'tcx>,
+
+    /// The method calls in the tables, by expression.
+    method_calls: NodeMap<Vec<(u32, ty::MethodCallee<'tcx>)>>,
+
+    /// The captured variables in the tables, by closure.
+    upvar_captures: NodeMap<Vec<(NodeId, ty::UpvarCapture)>>,
+
+    local_def_indices: FnvHashSet<DefIndex>,
+    used_trait_imports: Vec<(DefIndex, u32)>,
+    items: Vec<SerializedItemResults>,
+    data: Cursor<Vec<u8>>,
+}
+
+impl<'a, 'tcx, 'm> ResultsEncoder<'a, 'tcx, 'm> {
+    fn new(tcx: TyCtxt<'a, 'tcx, 'tcx>, mir_map: &'m MirMap<'tcx>) -> Self {
+        let mut method_calls = NodeMap();
+        let mut upvar_captures = NodeMap();
+        {
+            let tables = tcx.tables.borrow();
+            for (method_call, &callee) in &tables.method_map {
+                method_calls.entry(method_call.expr_id)
+                            .or_insert(vec![])
+                            .push((method_call.autoderef, callee));
+            }
+            for (upvar_id, &capture) in &tables.upvar_capture_map {
+                upvar_captures.entry(upvar_id.closure_expr_id)
+                              .or_insert(vec![])
+                              .push((upvar_id.var_id, capture));
+            }
+        }
+
+        ResultsEncoder {
+            tcx: tcx,
+            mir_map: mir_map,
+            method_calls: method_calls,
+            upvar_captures: upvar_captures,
+            local_def_indices: FnvHashSet(),
+            used_trait_imports: vec![],
+            items: vec![],
+            data: Cursor::new(Vec::new()),
+        }
+    }
+
+    fn encode_item(&mut self, item: &hir::Item, node_ids: &ItemNodeIds) {
+        let tcx = self.tcx;
+        let (data, mut def_ids) = match self.item_data(item, node_ids) {
+            Some(data) => data,
+            None => return,
+        };
+
+        // Each item gets its own type abbreviations, so that the
+        // def-ids written for its types are all recorded with it.
+        let position = self.data.position() as usize;
+        {
+            let ecx = EncodingContext::new(tcx);
+            let cursor = &mut self.data;
+            let ((), written_def_ids) = tls_context::record_written_def_ids(|| {
+                tls::enter_encoding_context(&ecx, &mut Encoder::new(cursor), |_, encoder| {
+                    data.encode(encoder).unwrap()
+                })
+            });
+            def_ids.extend(written_def_ids);
+        }
+
+        let def_id = tcx.map.local_def_id(item.id);
+        let mut local_def_indices: Vec<_> =
+            def_ids.into_iter()
+                   .filter(|def_id| def_id.is_local())
+                   .map(|def_id| def_id.index)
+                   .collect();
+        local_def_indices.sort();
+        local_def_indices.dedup();
+
+        self.local_def_indices.insert(def_id.index);
+        self.local_def_indices.extend(local_def_indices.iter().cloned());
+        self.items.push(SerializedItemResults {
+            def_index: def_id.index,
+            source_hash: source_hash(tcx, item.span),
+            node_count: node_ids.ids.len() as u32,
+            local_def_indices: local_def_indices,
+            lints: vec![],
+            used_mut_nodes: vec![],
+            position: position,
+        });
+    }
+
+    /// The tables and the MIR of an item, along with the def-ids they
+    /// mention outside of types; `None` if they cannot be saved.
+    fn item_data(&self, item: &hir::Item, node_ids: &ItemNodeIds)
+                 -> Option<(SerializedItemData<'tcx>, Vec<DefId>)> {
+        let tcx = self.tcx;
+
+        // Items generated by macros have no source of their own for
+        // the spans in their MIR to be relative to.
+        if item.span.expn_id != NO_EXPANSION {
+            return None;
+        }
+
+        let tables = tcx.tables.borrow();
+        let cast_kinds = tcx.cast_kinds.borrow();
+        let def_map = tcx.def_map.borrow();
+        let mut def_ids = vec![];
+        let mut data = SerializedItemData {
+            node_types: vec![],
+            item_substs: vec![],
+            adjustments: vec![],
+            method_map: vec![],
+            upvar_capture_map: vec![],
+            closure_tys: vec![],
+            closure_kinds: vec![],
+            liberated_fn_sigs: vec![],
+            fru_field_types: vec![],
+            cast_kinds: vec![],
+            path_resolutions: vec![],
+            mir: vec![],
+        };
+
+        for (index, &id) in node_ids.ids.iter().enumerate() {
+            let index = index as u32;
+
+            // The concrete type behind an `impl Trait` is recorded
+            // when type checking the item, but not in the tables.
+            if let Some(hir_map::NodeTy(ty)) = tcx.map.find(id) {
+                if let hir::TyImplTrait(..) = ty.node {
+                    return None;
+                }
+            }
+
+            if let Some(&ty) = tables.node_types.get(&id) {
+                data.node_types.push((index, ty));
+            }
+            if let Some(item_substs) = tables.item_substs.get(&id) {
+                data.item_substs.push((index, item_substs.clone()));
+            }
+            if let Some(&adjustment) = tables.adjustments.get(&id) {
+                data.adjustments.push((index, erase_adjustment_region(tcx, adjustment)));
+            }
+            if let Some(method_calls) = self.method_calls.get(&id) {
+                for &(autoderef, callee) in method_calls {
+                    def_ids.push(callee.def_id);
+                    data.method_map.push((index, autoderef, callee));
+                }
+            }
+            if let Some(upvar_captures) = self.upvar_captures.get(&id) {
+                for &(var_id, capture) in upvar_captures {
+                    let var_index = match node_ids.index(var_id) {
+                        Some(var_index) => var_index,
+                        None => return None,
+                    };
+                    let capture = match capture {
+                        ty::UpvarCapture::ByRef(borrow) => {
+                            ty::UpvarCapture::ByRef(ty::UpvarBorrow {
+                                region: ty::ReStatic,
+                                ..borrow
+                            })
+                        }
+                        capture => capture,
+                    };
+                    data.upvar_capture_map.push((var_index, index, capture));
+                }
+            }
+            if let Some(def_id) = tcx.map.opt_local_def_id(id) {
+                if let Some(closure_ty) = tables.closure_tys.get(&def_id) {
+                    data.closure_tys.push((index, closure_ty.clone()));
+                }
+                if let Some(&closure_kind) = tables.closure_kinds.get(&def_id) {
+                    data.closure_kinds.push((index, closure_kind));
+                }
+            }
+            if let Some(fn_sig) = tables.liberated_fn_sigs.get(&id) {
+                data.liberated_fn_sigs.push((index, fn_sig.clone()));
+            }
+            if let Some(field_types) = tables.fru_field_types.get(&id) {
+                data.fru_field_types.push((index, field_types.clone()));
+            }
+            if let Some(&cast_kind) = cast_kinds.get(&id) {
+                data.cast_kinds.push((index, cast_kind));
+            }
+            if let Some(resolution) = def_map.get(&id) {
+                if resolution.depth == 0 {
+                    let def = map_def(resolution.base_def, &mut |def_id| {
+                        def_ids.push(def_id);
+                        def_id
+                    });
+                    if let Some(def) = def {
+                        data.path_resolutions.push((index, def));
+                    }
+                }
+            }
+            if let Some(mir) = self.mir_map.map.get(&id) {
+                let mut mir = mir.clone();
+                let saveable = {
+                    let mut preparer = MirPreparer {
+                        tcx: tcx,
+                        item_span: item.span,
+                        def_ids: &mut def_ids,
+                        saveable: true,
+                    };
+                    preparer.visit_mir(&mut mir);
+                    for promoted in &mut mir.promoted {
+                        preparer.visit_mir(promoted);
+                    }
+                    preparer.saveable
+                };
+                if !saveable {
+                    return None;
+                }
+                data.mir.push((index, mir));
+            }
+        }
+
+        Some((data, def_ids))
+    }
+
+    fn finish(self) -> SerializedResults {
+        let tcx = self.tcx;
+        let mut local_def_indices: Vec<_> = self.local_def_indices.into_iter().collect();
+        local_def_indices.sort();
+
+        SerializedResults {
+            krate_hash: tcx.calculate_krate_hash().as_u64(),
+            crates: serialized_crates(tcx),
+            local_def_paths: local_def_indices.into_iter().map(|index| {
+                (index, tcx.def_path(DefId { krate: LOCAL_CRATE, index: index }))
+            }).collect(),
+            used_trait_imports: self.used_trait_imports,
+            items: self.items,
+            data: self.data.into_inner(),
+        }
+    }
+}
+
+impl<'a, 'tcx, 'm, 'v> Visitor<'v> for ResultsEncoder<'a, 'tcx, 'm> {
+    fn visit_item(&mut self, item: &'v hir::Item) {
+        let tcx = self.tcx;
+        let node_ids = ItemNodeIds::new(item);
+        if let hir::ItemUse(..) = item.node {
+            let def_index = tcx.map.local_def_id(item.id).index;
+            let used_trait_imports = tcx.used_trait_imports.borrow();
+            for (index, id) in node_ids.ids.iter().enumerate() {
+                if used_trait_imports.contains(id) {
+                    self.local_def_indices.insert(def_index);
+                    self.used_trait_imports.push((def_index, index as u32));
+                }
+            }
+        } else {
+            self.encode_item(item, &node_ids);
+        }
+    }
+}
+
+fn serialized_crates<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) -> Vec<SerializedCrate> {
+    tcx.sess.cstore.crates().into_iter().map(|cnum| {
+        SerializedCrate {
+            cnum: cnum,
+            name: tcx.sess.cstore.crate_name(cnum).to_string(),
+            hash: tcx.sess.cstore.crate_hash(cnum).as_u64(),
+        }
+    }).collect()
+}
+
+/// Hash of the source text of an item. Its HIR hash does not cover
+/// the layout of the source, on which the spans in its MIR depend.
+fn source_hash<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, span: Span) -> u64 {
+    let mut state = SipHasher::new();
+    tcx.sess.codemap().span_to_snippet(span).ok().hash(&mut state);
+    state.finish()
+}
+
+fn erase_adjustment_region<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                     adjustment: AutoAdjustment<'tcx>)
+                                     -> AutoAdjustment<'tcx> {
+    match adjustment {
+        AdjustDerefRef(AutoDerefRef {
+            autoderefs,
+            autoref: Some(AutoPtr(_, mutbl)),
+            unsize
+        }) => {
+            AdjustDerefRef(AutoDerefRef {
+                autoderefs: autoderefs,
+                autoref: Some(AutoPtr(tcx.mk_region(ty::ReStatic), mutbl)),
+                unsize: unsize,
+            })
+        }
+        adjustment => adjustment,
+    }
+}
+
+/// Applies `f` to the def-ids in `def`; `None` if `def` refers to
+/// node-ids, which are not saved.
+fn map_def<F>(def: Def, f: &mut F) -> Option<Def>
+    where F: FnMut(DefId) -> DefId
+{
+    Some(match def {
+        Def::Fn(def_id) => Def::Fn(f(def_id)),
+        Def::SelfTy(trait_def_id, None) => Def::SelfTy(trait_def_id.map(|d| f(d)), None),
+        Def::Mod(def_id) => Def::Mod(f(def_id)),
+        Def::ForeignMod(def_id) => Def::ForeignMod(f(def_id)),
+        Def::Static(def_id, mutbl) => Def::Static(f(def_id), mutbl),
+        Def::Const(def_id) => Def::Const(f(def_id)),
+        Def::AssociatedConst(def_id) => Def::AssociatedConst(f(def_id)),
+        Def::Variant(enum_def_id, def_id) => Def::Variant(f(enum_def_id), f(def_id)),
+        Def::Enum(def_id) => Def::Enum(f(def_id)),
+        Def::TyAlias(def_id) => Def::TyAlias(f(def_id)),
+        Def::AssociatedTy(trait_def_id, def_id) => Def::AssociatedTy(f(trait_def_id), f(def_id)),
+        Def::Trait(def_id) => Def::Trait(f(def_id)),
+        Def::PrimTy(prim_ty) => Def::PrimTy(prim_ty),
+        Def::TyParam(space, index, def_id, name) => Def::TyParam(space, index, f(def_id), name),
+        Def::Struct(def_id) => Def::Struct(f(def_id)),
+        Def::Method(def_id) => Def::Method(f(def_id)),
+        Def::SelfTy(_, Some(_)) |
+        Def::Local(..) |
+        Def::Upvar(..) |
+        Def::Label(..) |
+        Def::Err => return None,
+    })
+}
+
+/// The node-ids of an item, in the order in which an `IdVisitor`
+/// visits them.
+struct ItemNodeIds {
+    ids: Vec<NodeId>,
+    indices: NodeMap<u32>,
+}
+
+impl ItemNodeIds {
+    fn new(item: &hir::Item) -> ItemNodeIds {
+        let mut node_ids = ItemNodeIds {
+            ids: vec![],
+            indices: NodeMap(),
+        };
+        IdVisitor::new(&mut node_ids).visit_item(item);
+
+        // Starting from an item, the visitor skips the items of traits
+        // and impls.
+        match item.node {
+            hir::ItemTrait(_, _, _, ref trait_items) => {
+                for trait_item in trait_items {
+                    IdVisitor::new(&mut node_ids).visit_trait_item(trait_item);
+                }
+            }
+            hir::ItemImpl(_, _, _, _, _, ref impl_items) => {
+                for impl_item in impl_items {
+                    IdVisitor::new(&mut node_ids).visit_impl_item(impl_item);
+                }
+            }
+            _ => {}
+        }
+
+        node_ids
+    }
+
+    fn index(&self, id: NodeId) -> Option<u32> {
+        self.indices.get(&id).cloned()
+    }
+}
+
+impl IdVisitingOperation for ItemNodeIds {
+    fn visit_id(&mut self, id: NodeId) {
+        if !self.indices.contains_key(&id) {
+            self.indices.insert(id, self.ids.len() as u32);
+            self.ids.push(id);
+        }
+    }
+}
+
+/// Makes the spans in some MIR relative to its item, for them to stay
+/// correct if the item moves.
+fn relative_span<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, item_span: Span, span: Span) -> Span {
+    // Spans from macros are replaced by where the macro was invoked.
+    let span = tcx.sess.codemap().source_callsite(span);
+    if item_span.lo <= span.lo && span.hi <= item_span.hi {
+        mk_sp(span.lo - item_span.lo, span.hi - item_span.lo)
+    } else {
+        mk_sp(BytePos(0), item_span.hi - item_span.lo)
+    }
+}
+
+/// Prepares a copy of some MIR for saving: erases its regions, makes
+/// its spans relative and records the def-ids it mentions.
+struct MirPreparer<'a, 'tcx: 'a, 'd> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    item_span: Span,
+    def_ids: &'d mut Vec<DefId>,
+
+    /// Whether the MIR can be saved; constants of aggregates refer to
+    /// the node-ids of their expressions, which are not saved.
+    saveable: bool,
+}
+
+impl<'a, 'tcx, 'd> MutVisitor<'tcx> for MirPreparer<'a, 'tcx, 'd> {
+    fn visit_rvalue(&mut self, rvalue: &mut Rvalue<'tcx>) {
+        match *rvalue {
+            Rvalue::Ref(ref mut region, _, _) => *region = ty::ReStatic,
+            Rvalue::Aggregate(AggregateKind::Adt(adt_def, _, _), _) => {
+                self.def_ids.push(adt_def.did);
+            }
+            _ => {}
+        }
+        self.super_rvalue(rvalue);
+    }
+
+    fn visit_terminator_kind(&mut self, block: BasicBlock, kind: &mut TerminatorKind<'tcx>) {
+        if let TerminatorKind::Switch { adt_def, .. } = *kind {
+            self.def_ids.push(adt_def.did);
+        }
+        self.super_terminator_kind(block, kind);
+    }
+
+    fn visit_const_val(&mut self, const_val: &mut ConstVal) {
+        match *const_val {
+            ConstVal::Struct(_) |
+            ConstVal::Tuple(_) |
+            ConstVal::Array(..) |
+            ConstVal::Repeat(..) => self.saveable = false,
+            ConstVal::Function(def_id) => self.def_ids.push(def_id),
+            _ => {}
+        }
+        self.super_const_val(const_val);
+    }
+
+    fn visit_def_id(&mut self, def_id: &mut DefId) {
+        self.def_ids.push(*def_id);
+    }
+
+    fn visit_span(&mut self, span: &mut Span) {
+        *span = relative_span(self.tcx, self.item_span, *span);
+    }
+}
+
+/// Undoes the `MirPreparer` on restored MIR, translating its def-ids
+/// and making its spans absolute again.
+struct MirTranslator<'t> {
+    translation: &'t DefIdTranslation,
+    item_span: Span,
+}
+
+impl<'t, 'tcx> MutVisitor<'tcx> for MirTranslator<'t> {
+    fn visit_const_val(&mut self, const_val: &mut ConstVal) {
+        if let ConstVal::Function(ref mut def_id) = *const_val {
+            *def_id = self.translation.translate(*def_id);
+        }
+        self.super_const_val(const_val);
+    }
+
+    fn visit_def_id(&mut self, def_id: &mut DefId) {
+        *def_id = self.translation.translate(*def_id);
+    }
+
+    fn visit_span(&mut self, span: &mut Span) {
+        *span = mk_sp(self.item_span.lo + span.lo, self.item_span.lo + span.hi);
+    }
+}
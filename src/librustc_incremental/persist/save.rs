@@ -8,6 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use calculate_svh::SvhCalculate;
 use rbml::opaque::Encoder;
 use rustc::dep_graph::DepNode;
 use rustc::middle::cstore::LOCAL_CRATE;
@@ -30,9 +31,9 @@ pub fn save_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
     save_in(&mut hcx, metadata_hash_path(tcx, LOCAL_CRATE), encode_metadata_hashes);
 }
 
-fn save_in<'a, 'tcx, F>(hcx: &mut HashContext<'a, 'tcx>,
-                        opt_path_buf: Option<PathBuf>,
-                        encode: F)
+pub fn save_in<'a, 'tcx, F>(hcx: &mut HashContext<'a, 'tcx>,
+                            opt_path_buf: Option<PathBuf>,
+                            encode: F)
     where F: FnOnce(&mut HashContext<'a, 'tcx>, &mut Encoder) -> io::Result<()>
 {
     let tcx = hcx.tcx;
@@ -109,6 +110,7 @@ pub fn encode_dep_graph<'a, 'tcx>(hcx: &mut HashContext<'a, 'tcx>,
 
     // Create the serialized dep-graph.
     let graph = SerializedDepGraph {
+        krate_hash: tcx.calculate_krate_hash().as_u64(),
         nodes: query.nodes().into_iter()
                             .map(|node| builder.map(node))
                             .collect(),
@@ -0,0 +1,178 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Implementations of the thread-local encoding and decoding context
+//! traits in `rustc::middle::cstore::tls`, for the results saved by
+//! `results.rs`. Types are written with the same encoder as metadata.
+
+use rbml::opaque::Encoder as OpaqueEncoder;
+use rbml::opaque::Decoder as OpaqueDecoder;
+use rustc::hir::def_id::{DefId, DefIndex};
+use rustc::middle::cstore::{tls, LOCAL_CRATE};
+use rustc::ty::subst::Substs;
+use rustc::ty::{self, TyCtxt};
+use rustc_data_structures::fnv::FnvHashMap;
+use rustc_metadata::encoder;
+use rustc_metadata::tydecode::TyDecoder;
+use rustc_metadata::tyencode;
+use std::cell::RefCell;
+use std::mem;
+
+thread_local! {
+    static WRITTEN_DEF_IDS: RefCell<Vec<DefId>> = RefCell::new(vec![])
+}
+
+/// Runs `f`, returning the def-ids written as part of types while it ran.
+pub fn record_written_def_ids<F, R>(f: F) -> (R, Vec<DefId>)
+    where F: FnOnce() -> R
+{
+    WRITTEN_DEF_IDS.with(|def_ids| def_ids.borrow_mut().clear());
+    let result = f();
+    let def_ids = WRITTEN_DEF_IDS.with(|def_ids| {
+        mem::replace(&mut *def_ids.borrow_mut(), vec![])
+    });
+    (result, def_ids)
+}
+
+fn def_to_string(tcx: TyCtxt, def_id: DefId) -> String {
+    WRITTEN_DEF_IDS.with(|def_ids| def_ids.borrow_mut().push(def_id));
+    encoder::def_to_string(tcx, def_id)
+}
+
+/// Encodes types with their regions erased; the passes that would need
+/// them are the ones skipped when the results are used.
+pub struct EncodingContext<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    type_abbrevs: tyencode::abbrev_map<'tcx>,
+}
+
+impl<'a, 'tcx> EncodingContext<'a, 'tcx> {
+    pub fn new(tcx: TyCtxt<'a, 'tcx, 'tcx>) -> Self {
+        EncodingContext {
+            tcx: tcx,
+            type_abbrevs: RefCell::new(FnvHashMap()),
+        }
+    }
+
+    fn ty_str_ctxt<'b>(&'b self) -> tyencode::ctxt<'b, 'tcx> {
+        tyencode::ctxt {
+            diag: self.tcx.sess.diagnostic(),
+            ds: def_to_string,
+            tcx: self.tcx,
+            abbrevs: &self.type_abbrevs
+        }
+    }
+}
+
+impl<'a, 'tcx: 'a> tls::EncodingContext<'tcx> for EncodingContext<'a, 'tcx> {
+
+    fn tcx<'s>(&'s self) -> TyCtxt<'s, 'tcx, 'tcx> {
+        self.tcx
+    }
+
+    fn encode_ty(&self, encoder: &mut OpaqueEncoder, t: ty::Ty<'tcx>) {
+        let t = self.tcx.erase_regions(&t);
+        tyencode::enc_ty(encoder.cursor, &self.ty_str_ctxt(), t);
+    }
+
+    fn encode_substs(&self, encoder: &mut OpaqueEncoder, substs: &Substs<'tcx>) {
+        let substs = self.tcx.erase_regions(&self.tcx.mk_substs(substs.clone()));
+        tyencode::enc_substs(encoder.cursor, &self.ty_str_ctxt(), substs);
+    }
+}
+
+/// Maps the local def-ids of the session that saved some results to
+/// those of the current session.
+pub struct DefIdTranslation {
+    local: FnvHashMap<DefIndex, DefId>,
+}
+
+impl DefIdTranslation {
+    pub fn new(local: FnvHashMap<DefIndex, DefId>) -> DefIdTranslation {
+        DefIdTranslation { local: local }
+    }
+
+    /// The current def-id for a def-index of the previous session;
+    /// `None` if the definition no longer exists.
+    pub fn local_def_id(&self, index: DefIndex) -> Option<DefId> {
+        self.local.get(&index).cloned()
+    }
+
+    /// Def-ids from other crates are unchanged, as the results are only
+    /// used if those crates are.
+    pub fn translate(&self, def_id: DefId) -> DefId {
+        if def_id.krate != LOCAL_CRATE {
+            return def_id;
+        }
+        match self.local.get(&def_id.index) {
+            Some(&def_id) => def_id,
+            None => bug!("no current def-id for {:?}", def_id),
+        }
+    }
+}
+
+pub struct DecodingContext<'a, 'tcx: 'a> {
+    pub tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    pub data: &'a [u8],
+    pub translation: &'a DefIdTranslation,
+}
+
+impl<'a, 'tcx: 'a> tls::DecodingContext<'tcx> for DecodingContext<'a, 'tcx> {
+
+    fn tcx<'s>(&'s self) -> TyCtxt<'s, 'tcx, 'tcx> {
+        self.tcx
+    }
+
+    fn decode_ty(&self, decoder: &mut OpaqueDecoder) -> ty::Ty<'tcx> {
+        let def_id_convert = &mut |did| self.translation.translate(did);
+
+        let starting_position = decoder.position();
+
+        // The type cache is keyed by crate and position; the local crate
+        // has no metadata, so the positions in `data` are free to use.
+        let mut ty_decoder = TyDecoder::new(
+            self.data,
+            LOCAL_CRATE,
+            starting_position,
+            self.tcx,
+            def_id_convert);
+
+        let ty = ty_decoder.parse_ty();
+
+        let end_position = ty_decoder.position();
+
+        decoder.advance(end_position - starting_position);
+        ty
+    }
+
+    fn decode_substs(&self, decoder: &mut OpaqueDecoder) -> Substs<'tcx> {
+        let def_id_convert = &mut |did| self.translation.translate(did);
+
+        let starting_position = decoder.position();
+
+        let mut ty_decoder = TyDecoder::new(
+            self.data,
+            LOCAL_CRATE,
+            starting_position,
+            self.tcx,
+            def_id_convert);
+
+        let substs = ty_decoder.parse_substs();
+
+        let end_position = ty_decoder.position();
+
+        decoder.advance(end_position - starting_position);
+        substs
+    }
+
+    fn translate_def_id(&self, def_id: DefId) -> DefId {
+        self.translation.translate(def_id)
+    }
+}
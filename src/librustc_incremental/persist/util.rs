@@ -20,6 +20,10 @@ pub fn dep_graph_path(tcx: TyCtxt) -> Option<PathBuf> {
     path(tcx, LOCAL_CRATE, "local")
 }
 
+pub fn results_path(tcx: TyCtxt) -> Option<PathBuf> {
+    path(tcx, LOCAL_CRATE, "results")
+}
+
 pub fn metadata_hash_path(tcx: TyCtxt, cnum: ast::CrateNum) -> Option<PathBuf> {
     path(tcx, cnum, "metadata")
 }
@@ -8,9 +8,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use rustc::hir::def::Def;
+use rustc::hir::def_id::DefId;
 use rustc::hir::pat_util;
-use rustc::ty;
+use rustc::traits::{self, ProjectionMode};
+use rustc::ty::{self, Ty, TyCtxt};
 use rustc::ty::adjustment;
+use rustc::ty::fold::TypeFoldable;
+use rustc::ty::subst::Substs;
 use util::nodemap::FnvHashMap;
 use lint::{LateContext, EarlyContext, LintContext, LintArray};
 use lint::{LintPass, EarlyLintPass, LateLintPass};
@@ -19,7 +24,8 @@ use std::collections::hash_map::Entry::{Occupied, Vacant};
 
 use syntax::ast;
 use syntax::attr::{self, AttrMetaMethods};
-use syntax::codemap::Span;
+use syntax::codemap::{BytePos, Span};
+use syntax::errors::Applicability;
 use syntax::feature_gate::{KNOWN_ATTRIBUTES, AttributeType};
 use syntax::ptr::P;
 
@@ -59,8 +65,28 @@ impl UnusedMut {
         let used_mutables = cx.tcx.used_mut_nodes.borrow();
         for (_, v) in &mutables {
             if !v.iter().any(|e| used_mutables.contains(e)) {
-                cx.span_lint(UNUSED_MUT, cx.tcx.map.span(v[0]),
-                             "variable does not need to be mutable");
+                let mut err = cx.struct_span_lint(UNUSED_MUT, cx.tcx.map.span(v[0]),
+                                                  "variable does not need to be mutable");
+                // Alternatives of a pattern must bind the variable the
+                // same way, so `mut` goes from all of them at once.
+                let removals: Option<Vec<_>> = v.iter().map(|&id| {
+                    let span = cx.tcx.map.span(id);
+                    cx.sess().codemap().span_to_snippet(span).ok().and_then(|snippet| {
+                        if snippet.starts_with("mut") &&
+                           snippet[3..].starts_with(char::is_whitespace) {
+                            Some((span, snippet[3..].trim_left().to_string()))
+                        } else {
+                            None
+                        }
+                    })
+                }).collect();
+                if let Some(mut removals) = removals {
+                    removals.sort_by_key(|&(span, _)| span.lo);
+                    err.multipart_suggestion("remove this `mut`",
+                                             removals,
+                                             Applicability::MachineApplicable);
+                }
+                err.emit();
             }
         }
     }
@@ -101,7 +127,7 @@ impl LateLintPass for UnusedMut {
 declare_lint! {
     pub UNUSED_MUST_USE,
     Warn,
-    "unused result of a type flagged as #[must_use]"
+    "unused result of a type, function or trait flagged as #[must_use]"
 }
 
 declare_lint! {
@@ -130,25 +156,80 @@ impl LateLintPass for UnusedResults {
             return;
         }
 
+        // The function or method called and its substitutions, if the
+        // statement is a call.
+        let callee = match expr.node {
+            hir::ExprCall(ref callee, _) => {
+                match cx.tcx.def_map.borrow().get(&callee.id).map(|d| d.full_def()) {
+                    Some(Def::Fn(def_id)) | Some(Def::Method(def_id)) => {
+                        Some((def_id, cx.tcx.node_id_item_substs(callee.id).substs))
+                    }
+                    _ => None
+                }
+            }
+            hir::ExprMethodCall(..) => {
+                cx.tcx.tables.borrow().method_map.get(&ty::MethodCall::expr(expr.id))
+                                                 .map(|m| (m.def_id, m.substs))
+            }
+            _ => None
+        };
+        let fn_warned = match callee {
+            Some((def_id, substs)) => {
+                let check_fn = |def_id| {
+                    let attrs = cx.tcx.get_attrs(def_id);
+                    let what = format!("unused return value of `{}`",
+                                       cx.tcx.item_path_str(def_id));
+                    check_must_use(cx, &attrs[..], s.span, &what)
+                };
+                // A call of a trait method that is known to dispatch to the
+                // method of an impl is marked by either of them.
+                let impl_def_id = impl_method(cx.tcx, def_id, substs);
+                (impl_def_id != def_id && check_fn(impl_def_id)) || check_fn(def_id)
+            }
+            None => false
+        };
+        // The type is not checked once the function has been, so that a
+        // `#[must_use]` function returning a `#[must_use]` type warns once.
+        if fn_warned {
+            return;
+        }
+
         let t = cx.tcx.expr_ty(&expr);
-        let warned = match t.sty {
+        let ty_warned = match t.sty {
             ty::TyTuple(ref tys) if tys.is_empty() => return,
             ty::TyBool => return,
             ty::TyStruct(def, _) |
             ty::TyEnum(def, _) => {
                 let attrs = cx.tcx.get_attrs(def.did);
-                check_must_use(cx, &attrs[..], s.span)
+                check_must_use(cx, &attrs[..], s.span, "unused result")
+            }
+            ty::TyBox(inner) => check_trait_object(cx, inner, s.span, "boxed"),
+            ty::TyRef(_, mt) => check_trait_object(cx, mt.ty, s.span, "borrowed"),
+            ty::TyAnon(def_id, _) => {
+                // `impl Trait` is marked by any of its traits.
+                cx.tcx.lookup_predicates(def_id).predicates.iter().any(|predicate| {
+                    if let ty::Predicate::Trait(ref poly_trait_predicate) = *predicate {
+                        let def_id = poly_trait_predicate.def_id();
+                        let attrs = cx.tcx.get_attrs(def_id);
+                        let what = format!("unused implementer of `{}`",
+                                           cx.tcx.item_path_str(def_id));
+                        check_must_use(cx, &attrs[..], s.span, &what)
+                    } else {
+                        false
+                    }
+                })
             }
             _ => false,
         };
-        if !warned {
+        if !ty_warned {
             cx.span_lint(UNUSED_RESULTS, s.span, "unused result");
         }
 
-        fn check_must_use(cx: &LateContext, attrs: &[ast::Attribute], sp: Span) -> bool {
+        fn check_must_use(cx: &LateContext, attrs: &[ast::Attribute], sp: Span, what: &str)
+                          -> bool {
             for attr in attrs {
                 if attr.check_name("must_use") {
-                    let mut msg = "unused result which must be used".to_string();
+                    let mut msg = format!("{} which must be used", what);
                     // check for #[must_use="..."]
                     match attr.value_str() {
                         None => {}
@@ -163,6 +244,59 @@ impl LateLintPass for UnusedResults {
             }
             false
         }
+
+        fn check_trait_object(cx: &LateContext, t: Ty, sp: Span, how: &str) -> bool {
+            match t.sty {
+                ty::TyTrait(ref data) => {
+                    let def_id = data.principal_def_id();
+                    let attrs = cx.tcx.get_attrs(def_id);
+                    let what = format!("unused {} `{}` trait object",
+                                       how,
+                                       cx.tcx.item_path_str(def_id));
+                    check_must_use(cx, &attrs[..], sp, &what)
+                }
+                _ => false
+            }
+        }
+    }
+}
+
+/// The method of an impl that a call of the trait method `def_id` with
+/// `substs` dispatches to, if that is known whatever the type parameters in
+/// scope are; `def_id` otherwise.
+fn impl_method<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                         def_id: DefId,
+                         substs: &Substs<'tcx>)
+                         -> DefId {
+    let trait_id = match tcx.trait_of_item(def_id) {
+        Some(trait_id) => trait_id,
+        None => return def_id,
+    };
+    let trait_ref = ty::Binder(substs.clone().erase_regions().to_trait_ref(tcx, trait_id));
+    if trait_ref.has_param_types() || trait_ref.has_projection_types() ||
+       trait_ref.needs_infer() {
+        return def_id;
+    }
+
+    tcx.populate_implementations_for_trait_if_necessary(trait_id);
+    let impl_def_id = tcx.infer_ctxt(None, None, ProjectionMode::AnyFinal).enter(|infcx| {
+        let mut selcx = traits::SelectionContext::new(&infcx);
+        let obligation = traits::Obligation::new(traits::ObligationCause::dummy(),
+                                                 trait_ref.to_poly_trait_predicate());
+        match selcx.select(&obligation) {
+            Ok(Some(traits::VtableImpl(impl_data))) => Some(impl_data.impl_def_id),
+            _ => None,
+        }
+    });
+    match impl_def_id {
+        Some(impl_def_id) => {
+            tcx.lookup_trait_def(trait_id)
+               .ancestors(impl_def_id)
+               .fn_defs(tcx, tcx.item_name(def_id))
+               .next()
+               .map_or(def_id, |node_item| node_item.item.def_id)
+        }
+        None => def_id,
     }
 }
 
@@ -296,8 +430,27 @@ impl UnusedParens {
         if let ast::ExprKind::Paren(ref inner) = value.node {
             let necessary = struct_lit_needs_parens && contains_exterior_struct_lit(&inner);
             if !necessary {
-                cx.span_lint(UNUSED_PARENS, value.span,
-                             &format!("unnecessary parentheses around {}", msg))
+                let mut err = cx.struct_span_lint(UNUSED_PARENS, value.span,
+                                                  &format!("unnecessary parentheses around {}",
+                                                           msg));
+                let codemap = cx.sess().codemap();
+                if let Ok(snippet) = codemap.span_to_snippet(inner.span) {
+                    // Keep `if(x)` from becoming `ifx`.
+                    let before = Span {
+                        lo: value.span.lo - BytePos(1),
+                        hi: value.span.lo,
+                        expn_id: value.span.expn_id,
+                    };
+                    let space = match codemap.span_to_snippet(before) {
+                        Ok(ref c) if !c.starts_with(char::is_whitespace) => " ",
+                        _ => "",
+                    };
+                    err.span_suggestion_with_applicability(value.span,
+                                                           "remove these parentheses",
+                                                           format!("{}{}", space, snippet),
+                                                           Applicability::MachineApplicable);
+                }
+                err.emit();
             }
         }
 
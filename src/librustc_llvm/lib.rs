@@ -726,6 +726,10 @@ extern {
     /* Operations on scalar constants */
     pub fn LLVMConstInt(IntTy: TypeRef, N: c_ulonglong, SignExtend: Bool)
                         -> ValueRef;
+    pub fn LLVMConstIntOfArbitraryPrecision(IntTy: TypeRef,
+                                            NumWords: c_uint,
+                                            Words: *const u64)
+                                            -> ValueRef;
     pub fn LLVMConstIntOfString(IntTy: TypeRef, Text: *const c_char, Radix: u8)
                                 -> ValueRef;
     pub fn LLVMConstIntOfStringAndSize(IntTy: TypeRef,
@@ -2024,6 +2028,16 @@ extern {
 
     pub fn LLVMRustHasFeature(T: TargetMachineRef,
                               s: *const c_char) -> bool;
+    pub fn LLVMRustGetTargetCPUsCount(T: TargetMachineRef) -> size_t;
+    pub fn LLVMRustGetTargetCPU(T: TargetMachineRef,
+                                Index: size_t,
+                                Name: *mut *const c_char,
+                                Desc: *mut *const c_char);
+    pub fn LLVMRustGetTargetFeaturesCount(T: TargetMachineRef) -> size_t;
+    pub fn LLVMRustGetTargetFeature(T: TargetMachineRef,
+                                    Index: size_t,
+                                    Name: *mut *const c_char,
+                                    Desc: *mut *const c_char);
 
     pub fn LLVMRustCreateTargetMachine(Triple: *const c_char,
                                        CPU: *const c_char,
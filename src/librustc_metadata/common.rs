@@ -223,6 +223,11 @@ pub const tag_macro_def: usize = 0x9e;
 pub const tag_macro_def_body: usize = 0x9f;
 pub const tag_macro_def_span_lo: usize = 0xa8;
 pub const tag_macro_def_span_hi: usize = 0xa9;
+// the module paths of a `macro` item, absent for `macro_rules!` macros
+pub const tag_macro_def_module: usize = 0xaa;
+pub const tag_macro_def_def_site: usize = 0xab;
+// the crate that defines a reexported `macro` item
+pub const tag_macro_def_def_site_crate: usize = 0xac;
 
 pub const tag_paren_sugar: usize = 0xa0;
 
@@ -252,3 +257,5 @@ pub fn rustc_version() -> String {
 }
 
 pub const tag_panic_strategy: usize = 0x114;
+
+pub const tag_macro_derive_registrar: usize = 0x115;
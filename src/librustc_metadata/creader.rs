@@ -38,7 +38,7 @@ use syntax::codemap::{self, Span, mk_sp, Pos};
 use syntax::parse;
 use syntax::attr;
 use syntax::attr::AttrMetaMethods;
-use syntax::parse::token::InternedString;
+use syntax::parse::token::{self, InternedString};
 use syntax::visit;
 use log;
 
@@ -123,6 +123,18 @@ fn register_native_lib(sess: &Session,
     cstore.add_used_library(name, kind);
 }
 
+/// Macros exported by a crate loaded through `#[macro_use] extern crate`.
+pub struct Macros {
+    pub macro_rules: Vec<ast::MacroDef>,
+
+    /// The registrar function of a `proc-macro` crate, which registers its
+    /// custom derive modes.
+    pub custom_derive_registrar: Option<DefIndex>,
+
+    pub svh: Svh,
+    pub dylib: Option<PathBuf>,
+}
+
 // Extra info about a crate loaded for plugins or exported macros.
 struct ExtensionCrate {
     metadata: PMDSource,
@@ -298,6 +310,11 @@ impl<'a> CrateReader<'a> {
         self.verify_rustc_version(name, span, &lib.metadata);
         self.verify_no_symbol_conflicts(span, &lib.metadata);
 
+        if decoder::get_derive_registrar_fn(lib.metadata.as_slice()).is_some() {
+            self.sess.span_err(span, "crates of the `proc-macro` crate type \
+                                      cannot be linked at runtime");
+        }
+
         // Claim this crate number and cache it
         let cnum = self.next_crate_num;
         self.next_crate_num += 1;
@@ -537,7 +554,9 @@ impl<'a> CrateReader<'a> {
             }
             LoadResult::Loaded(library) => {
                 let dylib = library.dylib.clone();
-                let metadata = if should_link {
+                let is_proc_macro = decoder::get_derive_registrar_fn(
+                    library.metadata.as_slice()).is_some();
+                let metadata = if should_link && !is_proc_macro {
                     // Register crate now to avoid double-reading metadata
                     let (_, cmd, _) = self.register_crate(&None, &info.ident,
                                                           &info.name, span,
@@ -559,15 +578,20 @@ impl<'a> CrateReader<'a> {
     }
 
     /// Read exported macros.
-    pub fn read_exported_macros(&mut self, item: &ast::Item) -> Vec<ast::MacroDef> {
+    pub fn read_macros(&mut self, item: &ast::Item) -> Macros {
         let ci = self.extract_crate_info(item).unwrap();
         let ekrate = self.read_extension_crate(item.span, &ci);
 
         let source_name = format!("<{} macros>", item.ident);
-        let mut macros = vec![];
+        let mut ret = Macros {
+            macro_rules: Vec::new(),
+            custom_derive_registrar: None,
+            svh: decoder::get_crate_hash(ekrate.metadata.as_slice()),
+            dylib: None,
+        };
         decoder::each_exported_macro(ekrate.metadata.as_slice(),
                                      &self.cstore.intr,
-            |name, attrs, span, body| {
+            |name, attrs, span, body, decl| {
                 // NB: Don't use parse::parse_tts_from_source_str because it parses with
                 // quote_depth > 0.
                 let mut p = parse::new_parser_from_source_str(&self.sess.parse_sess,
@@ -590,7 +614,7 @@ impl<'a> CrateReader<'a> {
                     attr::mark_used(attr);
                 }
 
-                macros.push(ast::MacroDef {
+                ret.macro_rules.push(ast::MacroDef {
                     ident: ast::Ident::with_empty_ctxt(name),
                     attrs: attrs,
                     id: ast::DUMMY_NODE_ID,
@@ -602,13 +626,51 @@ impl<'a> CrateReader<'a> {
                     allow_internal_unstable: false,
 
                     body: body,
+                    // The macros of `macro` items are named by the name
+                    // of their crate.
+                    decl: decl.map(|decl| {
+                        let krate = Some(token::intern(&ci.name));
+                        ast::DeclMacro {
+                            krate: krate,
+                            def_site_crate: decl.def_site_crate.or(krate),
+                            ..decl
+                        }
+                    }),
                 });
                 self.sess.imported_macro_spans.borrow_mut()
                     .insert(local_span, (name.as_str().to_string(), span));
                 true
             }
         );
-        macros
+
+        match decoder::get_derive_registrar_fn(ekrate.metadata.as_slice()) {
+            Some(id) => ret.custom_derive_registrar = Some(id),
+
+            // If this crate is not a proc-macro crate then we might be able to
+            // register it with the local crate store to prevent loading the
+            // metadata twice.
+            //
+            // If it's a proc-macro crate, though, then we definitely don't
+            // want to register it with the local crate store as we're just
+            // going to use it as we would a plugin.
+            None => return ret,
+        }
+
+        self.cstore.add_used_for_derive_macros(item);
+
+        if ekrate.target_only {
+            let message = format!("proc-macro crate is not available for \
+                                   triple `{}` (only found {})",
+                                  config::host_triple(),
+                                  self.sess.opts.target_triple);
+            self.sess.span_fatal(item.span, &message);
+        }
+
+        ret.dylib = ekrate.dylib.clone();
+        if ret.dylib.is_none() {
+            self.sess.span_err(item.span, "proc-macro crate not dylib");
+        }
+        ret
     }
 
     /// Look for a plugin registrar. Returns library path, crate
@@ -779,6 +841,7 @@ impl<'a> CrateReader<'a> {
             match *ct {
                 config::CrateTypeExecutable => need_exe_alloc = true,
                 config::CrateTypeDylib |
+                config::CrateTypeProcMacro |
                 config::CrateTypeCdylib |
                 config::CrateTypeStaticlib => need_lib_alloc = true,
                 config::CrateTypeRlib => {}
@@ -929,6 +992,13 @@ impl<'a> LocalCrateReader<'a> {
                     return;
                 }
 
+                // Crates of the `proc-macro` crate type were already loaded
+                // into the compiler during macro expansion, and are never
+                // linked into the output.
+                if self.cstore.was_used_for_derive_macros(i) {
+                    return;
+                }
+
                 match self.creader.extract_crate_info(i) {
                     Some(info) => {
                         let (cnum, _, _) = self.creader.resolve_crate(&None,
@@ -26,7 +26,7 @@ use rustc::hir::map::DefKey;
 use rustc::hir::svh::Svh;
 use rustc::middle::cstore::{ExternCrate};
 use rustc::session::config::PanicStrategy;
-use rustc::util::nodemap::{FnvHashMap, NodeMap, NodeSet, DefIdMap};
+use rustc::util::nodemap::{FnvHashMap, FnvHashSet, NodeMap, NodeSet, DefIdMap};
 
 use std::cell::{RefCell, Ref, Cell};
 use std::rc::Rc;
@@ -34,7 +34,7 @@ use std::path::PathBuf;
 use flate::Bytes;
 use syntax::ast;
 use syntax::attr;
-use syntax::codemap;
+use syntax::codemap::{self, Span};
 use syntax::parse::token::IdentInterner;
 
 pub use middle::cstore::{NativeLibraryKind, LinkagePreference};
@@ -103,6 +103,9 @@ pub struct CStore {
     used_libraries: RefCell<Vec<(String, NativeLibraryKind)>>,
     used_link_args: RefCell<Vec<String>>,
     statically_included_foreign_items: RefCell<NodeSet>,
+    /// Spans of the `extern crate` items which loaded `proc-macro` crates.
+    /// Node ids aren't assigned yet when macros are loaded.
+    used_for_derive_macro: RefCell<FnvHashSet<Span>>,
     pub intr: Rc<IdentInterner>,
     pub visible_parent_map: RefCell<DefIdMap<DefId>>,
 }
@@ -119,6 +122,7 @@ impl CStore {
             used_link_args: RefCell::new(Vec::new()),
             intr: intr,
             statically_included_foreign_items: RefCell::new(NodeSet()),
+            used_for_derive_macro: RefCell::new(FnvHashSet()),
             visible_parent_map: RefCell::new(FnvHashMap()),
         }
     }
@@ -257,6 +261,14 @@ impl CStore {
         self.statically_included_foreign_items.borrow().contains(&id)
     }
 
+    pub fn add_used_for_derive_macros(&self, i: &ast::Item) {
+        self.used_for_derive_macro.borrow_mut().insert(i.span);
+    }
+
+    pub fn was_used_for_derive_macros(&self, i: &ast::Item) -> bool {
+        self.used_for_derive_macro.borrow().contains(&i.span)
+    }
+
     pub fn do_extern_mod_stmt_cnum(&self, emod_id: ast::NodeId) -> Option<ast::CrateNum>
     {
         self.extern_mod_crate_map.borrow().get(&emod_id).cloned()
@@ -60,6 +60,8 @@ use syntax::codemap::{self, Span, BytePos, NO_EXPANSION};
 use syntax::print::pprust;
 use syntax::ptr::P;
 
+use rustc_i128::u128;
+
 
 pub type Cmd<'a> = &'a crate_metadata;
 
@@ -135,6 +137,7 @@ enum Family {
     DefaultImpl,           // d
     Trait,                 // I
     Struct(VariantKind),   // S, s, u
+    Union,                 // U
     PublicField,           // g
     InheritedField,        // N
     Constant,              // C
@@ -162,6 +165,7 @@ fn item_family(item: rbml::Doc) -> Family {
       'S' => Struct(VariantKind::Struct),
       's' => Struct(VariantKind::Tuple),
       'u' => Struct(VariantKind::Unit),
+      'U' => Union,
       'g' => PublicField,
       'N' => InheritedField,
        c => bug!("unexpected family char: {}", c)
@@ -295,7 +299,7 @@ fn item_name(intr: &IdentInterner, item: rbml::Doc) -> ast::Name {
 
 fn family_to_variant_kind<'tcx>(family: Family) -> Option<ty::VariantKind> {
     match family {
-        Struct(VariantKind::Struct) | Variant(VariantKind::Struct) =>
+        Struct(VariantKind::Struct) | Variant(VariantKind::Struct) | Union =>
             Some(ty::VariantKind::Struct),
         Struct(VariantKind::Tuple) | Variant(VariantKind::Tuple) =>
             Some(ty::VariantKind::Tuple),
@@ -322,7 +326,7 @@ fn item_to_def_like(cdata: Cmd, item: rbml::Doc, did: DefId) -> DefLike {
         }
         ImmStatic => DlDef(Def::Static(did, false)),
         MutStatic => DlDef(Def::Static(did, true)),
-        Struct(..) => DlDef(Def::Struct(did)),
+        Struct(..) | Union => DlDef(Def::Struct(did)),
         Fn        => DlDef(Def::Fn(did)),
         Method | StaticMethod => {
             DlDef(Def::Method(did))
@@ -425,7 +429,7 @@ pub fn get_adt_def<'a, 'tcx>(intr: &IdentInterner,
                 did: did,
                 name: item_name(intr, item),
                 fields: get_variant_fields(intr, cdata, item),
-                disr_val: ConstInt::Infer(disr),
+                disr_val: ConstInt::Infer(disr as u128),
                 kind: expect_variant_kind(item_family(item)),
             }
         }).collect()
@@ -478,6 +482,10 @@ pub fn get_adt_def<'a, 'tcx>(intr: &IdentInterner,
             (ty::AdtKind::Struct,
              vec![get_struct_variant(intr, cdata, doc, ctor_did)])
         }
+        Union => {
+            (ty::AdtKind::Union,
+             vec![get_struct_variant(intr, cdata, doc, did)])
+        }
         _ => bug!("get_adt_def called on a non-ADT {:?} - {:?}",
                   item_family(doc), did)
     };
@@ -1499,8 +1507,13 @@ pub fn get_plugin_registrar_fn(data: &[u8]) -> Option<DefIndex> {
         .map(|doc| DefIndex::from_u32(reader::doc_as_u32(doc)))
 }
 
+pub fn get_derive_registrar_fn(data: &[u8]) -> Option<DefIndex> {
+    reader::maybe_get_doc(rbml::Doc::new(data), tag_macro_derive_registrar)
+        .map(|doc| DefIndex::from_u32(reader::doc_as_u32(doc)))
+}
+
 pub fn each_exported_macro<F>(data: &[u8], intr: &IdentInterner, mut f: F) where
-    F: FnMut(ast::Name, Vec<ast::Attribute>, Span, String) -> bool,
+    F: FnMut(ast::Name, Vec<ast::Attribute>, Span, String, Option<ast::DeclMacro>) -> bool,
 {
     let macros = reader::get_doc(rbml::Doc::new(data), tag_macro_defs);
     for macro_doc in reader::tagged_docs(macros, tag_macro_def) {
@@ -1508,12 +1521,26 @@ pub fn each_exported_macro<F>(data: &[u8], intr: &IdentInterner, mut f: F) where
         let attrs = get_attributes(macro_doc);
         let span = get_macro_span(macro_doc);
         let body = reader::get_doc(macro_doc, tag_macro_def_body);
-        if !f(name, attrs, span, body.as_str().to_string()) {
+        let decl = reader::maybe_get_doc(macro_doc, tag_macro_def_module).map(|module| {
+            let def_site = reader::get_doc(macro_doc, tag_macro_def_def_site);
+            let def_site_crate = reader::maybe_get_doc(macro_doc, tag_macro_def_def_site_crate);
+            ast::DeclMacro {
+                krate: None,
+                module: get_module_path(module),
+                def_site_crate: def_site_crate.map(|doc| token::intern(doc.as_str_slice())),
+                def_site: get_module_path(def_site),
+            }
+        });
+        if !f(name, attrs, span, body.as_str().to_string(), decl) {
             break;
         }
     }
 }
 
+fn get_module_path(doc: rbml::Doc) -> Vec<ast::Name> {
+    doc.as_str_slice().split("::").filter(|name| !name.is_empty()).map(token::intern).collect()
+}
+
 pub fn get_macro_span(doc: rbml::Doc) -> Span {
     let lo_doc = reader::get_doc(doc, tag_macro_def_span_lo);
     let lo = BytePos(reader::doc_as_u32(lo_doc));
@@ -984,7 +984,7 @@ fn encode_info_for_item<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
                                  vis,
                                  index);
       }
-      hir::ItemStruct(ref struct_def, _) => {
+      hir::ItemStruct(ref struct_def, _) | hir::ItemUnion(ref struct_def, _) => {
         /* Index the class*/
         let _task = index.record(def_id, rbml_w);
 
@@ -995,6 +995,7 @@ fn encode_info_for_item<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
         rbml_w.start_tag(tag_items_data_item);
         encode_def_id_and_key(ecx, rbml_w, def_id);
         encode_family(rbml_w, match *struct_def {
+            _ if def.is_union() => 'U',
             hir::VariantData::Struct(..) => 'S',
             hir::VariantData::Tuple(..) => 's',
             hir::VariantData::Unit(..) => 'u',
@@ -1394,6 +1395,26 @@ fn my_visit_expr(expr: &hir::Expr,
     }
 }
 
+fn my_visit_ty<'a, 'tcx>(ty: &hir::Ty,
+                         rbml_w: &mut Encoder,
+                         ecx: &EncodeContext<'a, 'tcx>,
+                         index: &mut CrateIndex<'a, 'tcx>) {
+    match ty.node {
+        hir::TyImplTrait(_) => {
+            let def_id = ecx.tcx.map.local_def_id(ty.id);
+
+            let _task = index.record(def_id, rbml_w);
+
+            rbml_w.start_tag(tag_items_data_item);
+            encode_def_id_and_key(ecx, rbml_w, def_id);
+            encode_family(rbml_w, 'y');
+            encode_bounds_and_type_for_item(rbml_w, ecx, index, ty.id);
+            rbml_w.end_tag();
+        }
+        _ => { }
+    }
+}
+
 struct EncodeVisitor<'a, 'b:'a, 'c:'a, 'tcx:'c> {
     rbml_w_for_visit_item: &'a mut Encoder<'b>,
     ecx: &'a EncodeContext<'c, 'tcx>,
@@ -1405,6 +1426,10 @@ impl<'a, 'b, 'c, 'tcx> Visitor<'tcx> for EncodeVisitor<'a, 'b, 'c, 'tcx> {
         intravisit::walk_expr(self, ex);
         my_visit_expr(ex, self.rbml_w_for_visit_item, self.ecx, self.index);
     }
+    fn visit_ty(&mut self, ty: &'tcx hir::Ty) {
+        intravisit::walk_ty(self, ty);
+        my_visit_ty(ty, self.rbml_w_for_visit_item, self.ecx, self.index);
+    }
     fn visit_item(&mut self, i: &'tcx hir::Item) {
         intravisit::walk_item(self, i);
         encode_info_for_item(self.ecx, self.rbml_w_for_visit_item, i, self.index);
@@ -1610,6 +1635,16 @@ fn encode_plugin_registrar_fn(ecx: &EncodeContext, rbml_w: &mut Encoder) {
     }
 }
 
+fn encode_derive_registrar_fn(ecx: &EncodeContext, rbml_w: &mut Encoder) {
+    match ecx.tcx.sess.derive_registrar_fn.get() {
+        Some(id) => {
+            let def_id = ecx.tcx.map.local_def_id(id);
+            rbml_w.wr_tagged_u32(tag_macro_derive_registrar, def_id.index.as_u32());
+        }
+        None => {}
+    }
+}
+
 fn encode_codemap(ecx: &EncodeContext, rbml_w: &mut Encoder) {
     rbml_w.start_tag(tag_codemap);
     let codemap = ecx.tcx.sess.codemap();
@@ -1650,12 +1685,24 @@ fn encode_macro_defs(rbml_w: &mut Encoder,
 
         rbml_w.wr_tagged_str(tag_macro_def_body,
                              &::syntax::print::pprust::tts_to_string(&def.body));
+        if let Some(ref decl) = def.decl {
+            rbml_w.wr_tagged_str(tag_macro_def_module, &module_path_to_string(&decl.module));
+            rbml_w.wr_tagged_str(tag_macro_def_def_site,
+                                 &module_path_to_string(&decl.def_site));
+            if let Some(krate) = decl.def_site_crate {
+                rbml_w.wr_tagged_str(tag_macro_def_def_site_crate, &krate.as_str());
+            }
+        }
 
         rbml_w.end_tag();
     }
     rbml_w.end_tag();
 }
 
+fn module_path_to_string(path: &[Name]) -> String {
+    path.iter().map(|name| name.to_string()).collect::<Vec<_>>().join("::")
+}
+
 fn encode_struct_field_attrs(ecx: &EncodeContext,
                              rbml_w: &mut Encoder,
                              krate: &hir::Crate) {
@@ -1933,6 +1980,7 @@ fn encode_metadata_inner(rbml_w: &mut Encoder,
     // Encode the plugin registrar function
     i = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap();
     encode_plugin_registrar_fn(&ecx, rbml_w);
+    encode_derive_registrar_fn(&ecx, rbml_w);
     stats.plugin_registrar_fn_bytes = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap() - i;
 
     // Encode codemap
@@ -19,6 +19,8 @@
 
 #![feature(box_patterns)]
 #![feature(enumset)]
+#![feature(proc_macro_internals)]
+#![feature(proc_macro_lib)]
 #![feature(quote)]
 #![feature(rustc_diagnostic_macros)]
 #![feature(rustc_private)]
@@ -32,12 +34,15 @@
 extern crate flate;
 extern crate rbml;
 extern crate serialize as rustc_serialize; // used by deriving
+extern crate proc_macro;
+extern crate syntax_ext;
 
 #[macro_use]
 extern crate rustc;
 extern crate rustc_back;
 extern crate rustc_llvm;
 extern crate rustc_const_math;
+extern crate rustc_i128;
 
 pub use rustc::middle;
 
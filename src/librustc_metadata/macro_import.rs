@@ -10,25 +10,33 @@
 
 //! Used by `rustc` when loading a crate with exported macros.
 
-use creader::CrateReader;
+use std::collections::{HashSet, HashMap};
+use std::env;
+use std::mem;
+
+use creader::{CrateReader, Macros};
 use cstore::CStore;
 
+use proc_macro::TokenStream;
+use proc_macro::__internal::Registry;
+use rustc::hir::def_id::DefIndex;
 use rustc::session::Session;
-
-use std::collections::{HashSet, HashMap};
+use rustc_back::dynamic_lib::DynamicLibrary;
 use syntax::codemap::Span;
+use syntax::ext::base::LoadedMacro;
 use syntax::parse::token;
 use syntax::ast;
 use syntax::attr;
 use syntax::visit;
 use syntax::visit::Visitor;
 use syntax::attr::AttrMetaMethods;
+use syntax_ext::deriving::custom::CustomDerive;
 
 struct MacroLoader<'a> {
     sess: &'a Session,
     span_whitelist: HashSet<Span>,
     reader: CrateReader<'a>,
-    macros: Vec<ast::MacroDef>,
+    macros: Vec<LoadedMacro>,
 }
 
 impl<'a> MacroLoader<'a> {
@@ -51,7 +59,7 @@ pub fn read_macro_defs(sess: &Session,
                        cstore: &CStore,
                        krate: &ast::Crate,
                        crate_name: &str)
-                       -> Vec<ast::MacroDef>
+                       -> Vec<LoadedMacro>
 {
     let mut loader = MacroLoader::new(sess, cstore, crate_name);
 
@@ -144,10 +152,14 @@ impl<'a> MacroLoader<'a> {
                        vi: &ast::Item,
                        import: Option<MacroSelection>,
                        reexport: MacroSelection) {
-        if let Some(sel) = import.as_ref() {
-            if sel.is_empty() && reexport.is_empty() {
-                return;
-            }
+        // The macros of `macro` items are named by path rather than imported
+        // with `#[macro_use]`, so when those are enabled the macros of every
+        // crate at the crate root are read.
+        let decl_only = import.as_ref().map_or(false, |sel| sel.is_empty()) && reexport.is_empty();
+        let decl_macro = self.sess.features.borrow().decl_macro &&
+                         self.span_whitelist.contains(&vi.span);
+        if decl_only && !decl_macro {
+            return;
         }
 
         if !self.span_whitelist.contains(&vi.span) {
@@ -156,10 +168,42 @@ impl<'a> MacroLoader<'a> {
             return;
         }
 
-        let macros = self.reader.read_exported_macros(vi);
+        let mut macros = self.reader.read_macros(vi);
         let mut seen = HashSet::new();
 
-        for mut def in macros {
+        if let Some(index) = macros.custom_derive_registrar {
+            // custom derive crates currently should not have any macro_rules!
+            // exported macros, enforced elsewhere
+            assert_eq!(macros.macro_rules.len(), 0);
+
+            if decl_only {
+                return;
+            }
+
+            if import.is_some() {
+                self.sess.span_err(vi.span,
+                                   "`proc-macro` crates cannot be selectively \
+                                    imported from, must use `#[macro_use]`");
+            }
+
+            if reexport.len() > 0 {
+                self.sess.span_err(vi.span,
+                                   "`proc-macro` crates cannot be reexported \
+                                    from");
+            }
+
+            self.load_derive_macros(vi.span, &macros, index);
+            return
+        }
+
+        for mut def in macros.macro_rules.drain(..) {
+            if def.decl.is_some() {
+                def.use_locally = decl_macro;
+                def.export = false;
+                self.macros.push(LoadedMacro::Def(def));
+                continue;
+            }
+
             let name = def.ident.name.as_str();
 
             def.use_locally = match import.as_ref() {
@@ -170,7 +214,7 @@ impl<'a> MacroLoader<'a> {
             def.allow_internal_unstable = attr::contains_name(&def.attrs,
                                                               "allow_internal_unstable");
             debug!("load_macros: loaded: {:?}", def);
-            self.macros.push(def);
+            self.macros.push(LoadedMacro::Def(def));
             seen.insert(name);
         }
 
@@ -190,4 +234,52 @@ impl<'a> MacroLoader<'a> {
             }
         }
     }
+
+    /// Load the custom derive macros into the list of macros we're loading.
+    ///
+    /// Note that this is intentionally similar to how we load plugins today,
+    /// but also intentionally separate. Plugins are likely always going to be
+    /// implemented as dynamic libraries, but we have a possible future where
+    /// custom derive (and other macro-1.1 style features) are implemented via
+    /// executables and custom IPC.
+    fn load_derive_macros(&mut self, span: Span, macros: &Macros, index: DefIndex) {
+        let path = match macros.dylib {
+            Some(ref p) => p,
+            None => return,
+        };
+
+        // Make sure the path contains a / or the linker will search for it.
+        let path = env::current_dir().unwrap().join(path);
+        let lib = match DynamicLibrary::open(Some(&path)) {
+            Ok(lib) => lib,
+            Err(err) => self.sess.span_fatal(span, &err),
+        };
+
+        let sym = self.sess.generate_derive_registrar_symbol(&macros.svh, index);
+        let registrar = unsafe {
+            let sym = match lib.symbol(&sym) {
+                Ok(f) => f,
+                Err(err) => self.sess.span_fatal(span, &err),
+            };
+            mem::transmute::<*mut u8, fn(&mut Registry)>(sym)
+        };
+
+        struct MyRegistrar<'a>(&'a mut Vec<LoadedMacro>);
+
+        impl<'a> Registry for MyRegistrar<'a> {
+            fn register_custom_derive(&mut self,
+                                      trait_name: &str,
+                                      expand: fn(TokenStream) -> TokenStream) {
+                let derive = Box::new(CustomDerive::new(expand));
+                self.0.push(LoadedMacro::CustomDerive(trait_name.to_string(),
+                                                      derive));
+            }
+        }
+
+        registrar(&mut MyRegistrar(&mut self.macros));
+
+        // Intentionally leak the dynamic library. We can't ever unload it
+        // since the library can make things that will live arbitrarily long.
+        mem::forget(lib);
+    }
 }
@@ -73,6 +73,7 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
 
     match t.sty {
         ty::TyBool => { write!(w, "b"); }
+        ty::TyNever => { write!(w, "!"); }
         ty::TyChar => { write!(w, "c"); }
         ty::TyInt(t) => {
             match t {
@@ -80,7 +81,8 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
                 ast::IntTy::I8 => write!(w, "MB"),
                 ast::IntTy::I16 => write!(w, "MW"),
                 ast::IntTy::I32 => write!(w, "ML"),
-                ast::IntTy::I64 => write!(w, "MD")
+                ast::IntTy::I64 => write!(w, "MD"),
+                ast::IntTy::I128 => write!(w, "MQ")
             };
         }
         ty::TyUint(t) => {
@@ -89,7 +91,8 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
                 ast::UintTy::U8 => write!(w, "Mb"),
                 ast::UintTy::U16 => write!(w, "Mw"),
                 ast::UintTy::U32 => write!(w, "Ml"),
-                ast::UintTy::U64 => write!(w, "Md")
+                ast::UintTy::U64 => write!(w, "Md"),
+                ast::UintTy::U128 => write!(w, "Mq")
             };
         }
         ty::TyFloat(t) => {
@@ -170,6 +173,11 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
             enc_trait_ref(w, cx, data.trait_ref);
             write!(w, "{}]", data.item_name);
         }
+        ty::TyAnon(def_id, substs) => {
+            write!(w, "A[{}|", (cx.ds)(cx.tcx, def_id));
+            enc_substs(w, cx, substs);
+            write!(w, "]");
+        }
         ty::TyError => {
             write!(w, "e");
         }
@@ -367,14 +375,7 @@ fn enc_fn_sig<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>,
     } else {
         write!(w, "N");
     }
-    match fsig.0.output {
-        ty::FnConverging(result_type) => {
-            enc_ty(w, cx, result_type);
-        }
-        ty::FnDiverging => {
-            write!(w, "z");
-        }
-    }
+    enc_ty(w, cx, fsig.0.output);
 }
 
 pub fn enc_builtin_bounds(w: &mut Cursor<Vec<u8>>, _cx: &ctxt, bs: &ty::BuiltinBounds) {
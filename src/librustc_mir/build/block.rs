@@ -58,6 +58,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                                 this.expr_into_pattern(block, remainder_scope_id, pattern, init)
                             } else {
                                 this.declare_bindings(remainder_scope_id, &pattern);
+                                this.storage_live_for_bindings(block, &pattern);
                                 block.unit()
                             }
                         }));
@@ -157,6 +157,21 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
             ExprKind::Adt {
                 adt_def, variant_index, substs, fields, base
             } => { // see (*) above
+                if adt_def.is_union() {
+                    // A union has no aggregate form, it is initialized by
+                    // writing its one field into a temporary.
+                    let result = this.temp(expr.ty);
+                    for f in fields {
+                        let value = this.hir.mirror(f.expr);
+                        let field_ty = value.ty;
+                        let operand = unpack!(block = this.as_operand(block, value));
+                        this.cfg.push_assign(block, scope_id, expr_span,
+                                             &result.clone().field(f.name, field_ty),
+                                             Rvalue::Use(operand));
+                    }
+                    return block.and(Rvalue::Use(Operand::Consume(result)));
+                }
+
                 // first process the set of fields that were provided
                 // (evaluating them in order given by user)
                 let fields_map: FnvHashMap<_, _> =
@@ -195,6 +210,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
             ExprKind::Block { .. } |
             ExprKind::Match { .. } |
             ExprKind::If { .. } |
+            ExprKind::NeverToAny { .. } |
             ExprKind::Loop { .. } |
             ExprKind::LogicalOp { .. } |
             ExprKind::Call { .. } |
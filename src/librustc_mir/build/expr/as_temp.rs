@@ -38,6 +38,10 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
         let temp_lifetime = expr.temp_lifetime;
         let expr_span = expr.span;
 
+        if temp_lifetime.is_some() {
+            this.storage_live(block, expr_span, &temp);
+        }
+
         // Careful here not to cause an infinite cycle. If we always
         // called `into`, then for lvalues like `x.f`, it would
         // eventually fallback to us, and we'd loop. There's a reason
@@ -40,6 +40,18 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                 let lhs_needs_drop = this.hir.needs_drop(lhs_ty);
                 let rhs_needs_drop = this.hir.needs_drop(rhs_ty);
 
+                // Writing to a union field never drops the previous contents,
+                // as there is no telling which field was last written to.
+                let lhs_is_union_field = match lhs.kind {
+                    ExprKind::Field { lhs: ExprRef::Hair(base), .. } => {
+                        this.hir.tcx().expr_ty_adjusted(base).is_union()
+                    }
+                    ExprKind::Field { lhs: ExprRef::Mirror(ref base), .. } => {
+                        base.ty.is_union()
+                    }
+                    _ => false
+                };
+
                 // Note: we evaluate assignments right-to-left. This
                 // is better for borrowck interaction with overloaded
                 // operators like x[j] = x[i].
@@ -54,7 +66,9 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                 };
 
                 let lhs = unpack!(block = this.as_lvalue(block, lhs));
-                unpack!(block = this.build_drop(block, lhs_span, lhs.clone(), lhs_ty));
+                if !lhs_is_union_field {
+                    unpack!(block = this.build_drop(block, lhs_span, lhs.clone(), lhs_ty));
+                }
                 this.cfg.push_assign(block, scope_id, expr_span, &lhs, rhs);
                 block.unit()
             }
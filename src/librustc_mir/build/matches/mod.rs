@@ -135,6 +135,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                                                  ty,
                                                  irrefutable_pat.span);
                 let lvalue = Lvalue::Var(index);
+                self.storage_live(block, irrefutable_pat.span, &lvalue);
                 return self.into(&lvalue, block, initializer);
             }
             _ => {}
@@ -208,6 +209,37 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
             }
         }
     }
+
+    /// Marks the start of the storage of the variables bound by
+    /// `pattern`, for a `let` without an initializer.
+    pub fn storage_live_for_bindings(&mut self, block: BasicBlock, pattern: &Pattern<'tcx>) {
+        match *pattern.kind {
+            PatternKind::Binding { var, ref subpattern, .. } => {
+                let lvalue = Lvalue::Var(self.var_indices[&var]);
+                self.storage_live(block, pattern.span, &lvalue);
+                if let Some(subpattern) = subpattern.as_ref() {
+                    self.storage_live_for_bindings(block, subpattern);
+                }
+            }
+            PatternKind::Array { ref prefix, ref slice, ref suffix } |
+            PatternKind::Slice { ref prefix, ref slice, ref suffix } => {
+                for subpattern in prefix.iter().chain(slice).chain(suffix) {
+                    self.storage_live_for_bindings(block, subpattern);
+                }
+            }
+            PatternKind::Constant { .. } | PatternKind::Range { .. } | PatternKind::Wild => {
+            }
+            PatternKind::Deref { ref subpattern } => {
+                self.storage_live_for_bindings(block, subpattern);
+            }
+            PatternKind::Leaf { ref subpatterns } |
+            PatternKind::Variant { ref subpatterns, .. } => {
+                for subpattern in subpatterns {
+                    self.storage_live_for_bindings(block, &subpattern.pattern);
+                }
+            }
+        }
+    }
 }
 
 /// List of blocks for each arm (and potentially other metadata in the
@@ -613,9 +645,10 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                     Rvalue::Ref(region, borrow_kind, binding.source),
             };
 
+            let lvalue = Lvalue::Var(var_index);
+            self.storage_live(block, binding.span, &lvalue);
             let scope_id = self.innermost_scope_id();
-            self.cfg.push_assign(block, scope_id, binding.span,
-                                 &Lvalue::Var(var_index), rvalue);
+            self.cfg.push_assign(block, scope_id, binding.span, &lvalue, rvalue);
         }
     }
 
@@ -60,6 +60,11 @@ When processing the `let x`, we will add one drop to the scope for
 y`, we will add another drop (in fact, to a subscope, but let's ignore
 that for now); any later drops would also drop `y`.
 
+Vars and temps that need no dropping still get an entry on the drop
+list, so that a `StorageDead` marking the end of their storage is
+emitted on every exit from the scope. These entries produce no
+cleanup on the unwinding path.
+
 ### Early exit
 
 There are numerous "normal" ways to early exit a scope: `break`,
@@ -131,7 +136,11 @@ pub struct Scope<'tcx> {
     /// place. This block does not execute any of the drops or free:
     /// each of those has their own cached-blocks, which will branch
     /// to this point.
-    cached_block: Option<BasicBlock>
+    cached_block: Option<BasicBlock>,
+
+    /// Whether any drop or free has been scheduled in this scope,
+    /// i.e. whether unwinding out of it needs to run any code.
+    needs_cleanup: bool,
 }
 
 struct DropData<'tcx> {
@@ -141,11 +150,29 @@ struct DropData<'tcx> {
     /// lvalue to drop
     value: Lvalue<'tcx>,
 
-    /// The cached block for the cleanups-on-diverge path. This block
-    /// contains code to run the current drop and all the preceding
-    /// drops (i.e. those having lower index in Drop’s Scope drop
-    /// array)
-    cached_block: Option<BasicBlock>
+    /// Whether this is a value drop or only the end of the storage of
+    /// a var or temp.
+    kind: DropKind,
+}
+
+enum DropKind {
+    Value {
+        /// The cached block for the cleanups-on-diverge path. This block
+        /// contains code to run the current drop and all the preceding
+        /// drops (i.e. those having lower index in Drop’s Scope drop
+        /// array)
+        cached_block: Option<BasicBlock>
+    },
+    Storage
+}
+
+impl<'tcx> DropData<'tcx> {
+    fn cached_block(&self) -> Option<BasicBlock> {
+        match self.kind {
+            DropKind::Value { cached_block } => cached_block,
+            DropKind::Storage => None,
+        }
+    }
 }
 
 struct FreeData<'tcx> {
@@ -184,7 +211,9 @@ impl<'tcx> Scope<'tcx> {
     fn invalidate_cache(&mut self) {
         self.cached_block = None;
         for dropdata in &mut self.drops {
-            dropdata.cached_block = None;
+            if let DropKind::Value { ref mut cached_block } = dropdata.kind {
+                *cached_block = None;
+            }
         }
         if let Some(ref mut freedata) = self.free {
             freedata.cached_block = None;
@@ -196,8 +225,12 @@ impl<'tcx> Scope<'tcx> {
     /// Precondition: the caches must be fully filled (i.e. diverge_cleanup is called) in order for
     /// this method to work correctly.
     fn cached_block(&self) -> Option<BasicBlock> {
-        if let Some(data) = self.drops.last() {
-            Some(data.cached_block.expect("drop cache is not filled"))
+        let last_value_drop = self.drops.iter().rev().filter_map(|data| match data.kind {
+            DropKind::Value { cached_block } => Some(cached_block),
+            DropKind::Storage => None,
+        }).next();
+        if let Some(cached_block) = last_value_drop {
+            Some(cached_block.expect("drop cache is not filled"))
         } else if let Some(ref data) = self.free {
             Some(data.cached_block.expect("free cache is not filled"))
         } else {
@@ -266,6 +299,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
             drops: vec![],
             free: None,
             cached_block: None,
+            needs_cleanup: false,
         });
         self.scope_auxiliary.vec.push(ScopeAuxiliary {
             extent: extent,
@@ -387,26 +421,35 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
     // Scheduling drops
     // ================
     /// Indicates that `lvalue` should be dropped on exit from
-    /// `extent`.
+    /// `extent`. The storage of a var or temp also ends there.
     pub fn schedule_drop(&mut self,
                          span: Span,
                          extent: CodeExtent,
                          lvalue: &Lvalue<'tcx>,
                          lvalue_ty: Ty<'tcx>) {
-        if !self.hir.needs_drop(lvalue_ty) {
-            return
-        }
+        let needs_drop = self.hir.needs_drop(lvalue_ty);
+        let drop_kind = if needs_drop {
+            DropKind::Value { cached_block: None }
+        } else {
+            // Only vars and temps have storage of their own.
+            match *lvalue {
+                Lvalue::Var(_) | Lvalue::Temp(_) => DropKind::Storage,
+                _ => return
+            }
+        };
+
         for scope in self.scopes.iter_mut().rev() {
             if scope.extent == extent {
                 // No need to invalidate any caches here. The just-scheduled drop will branch into
                 // the drop that comes before it in the vector.
+                scope.needs_cleanup |= needs_drop;
                 scope.drops.push(DropData {
                     span: span,
                     value: lvalue.clone(),
-                    cached_block: None
+                    kind: drop_kind
                 });
                 return;
-            } else {
+            } else if needs_drop {
                 // We must invalidate all the cached_blocks leading up to the scope we’re
                 // looking for, because all of the blocks in the chain will become incorrect.
                 scope.invalidate_cache()
@@ -428,6 +471,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
         for scope in self.scopes.iter_mut().rev() {
             if scope.extent == extent {
                 assert!(scope.free.is_none(), "scope already has a scheduled free!");
+                scope.needs_cleanup = true;
                 // We also must invalidate the caches in the scope for which the free is scheduled
                 // because the drops must branch into the free we schedule here.
                 scope.invalidate_cache();
@@ -456,7 +500,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
     /// See module comment for more details. None indicates there’s no
     /// cleanup to do at this point.
     pub fn diverge_cleanup(&mut self) -> Option<BasicBlock> {
-        if self.scopes.iter().all(|scope| scope.drops.is_empty() && scope.free.is_none()) {
+        if !self.scopes.iter().any(|scope| scope.needs_cleanup) {
             return None;
         }
         assert!(!self.scopes.is_empty()); // or `all` above would be true
@@ -493,6 +537,16 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
         Some(target)
     }
 
+    /// Marks the start of the storage of a var or temp.
+    pub fn storage_live(&mut self, block: BasicBlock, span: Span, lvalue: &Lvalue<'tcx>) {
+        let scope_id = self.innermost_scope_id();
+        self.cfg.push(block, Statement {
+            span: span,
+            scope: scope_id,
+            kind: StatementKind::StorageLive(lvalue.clone())
+        });
+    }
+
     /// Utility function for *non*-scope code to build their own drops
     pub fn build_drop(&mut self,
                       block: BasicBlock,
@@ -641,23 +695,38 @@ fn build_scope_drops<'tcx>(cfg: &mut CFG<'tcx>,
                            earlier_scopes: &[Scope<'tcx>],
                            mut block: BasicBlock)
                            -> BlockAnd<()> {
-    let mut iter = scope.drops.iter().rev().peekable();
+    let mut iter = scope.drops.iter().rev();
     while let Some(drop_data) = iter.next() {
-        // Try to find the next block with its cached block for us to diverge into in case the
-        // drop panics.
-        let on_diverge = iter.peek().iter().flat_map(|dd| dd.cached_block.into_iter()).next();
-        // If there’s no `cached_block`s within current scope, we must look for one in the
-        // enclosing scope.
-        let on_diverge = on_diverge.or_else(||{
-            earlier_scopes.iter().rev().flat_map(|s| s.cached_block()).next()
-        });
-        let next = cfg.start_new_block();
-        cfg.terminate(block, scope.id, drop_data.span, TerminatorKind::Drop {
-            value: drop_data.value.clone(),
-            target: next,
-            unwind: on_diverge
-        });
-        block = next;
+        if let DropKind::Value { .. } = drop_data.kind {
+            // Try to find the next block with its cached block for us to diverge into in case
+            // the drop panics.
+            let on_diverge = iter.clone().filter_map(|dd| dd.cached_block()).next();
+            // If there’s no `cached_block`s within current scope, we must look for one in the
+            // enclosing scope.
+            let on_diverge = on_diverge.or_else(||{
+                earlier_scopes.iter().rev().flat_map(|s| s.cached_block()).next()
+            });
+            let next = cfg.start_new_block();
+            cfg.terminate(block, scope.id, drop_data.span, TerminatorKind::Drop {
+                value: drop_data.value.clone(),
+                target: next,
+                unwind: on_diverge
+            });
+            block = next;
+        }
+
+        // Whether or not it needed dropping, the storage of a var or temp
+        // ends here.
+        match drop_data.value {
+            Lvalue::Var(_) | Lvalue::Temp(_) => {
+                cfg.push(block, Statement {
+                    span: drop_data.span,
+                    scope: scope.id,
+                    kind: StatementKind::StorageDead(drop_data.value.clone())
+                });
+            }
+            _ => {}
+        }
     }
     block.unit()
 }
@@ -700,8 +769,14 @@ fn build_diverge_scope<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
     // Next, build up the drops. Here we iterate the vector in
     // *forward* order, so that we generate drops[0] first (right to
     // left in diagram above).
+    // The end of storage is of no interest on the way out of the
+    // function, so only value drops are built here.
     for drop_data in &mut scope.drops {
-        target = if let Some(cached_block) = drop_data.cached_block {
+        let cached_block = match drop_data.kind {
+            DropKind::Value { ref mut cached_block } => cached_block,
+            DropKind::Storage => continue
+        };
+        target = if let Some(cached_block) = *cached_block {
             cached_block
         } else {
             let block = cfg.start_new_cleanup_block();
@@ -713,7 +788,7 @@ fn build_diverge_scope<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
                               target: target,
                               unwind: None
                           });
-            drop_data.cached_block = Some(block);
+            *cached_block = Some(block);
             block
         };
     }
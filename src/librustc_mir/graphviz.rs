@@ -10,7 +10,7 @@
 
 use dot;
 use rustc::mir::repr::*;
-use rustc::ty::{self, TyCtxt};
+use rustc::ty::TyCtxt;
 use std::fmt::Debug;
 use std::io::{self, Write};
 use syntax::ast::NodeId;
@@ -136,10 +136,7 @@ fn write_graph_label<'a, 'tcx, W: Write>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     write!(w, ") -&gt; ")?;
 
     // fn return type.
-    match mir.return_ty {
-        ty::FnOutput::FnConverging(ty) => write!(w, "{}", escape(ty))?,
-        ty::FnOutput::FnDiverging => write!(w, "!")?,
-    }
+    write!(w, "{}", escape(mir.return_ty))?;
 
     write!(w, r#"<br align="left"/>"#)?;
 
@@ -0,0 +1,115 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rustc::mir::repr::BinOp;
+use std::fmt;
+use syntax::codemap::Span;
+
+use super::memory::Pointer;
+
+/// An error evaluating a constant, at the statement or terminator that
+/// caused it.
+#[derive(Clone, Debug)]
+pub struct EvalError {
+    pub span: Span,
+    pub kind: EvalErrorKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum EvalErrorKind {
+    /// The evaluation needs something the interpreter does not support
+    /// yet; the caller may fall back to another evaluator.
+    Unimplemented(String),
+    Layout(String),
+    ReadUndefBytes,
+    ReadPointerAsBytes,
+    InvalidPointer,
+    PointerOutOfBounds {
+        ptr: Pointer,
+        size: u64,
+        alloc_size: u64,
+    },
+    IndexOutOfBounds {
+        len: u64,
+        index: u64,
+    },
+    Overflow(BinOp),
+    OverflowNeg,
+    DivisionByZero,
+    RemainderByZero,
+    InvalidBool,
+    InvalidChar(u64),
+    InvalidDiscriminant,
+    Panic,
+    NonConstFnCall(String),
+    ExecutionTimeLimitReached,
+    StackFrameLimitReached,
+}
+
+pub type EvalResult<T> = Result<T, EvalErrorKind>;
+
+impl EvalErrorKind {
+    pub fn is_unimplemented(&self) -> bool {
+        match *self {
+            EvalErrorKind::Unimplemented(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for EvalErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::EvalErrorKind::*;
+        match *self {
+            Unimplemented(ref what) => write!(f, "unimplemented in constants: {}", what),
+            Layout(ref err) => write!(f, "{}", err),
+            ReadUndefBytes => write!(f, "attempted to read undefined bytes"),
+            ReadPointerAsBytes => write!(f, "attempted to read the bytes of a pointer"),
+            InvalidPointer => write!(f, "attempted to dereference an invalid pointer"),
+            PointerOutOfBounds { ptr, size, alloc_size } => {
+                write!(f, "memory access of {} bytes at {} is outside of an allocation \
+                           of {} bytes", size, ptr, alloc_size)
+            }
+            IndexOutOfBounds { len, index } => {
+                write!(f, "array index out of bounds: the len is {} but the index is {}",
+                       len, index)
+            }
+            Overflow(op) => {
+                let what = match op {
+                    BinOp::Add => "add",
+                    BinOp::Sub => "subtract",
+                    BinOp::Mul => "multiply",
+                    BinOp::Div => "divide",
+                    BinOp::Rem => "calculate the remainder",
+                    BinOp::Shl => "shift left",
+                    BinOp::Shr => "shift right",
+                    _ => "compute",
+                };
+                write!(f, "attempted to {} with overflow", what)
+            }
+            OverflowNeg => write!(f, "attempted to negate with overflow"),
+            DivisionByZero => write!(f, "attempted to divide by zero"),
+            RemainderByZero => write!(f, "attempted remainder with a divisor of zero"),
+            InvalidBool => write!(f, "invalid boolean value"),
+            InvalidChar(c) => write!(f, "invalid character value {:#x}", c),
+            InvalidDiscriminant => write!(f, "invalid enum discriminant"),
+            Panic => write!(f, "evaluation of constant panicked"),
+            NonConstFnCall(ref path) => {
+                write!(f, "call to non-constant function `{}` in constant", path)
+            }
+            ExecutionTimeLimitReached => {
+                write!(f, "constant evaluation took too long; is there an infinite loop?")
+            }
+            StackFrameLimitReached => {
+                write!(f, "reached the recursion limit while evaluating a constant")
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The virtual memory of the interpreter.
+//!
+//! Memory is a set of independent allocations, each a vector of bytes
+//! laid out as they would be on the target. A pointer is an allocation
+//! and an offset into it; when one is written to memory, the bytes hold
+//! the offset and a *relocation* at that position records which
+//! allocation it points into. Bytes that were never written are
+//! undefined, and reading them is an error.
+
+use rustc::ty::layout::{Endian, TargetDataLayout};
+use rustc::util::nodemap::FnvHashMap;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::error::{EvalErrorKind, EvalResult};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AllocId(u64);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Pointer {
+    pub alloc_id: AllocId,
+    pub offset: u64,
+}
+
+impl Pointer {
+    pub fn offset(self, offset: u64) -> Pointer {
+        Pointer { alloc_id: self.alloc_id, offset: self.offset + offset }
+    }
+}
+
+impl fmt::Display for Pointer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "alloc{}+{}", (self.alloc_id).0, self.offset)
+    }
+}
+
+struct Allocation {
+    bytes: Vec<u8>,
+    /// Whether each byte has been written to.
+    defined: Vec<bool>,
+    /// The pointers stored in the allocation, by offset.
+    relocations: BTreeMap<u64, AllocId>,
+}
+
+pub struct Memory {
+    allocations: FnvHashMap<AllocId, Allocation>,
+    next_id: u64,
+    pointer_size: u64,
+    endian: Endian,
+}
+
+impl Memory {
+    pub fn new(data_layout: &TargetDataLayout) -> Memory {
+        Memory {
+            allocations: FnvHashMap(),
+            next_id: 0,
+            pointer_size: data_layout.pointer_size.bytes(),
+            endian: data_layout.endian,
+        }
+    }
+
+    pub fn pointer_size(&self) -> u64 {
+        self.pointer_size
+    }
+
+    /// Creates a new, undefined allocation of `size` bytes.
+    pub fn allocate(&mut self, size: u64) -> Pointer {
+        let id = AllocId(self.next_id);
+        self.next_id += 1;
+        self.allocations.insert(id, Allocation {
+            bytes: vec![0; size as usize],
+            defined: vec![false; size as usize],
+            relocations: BTreeMap::new(),
+        });
+        Pointer { alloc_id: id, offset: 0 }
+    }
+
+    fn get(&self, ptr: Pointer, size: u64) -> EvalResult<&Allocation> {
+        let alloc = match self.allocations.get(&ptr.alloc_id) {
+            Some(alloc) => alloc,
+            None => return Err(EvalErrorKind::InvalidPointer),
+        };
+        if ptr.offset + size > alloc.bytes.len() as u64 {
+            return Err(EvalErrorKind::PointerOutOfBounds {
+                ptr: ptr,
+                size: size,
+                alloc_size: alloc.bytes.len() as u64,
+            });
+        }
+        Ok(alloc)
+    }
+
+    fn get_mut(&mut self, ptr: Pointer, size: u64) -> EvalResult<&mut Allocation> {
+        self.get(ptr, size)?;
+        Ok(self.allocations.get_mut(&ptr.alloc_id).unwrap())
+    }
+
+    /// The relocations that overlap `size` bytes at `ptr`, including a
+    /// pointer that starts before and reaches into them.
+    fn relocations(&self, ptr: Pointer, size: u64) -> EvalResult<Vec<(u64, AllocId)>> {
+        let alloc = self.get(ptr, size)?;
+        let start = ptr.offset.saturating_sub(self.pointer_size - 1);
+        let end = ptr.offset + size;
+        Ok(alloc.relocations.iter()
+                .filter(|&(&offset, _)| start <= offset && offset < end)
+                .map(|(&offset, &id)| (offset, id))
+                .collect())
+    }
+
+    /// Reads plain bytes; they must all be defined and must not be part
+    /// of a pointer.
+    pub fn read_bytes(&self, ptr: Pointer, size: u64) -> EvalResult<&[u8]> {
+        if !self.relocations(ptr, size)?.is_empty() {
+            return Err(EvalErrorKind::ReadPointerAsBytes);
+        }
+        let alloc = self.get(ptr, size)?;
+        let range = ptr.offset as usize..(ptr.offset + size) as usize;
+        if alloc.defined[range.clone()].iter().any(|&defined| !defined) {
+            return Err(EvalErrorKind::ReadUndefBytes);
+        }
+        Ok(&alloc.bytes[range])
+    }
+
+    /// Overwrites `size` bytes at `ptr`, along with any pointers that
+    /// overlapped them.
+    pub fn write_bytes(&mut self, ptr: Pointer, bytes: &[u8]) -> EvalResult<()> {
+        self.clear_relocations(ptr, bytes.len() as u64)?;
+        let alloc = self.get_mut(ptr, bytes.len() as u64)?;
+        let range = ptr.offset as usize..ptr.offset as usize + bytes.len();
+        alloc.bytes[range.clone()].copy_from_slice(bytes);
+        for defined in &mut alloc.defined[range] {
+            *defined = true;
+        }
+        Ok(())
+    }
+
+    fn clear_relocations(&mut self, ptr: Pointer, size: u64) -> EvalResult<()> {
+        let relocations = self.relocations(ptr, size)?;
+        let pointer_size = self.pointer_size;
+        let alloc = self.get_mut(ptr, size)?;
+        for (offset, _) in relocations {
+            alloc.relocations.remove(&offset);
+            // What is left of a partially overwritten pointer is garbage.
+            let start = offset as usize;
+            for defined in &mut alloc.defined[start..start + pointer_size as usize] {
+                *defined = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies `size` bytes, including their definedness and any pointers
+    /// stored in them.
+    pub fn copy(&mut self, src: Pointer, dest: Pointer, size: u64) -> EvalResult<()> {
+        let (bytes, defined, relocations) = {
+            let alloc = self.get(src, size)?;
+            let range = src.offset as usize..(src.offset + size) as usize;
+            let relocations: Vec<_> = alloc.relocations.iter()
+                .filter(|&(&offset, _)| src.offset <= offset && offset < src.offset + size)
+                .map(|(&offset, &id)| (offset - src.offset, id))
+                .collect();
+            (alloc.bytes[range.clone()].to_vec(), alloc.defined[range].to_vec(), relocations)
+        };
+        self.clear_relocations(dest, size)?;
+        let alloc = self.get_mut(dest, size)?;
+        let start = dest.offset as usize;
+        alloc.bytes[start..start + size as usize].copy_from_slice(&bytes);
+        alloc.defined[start..start + size as usize].copy_from_slice(&defined);
+        for (offset, id) in relocations {
+            alloc.relocations.insert(dest.offset + offset, id);
+        }
+        Ok(())
+    }
+
+    pub fn read_uint(&self, ptr: Pointer, size: u64) -> EvalResult<u64> {
+        let bytes = self.read_bytes(ptr, size)?;
+        let mut value = 0;
+        for i in 0..size as usize {
+            let byte = match self.endian {
+                Endian::Little => bytes[size as usize - 1 - i],
+                Endian::Big => bytes[i],
+            };
+            value = (value << 8) | byte as u64;
+        }
+        Ok(value)
+    }
+
+    pub fn write_uint(&mut self, ptr: Pointer, value: u64, size: u64) -> EvalResult<()> {
+        let mut bytes = vec![0; size as usize];
+        for i in 0..size as usize {
+            let byte = (value >> (8 * i)) as u8;
+            match self.endian {
+                Endian::Little => bytes[i] = byte,
+                Endian::Big => bytes[size as usize - 1 - i] = byte,
+            }
+        }
+        self.write_bytes(ptr, &bytes)
+    }
+
+    /// Reads a pointer; null and integer addresses point nowhere.
+    pub fn read_ptr(&self, ptr: Pointer) -> EvalResult<Pointer> {
+        let size = self.pointer_size;
+        let target = self.get(ptr, size)?.relocations.get(&ptr.offset).cloned();
+        match target {
+            Some(alloc_id) => {
+                let alloc = self.get(ptr, size)?;
+                let range = ptr.offset as usize..(ptr.offset + size) as usize;
+                if alloc.defined[range].iter().any(|&defined| !defined) {
+                    return Err(EvalErrorKind::ReadUndefBytes);
+                }
+                let mut offset = 0;
+                for i in 0..size as usize {
+                    let byte = match self.endian {
+                        Endian::Little => alloc.bytes[(ptr.offset + size) as usize - 1 - i],
+                        Endian::Big => alloc.bytes[ptr.offset as usize + i],
+                    };
+                    offset = (offset << 8) | byte as u64;
+                }
+                Ok(Pointer { alloc_id: alloc_id, offset: offset })
+            }
+            None => Err(EvalErrorKind::InvalidPointer),
+        }
+    }
+
+    pub fn write_ptr(&mut self, dest: Pointer, ptr: Pointer) -> EvalResult<()> {
+        let size = self.pointer_size;
+        self.write_uint(dest, ptr.offset, size)?;
+        self.get_mut(dest, size)?.relocations.insert(dest.offset, ptr.alloc_id);
+        Ok(())
+    }
+
+    /// Whether the `size` bytes at `ptr` are all zero and hold no
+    /// pointer, as for the null variant of a nullable pointer.
+    pub fn is_null(&self, ptr: Pointer, size: u64) -> EvalResult<bool> {
+        if !self.relocations(ptr, size)?.is_empty() {
+            return Ok(false);
+        }
+        Ok(self.read_bytes(ptr, size)?.iter().all(|&byte| byte == 0))
+    }
+}
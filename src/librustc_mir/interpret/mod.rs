@@ -0,0 +1,1061 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An interpreter for MIR, used to evaluate constants and statics.
+//!
+//! Unlike the HIR-based evaluator in `rustc_const_eval`, which folds
+//! expressions, the interpreter executes the MIR of a constant, and of
+//! the `const fn`s it calls, statement by statement. Every local lives
+//! in an allocation of the virtual `Memory`, laid out like it would be
+//! on the target, so loops, matches, assignments to fields and borrows
+//! of locals all work the way they do at runtime.
+//!
+//! Once evaluation is done, the result is read back out of memory as a
+//! `Value`, which translation turns into an LLVM constant. Values that
+//! contain pointers cannot be read back yet; neither statics nor
+//! function pointers can be used during evaluation. Those, like the
+//! other unsupported operations, are reported as `Unimplemented`, so
+//! that callers can fall back to evaluating the constant another way.
+//!
+//! Only constants that have MIR by the time they are translated are
+//! evaluated here. Array lengths, repeat counts and enum discriminants are
+//! part of types, which are collected and checked before any MIR is built,
+//! so `rustc_const_eval` evaluates them, and a `const fn` that only the
+//! interpreter can run cannot be used in them.
+
+use rustc::hir::def_id::DefId;
+use rustc::infer::TransNormalize;
+use rustc::middle::const_val::ConstVal;
+use rustc::mir::mir_map::MirMap;
+use rustc::mir::repr::*;
+use rustc::mir::tcx::LvalueTy;
+use rustc::traits::ProjectionMode;
+use rustc::ty::{self, Ty, TyCtxt};
+use rustc::ty::layout::{Layout, Primitive, Struct};
+use rustc::ty::subst::{Subst, Substs};
+use rustc::util::nodemap::DefIdMap;
+use rustc_const_math::ConstInt;
+use std::cmp::Ordering;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::{char, mem, u64};
+use syntax::ast::FloatTy;
+use syntax::codemap::{Span, DUMMY_SP};
+
+use transform::qualify_consts::is_const_fn;
+
+mod error;
+mod memory;
+
+pub use self::error::{EvalError, EvalErrorKind};
+
+use self::error::EvalResult;
+use self::EvalErrorKind::*;
+use self::memory::{Memory, Pointer};
+
+/// The number of statements and terminators a single evaluation may
+/// execute before it is assumed not to terminate.
+const STEP_LIMIT: u64 = 1_000_000;
+
+/// The value of a constant, read back out of memory according to its
+/// type.
+#[derive(Clone, Debug)]
+pub struct Value<'tcx> {
+    pub ty: Ty<'tcx>,
+    pub kind: ValueKind<'tcx>,
+}
+
+#[derive(Clone, Debug)]
+pub enum ValueKind<'tcx> {
+    Bool(bool),
+    Char(char),
+    /// The bits of an integer, zero-extended from its size.
+    Int(u64),
+    Float(f64),
+    /// A struct, tuple, closure or enum variant.
+    Aggregate {
+        variant: usize,
+        fields: Vec<Value<'tcx>>,
+    },
+    Array(Vec<Value<'tcx>>),
+}
+
+/// Evaluates the constant or static `def_id`, or the constant function
+/// `def_id` called without arguments.
+pub fn eval_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                           mir_map: &'a MirMap<'tcx>,
+                           def_id: DefId,
+                           substs: &'tcx Substs<'tcx>)
+                           -> Result<Value<'tcx>, EvalError> {
+    let mut ecx = EvalContext {
+        tcx: tcx,
+        mir_map: mir_map,
+        extern_mir: DefIdMap(),
+        memory: Memory::new(&tcx.data_layout),
+        depth: 0,
+        steps_remaining: STEP_LIMIT,
+        span: DUMMY_SP,
+    };
+    ecx.eval_item(def_id, substs).map_err(|kind| {
+        EvalError {
+            span: ecx.span,
+            kind: kind,
+        }
+    })
+}
+
+#[derive(Clone)]
+enum CachedMir<'a, 'tcx: 'a> {
+    Ref(&'a Mir<'tcx>),
+    Owned(Rc<Mir<'tcx>>),
+}
+
+impl<'a, 'tcx> Deref for CachedMir<'a, 'tcx> {
+    type Target = Mir<'tcx>;
+    fn deref(&self) -> &Mir<'tcx> {
+        match *self {
+            CachedMir::Ref(mir) => mir,
+            CachedMir::Owned(ref mir) => mir,
+        }
+    }
+}
+
+struct EvalContext<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    mir_map: &'a MirMap<'tcx>,
+    extern_mir: DefIdMap<Rc<Mir<'tcx>>>,
+    memory: Memory,
+    /// The number of calls being evaluated.
+    depth: usize,
+    steps_remaining: u64,
+    /// The statement or terminator being evaluated in the innermost
+    /// call.
+    span: Span,
+}
+
+/// A call being evaluated.
+struct Frame<'m, 'tcx: 'm> {
+    mir: &'m Mir<'tcx>,
+    substs: &'tcx Substs<'tcx>,
+    args: Vec<Pointer>,
+    vars: Vec<Pointer>,
+    temps: Vec<Pointer>,
+    return_ptr: Pointer,
+}
+
+/// The location of an lvalue.
+#[derive(Copy, Clone)]
+struct Lval {
+    ptr: Pointer,
+    /// The length of an unsized slice or string.
+    len: Option<u64>,
+}
+
+impl<'a, 'tcx> EvalContext<'a, 'tcx> {
+    fn eval_item(&mut self, def_id: DefId, substs: &'tcx Substs<'tcx>)
+                 -> EvalResult<Value<'tcx>> {
+        let mir = self.load_mir(def_id)?;
+        let ty = self.monomorphize(&mir.return_ty, substs);
+        let size = self.type_size(ty)?;
+        let ptr = self.memory.allocate(size);
+        self.call(&mir, substs, vec![], ptr)?;
+        self.read_value(ptr, ty)
+    }
+
+    fn load_mir(&mut self, def_id: DefId) -> EvalResult<CachedMir<'a, 'tcx>> {
+        if let Some(node_id) = self.tcx.map.as_local_node_id(def_id) {
+            if let Some(mir) = self.mir_map.map.get(&node_id) {
+                return Ok(CachedMir::Ref(mir));
+            }
+        } else {
+            if let Some(mir) = self.extern_mir.get(&def_id).cloned() {
+                return Ok(CachedMir::Owned(mir));
+            }
+            if let Some(mir) = self.tcx.sess.cstore.maybe_get_item_mir(self.tcx, def_id) {
+                let mir = Rc::new(mir);
+                self.extern_mir.insert(def_id, mir.clone());
+                return Ok(CachedMir::Owned(mir));
+            }
+        }
+        Err(Unimplemented(format!("no MIR for `{}`", self.tcx.item_path_str(def_id))))
+    }
+
+    /// Evaluates `mir`, storing its return value at `return_ptr`.
+    fn call(&mut self,
+            mir: &Mir<'tcx>,
+            substs: &'tcx Substs<'tcx>,
+            args: Vec<Pointer>,
+            return_ptr: Pointer)
+            -> EvalResult<()> {
+        if self.depth >= self.tcx.sess.recursion_limit.get() {
+            return Err(StackFrameLimitReached);
+        }
+        self.depth += 1;
+
+        // Each var and temp is given its own allocation for the whole
+        // call; storage markers have nothing left to do.
+        let mut frame = Frame {
+            mir: mir,
+            substs: substs,
+            args: args,
+            vars: vec![],
+            temps: vec![],
+            return_ptr: return_ptr,
+        };
+        for decl in &mir.var_decls {
+            frame.vars.push(self.allocate_local(decl.ty, substs)?);
+        }
+        for decl in &mir.temp_decls {
+            frame.temps.push(self.allocate_local(decl.ty, substs)?);
+        }
+
+        let mut block = START_BLOCK;
+        loop {
+            let data = mir.basic_block_data(block);
+            for statement in &data.statements {
+                self.step(statement.span)?;
+                match statement.kind {
+                    StatementKind::Assign(ref lvalue, ref rvalue) => {
+                        let dest = self.eval_lvalue(&frame, lvalue)?;
+                        let dest_ty = self.lvalue_ty(&frame, lvalue);
+                        self.eval_rvalue_into(&frame, rvalue, dest.ptr, dest_ty)?;
+                    }
+                    StatementKind::StorageLive(_) |
+                    StatementKind::StorageDead(_) => {}
+                }
+            }
+
+            let terminator = data.terminator();
+            self.step(terminator.span)?;
+            match self.eval_terminator(&frame, terminator)? {
+                Some(target) => block = target,
+                None => break,
+            }
+        }
+
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn step(&mut self, span: Span) -> EvalResult<()> {
+        self.span = span;
+        if self.steps_remaining == 0 {
+            return Err(ExecutionTimeLimitReached);
+        }
+        self.steps_remaining -= 1;
+        Ok(())
+    }
+
+    fn allocate_local(&mut self, ty: Ty<'tcx>, substs: &'tcx Substs<'tcx>)
+                      -> EvalResult<Pointer> {
+        let ty = self.monomorphize(&ty, substs);
+        let size = self.type_size(ty)?;
+        Ok(self.memory.allocate(size))
+    }
+
+    fn monomorphize<T>(&self, value: &T, substs: &'tcx Substs<'tcx>) -> T
+        where T: TransNormalize<'tcx>
+    {
+        self.tcx.normalize_associated_type(&value.subst(self.tcx, substs))
+    }
+
+    fn lvalue_ty<'m>(&self, frame: &Frame<'m, 'tcx>, lvalue: &Lvalue<'tcx>) -> Ty<'tcx> {
+        let ty = frame.mir.lvalue_ty(self.tcx, lvalue);
+        self.monomorphize(&ty, frame.substs).to_ty(self.tcx)
+    }
+
+    fn operand_ty<'m>(&self, frame: &Frame<'m, 'tcx>, operand: &Operand<'tcx>) -> Ty<'tcx> {
+        let ty = frame.mir.operand_ty(self.tcx, operand);
+        self.monomorphize(&ty, frame.substs)
+    }
+
+    fn type_layout(&self, ty: Ty<'tcx>) -> EvalResult<&'tcx Layout> {
+        self.tcx.normalizing_infer_ctxt(ProjectionMode::Any).enter(|infcx| {
+            ty.layout(&infcx).map_err(|err| EvalErrorKind::Layout(err.to_string()))
+        })
+    }
+
+    fn type_size(&self, ty: Ty<'tcx>) -> EvalResult<u64> {
+        Ok(self.type_layout(ty)?.size(&self.tcx.data_layout).bytes())
+    }
+
+    /// The size and signedness of an integer-like type.
+    fn int_info(&self, ty: Ty<'tcx>) -> EvalResult<(u64, bool)> {
+        let signed = match ty.sty {
+            ty::TyInt(_) => true,
+            ty::TyUint(_) | ty::TyBool | ty::TyChar => false,
+            _ => return Err(Unimplemented(format!("arithmetic on `{}`", ty))),
+        };
+        let size = self.type_size(ty)?;
+        if size > 8 {
+            return Err(Unimplemented(format!("arithmetic on `{}`", ty)));
+        }
+        Ok((size, signed))
+    }
+
+    /// The offset of field `field` of the given variant of `ty`.
+    fn field_offset(&self, ty: Ty<'tcx>, variant: usize, field: usize) -> EvalResult<u64> {
+        match *self.type_layout(ty)? {
+            Layout::Univariant { ref variant, .. } => Ok(struct_field_offset(variant, field)),
+            // The discriminant comes first.
+            Layout::General { ref variants, .. } => {
+                Ok(struct_field_offset(&variants[variant], field + 1))
+            }
+            Layout::StructWrappedNullablePointer { nndiscr, ref nonnull, .. } => {
+                if variant as u64 == nndiscr {
+                    Ok(struct_field_offset(nonnull, field))
+                } else {
+                    Ok(0)
+                }
+            }
+            Layout::RawNullablePointer { .. } => Ok(0),
+            _ => Err(Unimplemented(format!("field access on `{}`", ty))),
+        }
+    }
+
+    fn eval_lvalue<'m>(&mut self, frame: &Frame<'m, 'tcx>, lvalue: &Lvalue<'tcx>)
+                   -> EvalResult<Lval> {
+        let ptr = match *lvalue {
+            Lvalue::Var(index) => frame.vars[index as usize],
+            Lvalue::Temp(index) => frame.temps[index as usize],
+            Lvalue::Arg(index) => frame.args[index as usize],
+            Lvalue::ReturnPointer => frame.return_ptr,
+            Lvalue::Static(def_id) => {
+                return Err(Unimplemented(format!("use of static `{}`",
+                                                 self.tcx.item_path_str(def_id))));
+            }
+            Lvalue::Projection(ref proj) => return self.eval_projection(frame, proj),
+        };
+        Ok(Lval { ptr: ptr, len: None })
+    }
+
+    fn eval_projection<'m>(&mut self, frame: &Frame<'m, 'tcx>, proj: &LvalueProjection<'tcx>)
+                       -> EvalResult<Lval> {
+        let base = self.eval_lvalue(frame, &proj.base)?;
+        let base_lvalue_ty = frame.mir.lvalue_ty(self.tcx, &proj.base);
+        let base_lvalue_ty = self.monomorphize(&base_lvalue_ty, frame.substs);
+        let base_ty = base_lvalue_ty.to_ty(self.tcx);
+
+        match proj.elem {
+            ProjectionElem::Deref => {
+                let pointee = match base_ty.builtin_deref(true, ty::NoPreference) {
+                    Some(mt) => mt.ty,
+                    None => bug!("dereference of non-pointer type `{}`", base_ty),
+                };
+                let ptr = self.memory.read_ptr(base.ptr)?;
+                let len = match pointee.sty {
+                    ty::TySlice(_) | ty::TyStr => {
+                        let pointer_size = self.memory.pointer_size();
+                        Some(self.memory.read_uint(base.ptr.offset(pointer_size), pointer_size)?)
+                    }
+                    ty::TyTrait(_) => return Err(Unimplemented("trait objects".to_string())),
+                    _ => None,
+                };
+                Ok(Lval { ptr: ptr, len: len })
+            }
+            ProjectionElem::Field(ref field, _) => {
+                let variant = match base_lvalue_ty {
+                    LvalueTy::Downcast { variant_index, .. } => variant_index,
+                    LvalueTy::Ty { .. } => 0,
+                };
+                let offset = self.field_offset(base_ty, variant, field.index())?;
+                Ok(Lval { ptr: base.ptr.offset(offset), len: base.len })
+            }
+            ProjectionElem::Index(ref index) => {
+                let ptr = self.eval_operand(frame, index)?;
+                let index = self.memory.read_uint(ptr, self.memory.pointer_size())?;
+                self.index(base, base_ty, index)
+            }
+            ProjectionElem::ConstantIndex { offset, from_end, .. } => {
+                let index = if from_end {
+                    self.len(base, base_ty) - offset as u64
+                } else {
+                    offset as u64
+                };
+                self.index(base, base_ty, index)
+            }
+            ProjectionElem::Downcast(..) => Ok(base),
+        }
+    }
+
+    fn len(&self, lval: Lval, ty: Ty<'tcx>) -> u64 {
+        match ty.sty {
+            ty::TyArray(_, len) => len as u64,
+            _ => lval.len.unwrap_or_else(|| bug!("length of `{}` is unknown", ty)),
+        }
+    }
+
+    fn index(&self, base: Lval, base_ty: Ty<'tcx>, index: u64) -> EvalResult<Lval> {
+        let len = self.len(base, base_ty);
+        if index >= len {
+            return Err(IndexOutOfBounds { len: len, index: index });
+        }
+        let elem_ty = base_ty.builtin_index().unwrap_or_else(|| {
+            bug!("indexing into non-array type `{}`", base_ty)
+        });
+        let elem_size = self.type_size(elem_ty)?;
+        Ok(Lval { ptr: base.ptr.offset(index * elem_size), len: None })
+    }
+
+    /// Returns a pointer to the value of `operand`.
+    fn eval_operand<'m>(&mut self, frame: &Frame<'m, 'tcx>, operand: &Operand<'tcx>)
+                    -> EvalResult<Pointer> {
+        let constant = match *operand {
+            Operand::Consume(ref lvalue) => return Ok(self.eval_lvalue(frame, lvalue)?.ptr),
+            Operand::Constant(ref constant) => constant,
+        };
+
+        let ty = self.monomorphize(&constant.ty, frame.substs);
+        let size = self.type_size(ty)?;
+        let ptr = self.memory.allocate(size);
+        match constant.literal {
+            // Function items are zero-sized and have nothing to evaluate.
+            Literal::Item { .. } if size == 0 => {}
+            Literal::Item { def_id, substs } => {
+                let substs = self.monomorphize(&substs, frame.substs);
+                if substs.self_ty().is_some() {
+                    return Err(Unimplemented("associated constants".to_string()));
+                }
+                let mir = self.load_mir(def_id)?;
+                self.call(&mir, substs, vec![], ptr)?;
+            }
+            Literal::Value { ref value } => self.write_constval(ptr, value, ty)?,
+            Literal::Promoted { index } => {
+                self.call(&frame.mir.promoted[index], frame.substs, vec![], ptr)?;
+            }
+        }
+        Ok(ptr)
+    }
+
+    fn write_constval(&mut self, ptr: Pointer, value: &ConstVal, ty: Ty<'tcx>)
+                      -> EvalResult<()> {
+        match *value {
+            ConstVal::Bool(b) => self.memory.write_uint(ptr, b as u64, 1),
+            ConstVal::Char(c) => self.memory.write_uint(ptr, c as u64, 4),
+            ConstVal::Integral(i) => {
+                let size = self.type_size(ty)?;
+                let bits = int_bits(i)?;
+                self.memory.write_uint(ptr, truncate(bits, size), size)
+            }
+            ConstVal::Float(f) => match ty.sty {
+                ty::TyFloat(fty) => self.write_float(ptr, f, fty),
+                _ => bug!("float constant of type `{}`", ty),
+            },
+            ConstVal::Str(ref s) => {
+                let bytes = self.memory.allocate(s.len() as u64);
+                self.memory.write_bytes(bytes, s.as_bytes())?;
+                self.write_ref(ptr, Lval { ptr: bytes, len: Some(s.len() as u64) })
+            }
+            ConstVal::ByteStr(ref s) => {
+                let bytes = self.memory.allocate(s.len() as u64);
+                self.memory.write_bytes(bytes, s)?;
+                self.memory.write_ptr(ptr, bytes)
+            }
+            _ => Err(Unimplemented(format!("constant {}", value.description()))),
+        }
+    }
+
+    fn write_ref(&mut self, dest: Pointer, lval: Lval) -> EvalResult<()> {
+        self.memory.write_ptr(dest, lval.ptr)?;
+        if let Some(len) = lval.len {
+            let pointer_size = self.memory.pointer_size();
+            self.memory.write_uint(dest.offset(pointer_size), len, pointer_size)?;
+        }
+        Ok(())
+    }
+
+    fn read_bool(&self, ptr: Pointer) -> EvalResult<bool> {
+        match self.memory.read_uint(ptr, 1)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(InvalidBool),
+        }
+    }
+
+    fn read_float(&self, ptr: Pointer, fty: FloatTy) -> EvalResult<f64> {
+        Ok(match fty {
+            FloatTy::F32 => {
+                let bits = self.memory.read_uint(ptr, 4)? as u32;
+                unsafe { mem::transmute::<u32, f32>(bits) as f64 }
+            }
+            FloatTy::F64 => {
+                let bits = self.memory.read_uint(ptr, 8)?;
+                unsafe { mem::transmute::<u64, f64>(bits) }
+            }
+        })
+    }
+
+    fn write_float(&mut self, ptr: Pointer, value: f64, fty: FloatTy) -> EvalResult<()> {
+        match fty {
+            FloatTy::F32 => {
+                let bits = unsafe { mem::transmute::<f32, u32>(value as f32) };
+                self.memory.write_uint(ptr, bits as u64, 4)
+            }
+            FloatTy::F64 => {
+                let bits = unsafe { mem::transmute::<f64, u64>(value) };
+                self.memory.write_uint(ptr, bits, 8)
+            }
+        }
+    }
+
+    fn eval_rvalue_into<'m>(&mut self,
+                        frame: &Frame<'m, 'tcx>,
+                        rvalue: &Rvalue<'tcx>,
+                        dest: Pointer,
+                        dest_ty: Ty<'tcx>)
+                        -> EvalResult<()> {
+        match *rvalue {
+            Rvalue::Use(ref operand) => {
+                let src = self.eval_operand(frame, operand)?;
+                let size = self.type_size(dest_ty)?;
+                self.memory.copy(src, dest, size)
+            }
+            Rvalue::Repeat(ref operand, ref count) => {
+                let elem_size = self.type_size(self.operand_ty(frame, operand))?;
+                let src = self.eval_operand(frame, operand)?;
+                for i in 0..count.value.as_u64(self.tcx.sess.target.uint_type) {
+                    self.memory.copy(src, dest.offset(i * elem_size), elem_size)?;
+                }
+                Ok(())
+            }
+            Rvalue::Ref(_, _, ref lvalue) => {
+                let lval = self.eval_lvalue(frame, lvalue)?;
+                self.write_ref(dest, lval)
+            }
+            Rvalue::Len(ref lvalue) => {
+                let lval = self.eval_lvalue(frame, lvalue)?;
+                let len = self.len(lval, self.lvalue_ty(frame, lvalue));
+                let pointer_size = self.memory.pointer_size();
+                self.memory.write_uint(dest, len, pointer_size)
+            }
+            Rvalue::Cast(kind, ref operand, _) => {
+                let src_ty = self.operand_ty(frame, operand);
+                let src = self.eval_operand(frame, operand)?;
+                self.eval_cast(kind, src, src_ty, dest, dest_ty)
+            }
+            Rvalue::BinaryOp(op, ref lhs, ref rhs) => {
+                let lhs_ty = self.operand_ty(frame, lhs);
+                let rhs_ty = self.operand_ty(frame, rhs);
+                let lhs = self.eval_operand(frame, lhs)?;
+                let rhs = self.eval_operand(frame, rhs)?;
+                self.eval_binop(op, lhs, lhs_ty, rhs, rhs_ty, dest)
+            }
+            Rvalue::UnaryOp(op, ref operand) => {
+                let ty = self.operand_ty(frame, operand);
+                let src = self.eval_operand(frame, operand)?;
+                self.eval_unop(op, src, ty, dest)
+            }
+            Rvalue::Box(ty) => {
+                let ptr = self.allocate_local(ty, frame.substs)?;
+                self.memory.write_ptr(dest, ptr)
+            }
+            Rvalue::Aggregate(ref kind, ref operands) => {
+                let mut fields = Vec::with_capacity(operands.len());
+                for operand in operands {
+                    let size = self.type_size(self.operand_ty(frame, operand))?;
+                    fields.push((self.eval_operand(frame, operand)?, size));
+                }
+                self.eval_aggregate(kind, &fields, dest, dest_ty)
+            }
+            Rvalue::Slice { .. } => Err(Unimplemented("subslice patterns".to_string())),
+            Rvalue::InlineAsm { .. } => Err(Unimplemented("inline assembly".to_string())),
+        }
+    }
+
+    fn eval_cast(&mut self,
+                 kind: CastKind,
+                 src: Pointer,
+                 src_ty: Ty<'tcx>,
+                 dest: Pointer,
+                 dest_ty: Ty<'tcx>)
+                 -> EvalResult<()> {
+        match kind {
+            CastKind::Unsize => {
+                let src_pointee = src_ty.builtin_deref(true, ty::NoPreference);
+                let dest_pointee = dest_ty.builtin_deref(true, ty::NoPreference);
+                if let (Some(src_pointee), Some(dest_pointee)) = (src_pointee, dest_pointee) {
+                    if let (&ty::TyArray(_, len), &ty::TySlice(_)) =
+                           (&src_pointee.ty.sty, &dest_pointee.ty.sty) {
+                        let ptr = self.memory.read_ptr(src)?;
+                        return self.write_ref(dest, Lval { ptr: ptr, len: Some(len as u64) });
+                    }
+                }
+                Err(Unimplemented(format!("unsizing `{}` to `{}`", src_ty, dest_ty)))
+            }
+            CastKind::Misc => self.eval_misc_cast(src, src_ty, dest, dest_ty),
+            CastKind::ReifyFnPointer |
+            CastKind::UnsafeFnPointer => Err(Unimplemented("function pointers".to_string())),
+        }
+    }
+
+    fn eval_misc_cast(&mut self,
+                      src: Pointer,
+                      src_ty: Ty<'tcx>,
+                      dest: Pointer,
+                      dest_ty: Ty<'tcx>)
+                      -> EvalResult<()> {
+        let (value, signed) = match src_ty.sty {
+            ty::TyFloat(fty) => {
+                let value = self.read_float(src, fty)?;
+                return match dest_ty.sty {
+                    ty::TyFloat(dest_fty) => self.write_float(dest, value, dest_fty),
+                    ty::TyInt(_) | ty::TyUint(_) => {
+                        let (size, signed) = self.int_info(dest_ty)?;
+                        let bits = if signed { value as i64 as u64 } else { value as u64 };
+                        self.memory.write_uint(dest, truncate(bits, size), size)
+                    }
+                    _ => bug!("invalid cast from `{}` to `{}`", src_ty, dest_ty),
+                };
+            }
+            ty::TyInt(_) | ty::TyUint(_) | ty::TyBool | ty::TyChar => {
+                let (size, signed) = self.int_info(src_ty)?;
+                let value = self.memory.read_uint(src, size)?;
+                (if signed { sign_extend(value, size) } else { value }, signed)
+            }
+            ty::TyEnum(..) => {
+                let signed = match *self.type_layout(src_ty)? {
+                    Layout::CEnum { signed, .. } => signed,
+                    _ => bug!("cast of non-C-like enum `{}`", src_ty),
+                };
+                let size = self.type_size(src_ty)?;
+                let value = self.memory.read_uint(src, size)?;
+                (if signed { sign_extend(value, size) } else { value }, signed)
+            }
+            _ => return Err(Unimplemented(format!("cast from `{}` to `{}`", src_ty, dest_ty))),
+        };
+
+        match dest_ty.sty {
+            ty::TyInt(_) | ty::TyUint(_) | ty::TyChar => {
+                let (size, _) = self.int_info(dest_ty)?;
+                self.memory.write_uint(dest, truncate(value, size), size)
+            }
+            ty::TyFloat(fty) => {
+                // Convert straight to the target precision, to round once.
+                let value = match (fty, signed) {
+                    (FloatTy::F32, true) => value as i64 as f32 as f64,
+                    (FloatTy::F32, false) => value as f32 as f64,
+                    (FloatTy::F64, true) => value as i64 as f64,
+                    (FloatTy::F64, false) => value as f64,
+                };
+                self.write_float(dest, value, fty)
+            }
+            _ => Err(Unimplemented(format!("cast from `{}` to `{}`", src_ty, dest_ty))),
+        }
+    }
+
+    fn eval_binop(&mut self,
+                  op: BinOp,
+                  lhs: Pointer,
+                  lhs_ty: Ty<'tcx>,
+                  rhs: Pointer,
+                  rhs_ty: Ty<'tcx>,
+                  dest: Pointer)
+                  -> EvalResult<()> {
+        if let ty::TyFloat(fty) = lhs_ty.sty {
+            let a = self.read_float(lhs, fty)?;
+            let b = self.read_float(rhs, fty)?;
+            let result = match op {
+                BinOp::Eq => a == b,
+                BinOp::Ne => a != b,
+                BinOp::Lt => a < b,
+                BinOp::Le => a <= b,
+                BinOp::Gt => a > b,
+                BinOp::Ge => a >= b,
+                _ => {
+                    let result = match op {
+                        BinOp::Add => a + b,
+                        BinOp::Sub => a - b,
+                        BinOp::Mul => a * b,
+                        BinOp::Div => a / b,
+                        BinOp::Rem => a % b,
+                        _ => bug!("invalid operation {:?} on floats", op),
+                    };
+                    return self.write_float(dest, result, fty);
+                }
+            };
+            return self.memory.write_uint(dest, result as u64, 1);
+        }
+
+        let (size, signed) = self.int_info(lhs_ty)?;
+        let a = self.memory.read_uint(lhs, size)?;
+
+        if let BinOp::Shl | BinOp::Shr = op {
+            let (rhs_size, rhs_signed) = self.int_info(rhs_ty)?;
+            let amount = self.memory.read_uint(rhs, rhs_size)?;
+            let negative = rhs_signed && (sign_extend(amount, rhs_size) as i64) < 0;
+            if negative || amount >= size * 8 {
+                return Err(Overflow(op));
+            }
+            let result = match op {
+                BinOp::Shl => a << amount,
+                _ if signed => ((sign_extend(a, size) as i64) >> amount) as u64,
+                _ => a >> amount,
+            };
+            return self.memory.write_uint(dest, truncate(result, size), size);
+        }
+
+        let b = self.memory.read_uint(rhs, size)?;
+        let ordering = if signed {
+            (sign_extend(a, size) as i64).cmp(&(sign_extend(b, size) as i64))
+        } else {
+            a.cmp(&b)
+        };
+        let result = match op {
+            BinOp::Eq => ordering == Ordering::Equal,
+            BinOp::Ne => ordering != Ordering::Equal,
+            BinOp::Lt => ordering == Ordering::Less,
+            BinOp::Le => ordering != Ordering::Greater,
+            BinOp::Gt => ordering == Ordering::Greater,
+            BinOp::Ge => ordering != Ordering::Less,
+            _ => {
+                let result = match op {
+                    BinOp::BitAnd => a & b,
+                    BinOp::BitOr => a | b,
+                    BinOp::BitXor => a ^ b,
+                    _ if signed => signed_arith(op, a, b, size)?,
+                    _ => unsigned_arith(op, a, b, size)?,
+                };
+                return self.memory.write_uint(dest, result, size);
+            }
+        };
+        self.memory.write_uint(dest, result as u64, 1)
+    }
+
+    fn eval_unop(&mut self, op: UnOp, src: Pointer, ty: Ty<'tcx>, dest: Pointer)
+                 -> EvalResult<()> {
+        if let ty::TyFloat(fty) = ty.sty {
+            let value = self.read_float(src, fty)?;
+            return self.write_float(dest, -value, fty);
+        }
+
+        let (size, _) = self.int_info(ty)?;
+        let value = self.memory.read_uint(src, size)?;
+        let result = match (op, &ty.sty) {
+            (UnOp::Not, &ty::TyBool) => value ^ 1,
+            (UnOp::Not, _) => truncate(!value, size),
+            (UnOp::Neg, _) => {
+                if value == 1 << (size * 8 - 1) {
+                    return Err(OverflowNeg);
+                }
+                truncate(value.wrapping_neg(), size)
+            }
+        };
+        self.memory.write_uint(dest, result, size)
+    }
+
+    /// Writes the aggregate made of `fields`, given as pointers to
+    /// their values and their sizes.
+    fn eval_aggregate(&mut self,
+                      kind: &AggregateKind<'tcx>,
+                      fields: &[(Pointer, u64)],
+                      dest: Pointer,
+                      dest_ty: Ty<'tcx>)
+                      -> EvalResult<()> {
+        let (adt_def, variant) = match *kind {
+            AggregateKind::Vec => {
+                for (i, &(src, size)) in fields.iter().enumerate() {
+                    self.memory.copy(src, dest.offset(i as u64 * size), size)?;
+                }
+                return Ok(());
+            }
+            AggregateKind::Tuple |
+            AggregateKind::Closure(..) => return self.write_fields(dest, dest_ty, 0, fields),
+            AggregateKind::Adt(adt_def, variant, _) => (adt_def, variant),
+        };
+
+        if adt_def.is_union() {
+            return Err(Unimplemented("unions".to_string()));
+        }
+        let disr = int_bits(adt_def.variants[variant].disr_val)?;
+        let tcx = self.tcx;
+        let data_layout = &tcx.data_layout;
+        match *self.type_layout(dest_ty)? {
+            Layout::CEnum { discr, .. } => {
+                let size = Primitive::Int(discr).size(data_layout).bytes();
+                self.memory.write_uint(dest, truncate(disr, size), size)
+            }
+            Layout::General { discr, .. } => {
+                let size = Primitive::Int(discr).size(data_layout).bytes();
+                self.memory.write_uint(dest, truncate(disr, size), size)?;
+                self.write_fields(dest, dest_ty, variant, fields)
+            }
+            // The other variant is represented by zeroes in place of the
+            // non-null field.
+            Layout::RawNullablePointer { nndiscr, .. } |
+            Layout::StructWrappedNullablePointer { nndiscr, .. } if variant as u64 != nndiscr => {
+                let size = self.type_size(dest_ty)?;
+                self.memory.write_bytes(dest, &vec![0; size as usize])
+            }
+            _ => self.write_fields(dest, dest_ty, variant, fields),
+        }
+    }
+
+    fn write_fields(&mut self,
+                    dest: Pointer,
+                    ty: Ty<'tcx>,
+                    variant: usize,
+                    fields: &[(Pointer, u64)])
+                    -> EvalResult<()> {
+        for (i, &(src, size)) in fields.iter().enumerate() {
+            let offset = self.field_offset(ty, variant, i)?;
+            self.memory.copy(src, dest.offset(offset), size)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the discriminant of the enum at `ptr`, returning the index
+    /// of its variant.
+    fn read_variant(&self, ptr: Pointer, ty: Ty<'tcx>) -> EvalResult<usize> {
+        let adt_def = match ty.sty {
+            ty::TyEnum(adt_def, _) => adt_def,
+            _ => bug!("reading the discriminant of non-enum type `{}`", ty),
+        };
+        let data_layout = &self.tcx.data_layout;
+        let (discr, size) = match *self.type_layout(ty)? {
+            Layout::Univariant { .. } => return Ok(0),
+            Layout::CEnum { discr, .. } |
+            Layout::General { discr, .. } => {
+                let size = Primitive::Int(discr).size(data_layout).bytes();
+                (self.memory.read_uint(ptr, size)?, size)
+            }
+            Layout::RawNullablePointer { nndiscr, value } => {
+                let null = self.memory.is_null(ptr, value.size(data_layout).bytes())?;
+                let variant = if null { 1 - nndiscr } else { nndiscr };
+                return Ok(variant as usize);
+            }
+            _ => {
+                return Err(Unimplemented(format!("reading the discriminant of `{}`", ty)));
+            }
+        };
+        for (i, variant) in adt_def.variants.iter().enumerate() {
+            if truncate(int_bits(variant.disr_val)?, size) == discr {
+                return Ok(i);
+            }
+        }
+        Err(InvalidDiscriminant)
+    }
+
+    /// Evaluates a terminator, returning the block to continue with,
+    /// or `None` once the call returns.
+    fn eval_terminator<'m>(&mut self, frame: &Frame<'m, 'tcx>, terminator: &Terminator<'tcx>)
+                       -> EvalResult<Option<BasicBlock>> {
+        let target = match terminator.kind {
+            TerminatorKind::Goto { target } => target,
+            TerminatorKind::If { ref cond, targets: (then, else_) } => {
+                let ptr = self.eval_operand(frame, cond)?;
+                if self.read_bool(ptr)? { then } else { else_ }
+            }
+            TerminatorKind::Switch { ref discr, ref targets, .. } => {
+                let lval = self.eval_lvalue(frame, discr)?;
+                let ty = self.lvalue_ty(frame, discr);
+                targets[self.read_variant(lval.ptr, ty)?]
+            }
+            TerminatorKind::SwitchInt { ref discr, switch_ty, ref values, ref targets } => {
+                let lval = self.eval_lvalue(frame, discr)?;
+                let switch_ty = self.monomorphize(&switch_ty, frame.substs);
+                let (size, _) = self.int_info(switch_ty)?;
+                let discr = self.memory.read_uint(lval.ptr, size)?;
+                let mut target = targets[values.len()];
+                for (value, &value_target) in values.iter().zip(targets) {
+                    if constval_bits(value, size)? == discr {
+                        target = value_target;
+                        break;
+                    }
+                }
+                target
+            }
+            TerminatorKind::Return => return Ok(None),
+            // Nothing is freed until evaluation is over.
+            TerminatorKind::Drop { target, .. } => target,
+            // Unwinding only ever starts after a panic, which already
+            // ended evaluation.
+            TerminatorKind::Resume => return Err(Panic),
+            TerminatorKind::Call { ref func, ref args, ref destination, .. } => {
+                self.eval_call(frame, func, args, destination)?
+            }
+        };
+        Ok(Some(target))
+    }
+
+    fn eval_call<'m>(&mut self,
+                 frame: &Frame<'m, 'tcx>,
+                 func: &Operand<'tcx>,
+                 args: &[Operand<'tcx>],
+                 destination: &Option<(Lvalue<'tcx>, BasicBlock)>)
+                 -> EvalResult<BasicBlock> {
+        let fn_ty = self.operand_ty(frame, func);
+        let (def_id, substs) = match fn_ty.sty {
+            ty::TyFnDef(def_id, substs, _) => (def_id, substs),
+            _ => return Err(Unimplemented(format!("calls through `{}`", fn_ty))),
+        };
+
+        // Bounds checks call `panic_bounds_check(loc, index, len)`.
+        if Some(def_id) == self.tcx.lang_items.panic_bounds_check_fn() {
+            let pointer_size = self.memory.pointer_size();
+            let index = self.eval_operand(frame, &args[1])?;
+            let len = self.eval_operand(frame, &args[2])?;
+            return Err(IndexOutOfBounds {
+                len: self.memory.read_uint(len, pointer_size)?,
+                index: self.memory.read_uint(index, pointer_size)?,
+            });
+        }
+        let (dest, target) = match *destination {
+            Some((ref dest, target)) => (dest, target),
+            None => return Err(Panic),
+        };
+        if self.tcx.trait_of_item(def_id).is_some() {
+            return Err(Unimplemented("calls to trait methods".to_string()));
+        }
+        if !is_const_fn(self.tcx, def_id) {
+            return Err(NonConstFnCall(self.tcx.item_path_str(def_id)));
+        }
+
+        // Arguments are passed by value, in allocations of their own.
+        let mut arg_ptrs = Vec::with_capacity(args.len());
+        for arg in args {
+            let size = self.type_size(self.operand_ty(frame, arg))?;
+            let src = self.eval_operand(frame, arg)?;
+            let ptr = self.memory.allocate(size);
+            self.memory.copy(src, ptr, size)?;
+            arg_ptrs.push(ptr);
+        }
+        let dest = self.eval_lvalue(frame, dest)?;
+        let mir = self.load_mir(def_id)?;
+        self.call(&mir, substs, arg_ptrs, dest.ptr)?;
+        Ok(target)
+    }
+
+    /// Reads the value of type `ty` at `ptr` back out of memory.
+    fn read_value(&self, ptr: Pointer, ty: Ty<'tcx>) -> EvalResult<Value<'tcx>> {
+        let kind = match ty.sty {
+            ty::TyBool => ValueKind::Bool(self.read_bool(ptr)?),
+            ty::TyChar => {
+                let c = self.memory.read_uint(ptr, 4)?;
+                match char::from_u32(c as u32) {
+                    Some(c) => ValueKind::Char(c),
+                    None => return Err(InvalidChar(c)),
+                }
+            }
+            ty::TyInt(_) | ty::TyUint(_) => {
+                let (size, _) = self.int_info(ty)?;
+                ValueKind::Int(self.memory.read_uint(ptr, size)?)
+            }
+            ty::TyFloat(fty) => ValueKind::Float(self.read_float(ptr, fty)?),
+            ty::TyArray(elem_ty, len) => {
+                let elem_size = self.type_size(elem_ty)?;
+                let mut elems = Vec::with_capacity(len);
+                for i in 0..len as u64 {
+                    elems.push(self.read_value(ptr.offset(i * elem_size), elem_ty)?);
+                }
+                ValueKind::Array(elems)
+            }
+            ty::TyTuple(tys) => self.read_fields(ptr, ty, 0, tys)?,
+            ty::TyClosure(_, ref substs) => self.read_fields(ptr, ty, 0, substs.upvar_tys)?,
+            ty::TyStruct(adt_def, substs) if !adt_def.is_union() => {
+                let tys: Vec<_> = adt_def.struct_variant().fields.iter().map(|field| {
+                    self.tcx.normalize_associated_type(&field.ty(self.tcx, substs))
+                }).collect();
+                self.read_fields(ptr, ty, 0, &tys)?
+            }
+            ty::TyEnum(adt_def, substs) => {
+                let variant = self.read_variant(ptr, ty)?;
+                let tys: Vec<_> = adt_def.variants[variant].fields.iter().map(|field| {
+                    self.tcx.normalize_associated_type(&field.ty(self.tcx, substs))
+                }).collect();
+                self.read_fields(ptr, ty, variant, &tys)?
+            }
+            ty::TyFnDef(..) => ValueKind::Aggregate { variant: 0, fields: vec![] },
+            _ => return Err(Unimplemented(format!("`{}` in the value of a constant", ty))),
+        };
+        Ok(Value { ty: ty, kind: kind })
+    }
+
+    fn read_fields(&self, ptr: Pointer, ty: Ty<'tcx>, variant: usize, tys: &[Ty<'tcx>])
+                   -> EvalResult<ValueKind<'tcx>> {
+        let mut fields = Vec::with_capacity(tys.len());
+        for (i, &field_ty) in tys.iter().enumerate() {
+            let offset = self.field_offset(ty, variant, i)?;
+            fields.push(self.read_value(ptr.offset(offset), field_ty)?);
+        }
+        Ok(ValueKind::Aggregate { variant: variant, fields: fields })
+    }
+}
+
+fn struct_field_offset(st: &Struct, field: usize) -> u64 {
+    if field == 0 {
+        0
+    } else {
+        st.offset_after_field[field - 1].bytes()
+    }
+}
+
+/// The bits of an integer constant, sign-extended to 64 bits.
+fn int_bits(value: ConstInt) -> EvalResult<u64> {
+    match value {
+        ConstInt::I128(_) | ConstInt::U128(_) => {
+            Err(Unimplemented("128-bit integers".to_string()))
+        }
+        _ => Ok(value.to_u64_unchecked()),
+    }
+}
+
+fn constval_bits(value: &ConstVal, size: u64) -> EvalResult<u64> {
+    match *value {
+        ConstVal::Integral(i) => Ok(truncate(int_bits(i)?, size)),
+        ConstVal::Char(c) => Ok(c as u64),
+        ConstVal::Bool(b) => Ok(b as u64),
+        _ => Err(Unimplemented(format!("switch on {}", value.description()))),
+    }
+}
+
+fn truncate(value: u64, size: u64) -> u64 {
+    value & (u64::MAX >> (64 - size * 8))
+}
+
+fn sign_extend(value: u64, size: u64) -> u64 {
+    let shift = 64 - size * 8;
+    (((value << shift) as i64) >> shift) as u64
+}
+
+fn signed_arith(op: BinOp, a: u64, b: u64, size: u64) -> EvalResult<u64> {
+    let (a, b) = (sign_extend(a, size) as i64, sign_extend(b, size) as i64);
+    let result = match op {
+        BinOp::Add => a.checked_add(b),
+        BinOp::Sub => a.checked_sub(b),
+        BinOp::Mul => a.checked_mul(b),
+        BinOp::Div if b == 0 => return Err(DivisionByZero),
+        BinOp::Div => a.checked_div(b),
+        BinOp::Rem if b == 0 => return Err(RemainderByZero),
+        BinOp::Rem => a.checked_rem(b),
+        _ => bug!("invalid integer operation {:?}", op),
+    };
+    let max = (u64::MAX >> (65 - size * 8)) as i64;
+    match result {
+        Some(result) if -max - 1 <= result && result <= max => Ok(truncate(result as u64, size)),
+        _ => Err(Overflow(op)),
+    }
+}
+
+fn unsigned_arith(op: BinOp, a: u64, b: u64, size: u64) -> EvalResult<u64> {
+    let result = match op {
+        BinOp::Add => a.checked_add(b),
+        BinOp::Sub => a.checked_sub(b),
+        BinOp::Mul => a.checked_mul(b),
+        BinOp::Div if b == 0 => return Err(DivisionByZero),
+        BinOp::Div => a.checked_div(b),
+        BinOp::Rem if b == 0 => return Err(RemainderByZero),
+        BinOp::Rem => a.checked_rem(b),
+        _ => bug!("invalid integer operation {:?}", op),
+    };
+    match result {
+        Some(result) if result == truncate(result, size) => Ok(result),
+        _ => Err(Overflow(op)),
+    }
+}
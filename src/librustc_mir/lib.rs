@@ -46,6 +46,7 @@ pub mod diagnostics;
 pub mod build;
 pub mod graphviz;
 mod hair;
+pub mod interpret;
 pub mod mir_map;
 pub mod pretty;
 pub mod transform;
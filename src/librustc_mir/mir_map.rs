@@ -169,20 +169,30 @@ impl<'a, 'gcx> BuildMir<'a, 'gcx> {
 impl<'a, 'tcx> Visitor<'tcx> for BuildMir<'a, 'tcx> {
     // Const and static items.
     fn visit_item(&mut self, item: &'tcx hir::Item) {
-        match item.node {
-            hir::ItemConst(_, ref expr) => {
-                self.cx(MirSource::Const(item.id)).build(|cx| {
-                    build::construct_const(cx, item.id, expr)
-                });
-            }
-            hir::ItemStatic(_, m, ref expr) => {
-                self.cx(MirSource::Static(item.id, m)).build(|cx| {
-                    build::construct_const(cx, item.id, expr)
-                });
-            }
-            _ => {}
+        // Skip the items whose MIR is restored from the previous
+        // compilation by `rustc_incremental`.
+        let def_id = self.tcx.map.local_def_id(item.id);
+        if self.tcx.dep_graph.is_green(&DepNode::MirMapConstruction(def_id)) {
+            return;
         }
-        intravisit::walk_item(self, item);
+
+        let tcx = self.tcx;
+        tcx.sess.analyzing_item(item.id, || {
+            match item.node {
+                hir::ItemConst(_, ref expr) => {
+                    self.cx(MirSource::Const(item.id)).build(|cx| {
+                        build::construct_const(cx, item.id, expr)
+                    });
+                }
+                hir::ItemStatic(_, m, ref expr) => {
+                    self.cx(MirSource::Static(item.id, m)).build(|cx| {
+                        build::construct_const(cx, item.id, expr)
+                    });
+                }
+                _ => {}
+            }
+            intravisit::walk_item(self, item);
+        });
     }
 
     // Trait associated const defaults.
@@ -0,0 +1,224 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A pass that propagates constants into the operands that read them,
+//! folding arithmetic, comparisons and negations of constants as it
+//! goes.
+//!
+//! Only vars and temps that are assigned exactly once, and never
+//! borrowed or written in any other way, are considered: the value they
+//! are assigned is then the value they hold at every read. Their
+//! assignments are left in place; those to temps that end up unread are
+//! removed by `DeadStoreElimination`. Branches on a propagated constant
+//! are left for `SimplifyCfg` to fold.
+
+use rustc::middle::const_val::ConstVal;
+use rustc::mir::repr::*;
+use rustc::mir::transform::{MirPass, MirSource, Pass};
+use rustc::mir::visit::MutVisitor;
+use rustc::ty::TyCtxt;
+use std::cmp::Ordering;
+use pretty;
+
+use super::def_use::DefUseAnalysis;
+
+pub struct ConstPropagate;
+
+impl<'tcx> MirPass<'tcx> for ConstPropagate {
+    fn run_pass<'a>(&mut self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                    src: MirSource, mir: &mut Mir<'tcx>) {
+        let analysis = DefUseAnalysis::new(mir);
+        let mut values: Vec<Option<Constant<'tcx>>> = vec![None; analysis.num_locals()];
+        let mut defs = vec![];
+
+        // A folded value may make further rvalues constant, so keep
+        // going until nothing changes.
+        loop {
+            let mut changed = false;
+            for index in 0..analysis.num_locals() {
+                if values[index].is_some() {
+                    continue;
+                }
+                let info = analysis.local_info(index);
+                if info.defs.len() != 1 || !info.others.is_empty() {
+                    continue;
+                }
+                let location = info.defs[0];
+                let statement = &mir.basic_block_data(location.block).statements[location.index];
+                let (dest, rvalue) = match statement.kind {
+                    StatementKind::Assign(ref dest, ref rvalue) => (dest, rvalue),
+                    _ => continue,
+                };
+                if analysis.local_index(dest) != Some(index) {
+                    // Written as an output of inline assembly.
+                    continue;
+                }
+                let ty = mir.lvalue_ty(tcx, dest).to_ty(tcx);
+                let literal = Evaluator { analysis: &analysis, values: &values }
+                    .eval_rvalue(rvalue);
+                if let Some(literal) = literal {
+                    values[index] = Some(Constant {
+                        span: statement.span,
+                        ty: ty,
+                        literal: literal,
+                    });
+                    defs.push(location);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        if defs.is_empty() {
+            return;
+        }
+
+        // The assignments themselves become plain constant assignments.
+        for location in defs {
+            let statement = &mut mir.basic_block_data_mut(location.block)
+                                    .statements[location.index];
+            if let StatementKind::Assign(ref dest, ref mut rvalue) = statement.kind {
+                let index = analysis.local_index(dest).unwrap();
+                *rvalue = Rvalue::Use(Operand::Constant(values[index].clone().unwrap()));
+            }
+        }
+        Propagator { analysis: &analysis, values: &values }.visit_mir(mir);
+
+        pretty::dump_mir(tcx, "const_propagate", &0, src, mir, None);
+    }
+}
+
+impl Pass for ConstPropagate {}
+
+struct Evaluator<'b, 'tcx: 'b> {
+    analysis: &'b DefUseAnalysis,
+    values: &'b [Option<Constant<'tcx>>],
+}
+
+impl<'b, 'tcx> Evaluator<'b, 'tcx> {
+    fn eval_operand(&self, operand: &Operand<'tcx>) -> Option<Literal<'tcx>> {
+        match *operand {
+            Operand::Constant(ref constant) => Some(constant.literal.clone()),
+            Operand::Consume(ref lvalue) => {
+                self.analysis.local_index(lvalue)
+                             .and_then(|index| self.values[index].as_ref())
+                             .map(|constant| constant.literal.clone())
+            }
+        }
+    }
+
+    fn eval_value(&self, operand: &Operand<'tcx>) -> Option<ConstVal> {
+        match self.eval_operand(operand) {
+            Some(Literal::Value { value }) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn eval_rvalue(&self, rvalue: &Rvalue<'tcx>) -> Option<Literal<'tcx>> {
+        let value = match *rvalue {
+            Rvalue::Use(ref operand) => return self.eval_operand(operand),
+            Rvalue::BinaryOp(op, ref lhs, ref rhs) => {
+                let lhs = self.eval_value(lhs);
+                let rhs = self.eval_value(rhs);
+                match (lhs, rhs) {
+                    (Some(lhs), Some(rhs)) => fold_binop(op, lhs, rhs),
+                    _ => None,
+                }
+            }
+            Rvalue::UnaryOp(op, ref operand) => {
+                self.eval_value(operand).and_then(|value| fold_unop(op, value))
+            }
+            _ => None,
+        };
+        value.map(|value| Literal::Value { value: value })
+    }
+}
+
+fn fold_binop(op: BinOp, lhs: ConstVal, rhs: ConstVal) -> Option<ConstVal> {
+    match (lhs, rhs) {
+        (ConstVal::Integral(lhs), ConstVal::Integral(rhs)) => {
+            // Only fold values of a known integer type; the result of
+            // folding two inferred literals could not be translated.
+            if lhs.int_type().is_none() || rhs.int_type().is_none() {
+                return None;
+            }
+            let ordering = |wanted: &[Ordering]| {
+                lhs.try_cmp(rhs).ok().map(|ord| ConstVal::Bool(wanted.contains(&ord)))
+            };
+            // Overflow and division by zero are left to happen at runtime.
+            let result = match op {
+                BinOp::Add => lhs + rhs,
+                BinOp::Sub => lhs - rhs,
+                BinOp::Mul => lhs * rhs,
+                BinOp::Div => lhs / rhs,
+                BinOp::Rem => lhs % rhs,
+                BinOp::BitXor => lhs ^ rhs,
+                BinOp::BitAnd => lhs & rhs,
+                BinOp::BitOr => lhs | rhs,
+                BinOp::Eq => return ordering(&[Ordering::Equal]),
+                BinOp::Ne => return ordering(&[Ordering::Less, Ordering::Greater]),
+                BinOp::Lt => return ordering(&[Ordering::Less]),
+                BinOp::Le => return ordering(&[Ordering::Less, Ordering::Equal]),
+                BinOp::Gt => return ordering(&[Ordering::Greater]),
+                BinOp::Ge => return ordering(&[Ordering::Greater, Ordering::Equal]),
+                BinOp::Shl | BinOp::Shr => return None,
+            };
+            result.ok().map(ConstVal::Integral)
+        }
+        (ConstVal::Bool(lhs), ConstVal::Bool(rhs)) => {
+            let result = match op {
+                BinOp::BitAnd => lhs & rhs,
+                BinOp::BitOr => lhs | rhs,
+                BinOp::BitXor => lhs ^ rhs,
+                BinOp::Eq => lhs == rhs,
+                BinOp::Ne => lhs != rhs,
+                _ => return None,
+            };
+            Some(ConstVal::Bool(result))
+        }
+        _ => None,
+    }
+}
+
+fn fold_unop(op: UnOp, value: ConstVal) -> Option<ConstVal> {
+    match (op, value) {
+        (UnOp::Not, ConstVal::Bool(value)) => Some(ConstVal::Bool(!value)),
+        (UnOp::Not, ConstVal::Integral(value)) if value.int_type().is_some() => {
+            (!value).ok().map(ConstVal::Integral)
+        }
+        (UnOp::Neg, ConstVal::Integral(value)) if value.int_type().is_some() => {
+            (-value).ok().map(ConstVal::Integral)
+        }
+        _ => None,
+    }
+}
+
+/// Replaces reads of locals with known values by the values themselves.
+struct Propagator<'b, 'tcx: 'b> {
+    analysis: &'b DefUseAnalysis,
+    values: &'b [Option<Constant<'tcx>>],
+}
+
+impl<'b, 'tcx> MutVisitor<'tcx> for Propagator<'b, 'tcx> {
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>) {
+        let constant = match *operand {
+            Operand::Consume(ref lvalue) => {
+                self.analysis.local_index(lvalue).and_then(|index| self.values[index].clone())
+            }
+            Operand::Constant(_) => None,
+        };
+        match constant {
+            Some(constant) => *operand = Operand::Constant(constant),
+            None => self.super_operand(operand),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A pass that removes temporaries that only hold a copy of another
+//! lvalue on its way to a single use.
+//!
+//! MIR building produces plenty of `tmp = x; ... f(tmp)` sequences. When
+//! `tmp` is assigned and read exactly once, in the same block, and `x`
+//! is of a `Copy` type, is never borrowed and is left alone in between,
+//! the read of `tmp` is replaced with a read of `x` and the copy into
+//! `tmp` is removed.
+
+use rustc::mir::repr::*;
+use rustc::mir::transform::{MirPass, MirSource, Pass};
+use rustc::mir::visit::{LvalueContext, MutVisitor, Visitor};
+use rustc::ty::{self, TyCtxt};
+use syntax::codemap::DUMMY_SP;
+use pretty;
+
+use super::def_use::{self, DefUseAnalysis, Location};
+
+pub struct CopyPropagation;
+
+impl<'tcx> MirPass<'tcx> for CopyPropagation {
+    fn run_pass<'a>(&mut self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                    src: MirSource, mir: &mut Mir<'tcx>) {
+        // Whether a value is `Copy` depends on the bounds in scope, which
+        // only functions have.
+        let param_env = match src {
+            MirSource::Fn(id) => ty::ParameterEnvironment::for_item(tcx, id),
+            _ => return,
+        };
+
+        // Each round propagates copies that do not interfere with one
+        // another; what they uncover is picked up by the next one.
+        loop {
+            let analysis = DefUseAnalysis::new(mir);
+            let mut touched: Vec<Location> = vec![];
+            let mut propagated: Vec<usize> = vec![];
+            let mut removed: Vec<Location> = vec![];
+
+            for index in 0..analysis.num_locals() {
+                if !analysis.is_temp(index) {
+                    continue;
+                }
+                let info = analysis.local_info(index);
+                if info.defs.len() != 1 || info.uses.len() != 1 || !info.others.is_empty() {
+                    continue;
+                }
+                let (def, use_) = (info.defs[0], info.uses[0]);
+                if def.block != use_.block || def.index >= use_.index {
+                    continue;
+                }
+                if touched.contains(&def) || touched.contains(&use_) {
+                    continue;
+                }
+
+                let source = {
+                    let statement = &mir.basic_block_data(def.block).statements[def.index];
+                    match statement.kind {
+                        StatementKind::Assign(ref dest, Rvalue::Use(Operand::Consume(ref src)))
+                                if analysis.local_index(dest) == Some(index) => src.clone(),
+                        _ => continue,
+                    }
+                };
+                if !can_propagate(tcx, mir, &param_env, &analysis, &source, def, use_) {
+                    continue;
+                }
+                if let Some(source_index) = analysis.local_index(def_use::base_lvalue(&source)) {
+                    if propagated.contains(&source_index) {
+                        continue;
+                    }
+                }
+
+                debug!("copy_propagation: replacing {:?} with {:?} at {:?}",
+                       analysis.local_lvalue(index), source, use_);
+                let mut replacer = Replacer {
+                    target: analysis.local_lvalue(index),
+                    replacement: source,
+                };
+                let data = mir.basic_block_data_mut(use_.block);
+                if use_.index < data.statements.len() {
+                    replacer.visit_statement(use_.block, &mut data.statements[use_.index]);
+                } else {
+                    replacer.visit_terminator(use_.block, data.terminator_mut());
+                }
+                touched.push(def);
+                touched.push(use_);
+                propagated.push(index);
+                removed.push(def);
+            }
+
+            if removed.is_empty() {
+                break;
+            }
+            // Remove the copies back to front, so that the remaining
+            // indices stay valid.
+            removed.sort_by(|a, b| (b.block, b.index).cmp(&(a.block, a.index)));
+            for location in removed {
+                mir.basic_block_data_mut(location.block).statements.remove(location.index);
+            }
+        }
+
+        pretty::dump_mir(tcx, "copy_propagation", &0, src, mir, None);
+    }
+}
+
+impl Pass for CopyPropagation {}
+
+/// Whether a read of `source` at `use_` sees the same value as one at
+/// `def`.
+fn can_propagate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                           mir: &Mir<'tcx>,
+                           param_env: &ty::ParameterEnvironment<'tcx>,
+                           analysis: &DefUseAnalysis,
+                           source: &Lvalue<'tcx>,
+                           def: Location,
+                           use_: Location)
+                           -> bool {
+    // Reading through a pointer or at a computed index may observe
+    // writes we cannot see here.
+    let mut lvalue = source;
+    while let Lvalue::Projection(ref proj) = *lvalue {
+        match proj.elem {
+            ProjectionElem::Deref | ProjectionElem::Index(_) => return false,
+            _ => lvalue = &proj.base,
+        }
+    }
+    let base = match analysis.local_index(lvalue) {
+        Some(base) => base,
+        None => match *lvalue {
+            // Arguments are never written to again once they are moved
+            // into the function's own locals, unless they are borrowed.
+            Lvalue::Arg(_) => return !arg_is_mentioned(mir, lvalue, def, use_) &&
+                                     is_copy(tcx, mir, param_env, source),
+            _ => return false,
+        },
+    };
+
+    let info = analysis.local_info(base);
+    if info.borrowed {
+        return false;
+    }
+    let between = |location: &Location| {
+        location.block == def.block && def.index < location.index && location.index < use_.index
+    };
+    // The storage of `source` must also stay live until the use.
+    if info.defs.iter().chain(&info.others).chain(&info.storage).any(between) {
+        return false;
+    }
+    is_copy(tcx, mir, param_env, source)
+}
+
+fn is_copy<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                     mir: &Mir<'tcx>,
+                     param_env: &ty::ParameterEnvironment<'tcx>,
+                     lvalue: &Lvalue<'tcx>)
+                     -> bool {
+    let ty = mir.lvalue_ty(tcx, lvalue).to_ty(tcx);
+    !ty.moves_by_default(tcx, param_env, DUMMY_SP)
+}
+
+/// Whether the argument `arg` is borrowed anywhere, or written to
+/// between `def` and `use_`.
+fn arg_is_mentioned<'tcx>(mir: &Mir<'tcx>, arg: &Lvalue<'tcx>, def: Location, use_: Location)
+                          -> bool {
+    let mut finder = ArgFinder { arg: arg, def: def, use_: use_, location: def, found: false };
+    for bb in mir.all_basic_blocks() {
+        let data = mir.basic_block_data(bb);
+        for (index, statement) in data.statements.iter().enumerate() {
+            finder.location = Location { block: bb, index: index };
+            finder.visit_statement(bb, statement);
+        }
+        finder.location = Location { block: bb, index: data.statements.len() };
+        finder.visit_terminator(bb, data.terminator());
+    }
+    finder.found
+}
+
+struct ArgFinder<'b, 'tcx: 'b> {
+    arg: &'b Lvalue<'tcx>,
+    def: Location,
+    use_: Location,
+    location: Location,
+    found: bool,
+}
+
+impl<'b, 'tcx> Visitor<'tcx> for ArgFinder<'b, 'tcx> {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        if def_use::base_lvalue(lvalue) == self.arg {
+            let between = self.location.block == self.def.block &&
+                          self.def.index < self.location.index &&
+                          self.location.index < self.use_.index;
+            match context {
+                LvalueContext::Borrow { .. } | LvalueContext::Slice { .. } => self.found = true,
+                LvalueContext::Consume |
+                LvalueContext::Inspect |
+                LvalueContext::Projection => {}
+                _ if between => self.found = true,
+                _ => {}
+            }
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+/// Replaces the (single) mention of `target` with `replacement`.
+struct Replacer<'tcx> {
+    target: Lvalue<'tcx>,
+    replacement: Lvalue<'tcx>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for Replacer<'tcx> {
+    fn visit_lvalue(&mut self, lvalue: &mut Lvalue<'tcx>, context: LvalueContext) {
+        if *lvalue == self.target {
+            *lvalue = self.replacement.clone();
+        } else {
+            self.super_lvalue(lvalue, context);
+        }
+    }
+}
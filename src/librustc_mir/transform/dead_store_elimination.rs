@@ -0,0 +1,86 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A pass that removes assignments to temps that are never read.
+//!
+//! Constant and copy propagation leave such assignments behind. Vars are
+//! kept, so that their values remain visible in a debugger, as are
+//! assignments whose evaluation may have an effect: inline assembly, and
+//! divisions, which panic when dividing by zero. Once no assignment to a
+//! temp is left, its storage markers are removed as well.
+
+use rustc::mir::repr::*;
+use rustc::mir::transform::{MirPass, MirSource, Pass};
+use rustc::ty::TyCtxt;
+use pretty;
+
+use super::def_use::DefUseAnalysis;
+
+pub struct DeadStoreElimination;
+
+impl<'tcx> MirPass<'tcx> for DeadStoreElimination {
+    fn run_pass<'a>(&mut self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                    src: MirSource, mir: &mut Mir<'tcx>) {
+        // Removing an assignment may leave the temps it read unread in
+        // turn.
+        loop {
+            let analysis = DefUseAnalysis::new(mir);
+            let mut removed = vec![];
+            for index in 0..analysis.num_locals() {
+                if !analysis.is_temp(index) {
+                    continue;
+                }
+                let info = analysis.local_info(index);
+                if !info.uses.is_empty() || !info.others.is_empty() {
+                    continue;
+                }
+                let dead: Vec<_> = info.defs.iter().cloned().filter(|location| {
+                    let statement = &mir.basic_block_data(location.block)
+                                        .statements[location.index];
+                    match statement.kind {
+                        StatementKind::Assign(ref dest, ref rvalue) => {
+                            analysis.local_index(dest) == Some(index) && is_pure(rvalue)
+                        }
+                        _ => false,
+                    }
+                }).collect();
+                if dead.len() == info.defs.len() {
+                    removed.extend(info.storage.iter().cloned());
+                }
+                removed.extend(dead);
+            }
+
+            if removed.is_empty() {
+                break;
+            }
+            // Remove back to front, so that the remaining indices stay
+            // valid.
+            removed.sort_by(|a, b| (b.block, b.index).cmp(&(a.block, a.index)));
+            for location in removed {
+                debug!("dead_store_elimination: removing {:?}", location);
+                mir.basic_block_data_mut(location.block).statements.remove(location.index);
+            }
+        }
+
+        pretty::dump_mir(tcx, "dead_store_elimination", &0, src, mir, None);
+    }
+}
+
+impl Pass for DeadStoreElimination {}
+
+/// Whether evaluating `rvalue` has no effect besides producing a value.
+fn is_pure(rvalue: &Rvalue) -> bool {
+    match *rvalue {
+        Rvalue::InlineAsm { .. } |
+        Rvalue::BinaryOp(BinOp::Div, _, _) |
+        Rvalue::BinaryOp(BinOp::Rem, _, _) => false,
+        _ => true,
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Def-use information for the vars and temps of a MIR body, shared by
+//! the optimization passes.
+
+use rustc::mir::repr::*;
+use rustc::mir::visit::{LvalueContext, Visitor};
+
+/// A statement (`index < statements.len()`) or the terminator of a block.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    pub block: BasicBlock,
+    pub index: usize,
+}
+
+#[derive(Default)]
+pub struct LocalInfo {
+    /// Assignments to the local as a whole (the left-hand side of a
+    /// statement, including the outputs of inline assembly).
+    pub defs: Vec<Location>,
+    /// Reads of the local, or of part of it, as an operand or through
+    /// an inspection like a discriminant or length read.
+    pub uses: Vec<Location>,
+    /// Any other mention: call destinations, drops, writes to part of
+    /// the local, and borrows.
+    pub others: Vec<Location>,
+    /// The `StorageLive` and `StorageDead` statements of the local.
+    pub storage: Vec<Location>,
+    /// Whether the local, or part of it, is ever borrowed.
+    pub borrowed: bool,
+}
+
+pub struct DefUseAnalysis {
+    num_vars: usize,
+    info: Vec<LocalInfo>,
+}
+
+impl DefUseAnalysis {
+    pub fn new(mir: &Mir) -> DefUseAnalysis {
+        let num_locals = mir.var_decls.len() + mir.temp_decls.len();
+        let mut finder = DefUseFinder {
+            analysis: DefUseAnalysis {
+                num_vars: mir.var_decls.len(),
+                info: (0..num_locals).map(|_| LocalInfo::default()).collect(),
+            },
+            location: Location { block: START_BLOCK, index: 0 },
+        };
+        for bb in mir.all_basic_blocks() {
+            let data = mir.basic_block_data(bb);
+            for (index, statement) in data.statements.iter().enumerate() {
+                finder.location = Location { block: bb, index: index };
+                finder.visit_statement(bb, statement);
+            }
+            finder.location = Location { block: bb, index: data.statements.len() };
+            finder.visit_terminator(bb, data.terminator());
+        }
+        finder.analysis
+    }
+
+    pub fn num_locals(&self) -> usize {
+        self.info.len()
+    }
+
+    /// The index of a bare var or temp; args, statics and the return
+    /// pointer are not tracked.
+    pub fn local_index(&self, lvalue: &Lvalue) -> Option<usize> {
+        match *lvalue {
+            Lvalue::Var(index) => Some(index as usize),
+            Lvalue::Temp(index) => Some(self.num_vars + index as usize),
+            _ => None,
+        }
+    }
+
+    pub fn local_lvalue<'tcx>(&self, index: usize) -> Lvalue<'tcx> {
+        if index < self.num_vars {
+            Lvalue::Var(index as u32)
+        } else {
+            Lvalue::Temp((index - self.num_vars) as u32)
+        }
+    }
+
+    pub fn is_temp(&self, index: usize) -> bool {
+        index >= self.num_vars
+    }
+
+    pub fn local_info(&self, index: usize) -> &LocalInfo {
+        &self.info[index]
+    }
+}
+
+/// The var or temp an lvalue is rooted in, if any.
+pub fn base_lvalue<'a, 'tcx>(lvalue: &'a Lvalue<'tcx>) -> &'a Lvalue<'tcx> {
+    match *lvalue {
+        Lvalue::Projection(ref proj) => base_lvalue(&proj.base),
+        _ => lvalue,
+    }
+}
+
+struct DefUseFinder {
+    analysis: DefUseAnalysis,
+    location: Location,
+}
+
+impl<'tcx> Visitor<'tcx> for DefUseFinder {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        // The base of a projection is accounted for, along with the
+        // context the whole lvalue appears in, by the outermost lvalue.
+        if let LvalueContext::Projection = context {
+            return self.super_lvalue(lvalue, context);
+        }
+
+        if let Some(index) = self.analysis.local_index(base_lvalue(lvalue)) {
+            let bare = match *lvalue {
+                Lvalue::Projection(_) => false,
+                _ => true,
+            };
+            let location = self.location;
+            let info = &mut self.analysis.info[index];
+            match context {
+                LvalueContext::Store if bare => info.defs.push(location),
+                LvalueContext::Consume |
+                LvalueContext::Inspect => info.uses.push(location),
+                LvalueContext::Borrow { .. } |
+                LvalueContext::Slice { .. } => {
+                    info.borrowed = true;
+                    info.others.push(location);
+                }
+                LvalueContext::StorageLive |
+                LvalueContext::StorageDead => info.storage.push(location),
+                _ => info.others.push(location),
+            }
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A pass that inlines calls to small, monomorphic functions of the
+//! current crate.
+//!
+//! The body of the callee is appended to the caller: its scopes, vars
+//! and temps are renumbered after the caller's own, its arguments and
+//! return pointer become fresh temps, `Return` jumps to a block that
+//! writes the call's destination, and unwinding out of the callee
+//! continues in the cleanup block of the call.
+//!
+//! Callees are inlined as they were before the pass ran, so calls are
+//! inlined at most one level deep and recursive functions need no special
+//! treatment.
+
+use rustc::hir::def_id::DefId;
+use rustc::mir::mir_map::MirMap;
+use rustc::mir::repr::*;
+use rustc::mir::transform::{MirMapPass, MirSource, Pass};
+use rustc::mir::visit::{LvalueContext, MutVisitor};
+use rustc::ty::{Ty, TyCtxt};
+use rustc::util::nodemap::NodeMap;
+use syntax::ast::NodeId;
+use syntax::attr::{self, InlineAttr};
+use pretty;
+
+/// The number of statements and terminators up to which a callee is
+/// inlined.
+const DEFAULT_THRESHOLD: usize = 20;
+
+/// The same, for callees marked `#[inline]`.
+const HINT_THRESHOLD: usize = 50;
+
+pub struct Inline;
+
+impl<'tcx> MirMapPass<'tcx> for Inline {
+    fn run_pass<'a>(&mut self, tcx: TyCtxt<'a, 'tcx, 'tcx>, map: &mut MirMap<'tcx>) {
+        let callees: NodeMap<Mir<'tcx>> = map.map.iter()
+            .filter(|&(&id, mir)| is_inlinable(tcx, id, mir))
+            .map(|(&id, mir)| (id, mir.clone()))
+            .collect();
+
+        for (&id, mir) in &mut map.map {
+            let src = MirSource::from_node(tcx, id);
+            if let MirSource::Fn(_) = src {} else {
+                continue;
+            }
+            let def_id = tcx.map.local_def_id(id);
+            let _task = tcx.dep_graph.in_task(self.dep_node(def_id));
+
+            let mut inlined = false;
+            // Inlined blocks are appended, so only the caller's own blocks
+            // are visited here.
+            for bb in mir.all_basic_blocks() {
+                if let Some((callee_def_id, callee)) = callee_at(tcx, id, &callees, mir, bb) {
+                    debug!("inline: inlining {:?} into {:?} at {:?}", callee_def_id, def_id, bb);
+                    tcx.dep_graph.read(self.dep_node(callee_def_id));
+                    integrate(mir, bb, callee);
+                    inlined = true;
+                }
+            }
+            if inlined {
+                pretty::dump_mir(tcx, "inline", &0, src, mir, None);
+            }
+        }
+    }
+}
+
+impl Pass for Inline {}
+
+/// Whether the body of the function `id` may be inlined at all.
+fn is_inlinable<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: NodeId, mir: &Mir<'tcx>) -> bool {
+    if let MirSource::Fn(_) = MirSource::from_node(tcx, id) {} else {
+        return false;
+    }
+    // Promoted constants are numbered per function, spread arguments
+    // are only unpacked on entry to a real call, and closures reach
+    // their upvars through an environment we do not have.
+    if !mir.promoted.is_empty() || !mir.upvar_decls.is_empty() ||
+       mir.arg_decls.iter().any(|arg| arg.spread) {
+        return false;
+    }
+    match attr::find_inline_attr(None, &tcx.get_attrs(tcx.map.local_def_id(id))) {
+        InlineAttr::Never => false,
+        _ => true,
+    }
+}
+
+/// The callee to inline for the call terminating `bb`, if any.
+fn callee_at<'a, 'b, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                           caller: NodeId,
+                           callees: &'b NodeMap<Mir<'tcx>>,
+                           mir: &Mir<'tcx>,
+                           bb: BasicBlock)
+                           -> Option<(DefId, &'b Mir<'tcx>)> {
+    let func = match mir.basic_block_data(bb).terminator().kind {
+        TerminatorKind::Call { ref func, destination: Some(_), .. } => func,
+        _ => return None,
+    };
+    let (def_id, substs) = match *func {
+        Operand::Constant(Constant { literal: Literal::Item { def_id, substs }, .. }) => {
+            (def_id, substs)
+        }
+        _ => return None,
+    };
+    if !substs.types.is_empty() {
+        return None;
+    }
+    let callee = match tcx.map.as_local_node_id(def_id) {
+        Some(id) if id != caller => match callees.get(&id) {
+            Some(callee) => callee,
+            None => return None,
+        },
+        _ => return None,
+    };
+
+    let threshold = match attr::find_inline_attr(None, &tcx.get_attrs(def_id)) {
+        InlineAttr::Always => return Some((def_id, callee)),
+        InlineAttr::Hint => HINT_THRESHOLD,
+        InlineAttr::None | InlineAttr::Never => DEFAULT_THRESHOLD,
+    };
+    let size = callee.basic_blocks.iter().map(|data| data.statements.len() + 1).sum::<usize>();
+    if size <= threshold {
+        Some((def_id, callee))
+    } else {
+        None
+    }
+}
+
+/// Replaces the call terminating `bb` with the body of `callee`.
+fn integrate<'tcx>(caller: &mut Mir<'tcx>, bb: BasicBlock, callee: &Mir<'tcx>) {
+    let terminator = caller.basic_block_data_mut(bb).terminator.take()
+                           .expect("invalid terminator state");
+    let (args, dest, dest_bb, cleanup) = match terminator.kind {
+        TerminatorKind::Call { args, destination: Some((dest, dest_bb)), cleanup, .. } => {
+            (args, dest, dest_bb, cleanup)
+        }
+        _ => bug!("inlining a call at {:?} that is not one", bb),
+    };
+
+    let scope_offset = caller.scopes.len();
+    for scope in &callee.scopes {
+        let parent_scope = match scope.parent_scope {
+            Some(parent) => ScopeId::new(parent.index() + scope_offset),
+            None => terminator.scope,
+        };
+        caller.scopes.push(ScopeData {
+            span: scope.span,
+            parent_scope: Some(parent_scope),
+        });
+    }
+
+    let var_offset = caller.var_decls.len();
+    for var in &callee.var_decls {
+        let mut var = var.clone();
+        var.scope = ScopeId::new(var.scope.index() + scope_offset);
+        caller.var_decls.push(var);
+    }
+    let temp_offset = caller.temp_decls.len();
+    caller.temp_decls.extend(callee.temp_decls.iter().cloned());
+    let arg_temps: Vec<u32> = callee.arg_decls.iter().map(|arg| new_temp(caller, arg.ty)).collect();
+    let return_temp = new_temp(caller, callee.return_ty);
+
+    let block_offset = caller.basic_blocks.len();
+    let return_block = BasicBlock::new(block_offset + callee.basic_blocks.len());
+    let mut integrator = Integrator {
+        scope_offset: scope_offset,
+        var_offset: var_offset,
+        temp_offset: temp_offset,
+        block_offset: block_offset,
+        args: &arg_temps,
+        return_temp: return_temp,
+        return_block: return_block,
+        cleanup: cleanup,
+        in_cleanup_block: false,
+    };
+    for (index, data) in callee.basic_blocks.iter().enumerate() {
+        let mut data = data.clone();
+        integrator.visit_basic_block_data(BasicBlock::new(index), &mut data);
+        caller.basic_blocks.push(data);
+    }
+
+    caller.basic_blocks.push(BasicBlockData {
+        statements: vec![Statement {
+            span: terminator.span,
+            scope: terminator.scope,
+            kind: StatementKind::Assign(dest,
+                                        Rvalue::Use(Operand::Consume(Lvalue::Temp(return_temp)))),
+        }],
+        terminator: Some(Terminator {
+            span: terminator.span,
+            scope: terminator.scope,
+            kind: TerminatorKind::Goto { target: dest_bb },
+        }),
+        is_cleanup: false,
+    });
+
+    let data = caller.basic_block_data_mut(bb);
+    for (arg, &temp) in args.into_iter().zip(&arg_temps) {
+        data.statements.push(Statement {
+            span: terminator.span,
+            scope: terminator.scope,
+            kind: StatementKind::Assign(Lvalue::Temp(temp), Rvalue::Use(arg)),
+        });
+    }
+    data.terminator = Some(Terminator {
+        span: terminator.span,
+        scope: terminator.scope,
+        kind: TerminatorKind::Goto { target: BasicBlock::new(block_offset) },
+    });
+}
+
+fn new_temp<'tcx>(mir: &mut Mir<'tcx>, ty: Ty<'tcx>) -> u32 {
+    mir.temp_decls.push(TempDecl { ty: ty });
+    (mir.temp_decls.len() - 1) as u32
+}
+
+/// Renumbers the contents of a callee's block for its new home in the
+/// caller.
+struct Integrator<'a> {
+    scope_offset: usize,
+    var_offset: usize,
+    temp_offset: usize,
+    block_offset: usize,
+    args: &'a [u32],
+    return_temp: u32,
+    return_block: BasicBlock,
+    cleanup: Option<BasicBlock>,
+    in_cleanup_block: bool,
+}
+
+impl<'a, 'tcx> MutVisitor<'tcx> for Integrator<'a> {
+    fn visit_basic_block_data(&mut self, block: BasicBlock, data: &mut BasicBlockData<'tcx>) {
+        self.in_cleanup_block = data.is_cleanup;
+        self.super_basic_block_data(block, data);
+    }
+
+    fn visit_lvalue(&mut self, lvalue: &mut Lvalue<'tcx>, context: LvalueContext) {
+        *lvalue = match *lvalue {
+            Lvalue::Var(index) => Lvalue::Var(index + self.var_offset as u32),
+            Lvalue::Temp(index) => Lvalue::Temp(index + self.temp_offset as u32),
+            Lvalue::Arg(index) => Lvalue::Temp(self.args[index as usize]),
+            Lvalue::ReturnPointer => Lvalue::Temp(self.return_temp),
+            Lvalue::Static(_) |
+            Lvalue::Projection(_) => return self.super_lvalue(lvalue, context),
+        };
+    }
+
+    fn visit_scope_id(&mut self, scope_id: &mut ScopeId) {
+        *scope_id = ScopeId::new(scope_id.index() + self.scope_offset);
+    }
+
+    fn visit_terminator_kind(&mut self, block: BasicBlock, kind: &mut TerminatorKind<'tcx>) {
+        self.super_terminator_kind(block, kind);
+        for target in kind.successors_mut() {
+            *target = BasicBlock::new(target.index() + self.block_offset);
+        }
+        match *kind {
+            TerminatorKind::Return => {
+                *kind = TerminatorKind::Goto { target: self.return_block };
+            }
+            TerminatorKind::Resume => {
+                if let Some(cleanup) = self.cleanup {
+                    *kind = TerminatorKind::Goto { target: cleanup };
+                }
+            }
+            // Unwinding out of the callee now unwinds into the caller's
+            // cleanup for the call.
+            TerminatorKind::Call { cleanup: ref mut unwind, .. } |
+            TerminatorKind::Drop { ref mut unwind, .. } => {
+                if unwind.is_none() && !self.in_cleanup_block {
+                    *unwind = self.cleanup;
+                }
+            }
+            TerminatorKind::Goto { .. } |
+            TerminatorKind::If { .. } |
+            TerminatorKind::Switch { .. } |
+            TerminatorKind::SwitchInt { .. } => {}
+        }
+    }
+}
@@ -16,3 +16,8 @@ pub mod type_check;
 pub mod break_cleanup_edges;
 pub mod promote_consts;
 pub mod qualify_consts;
+pub mod const_propagate;
+pub mod copy_propagation;
+pub mod dead_store_elimination;
+pub mod inline;
+mod def_use;
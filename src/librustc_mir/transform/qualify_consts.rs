@@ -114,7 +114,7 @@ impl fmt::Display for Mode {
     }
 }
 
-fn is_const_fn(tcx: TyCtxt, def_id: DefId) -> bool {
+pub fn is_const_fn(tcx: TyCtxt, def_id: DefId) -> bool {
     if let Some(node_id) = tcx.map.as_local_node_id(def_id) {
         let fn_like = FnLikeNode::from_node(tcx.map.get(node_id));
         match fn_like.map(|f| f.kind()) {
@@ -133,6 +133,9 @@ fn is_const_fn(tcx: TyCtxt, def_id: DefId) -> bool {
 
 struct Qualifier<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     mode: Mode,
+    /// Whether constants are evaluated by the MIR interpreter (`-Z miri`),
+    /// which lifts the restrictions on control flow and locals.
+    miri: bool,
     span: Span,
     def_id: DefId,
     mir: &'a Mir<'tcx>,
@@ -164,6 +167,7 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
         rpo.reset();
         Qualifier {
             mode: mode,
+            miri: tcx.sess.opts.debugging_opts.miri,
             span: mir.span,
             def_id: def_id,
             mir: mir,
@@ -190,7 +194,7 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
     // slightly pointless (even with feature-gating).
     fn not_const(&mut self) {
         self.add(Qualif::NOT_CONST);
-        if self.mode != Mode::Fn {
+        if self.mode != Mode::Fn && !self.miri {
             span_err!(self.tcx.sess, self.span, E0019,
                       "{} contains unimplemented expression type", self.mode);
         }
@@ -199,7 +203,7 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
     /// Error about extra statements in a constant.
     fn statement_like(&mut self) {
         self.add(Qualif::NOT_CONST);
-        if self.mode != Mode::Fn {
+        if self.mode != Mode::Fn && !self.miri {
             span_err!(self.tcx.sess, self.span, E0016,
                       "blocks in {}s are limited to items and tail expressions",
                       self.mode);
@@ -291,11 +295,14 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
     fn assign(&mut self, dest: &Lvalue<'tcx>) {
         let qualif = self.qualif;
         let span = self.span;
+        let miri = self.miri;
         let store = |slot: &mut Option<Qualif>| {
-            if slot.is_some() {
-                span_bug!(span, "multiple assignments to {:?}", dest);
+            // With branches, a temporary may be assigned in each of them.
+            match *slot {
+                Some(previous) if miri => *slot = Some(previous | qualif),
+                Some(_) => span_bug!(span, "multiple assignments to {:?}", dest),
+                None => *slot = Some(qualif),
             }
-            *slot = Some(qualif);
         };
 
         // Only handle promotable temps in non-const functions.
@@ -391,6 +398,22 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
     fn qualify_const(&mut self) -> Qualif {
         let mir = self.mir;
 
+        // The MIR interpreter evaluates arbitrary control flow, so every
+        // block is only checked for the operations that it cannot evaluate,
+        // such as calls to other functions and references to statics, and
+        // only the type of the value is taken into account.
+        if self.miri {
+            while let Some((bb, data)) = self.rpo.next() {
+                self.visit_basic_block_data(bb, data);
+            }
+            self.qualif = Qualif::empty();
+            self.add_type(mir.return_ty);
+            if self.mode == Mode::StaticMut {
+                self.deny_drop();
+            }
+            return self.qualif;
+        }
+
         let mut seen_blocks = BitVector::new(mir.basic_blocks.len());
         let mut bb = START_BLOCK;
         loop {
@@ -473,7 +496,7 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
             }
         }
 
-        let return_ty = mir.return_ty.unwrap();
+        let return_ty = mir.return_ty;
         self.qualif = self.return_qualif.unwrap_or(Qualif::NOT_CONST);
 
         match self.mode {
@@ -879,8 +902,9 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
     fn visit_assign(&mut self, _: BasicBlock, dest: &Lvalue<'tcx>, rvalue: &Rvalue<'tcx>) {
         self.visit_rvalue(rvalue);
 
-        // Check the allowed const fn argument forms.
-        if let (Mode::ConstFn, &Lvalue::Var(index)) = (self.mode, dest) {
+        // Check the allowed const fn argument forms, which the MIR
+        // interpreter does not restrict.
+        if let (Mode::ConstFn, &Lvalue::Var(index), false) = (self.mode, dest, self.miri) {
             if self.const_fn_arg_vars.insert(index as usize) {
                 // Direct use of an argument is permitted.
                 if let Rvalue::Use(Operand::Consume(Lvalue::Arg(_))) = *rvalue {
@@ -904,7 +928,14 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
     fn visit_statement(&mut self, bb: BasicBlock, statement: &Statement<'tcx>) {
         assert_eq!(self.location.block, bb);
         self.span = statement.span;
-        self.nest(|this| this.super_statement(bb, statement));
+        match statement.kind {
+            StatementKind::Assign(..) => {
+                self.nest(|this| this.super_statement(bb, statement));
+            }
+            // Storage live ranges have no bearing on constness.
+            StatementKind::StorageLive(_) |
+            StatementKind::StorageDead(_) => {}
+        }
         self.location.statement_index += 1;
     }
 
@@ -1029,7 +1060,7 @@ impl<'tcx> MirMapPass<'tcx> for QualifyAndPromoteConstants {
 
             // Statics must be Sync.
             if mode == Mode::Static {
-                let ty = mir.return_ty.unwrap();
+                let ty = mir.return_ty;
                 tcx.infer_ctxt(None, None, ProjectionMode::AnyFinal).enter(|infcx| {
                     let cause = traits::ObligationCause::new(mir.span, id, traits::SharedStatic);
                     let mut fulfillment_cx = traits::FulfillmentContext::new();
@@ -48,18 +48,20 @@ use rustc::hir::{self, PrimTy, TyBool, TyChar, TyFloat, TyInt, TyUint, TyStr};
 use rustc::session::Session;
 use rustc::lint;
 use rustc::hir::def::*;
-use rustc::hir::def_id::DefId;
+use rustc::hir::def_id::{CRATE_DEF_INDEX, DefId};
 use rustc::ty;
 use rustc::ty::subst::{ParamSpace, FnSpace, TypeSpace};
 use rustc::hir::{Freevar, FreevarMap, TraitCandidate, TraitMap, GlobMap};
 use rustc::util::nodemap::{NodeMap, NodeSet, FnvHashMap, FnvHashSet};
 
+use syntax::ext::macro_scope::ModulePath;
 use syntax::ext::mtwt;
 use syntax::ast::{self, FloatTy};
 use syntax::ast::{CRATE_NODE_ID, Name, NodeId, CrateNum, IntTy, UintTy};
-use syntax::codemap::{self, Span};
-use syntax::errors::DiagnosticBuilder;
+use syntax::codemap::{self, CodeMap, Span, DUMMY_SP, NO_EXPANSION};
+use syntax::errors::{Applicability, DiagnosticBuilder};
 use syntax::parse::token::{self, keywords};
+use syntax::ptr::P;
 use syntax::util::lev_distance::find_best_match_for_name;
 
 use syntax::visit::{self, FnKind, Visitor};
@@ -76,6 +78,8 @@ use std::mem::replace;
 
 use resolve_imports::{ImportDirective, NameResolution};
 
+pub use macros::MacroScopes;
+
 // NB: This module needs to be declared first so diagnostics are
 // registered before they are used.
 mod diagnostics;
@@ -83,6 +87,7 @@ mod diagnostics;
 mod check_unused;
 mod build_reduced_graph;
 mod resolve_imports;
+mod macros;
 
 enum SuggestionType {
     Macro(String),
@@ -94,6 +99,8 @@ enum SuggestionType {
 struct SuggestedCandidates {
     name: String,
     candidates: Vec<Path>,
+    /// Where a `use` importing one of the candidates would go.
+    use_span: Option<Span>,
 }
 
 enum ResolutionError<'a> {
@@ -237,7 +244,7 @@ fn resolve_struct_error<'b, 'a: 'b, 'c>(resolver: &'b Resolver<'a>,
                                            E0405,
                                            "trait `{}` is not in scope",
                                            name);
-            show_candidates(&mut err, &candidates);
+            show_candidates(&mut err, resolver.session.codemap(), &candidates);
             err.span_label(span, &format!("`{}` is not in scope", name));
             err
         }
@@ -301,7 +308,7 @@ fn resolve_struct_error<'b, 'a: 'b, 'c>(resolver: &'b Resolver<'a>,
                                            "{} `{}` is undefined or not in scope",
                                            kind,
                                            name);
-            show_candidates(&mut err, &candidates);
+            show_candidates(&mut err, resolver.session.codemap(), &candidates);
             err.span_label(span, &format!("undefined or not in scope"));
             err
         }
@@ -960,12 +967,14 @@ impl PrimitiveTypeTable {
         table.intern("i16", TyInt(IntTy::I16));
         table.intern("i32", TyInt(IntTy::I32));
         table.intern("i64", TyInt(IntTy::I64));
+        table.intern("i128", TyInt(IntTy::I128));
         table.intern("str", TyStr);
         table.intern("usize", TyUint(UintTy::Us));
         table.intern("u8", TyUint(UintTy::U8));
         table.intern("u16", TyUint(UintTy::U16));
         table.intern("u32", TyUint(UintTy::U32));
         table.intern("u64", TyUint(UintTy::U64));
+        table.intern("u128", TyUint(UintTy::U128));
 
         table
     }
@@ -1009,6 +1018,10 @@ pub struct Resolver<'a> {
     // The current self type if inside an impl (used for better errors).
     current_self_type: Option<Ty>,
 
+    // Where a `use` declaration would go in the current module (used to
+    // suggest missing imports).
+    use_injection_span: Option<Span>,
+
     // The idents for the primitive types.
     primitive_type_table: PrimitiveTypeTable,
 
@@ -1050,6 +1063,12 @@ pub struct Resolver<'a> {
 
     privacy_errors: Vec<PrivacyError<'a>>,
 
+    /// The macros of `macro` items, which imports may name.
+    macros: MacroScopes,
+    /// The root modules of the crates that the bodies of `macro` items from
+    /// other crates are resolved in, which the crate may not name itself.
+    extern_crate_roots: FnvHashMap<CrateNum, Module<'a>>,
+
     arenas: &'a ResolverArenas<'a>,
 }
 
@@ -1154,6 +1173,7 @@ impl<'a> Resolver<'a> {
     fn new(session: &'a Session,
            definitions: &'a mut Definitions,
            make_glob_map: MakeGlobMap,
+           macros: MacroScopes,
            arenas: &'a ResolverArenas<'a>)
            -> Resolver<'a> {
         let root_def_id = definitions.local_def_id(CRATE_NODE_ID);
@@ -1184,6 +1204,7 @@ impl<'a> Resolver<'a> {
 
             current_trait_ref: None,
             current_self_type: None,
+            use_injection_span: None,
 
             primitive_type_table: PrimitiveTypeTable::new(),
 
@@ -1204,6 +1225,9 @@ impl<'a> Resolver<'a> {
 
             privacy_errors: Vec::new(),
 
+            macros: macros,
+            extern_crate_roots: FnvHashMap(),
+
             arenas: arenas,
         }
     }
@@ -1445,6 +1469,13 @@ impl<'a> Resolver<'a> {
             }
 
             if let ModuleRibKind(module) = self.get_ribs(ns)[i].kind {
+                // The names from the body of a `macro` item are resolved in
+                // the module the macro was defined in.
+                let call_site = module;
+                let module = match module.def {
+                    Some(_) => self.def_site_module(ident).unwrap_or(module),
+                    None => module,
+                };
                 let name = ident.name;
                 let item = self.resolve_name_in_module(module, name, ns, true, record_used);
                 if let Success(binding) = item {
@@ -1452,6 +1483,18 @@ impl<'a> Resolver<'a> {
                     return Some(LexicalScopeBinding::Item(binding));
                 }
 
+                // The modules of other crates have no prelude, so a macro
+                // from another crate sees the prelude of the call site.
+                if module.prelude.borrow().is_none() {
+                    let prelude = *call_site.prelude.borrow();
+                    let binding = prelude.and_then(|prelude| {
+                        prelude.resolve_name(name, ns, false).success()
+                    });
+                    if let Some(binding) = binding {
+                        return Some(LexicalScopeBinding::Item(binding));
+                    }
+                }
+
                 // We can only see through anonymous modules
                 if module.def.is_some() { return None; }
             }
@@ -1460,6 +1503,109 @@ impl<'a> Resolver<'a> {
         None
     }
 
+    /// The module that the names with the context of `ident` are resolved in
+    /// if they come from the body of a `macro` item, i.e. the module that
+    /// the macro was defined in.
+    fn def_site_module(&mut self, ident: ast::Ident) -> Option<Module<'a>> {
+        let def_site = match mtwt::def_site(ident.ctxt) {
+            Some(def_site) => def_site,
+            None => return None,
+        };
+
+        let mut module = match def_site.krate {
+            Some(name) => match self.extern_crate_root(name) {
+                Some(module) => module,
+                None => return None,
+            },
+            None => self.graph_root,
+        };
+        for &name in &def_site.path {
+            module = match self.resolve_name_in_module(module, name, TypeNS, true, false)
+                               .success()
+                               .and_then(NameBinding::module) {
+                Some(module) => module,
+                None => return None,
+            };
+        }
+        Some(module)
+    }
+
+    /// Returns the path of `module` as the macros of `macro` items see it, or
+    /// `None` if the module is a block.
+    fn module_path(&self, mut module: Module<'a>) -> Option<ModulePath> {
+        let mut path = Vec::new();
+        loop {
+            if let Some(def_id) = module.def_id() {
+                if def_id.index == CRATE_DEF_INDEX {
+                    let krate = if def_id.is_local() {
+                        None
+                    } else {
+                        Some(token::intern(&self.session.cstore.crate_name(def_id.krate)))
+                    };
+                    path.reverse();
+                    return Some(ModulePath { krate: krate, path: path });
+                }
+            }
+            module = match module.parent_link {
+                ModuleParentLink(parent, name) => {
+                    path.push(name);
+                    parent
+                }
+                NoParentLink | BlockParentLink(..) => return None,
+            };
+        }
+    }
+
+    /// Returns the root module of the loaded crate named `name`, in which the
+    /// bodies of the `macro` items of that crate are resolved.
+    fn extern_crate_root(&mut self, name: Name) -> Option<Module<'a>> {
+        let cnum = match self.session.cstore.crates().into_iter().find(|&cnum| {
+            self.session.cstore.crate_name(cnum) == name.as_str()
+        }) {
+            Some(cnum) => cnum,
+            None => return None,
+        };
+
+        if let Some(&module) = self.extern_crate_roots.get(&cnum) {
+            return Some(module);
+        }
+        let def_id = DefId { krate: cnum, index: CRATE_DEF_INDEX };
+        let module = self.new_module(NoParentLink, Some(Def::Mod(def_id)), false);
+        self.build_reduced_graph_for_external_crate(module);
+        self.extern_crate_roots.insert(cnum, module);
+        Some(module)
+    }
+
+    /// Resolves the module part of a path from the body of a `macro` item,
+    /// the first name of which is resolved in the module the macro was
+    /// defined in.
+    fn resolve_module_path_at_def_site(&mut self,
+                                       def_site: Module<'a>,
+                                       module_path: &[Name],
+                                       span: Span)
+                                       -> ResolveResult<Module<'a>> {
+        let current_module = replace(&mut self.current_module, def_site);
+        let result = match self.resolve_module_prefix(module_path, span) {
+            Failed(err) => Failed(err),
+            Indeterminate => Indeterminate,
+            Success(NoPrefixFound) => {
+                match self.resolve_name_in_module(def_site, module_path[0], TypeNS, true, true)
+                          .success()
+                          .and_then(NameBinding::module) {
+                    Some(module) => {
+                        self.resolve_module_path_from_root(module, module_path, 1, span)
+                    }
+                    None => Failed(None),
+                }
+            }
+            Success(PrefixFound(module, index)) => {
+                self.resolve_module_path_from_root(module, module_path, index, span)
+            }
+        };
+        self.current_module = current_module;
+        result
+    }
+
     /// Returns the nearest normal module parent of the given module.
     fn get_nearest_normal_module_parent(&self, module_: Module<'a>) -> Option<Module<'a>> {
         let mut module_ = module_;
@@ -1617,6 +1763,7 @@ impl<'a> Resolver<'a> {
     fn resolve_crate(&mut self, krate: &Crate) {
         debug!("(resolving crate) starting");
         self.current_module = self.graph_root;
+        self.use_injection_span = use_injection_span(&krate.module.items);
         visit::walk_crate(self, krate);
     }
 
@@ -1628,7 +1775,8 @@ impl<'a> Resolver<'a> {
         match item.node {
             ItemKind::Enum(_, ref generics) |
             ItemKind::Ty(_, ref generics) |
-            ItemKind::Struct(_, ref generics) => {
+            ItemKind::Struct(_, ref generics) |
+            ItemKind::Union(_, ref generics) => {
                 self.with_type_parameter_rib(HasTypeParameters(generics, TypeSpace, ItemRibKind),
                                              |this| visit::walk_item(this, item));
             }
@@ -1692,7 +1840,16 @@ impl<'a> Resolver<'a> {
                 });
             }
 
-            ItemKind::Mod(_) | ItemKind::ForeignMod(_) => {
+            ItemKind::Mod(ref module) => {
+                let outer_use_span = replace(&mut self.use_injection_span,
+                                             use_injection_span(&module.items));
+                self.with_scope(item.id, |this| {
+                    visit::walk_item(this, item);
+                });
+                self.use_injection_span = outer_use_span;
+            }
+
+            ItemKind::ForeignMod(_) => {
                 self.with_scope(item.id, |this| {
                     visit::walk_item(this, item);
                 });
@@ -2761,8 +2918,14 @@ impl<'a> Resolver<'a> {
                                   .map(|ps| ps.identifier.name)
                                   .collect::<Vec<_>>();
 
+        let def_site = self.def_site_module(segments[0].identifier);
+        let result = match def_site {
+            Some(def_site) => self.resolve_module_path_at_def_site(def_site, &module_path, span),
+            None => self.resolve_module_path(&module_path, UseLexicalScope, span),
+        };
+
         let containing_module;
-        match self.resolve_module_path(&module_path, UseLexicalScope, span) {
+        match result {
             Failed(err) => {
                 let (span, msg) = match err {
                     Some((span, msg)) => (span, msg),
@@ -2785,7 +2948,16 @@ impl<'a> Resolver<'a> {
         let name = segments.last().unwrap().identifier.name;
         let result = self.resolve_name_in_module(containing_module, name, namespace, false, true);
         result.success().map(|binding| {
-            self.check_privacy(name, binding, span);
+            // The privacy of a path from the body of a `macro` item is that
+            // of the path at the definition of the macro.
+            match def_site {
+                Some(def_site) => {
+                    let current_module = replace(&mut self.current_module, def_site);
+                    self.check_privacy(name, binding, span);
+                    self.current_module = current_module;
+                }
+                None => self.check_privacy(name, binding, span),
+            }
             binding
         }).ok_or(false)
     }
@@ -3385,6 +3557,7 @@ impl<'a> Resolver<'a> {
         SuggestedCandidates {
             name: lookup_name.as_str().to_string(),
             candidates: lookup_results,
+            use_span: self.use_injection_span,
         }
     }
 
@@ -3449,8 +3622,16 @@ impl<'a> Resolver<'a> {
 
     fn is_accessible(&self, vis: ty::Visibility) -> bool {
         let current_module = self.get_nearest_normal_module_parent_or_self(self.current_module);
-        let node_id = self.definitions.as_local_node_id(current_module.def_id().unwrap()).unwrap();
-        vis.is_accessible_from(node_id, self)
+        match self.definitions.as_local_node_id(current_module.def_id().unwrap()) {
+            Some(node_id) => vis.is_accessible_from(node_id, self),
+            // The current module is that of a `macro` item from another
+            // crate. The metadata does not record how far the private items
+            // of that crate are visible, so the macro may name any of them.
+            None => match vis {
+                ty::Visibility::Restricted(..) => false,
+                ty::Visibility::Public | ty::Visibility::PrivateExternal => true,
+            },
+        }
     }
 
     fn check_privacy(&mut self, name: Name, binding: &'a NameBinding<'a>, span: Span) {
@@ -3561,6 +3742,7 @@ fn path_names_to_string(path: &Path, depth: usize) -> String {
 /// entities with that name in all crates. This method allows outputting the
 /// results of this search in a programmer-friendly way
 fn show_candidates(session: &mut DiagnosticBuilder,
+                   codemap: &CodeMap,
                    candidates: &SuggestedCandidates) {
 
     let paths = &candidates.candidates;
@@ -3580,10 +3762,23 @@ fn show_candidates(session: &mut DiagnosticBuilder,
         // behave differently based on how many candidates we have:
         if !paths.is_empty() {
             if paths.len() == 1 {
-                session.help(
-                    &format!("you can import it into scope: `use {};`.",
-                        &path_strings[0]),
-                );
+                let msg = format!("you can import it into scope: `use {};`.", &path_strings[0]);
+                match candidates.use_span {
+                    // The candidate may be private, or its name may clash
+                    // with another one in the module.
+                    Some(span) => {
+                        let indent = line_indentation(codemap, span);
+                        session.span_suggestion_with_applicability(
+                            span,
+                            &msg,
+                            format!("use {};\n{}", &path_strings[0], indent),
+                            Applicability::MaybeIncorrect,
+                        );
+                    }
+                    None => {
+                        session.help(&msg);
+                    }
+                }
             } else {
                 session.help("you can import several candidates \
                     into scope (`use ...;`):");
@@ -3613,6 +3808,32 @@ fn show_candidates(session: &mut DiagnosticBuilder,
     };
 }
 
+/// The position before the first item of a module that is written out in
+/// the source, where a `use` declaration can be inserted.
+fn use_injection_span(items: &[P<Item>]) -> Option<Span> {
+    items.iter()
+         .find(|item| item.span.expn_id == NO_EXPANSION && item.span != DUMMY_SP)
+         .map(|item| {
+             // Outer attributes and doc comments belong to the item.
+             let lo = item.attrs
+                          .iter()
+                          .filter(|attr| attr.span.expn_id == NO_EXPANSION)
+                          .map(|attr| attr.span.lo)
+                          .chain(Some(item.span.lo))
+                          .min()
+                          .unwrap();
+             Span { lo: lo, hi: lo, expn_id: NO_EXPANSION }
+         })
+}
+
+/// The whitespace at the start of the line of `span`.
+fn line_indentation(codemap: &CodeMap, span: Span) -> String {
+    let loc = codemap.lookup_char_pos(span.lo);
+    loc.file.get_line(loc.line - 1)
+            .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+            .unwrap_or(String::new())
+}
+
 /// A somewhat inefficient routine to obtain the name of a module.
 fn module_to_string(module: Module) -> String {
     let mut names = Vec::new();
@@ -3675,11 +3896,12 @@ pub fn resolve_crate<'a, 'b>(resolver: &'b mut Resolver<'a>, krate: &'b Crate) {
 pub fn with_resolver<'a, T, F>(session: &'a Session,
                                definitions: &'a mut Definitions,
                                make_glob_map: MakeGlobMap,
+                               macros: MacroScopes,
                                f: F) -> T
     where F: for<'b> FnOnce(Resolver<'b>) -> T,
 {
     let arenas = Resolver::arenas();
-    let resolver = Resolver::new(session, definitions, make_glob_map, &arenas);
+    let resolver = Resolver::new(session, definitions, make_glob_map, macros, &arenas);
     f(resolver)
 }
 
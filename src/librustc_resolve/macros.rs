@@ -0,0 +1,538 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The scopes that the macros of `macro` items are named in.
+//!
+//! Macros are expanded before the crate is resolved, so the paths of macro
+//! invocations cannot be resolved in the module graph of the crate. Instead,
+//! the modules, `macro` items, imports and `extern crate` items of the crate
+//! are recorded as it is expanded, and the paths of macro invocations are
+//! resolved against those with the rules of the resolver: imports and
+//! `pub(restricted)` paths start at the crate root, an item is visible where
+//! its visibility says, and globs import the public names of a module. Once
+//! the crate is expanded, the resolver uses the same scopes for the imports
+//! that name macros.
+
+use syntax::ast::{self, DeclMacro, Name, Visibility};
+use syntax::codemap::Span;
+use syntax::ext::base::SyntaxExtension;
+use syntax::ext::macro_scope::{MacroItem, MacroResolver, ModulePath};
+use syntax::parse::token::keywords;
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// How many imports may be followed to find a macro, which stops cycles of
+/// glob imports.
+const MAX_IMPORT_DEPTH: usize = 32;
+
+/// Where an item can be named from.
+#[derive(Clone, PartialEq)]
+enum Vis {
+    Public,
+    /// Within the given module.
+    Module(ModulePath),
+    /// Within the module named by the path from the crate root, as given by
+    /// `pub(path)`.
+    Path(Vec<Name>),
+}
+
+impl Vis {
+    fn new(module: &ModulePath, vis: &Visibility) -> Vis {
+        match *vis {
+            Visibility::Public => Vis::Public,
+            Visibility::Crate(..) => Vis::Module(module.root()),
+            Visibility::Restricted { ref path, .. } => Vis::Path(import_path(module, path)),
+            Visibility::Inherited => Vis::Module(module.clone()),
+        }
+    }
+}
+
+/// The names of a module that the path of a macro may go through.
+#[derive(Default)]
+struct ModuleScope {
+    macros: HashMap<Name, (Rc<MacroItem>, Vis)>,
+    /// The child modules and the crates of the `extern crate` items.
+    modules: HashMap<Name, (ModulePath, Vis)>,
+    /// The names imported by single imports, with the path they are imported
+    /// from, starting at the crate root.
+    imports: HashMap<Name, (Vec<Name>, Vis)>,
+    /// The paths of the modules that are glob imported.
+    globs: Vec<(Vec<Name>, Vis)>,
+}
+
+impl ModuleScope {
+    /// The item named `name` that the module defines itself.
+    fn defined(&self, name: Name, ns: Namespace) -> Option<(Binding, &Vis)> {
+        match ns {
+            Namespace::Macro => self.macros.get(&name).map(|&(ref item, ref vis)| {
+                (Binding::Macro(item.clone()), vis)
+            }),
+            Namespace::Module => self.modules.get(&name).map(|&(ref path, ref vis)| {
+                (Binding::Module(path.clone()), vis)
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Namespace {
+    Macro,
+    Module,
+}
+
+enum Binding {
+    Macro(Rc<MacroItem>),
+    Module(ModulePath),
+}
+
+enum Failure {
+    NotFound,
+    Private,
+}
+
+/// The `macro` items of the crate and of the crates that it imports macros
+/// from, with the modules and imports that name them.
+#[derive(Default)]
+pub struct MacroScopes {
+    modules: HashMap<ModulePath, ModuleScope>,
+    /// The macros defined in the blocks being expanded, innermost last.
+    blocks: Vec<HashMap<Name, Rc<MacroItem>>>,
+}
+
+impl MacroScopes {
+    pub fn new() -> MacroScopes {
+        MacroScopes::default()
+    }
+
+    fn scope_mut(&mut self, module: &ModulePath) -> &mut ModuleScope {
+        self.modules.entry(module.clone()).or_insert_with(ModuleScope::default)
+    }
+
+    fn record_import(&mut self, module: &ModulePath, view_path: &ast::ViewPath, vis: Vis) {
+        match view_path.node {
+            ast::ViewPathSimple(ident, ref path) => {
+                let path = import_path(module, path);
+                self.scope_mut(module).imports.insert(ident.name, (path, vis));
+            }
+            ast::ViewPathGlob(ref path) => {
+                let path = import_path(module, path);
+                let scope = self.scope_mut(module);
+                if !scope.globs.iter().any(|&(ref glob, _)| *glob == path) {
+                    scope.globs.push((path, vis));
+                }
+            }
+            ast::ViewPathList(ref prefix, ref items) => {
+                let prefix = import_path(module, prefix);
+                for item in items {
+                    let (name, path) = match item.node {
+                        ast::PathListItemKind::Ident { name, rename, .. } => {
+                            let mut path = prefix.clone();
+                            path.push(name.name);
+                            (rename.unwrap_or(name).name, path)
+                        }
+                        ast::PathListItemKind::Mod { rename, .. } => match prefix.last() {
+                            Some(&last) => {
+                                (rename.map_or(last, |rename| rename.name), prefix.clone())
+                            }
+                            None => continue,
+                        },
+                    };
+                    self.scope_mut(module).imports.insert(name, (path, vis.clone()));
+                }
+            }
+        }
+    }
+
+    /// Whether `name` names a macro in `module`, which the resolver asks of
+    /// the imports that name nothing else. Returns `None` if it does not, and
+    /// whether the macro can be named from the module `from` otherwise.
+    pub fn names_macro(&self, module: &ModulePath, name: Name, from: &ModulePath)
+                       -> Option<bool> {
+        match self.lookup(module, name, Namespace::Macro, from, 0) {
+            Ok(_) => Some(true),
+            Err(Failure::Private) => Some(false),
+            Err(Failure::NotFound) => None,
+        }
+    }
+
+    /// Whether an item of `module` with the visibility `vis` can be named
+    /// from the module `from`.
+    fn is_accessible(&self, vis: &Vis, module: &ModulePath, from: &ModulePath, depth: usize)
+                     -> bool {
+        match *vis {
+            Vis::Public => true,
+            Vis::Module(ref restriction) => from.is_within(restriction),
+            Vis::Path(ref path) => {
+                let root = module.root();
+                match self.resolve_module(&root, path, &root, depth + 1) {
+                    Ok(restriction) => from.is_within(&restriction),
+                    // The resolver reports the path that does not name a
+                    // module, and takes the item to be public.
+                    Err(_) => true,
+                }
+            }
+        }
+    }
+
+    /// Looks up `name` in `module`, following its imports, as seen from the
+    /// module `from`.
+    fn lookup(&self,
+              module: &ModulePath,
+              name: Name,
+              ns: Namespace,
+              from: &ModulePath,
+              depth: usize)
+              -> Result<Binding, Failure> {
+        let scope = match self.modules.get(module) {
+            Some(scope) if depth <= MAX_IMPORT_DEPTH => scope,
+            _ => return Err(Failure::NotFound),
+        };
+
+        if let Some((binding, vis)) = scope.defined(name, ns) {
+            return if self.is_accessible(vis, module, from, depth) {
+                Ok(binding)
+            } else {
+                Err(Failure::Private)
+            };
+        }
+
+        let mut failure = Failure::NotFound;
+        if let Some(&(ref path, ref vis)) = scope.imports.get(&name) {
+            match self.resolve(&module.root(), path, ns, module, depth + 1) {
+                Ok(binding) => {
+                    if self.is_accessible(vis, module, from, depth) {
+                        return Ok(binding);
+                    }
+                    failure = Failure::Private;
+                }
+                Err(err) => failure = err,
+            }
+        }
+
+        for &(ref path, ref vis) in &scope.globs {
+            if !self.is_accessible(vis, module, from, depth) {
+                continue;
+            }
+            if let Ok(target) = self.resolve_module(&module.root(), path, module, depth + 1) {
+                if let Some(binding) = self.lookup_public(&target, name, ns, depth + 1) {
+                    return Ok(binding);
+                }
+            }
+        }
+
+        Err(failure)
+    }
+
+    /// Looks up `name` among the public names of `module`, which are those
+    /// that globs import and that other crates can name.
+    fn lookup_public(&self, module: &ModulePath, name: Name, ns: Namespace, depth: usize)
+                     -> Option<Binding> {
+        let scope = match self.modules.get(module) {
+            Some(scope) if depth <= MAX_IMPORT_DEPTH => scope,
+            _ => return None,
+        };
+
+        if let Some((binding, vis)) = scope.defined(name, ns) {
+            return if *vis == Vis::Public { Some(binding) } else { None };
+        }
+
+        if let Some(&(ref path, ref vis)) = scope.imports.get(&name) {
+            if *vis == Vis::Public {
+                return self.resolve(&module.root(), path, ns, module, depth + 1).ok();
+            }
+        }
+
+        for &(ref path, ref vis) in &scope.globs {
+            if *vis != Vis::Public {
+                continue;
+            }
+            if let Ok(target) = self.resolve_module(&module.root(), path, module, depth + 1) {
+                if let Some(binding) = self.lookup_public(&target, name, ns, depth + 1) {
+                    return Some(binding);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Adds the names that `lookup_public` may find in `module` to `names`.
+    fn public_names(&self,
+                    module: &ModulePath,
+                    ns: Namespace,
+                    depth: usize,
+                    names: &mut HashSet<Name>) {
+        let scope = match self.modules.get(module) {
+            Some(scope) if depth <= MAX_IMPORT_DEPTH => scope,
+            _ => return,
+        };
+
+        match ns {
+            Namespace::Macro => names.extend(scope.macros.keys().cloned()),
+            Namespace::Module => names.extend(scope.modules.keys().cloned()),
+        }
+        names.extend(scope.imports.keys().cloned());
+        for &(ref path, ref vis) in &scope.globs {
+            if *vis != Vis::Public {
+                continue;
+            }
+            if let Ok(target) = self.resolve_module(&module.root(), path, module, depth + 1) {
+                self.public_names(&target, ns, depth + 1, names);
+            }
+        }
+    }
+
+    /// Resolves `path` from `start`. All but the last name name modules.
+    fn resolve(&self,
+               start: &ModulePath,
+               path: &[Name],
+               ns: Namespace,
+               from: &ModulePath,
+               depth: usize)
+               -> Result<Binding, Failure> {
+        let (&last, modules) = match path.split_last() {
+            Some(split) => split,
+            None => return Err(Failure::NotFound),
+        };
+        let module = try!(self.resolve_module(start, modules, from, depth));
+        self.lookup(&module, last, ns, from, depth)
+    }
+
+    /// Resolves a path of modules from `start`.
+    fn resolve_module(&self, start: &ModulePath, path: &[Name], from: &ModulePath, depth: usize)
+                      -> Result<ModulePath, Failure> {
+        let mut module = start.clone();
+        for &name in path {
+            module = match try!(self.lookup(&module, name, Namespace::Module, from, depth)) {
+                Binding::Module(module) => module,
+                Binding::Macro(..) => return Err(Failure::NotFound),
+            };
+        }
+        Ok(module)
+    }
+}
+
+impl MacroResolver for MacroScopes {
+    fn record_item(&mut self, module: &ModulePath, item: &ast::Item) {
+        let name = item.ident.name;
+        let vis = Vis::new(module, &item.vis);
+        match item.node {
+            ast::ItemKind::Mod(..) => {
+                let child = module.child(name);
+                self.scope_mut(module).modules.insert(name, (child, vis));
+            }
+            ast::ItemKind::ExternCrate(orig_name) if module.path.is_empty() => {
+                // The macros of other crates are recorded by the name of
+                // the crate, which is what the `extern crate` item names.
+                let krate = ModulePath {
+                    krate: Some(orig_name.unwrap_or(name)),
+                    path: vec![],
+                };
+                self.scope_mut(module).modules.insert(name, (krate, vis));
+            }
+            ast::ItemKind::Use(ref view_path) => self.record_import(module, view_path, vis),
+            _ => {}
+        }
+    }
+
+    fn is_defined_by(&self, module: &ModulePath, in_block: bool, name: Name, span: Span)
+                     -> bool {
+        if in_block && !self.blocks.is_empty() {
+            let item = self.blocks.last().unwrap().get(&name);
+            item.map_or(false, |item| item.def.span == span)
+        } else {
+            let item = self.modules.get(module).and_then(|scope| scope.macros.get(&name));
+            item.map_or(false, |&(ref item, _)| item.def.span == span)
+        }
+    }
+
+    fn define(&mut self, module: &ModulePath, in_block: bool, item: MacroItem)
+              -> Result<(), ()> {
+        let name = item.def.ident.name;
+        if in_block && !self.blocks.is_empty() {
+            let macros = self.blocks.last_mut().unwrap();
+            if macros.contains_key(&name) {
+                return Err(());
+            }
+            macros.insert(name, Rc::new(item));
+        } else {
+            let vis = Vis::new(module, &item.vis);
+            let macros = &mut self.scope_mut(module).macros;
+            if macros.contains_key(&name) {
+                return Err(());
+            }
+            macros.insert(name, (Rc::new(item), vis));
+        }
+        Ok(())
+    }
+
+    fn define_imported(&mut self, ext: Rc<SyntaxExtension>, def: ast::MacroDef) {
+        let decl = def.decl.clone().expect("expected the definition of a `macro` item");
+
+        let mut module = ModulePath { krate: decl.krate, path: vec![] };
+        for &name in &decl.module {
+            let child = module.child(name);
+            self.scope_mut(&module).modules.insert(name, (child.clone(), Vis::Public));
+            module = child;
+        }
+
+        let item = MacroItem {
+            ext: ext,
+            def_site: ModulePath { krate: decl.def_site_crate, path: decl.def_site },
+            vis: Visibility::Public,
+            def: def,
+        };
+        let name = item.def.ident.name;
+        self.scope_mut(&module).macros.insert(name, (Rc::new(item), Vis::Public));
+    }
+
+    fn push_block(&mut self) {
+        self.blocks.push(HashMap::new());
+    }
+
+    fn pop_block(&mut self) {
+        self.blocks.pop();
+    }
+
+    fn find_in_blocks(&self, name: Name) -> Option<Rc<MacroItem>> {
+        self.blocks.iter().rev().filter_map(|macros| macros.get(&name)).next().cloned()
+    }
+
+    fn resolve_macro(&self, module: &ModulePath, path: &ast::Path)
+                     -> Result<Rc<MacroItem>, Option<String>> {
+        let names = path.segments.iter().map(|segment| segment.identifier.name).collect::<Vec<_>>();
+        let mut start = if path.global { module.root() } else { module.clone() };
+        let mut skip = 0;
+        if !path.global && names[0] == keywords::SelfValue.name() {
+            skip = 1;
+        }
+        while !path.global && skip < names.len() && names[skip] == keywords::Super.name() {
+            start = match start.parent() {
+                Some(parent) => parent,
+                None => return Err(Some("there are too many initial `super`s.".to_string())),
+            };
+            skip += 1;
+        }
+
+        let (&last, modules) = match names[skip..].split_last() {
+            Some(split) => split,
+            None => return Err(None),
+        };
+        let mut current = start;
+        for (i, &name) in modules.iter().enumerate() {
+            current = match self.lookup(&current, name, Namespace::Module, module, 0) {
+                Ok(Binding::Module(next)) => next,
+                Err(Failure::Private) => return Err(Some(format!("module `{}` is private", name))),
+                _ if i == 0 => {
+                    return Err(Some(format!("failed to resolve. Use of undeclared type or \
+                                             module `{}`", name)));
+                }
+                _ => {
+                    return Err(Some(format!("failed to resolve. Could not find `{}` in `{}`",
+                                            name, modules[i - 1])));
+                }
+            };
+        }
+
+        match self.lookup(&current, last, Namespace::Macro, module, 0) {
+            Ok(Binding::Macro(item)) => Ok(item),
+            Err(Failure::Private) => Err(Some(format!("macro `{}` is private", last))),
+            _ if names.len() == 1 => Err(None),
+            _ => Err(Some(format!("macro undefined: '{}!'", path))),
+        }
+    }
+
+    /// The public macros that other crates can name, by the modules that
+    /// they can be named from: those that can be reached from the crate root
+    /// through public modules. Macros that are imported publicly, including
+    /// by globs and from other crates, are exported as well.
+    fn exports(&self) -> Vec<ast::MacroDef> {
+        let mut exports = vec![];
+        let mut visited = HashSet::new();
+        let mut queue = vec![(vec![], ModulePath::local(vec![]))];
+        while let Some((reach_path, module)) = queue.pop() {
+            if !visited.insert(module.clone()) {
+                continue;
+            }
+
+            let mut names = HashSet::new();
+            self.public_names(&module, Namespace::Macro, 0, &mut names);
+            for name in names {
+                if let Some(Binding::Macro(item)) =
+                        self.lookup_public(&module, name, Namespace::Macro, 0) {
+                    exports.push(export(&reach_path, name, &item));
+                }
+            }
+
+            let mut names = HashSet::new();
+            self.public_names(&module, Namespace::Module, 0, &mut names);
+            for name in names {
+                if let Some(Binding::Module(child)) =
+                        self.lookup_public(&module, name, Namespace::Module, 0) {
+                    if child.krate.is_none() {
+                        let mut child_path = reach_path.clone();
+                        child_path.push(name);
+                        queue.push((child_path, child));
+                    }
+                }
+            }
+        }
+
+        exports.sort_by_key(|def| {
+            let module = def.decl.as_ref().map_or(vec![], |decl| {
+                decl.module.iter().map(|name| name.as_str()).collect()
+            });
+            (module, def.ident.name.as_str())
+        });
+        exports
+    }
+}
+
+/// The names of the path of an import of `module`, starting at the crate
+/// root.
+fn import_path(module: &ModulePath, path: &ast::Path) -> Vec<Name> {
+    let names = path.segments.iter().map(|segment| segment.identifier.name).collect::<Vec<_>>();
+    if path.global || names.is_empty() {
+        return names;
+    }
+
+    let mut base = module.path.clone();
+    let mut skip = 0;
+    if names[0] == keywords::SelfValue.name() {
+        skip = 1;
+    } else if names[0] == keywords::Super.name() {
+        while skip < names.len() && names[skip] == keywords::Super.name() {
+            base.pop();
+            skip += 1;
+        }
+    } else {
+        return names;
+    }
+    base.extend_from_slice(&names[skip..]);
+    base
+}
+
+/// The definition of a macro for export, by the name and module that other
+/// crates name it with.
+fn export(module: &[Name], name: Name, item: &MacroItem) -> ast::MacroDef {
+    ast::MacroDef {
+        ident: ast::Ident::with_empty_ctxt(name),
+        export: true,
+        decl: Some(DeclMacro {
+            krate: None,
+            module: module.to_vec(),
+            def_site_crate: item.def_site.krate,
+            def_site: item.def_site.path.clone(),
+        }),
+        imported_from: None,
+        ..item.def.clone()
+    }
+}
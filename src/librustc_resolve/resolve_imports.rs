@@ -527,6 +527,25 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
         match (&value_result, &type_result) {
             (&Indeterminate, _) | (_, &Indeterminate) => return Indeterminate,
             (&Failed(_), &Failed(_)) => {
+                // The import may name the macro of a `macro` item, which
+                // only the expansion of the crate uses.
+                let paths = (self.resolver.module_path(target_module),
+                             self.resolver.module_path(module_));
+                if let (Some(target_path), Some(current_path)) = paths {
+                    match self.resolver.macros.names_macro(&target_path, source, &current_path) {
+                        Some(true) => {
+                            self.resolver.used_imports.insert((directive.id, TypeNS));
+                            return Success(());
+                        }
+                        Some(false) => {
+                            let msg = format!("macro `{}` is private", source);
+                            self.resolver.session.span_err(directive.span, &msg);
+                            return Success(());
+                        }
+                        None => {}
+                    }
+                }
+
                 let resolutions = target_module.resolutions.borrow();
                 let names = resolutions.iter().filter_map(|(&(ref name, _), resolution)| {
                     if *name == source { return None; } // Never suggest the same name
@@ -15,7 +15,7 @@ use super::rpath;
 use super::msvc;
 use session::config;
 use session::config::NoDebugInfo;
-use session::config::{OutputFilenames, Input, OutputType};
+use session::config::{OutputFilenames, Input, OutputType, PrintRequest};
 use session::filesearch;
 use session::search_paths::PathKind;
 use session::Session;
@@ -237,6 +237,7 @@ pub fn invalid_output_for_target(sess: &Session,
     match (sess.target.target.options.dynamic_linking,
            sess.target.target.options.executables, crate_type) {
         (false, _, config::CrateTypeCdylib) |
+        (false, _, config::CrateTypeProcMacro) |
         (false, _, config::CrateTypeDylib) => true,
         (_, false, config::CrateTypeExecutable) => true,
         _ => false
@@ -260,6 +261,7 @@ pub fn filename_for_input(sess: &Session,
             outputs.out_directory.join(&format!("lib{}.rlib", libname))
         }
         config::CrateTypeCdylib |
+        config::CrateTypeProcMacro |
         config::CrateTypeDylib => {
             let (prefix, suffix) = (&sess.target.target.options.dll_prefix,
                                     &sess.target.target.options.dll_suffix);
@@ -602,6 +604,11 @@ fn link_staticlib(sess: &Session, objects: &[PathBuf], out_filename: &Path,
                                  platforms, and so may need to be preserved");
     }
 
+    if sess.opts.prints.contains(&PrintRequest::NativeStaticLibs) {
+        print_native_static_libs(sess, &all_native_libs);
+        return;
+    }
+
     for &(kind, ref lib) in &all_native_libs {
         let name = match kind {
             NativeLibraryKind::NativeStatic => "static library",
@@ -612,6 +619,26 @@ fn link_staticlib(sess: &Session, objects: &[PathBuf], out_filename: &Path,
     }
 }
 
+// For `--print native-static-libs`: the native libraries as a single line of
+// arguments for the linker of the target. Static libraries are left out as
+// they have been bundled into the archive.
+fn print_native_static_libs(sess: &Session, all_native_libs: &[(NativeLibraryKind, String)]) {
+    let lib_args: Vec<_> = all_native_libs.iter().filter_map(|&(kind, ref lib)| {
+        match kind {
+            NativeLibraryKind::NativeStatic => None,
+            NativeLibraryKind::NativeUnknown => {
+                if sess.target.target.options.is_like_msvc {
+                    Some(format!("{}.lib", lib))
+                } else {
+                    Some(format!("-l{}", lib))
+                }
+            }
+            NativeLibraryKind::NativeFramework => Some(format!("-framework {}", lib)),
+        }
+    }).collect();
+    sess.note_without_error(&format!("native-static-libs: {}", lib_args.join(" ")));
+}
+
 // Create a dynamic library or executable
 //
 // This will invoke the system linker/cc to create the resulting file. This
@@ -741,14 +768,16 @@ fn link_args(cmd: &mut Linker,
     // When linking a dynamic library, we put the metadata into a section of the
     // executable. This metadata is in a separate object file from the main
     // object file, so we link that in here.
-    if crate_type == config::CrateTypeDylib {
+    if crate_type == config::CrateTypeDylib ||
+       crate_type == config::CrateTypeProcMacro {
         cmd.add_object(&outputs.with_extension("metadata.o"));
     }
 
     // Try to strip as much out of the generated object by removing unused
     // sections if possible. See more comments in linker.rs
     if !sess.opts.cg.link_dead_code {
-        let keep_metadata = crate_type == config::CrateTypeDylib;
+        let keep_metadata = crate_type == config::CrateTypeDylib ||
+                            crate_type == config::CrateTypeProcMacro;
         cmd.gc_sections(keep_metadata);
     }
 
@@ -29,7 +29,8 @@ use syntax::ast;
 /// need out of the shared crate context before we get rid of it.
 pub struct LinkerInfo {
     dylib_exports: Vec<String>,
-    cdylib_exports: Vec<String>
+    cdylib_exports: Vec<String>,
+    proc_macro_exports: Vec<String>,
 }
 
 impl<'a, 'tcx> LinkerInfo {
@@ -37,7 +38,9 @@ impl<'a, 'tcx> LinkerInfo {
                reachable: &[String]) -> LinkerInfo {
         LinkerInfo {
             dylib_exports: exported_symbols(scx, reachable, CrateType::CrateTypeDylib),
-            cdylib_exports: exported_symbols(scx, reachable, CrateType::CrateTypeCdylib)
+            cdylib_exports: exported_symbols(scx, reachable, CrateType::CrateTypeCdylib),
+            proc_macro_exports: exported_symbols(scx, reachable,
+                                                 CrateType::CrateTypeProcMacro),
         }
     }
 
@@ -243,7 +246,11 @@ impl<'a> Linker for GnuLinker<'a> {
         // exported symbols to ensure we don't expose any more. The object files
         // have far more public symbols than we actually want to export, so we
         // hide them all here.
-        if crate_type == CrateType::CrateTypeDylib {
+        //
+        // Procedural macro crates are loaded like dylibs by the compiler, so
+        // they're treated the same way.
+        if crate_type == CrateType::CrateTypeDylib ||
+           crate_type == CrateType::CrateTypeProcMacro {
             return
         }
 
@@ -407,10 +414,10 @@ impl<'a> Linker for MsvcLinker<'a> {
             // straight to exports.
             writeln!(f, "LIBRARY")?;
             writeln!(f, "EXPORTS")?;
-            let symbols = if crate_type == CrateType::CrateTypeCdylib {
-                &self.info.cdylib_exports
-            } else {
-                &self.info.dylib_exports
+            let symbols = match crate_type {
+                CrateType::CrateTypeCdylib => &self.info.cdylib_exports,
+                CrateType::CrateTypeProcMacro => &self.info.proc_macro_exports,
+                _ => &self.info.dylib_exports,
             };
             for symbol in symbols {
                 writeln!(f, "  {}", symbol)?;
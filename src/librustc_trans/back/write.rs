@@ -19,6 +19,7 @@ use llvm::SMDiagnosticRef;
 use {CrateTranslation, ModuleTranslation};
 use util::common::time;
 use util::common::path2cstr;
+use util::profiling;
 use syntax::codemap::MultiSpan;
 use syntax::errors::{self, Handler, Level, RenderSpan};
 use syntax::errors::emitter::CoreEmitter;
@@ -501,10 +502,20 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         cgcx.handler.abort_if_errors();
 
         // Finally, run the actual optimization passes
-        time(config.time_passes, &format!("llvm function passes [{}]", cgcx.worker), ||
-             llvm::LLVMRustRunFunctionPassManager(fpm, llmod));
-        time(config.time_passes, &format!("llvm module passes [{}]", cgcx.worker), ||
-             llvm::LLVMRunPassManager(mpm, llmod));
+        {
+            let _activity = profiling::start_activity("llvm", || {
+                format!("function passes [{}]", name_extra)
+            });
+            time(config.time_passes, &format!("llvm function passes [{}]", cgcx.worker), ||
+                 llvm::LLVMRustRunFunctionPassManager(fpm, llmod));
+        }
+        {
+            let _activity = profiling::start_activity("llvm", || {
+                format!("module passes [{}]", name_extra)
+            });
+            time(config.time_passes, &format!("llvm module passes [{}]", cgcx.worker), ||
+                 llvm::LLVMRunPassManager(mpm, llmod));
+        }
 
         // Deallocate managers that we're now done with
         llvm::LLVMDisposePassManager(fpm);
@@ -564,6 +575,9 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         llvm::LLVMWriteBitcodeToFile(llmod, bc_out_c.as_ptr());
     }
 
+    let activity = profiling::start_activity("llvm", || {
+        format!("codegen passes [{}]", name_extra)
+    });
     time(config.time_passes, &format!("codegen passes [{}]", cgcx.worker), || {
         if config.emit_ir {
             let ext = format!("{}.ll", name_extra);
@@ -601,6 +615,7 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
             });
         }
     });
+    drop(activity);
 
     if copy_bc_to_obj {
         debug!("copying bitcode {:?} to obj {:?}", bc_out, obj_out);
@@ -916,6 +931,7 @@ fn run_work_multithreaded(sess: &Session,
         let diag_emitter = diag_emitter.clone();
         let plugin_passes = sess.plugin_llvm_passes.borrow().clone();
         let remark = sess.opts.cg.remark.clone();
+        let profiler = profiling::current();
 
         let (tx, rx) = channel();
         let mut tx = Some(tx);
@@ -923,6 +939,7 @@ fn run_work_multithreaded(sess: &Session,
 
         thread::Builder::new().name(format!("codegen-{}", i)).spawn(move || {
             let diag_handler = Handler::with_emitter(true, false, box diag_emitter);
+            profiling::install(profiler);
 
             // Must construct cgcx inside the proc because it has non-Send
             // fields.
@@ -60,6 +60,7 @@ use callee::{Callee, CallArgs, ArgExprs, ArgVals};
 use cleanup::{self, CleanupMethods, DropHint};
 use closure;
 use common::{Block, C_bool, C_bytes_in_context, C_i32, C_int, C_uint, C_integral};
+use common::C_big_integral;
 use collector::{self, TransItemState, TransItemCollectionMode};
 use common::{C_null, C_struct_in_context, C_u64, C_u8, C_undef};
 use common::{CrateContext, DropFlagHintsMap, Field, FunctionContext};
@@ -82,12 +83,14 @@ use meth;
 use mir;
 use monomorphize::{self, Instance};
 use partitioning::{self, PartitioningStrategy, CodegenUnit};
+use size_lints;
 use symbol_names_test;
 use trans_item::TransItem;
 use tvec;
 use type_::Type;
 use type_of;
 use type_of::*;
+use type_sizes;
 use value::Value;
 use Disr;
 use util::common::indenter;
@@ -101,6 +104,7 @@ use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 use std::str;
 use std::{i8, i16, i32, i64};
+use rustc_i128::{i128, u128};
 use syntax::codemap::{Span, DUMMY_SP};
 use syntax::parse::token::InternedString;
 use syntax::attr::AttrMetaMethods;
@@ -744,17 +748,18 @@ fn cast_shift_rhs<F, G>(op: hir::BinOp_,
 
 pub fn llty_and_min_for_signed_ty<'blk, 'tcx>(cx: Block<'blk, 'tcx>,
                                               val_t: Ty<'tcx>)
-                                              -> (Type, u64) {
+                                              -> (Type, u128) {
     match val_t.sty {
         ty::TyInt(t) => {
             let llty = Type::int_from_ty(cx.ccx(), t);
             let min = match t {
-                ast::IntTy::Is if llty == Type::i32(cx.ccx()) => i32::MIN as u64,
-                ast::IntTy::Is => i64::MIN as u64,
-                ast::IntTy::I8 => i8::MIN as u64,
-                ast::IntTy::I16 => i16::MIN as u64,
-                ast::IntTy::I32 => i32::MIN as u64,
-                ast::IntTy::I64 => i64::MIN as u64,
+                ast::IntTy::Is if llty == Type::i32(cx.ccx()) => i32::MIN as u128,
+                ast::IntTy::Is => i64::MIN as u128,
+                ast::IntTy::I8 => i8::MIN as u128,
+                ast::IntTy::I16 => i16::MIN as u128,
+                ast::IntTy::I32 => i32::MIN as u128,
+                ast::IntTy::I64 => i64::MIN as u128,
+                ast::IntTy::I128 => i128::min_value() as u128,
             };
             (llty, min)
         }
@@ -825,7 +830,7 @@ pub fn fail_if_zero_or_overflows<'blk, 'tcx>(cx: Block<'blk, 'tcx>,
             let is_min = ICmp(bcx,
                               llvm::IntEQ,
                               lhs,
-                              C_integral(llty, min, true),
+                              C_big_integral(llty, min, true),
                               debug_loc);
             with_cond(bcx, is_min, |bcx| {
                 controlflow::trans_fail(bcx, call_info, InternedString::new(overflow_text))
@@ -1042,7 +1047,7 @@ pub fn with_cond<'blk, 'tcx, F>(bcx: Block<'blk, 'tcx>, val: ValueRef, f: F) ->
     next_cx
 }
 
-enum Lifetime { Start, End }
+pub enum Lifetime { Start, End }
 
 // If LLVM lifetime intrinsic support is enabled (i.e. optimizations
 // on), and `ptr` is nonzero-sized, then extracts the size of `ptr`
@@ -1968,7 +1973,7 @@ pub fn trans_named_tuple_constructor<'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
 
     let sig = ccx.tcx().erase_late_bound_regions(&ctor_ty.fn_sig());
     let sig = ccx.tcx().normalize_associated_type(&sig);
-    let result_ty = sig.output.unwrap();
+    let result_ty = sig.output;
 
     // Get location to store the result. If the user does not care about
     // the result, just make a stack slot
@@ -2050,7 +2055,7 @@ pub fn trans_ctor_shim<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     if !fcx.fn_ty.ret.is_ignore() {
         let dest = fcx.get_ret_slot(bcx, "eret_slot");
         let dest_val = adt::MaybeSizedValue::sized(dest); // Can return unsized value
-        let repr = adt::represent_type(ccx, sig.output.unwrap());
+        let repr = adt::represent_type(ccx, sig.output);
         let mut llarg_idx = fcx.fn_ty.ret.is_indirect() as usize;
         let mut arg_idx = 0;
         for (i, arg_ty) in sig.inputs.into_iter().enumerate() {
@@ -2786,6 +2791,9 @@ pub fn trans_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             }
         }
     }
+    if shared_ccx.sess().print_type_sizes() {
+        type_sizes::print_type_sizes(shared_ccx.tcx());
+    }
     if shared_ccx.sess().count_llvm_insns() {
         for (k, v) in shared_ccx.stats().llvm_insns.borrow().iter() {
             println!("{:7} {}", *v, *k);
@@ -2801,7 +2809,8 @@ pub fn trans_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         reachable_symbols.push("main".to_string());
     }
 
-    if sess.crate_types.borrow().contains(&config::CrateTypeDylib) {
+    if sess.crate_types.borrow().contains(&config::CrateTypeDylib) ||
+       sess.crate_types.borrow().contains(&config::CrateTypeProcMacro) {
         reachable_symbols.push(shared_ccx.metadata_symbol_name());
     }
 
@@ -2938,6 +2947,11 @@ fn collect_and_partition_translation_items<'a, 'tcx>(scx: &SharedCrateContext<'a
         collector::collect_crate_translation_items(&scx, collection_mode)
     });
 
+    // Checked here rather than as each function is translated, which may
+    // happen in several codegen units, or not at all for the codegen units
+    // that incremental compilation reuses.
+    size_lints::check_items(scx, &items);
+
     let strategy = if scx.sess().opts.debugging_opts.incremental.is_some() {
         PartitioningStrategy::PerModule
     } else {
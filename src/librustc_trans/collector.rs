@@ -736,6 +736,7 @@ fn find_drop_glue_neighbors<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>,
     match ty.sty {
         ty::TyBool      |
         ty::TyChar      |
+        ty::TyNever     |
         ty::TyInt(_)    |
         ty::TyUint(_)   |
         ty::TyStr       |
@@ -747,6 +748,9 @@ fn find_drop_glue_neighbors<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>,
         ty::TyTrait(_)  => {
             /* nothing to do */
         }
+        ty::TyStruct(ref adt_def, _) if adt_def.is_union() => {
+            /* union fields are never dropped */
+        }
         ty::TyStruct(ref adt_def, substs) |
         ty::TyEnum(ref adt_def, substs) => {
             for field in adt_def.all_fields() {
@@ -786,6 +790,7 @@ fn find_drop_glue_neighbors<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>,
         }
         ty::TyProjection(_) |
         ty::TyParam(_)      |
+        ty::TyAnon(..)      |
         ty::TyInfer(_)      |
         ty::TyError         => {
             bug!("encountered unexpected type");
@@ -1084,7 +1089,8 @@ impl<'b, 'a, 'v> hir_visit::Visitor<'v> for RootCollector<'b, 'a, 'v> {
             }
 
             hir::ItemEnum(_, ref generics)        |
-            hir::ItemStruct(_, ref generics)      => {
+            hir::ItemStruct(_, ref generics)      |
+            hir::ItemUnion(_, ref generics)       => {
                 if !generics.is_parameterized() {
                     let ty = {
                         let tables = self.scx.tcx().tables.borrow();
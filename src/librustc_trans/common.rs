@@ -46,6 +46,8 @@ use util::nodemap::NodeMap;
 
 use arena::TypedArena;
 use libc::{c_uint, c_char};
+use rustc_i128::u128;
+use std::mem;
 use std::ops::Deref;
 use std::ffi::CString;
 use std::cell::{Cell, RefCell};
@@ -501,7 +503,7 @@ impl<'a, 'tcx> FunctionContext<'a, 'tcx> {
             abi: Abi::C,
             sig: ty::Binder(ty::FnSig {
                 inputs: vec![tcx.mk_mut_ptr(tcx.types.u8)],
-                output: ty::FnDiverging,
+                output: tcx.types.never,
                 variadic: false
             }),
         }));
@@ -813,6 +815,21 @@ pub fn C_integral(t: Type, u: u64, sign_extend: bool) -> ValueRef {
     }
 }
 
+/// Like `C_integral`, but for values that may not fit in 64 bits. `u` must
+/// already be sign- or zero-extended to 128 bits.
+pub fn C_big_integral(t: Type, u: u128, sign_extend: bool) -> ValueRef {
+    if mem::size_of::<u128>() == 16 {
+        // Shift in two steps so this still compiles when `u128` is
+        // just an alias for `u64` during bootstrap.
+        let words = [u as u64, (u >> 32 >> 32) as u64];
+        unsafe {
+            llvm::LLVMConstIntOfArbitraryPrecision(t.to_ref(), 2, words.as_ptr())
+        }
+    } else {
+        C_integral(t, u as u64, sign_extend)
+    }
+}
+
 pub fn C_floating(s: &str, t: Type) -> ValueRef {
     unsafe {
         let s = CString::new(s).unwrap();
@@ -1168,8 +1185,8 @@ pub fn inlined_variant_def<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
            inlined_vid);
     let adt_def = match ctor_ty.sty {
         ty::TyFnDef(_, _, &ty::BareFnTy { sig: ty::Binder(ty::FnSig {
-            output: ty::FnConverging(ty), ..
-        }), ..}) => ty,
+            output, ..
+        }), ..}) => output,
         _ => ctor_ty
     }.ty_adt_def().unwrap();
     let inlined_vid_def_id = ccx.tcx().map.local_def_id(inlined_vid);
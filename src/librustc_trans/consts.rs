@@ -24,7 +24,8 @@ use callee::Callee;
 use collector;
 use trans_item::TransItem;
 use common::{type_is_sized, C_nil, const_get_elt};
-use common::{CrateContext, C_integral, C_floating, C_bool, C_str_slice, C_bytes, val_ty};
+use common::{CrateContext, C_integral, C_big_integral, C_floating, C_bool, C_str_slice};
+use common::{C_bytes, val_ty};
 use common::{C_struct, C_undef, const_to_opt_int, const_to_opt_uint, VariantInfo, C_uint};
 use common::{type_is_fat_ptr, Field, C_vector, C_array, C_null};
 use datum::{Datum, Lvalue};
@@ -35,7 +36,7 @@ use type_of;
 use value::Value;
 use Disr;
 use rustc::ty::subst::Substs;
-use rustc::ty::adjustment::{AdjustDerefRef, AdjustReifyFnPointer};
+use rustc::ty::adjustment::{AdjustNeverToAny, AdjustDerefRef, AdjustReifyFnPointer};
 use rustc::ty::adjustment::{AdjustUnsafeFnPointer, AdjustMutToConstPointer};
 use rustc::ty::{self, Ty, TyCtxt};
 use rustc::ty::cast::{CastTy,IntTy};
@@ -63,19 +64,19 @@ pub fn const_lit(cx: &CrateContext, e: &hir::Expr, lit: &ast::Lit)
         LitKind::Byte(b) => C_integral(Type::uint_from_ty(cx, ast::UintTy::U8), b as u64, false),
         LitKind::Char(i) => C_integral(Type::char(cx), i as u64, false),
         LitKind::Int(i, ast::LitIntType::Signed(t)) => {
-            C_integral(Type::int_from_ty(cx, t), i, true)
+            C_big_integral(Type::int_from_ty(cx, t), i, false)
         }
         LitKind::Int(u, ast::LitIntType::Unsigned(t)) => {
-            C_integral(Type::uint_from_ty(cx, t), u, false)
+            C_big_integral(Type::uint_from_ty(cx, t), u, false)
         }
         LitKind::Int(i, ast::LitIntType::Unsuffixed) => {
             let lit_int_ty = cx.tcx().node_id_to_type(e.id);
             match lit_int_ty.sty {
                 ty::TyInt(t) => {
-                    C_integral(Type::int_from_ty(cx, t), i as u64, true)
+                    C_big_integral(Type::int_from_ty(cx, t), i, false)
                 }
                 ty::TyUint(t) => {
-                    C_integral(Type::uint_from_ty(cx, t), i as u64, false)
+                    C_big_integral(Type::uint_from_ty(cx, t), i, false)
                 }
                 _ => span_bug!(lit.span,
                         "integer literal has type {:?} (expected int \
@@ -353,6 +354,9 @@ pub fn const_expr<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                                                             &cx.tcx().expr_ty_adjusted(e));
     let opt_adj = cx.tcx().tables.borrow().adjustments.get(&e.id).cloned();
     match opt_adj {
+        Some(AdjustNeverToAny(..)) => {
+            span_bug!(e.span, "const expression of type ! encountered");
+        }
         Some(AdjustReifyFnPointer) => {
             match ety.sty {
                 ty::TyFnDef(def_id, substs, _) => {
@@ -476,6 +480,9 @@ fn check_unary_expr_validity(cx: &CrateContext, e: &hir::Expr, t: Ty,
 
 pub fn to_const_int(value: ValueRef, t: Ty, tcx: TyCtxt) -> Option<ConstInt> {
     match t.sty {
+        // LLVM offers no way to read back constants wider than 64 bits, so
+        // 128-bit operations are not checked at compile time here.
+        ty::TyInt(ast::IntTy::I128) | ty::TyUint(ast::UintTy::U128) => None,
         ty::TyInt(int_type) => const_to_opt_int(value).and_then(|input| match int_type {
             ast::IntTy::I8 => {
                 assert_eq!(input as i8 as i64, input);
@@ -496,6 +503,7 @@ pub fn to_const_int(value: ValueRef, t: Ty, tcx: TyCtxt) -> Option<ConstInt> {
                 ConstIsize::new(input, tcx.sess.target.int_type)
                     .ok().map(ConstInt::Isize)
             },
+            ast::IntTy::I128 => bug!("to_const_int: i128 is handled above"),
         }),
         ty::TyUint(uint_type) => const_to_opt_uint(value).and_then(|input| match uint_type {
             ast::UintTy::U8 => {
@@ -517,6 +525,7 @@ pub fn to_const_int(value: ValueRef, t: Ty, tcx: TyCtxt) -> Option<ConstInt> {
                 ConstUsize::new(input, tcx.sess.target.uint_type)
                     .ok().map(ConstInt::Usize)
             },
+            ast::UintTy::U128 => bug!("to_const_int: u128 is handled above"),
         }),
         _ => None,
     }
@@ -834,6 +843,11 @@ fn const_expr_unadjusted<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
             };
 
             let VariantInfo { discr, fields } = VariantInfo::of_node(cx.tcx(), ety, e.id);
+            if ety.is_union() {
+                // Only the one field named in a union expression is stored.
+                let (v, _) = const_expr(cx, &fs[0].expr, param_substs, fn_args, trueconst)?;
+                return Ok(adt::trans_const(cx, &repr, discr, &[v]));
+            }
             let cs = fields.iter().enumerate().map(|(ix, &Field(f_name, _))| {
                 match (fs.iter().find(|f| f_name == f.name.node), base_val) {
                     (Some(ref f), _) => {
@@ -1141,7 +1155,9 @@ pub fn trans_static(ccx: &CrateContext,
         let datum = get_static(ccx, def_id);
 
         let check_attrs = |attrs: &[ast::Attribute]| {
-            let default_to_mir = ccx.sess().opts.debugging_opts.orbit;
+            // The MIR interpreter needs the static's MIR.
+            let default_to_mir = ccx.sess().opts.debugging_opts.orbit ||
+                                 ccx.sess().opts.debugging_opts.miri;
             let invert = if default_to_mir { "rustc_no_mir" } else { "rustc_mir" };
             default_to_mir ^ attrs.iter().any(|item| item.check_name(invert))
         };
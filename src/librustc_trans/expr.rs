@@ -69,7 +69,7 @@ use tvec;
 use type_of;
 use value::Value;
 use Disr;
-use rustc::ty::adjustment::{AdjustDerefRef, AdjustReifyFnPointer};
+use rustc::ty::adjustment::{AdjustNeverToAny, AdjustDerefRef, AdjustReifyFnPointer};
 use rustc::ty::adjustment::{AdjustUnsafeFnPointer, AdjustMutToConstPointer};
 use rustc::ty::adjustment::CustomCoerceUnsized;
 use rustc::ty::{self, Ty, TyCtxt};
@@ -347,6 +347,7 @@ fn adjustment_required<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     }
 
     match adjustment {
+        AdjustNeverToAny(..) => true,
         AdjustReifyFnPointer => true,
         AdjustUnsafeFnPointer | AdjustMutToConstPointer => {
             // purely a type-level thing
@@ -379,6 +380,14 @@ fn apply_adjustments<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     debug!("unadjusted datum for expr {:?}: {:?} adjustment={:?}",
            expr, datum, adjustment);
     match adjustment {
+        AdjustNeverToAny(ref target) => {
+            // The source expression never produces a value, so any lvalue of
+            // the target type will do; nothing after this point is reachable.
+            let mono_target = bcx.monomorphize(target);
+            let llty = type_of::type_of(bcx.ccx(), mono_target);
+            let dummy = C_undef(llty.ptr_to());
+            datum = Datum::new(dummy, mono_target, Lvalue::new("never")).to_expr_datum();
+        }
         AdjustReifyFnPointer => {
             match datum.ty.sty {
                 ty::TyFnDef(def_id, substs, _) => {
@@ -795,7 +804,7 @@ fn trans_index<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             let ix_datum = unpack_datum!(bcx, trans(bcx, idx));
 
             let ref_ty = // invoked methods have LB regions instantiated:
-                bcx.tcx().no_late_bound_regions(&method_ty.fn_ret()).unwrap().unwrap();
+                bcx.tcx().no_late_bound_regions(&method_ty.fn_ret()).unwrap();
             let elt_ty = match ref_ty.builtin_deref(true, ty::NoPreference) {
                 None => {
                     span_bug!(index_expr.span,
@@ -971,7 +980,13 @@ fn trans_rvalue_stmt_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             let src_datum = unpack_datum!(bcx, trans(bcx, &src));
             let dst_datum = unpack_datum!(bcx, trans_to_lvalue(bcx, &dst, "assign"));
 
-            if bcx.fcx.type_needs_drop(dst_datum.ty) {
+            // Writing to a union field never drops the previous contents.
+            let is_union_field = match dst.node {
+                hir::ExprField(ref base, _) => bcx.tcx().expr_ty_adjusted(base).is_union(),
+                _ => false
+            };
+
+            if bcx.fcx.type_needs_drop(dst_datum.ty) && !is_union_field {
                 // If there are destructors involved, make sure we
                 // are copying from an rvalue, since that cannot possible
                 // alias an lvalue. We are concerned about code like:
@@ -1294,7 +1309,8 @@ fn trans_struct<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                  fields: leftovers })
         }
         None => {
-            if need_base.iter().any(|b| *b) {
+            // A union expression initializes exactly one of its fields.
+            if !ty.is_union() && need_base.iter().any(|b| *b) {
                 span_bug!(expr_span, "missing fields and no base expr")
             }
             None
@@ -1508,7 +1524,7 @@ fn trans_unary<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                     let bcx = if bcx.ccx().check_overflow() && is_signed {
                         let (llty, min) = base::llty_and_min_for_signed_ty(bcx, un_ty);
                         let is_min = ICmp(bcx, llvm::IntEQ, val,
-                                          C_integral(llty, min, true), debug_loc);
+                                          C_big_integral(llty, min, true), debug_loc);
                         with_cond(bcx, is_min, |bcx| {
                             let msg = InternedString::new(
                                 "attempted to negate with overflow");
@@ -2039,7 +2055,7 @@ fn deref_once<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             };
 
             let ref_ty = // invoked methods have their LB regions instantiated
-                ccx.tcx().no_late_bound_regions(&method_ty.fn_ret()).unwrap().unwrap();
+                ccx.tcx().no_late_bound_regions(&method_ty.fn_ret()).unwrap();
             let scratch = rvalue_scratch_datum(bcx, ref_ty, "overloaded_deref");
 
             bcx = Callee::method(bcx, method)
@@ -2175,11 +2191,13 @@ impl OverflowOpViaIntrinsic {
                 TyInt(I16) => "llvm.sadd.with.overflow.i16",
                 TyInt(I32) => "llvm.sadd.with.overflow.i32",
                 TyInt(I64) => "llvm.sadd.with.overflow.i64",
+                TyInt(I128) => "llvm.sadd.with.overflow.i128",
 
                 TyUint(U8) => "llvm.uadd.with.overflow.i8",
                 TyUint(U16) => "llvm.uadd.with.overflow.i16",
                 TyUint(U32) => "llvm.uadd.with.overflow.i32",
                 TyUint(U64) => "llvm.uadd.with.overflow.i64",
+                TyUint(U128) => "llvm.uadd.with.overflow.i128",
 
                 _ => bug!(),
             },
@@ -2188,11 +2206,13 @@ impl OverflowOpViaIntrinsic {
                 TyInt(I16) => "llvm.ssub.with.overflow.i16",
                 TyInt(I32) => "llvm.ssub.with.overflow.i32",
                 TyInt(I64) => "llvm.ssub.with.overflow.i64",
+                TyInt(I128) => "llvm.ssub.with.overflow.i128",
 
                 TyUint(U8) => "llvm.usub.with.overflow.i8",
                 TyUint(U16) => "llvm.usub.with.overflow.i16",
                 TyUint(U32) => "llvm.usub.with.overflow.i32",
                 TyUint(U64) => "llvm.usub.with.overflow.i64",
+                TyUint(U128) => "llvm.usub.with.overflow.i128",
 
                 _ => bug!(),
             },
@@ -2201,11 +2221,13 @@ impl OverflowOpViaIntrinsic {
                 TyInt(I16) => "llvm.smul.with.overflow.i16",
                 TyInt(I32) => "llvm.smul.with.overflow.i32",
                 TyInt(I64) => "llvm.smul.with.overflow.i64",
+                TyInt(I128) => "llvm.smul.with.overflow.i128",
 
                 TyUint(U8) => "llvm.umul.with.overflow.i8",
                 TyUint(U16) => "llvm.umul.with.overflow.i16",
                 TyUint(U32) => "llvm.umul.with.overflow.i32",
                 TyUint(U64) => "llvm.umul.with.overflow.i64",
+                TyUint(U128) => "llvm.umul.with.overflow.i128",
 
                 _ => bug!(),
             },
@@ -248,7 +248,7 @@ fn get_drop_glue_core<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     let tcx = ccx.tcx();
     let sig = ty::FnSig {
         inputs: vec![tcx.mk_mut_ptr(tcx.types.i8)],
-        output: ty::FnOutput::FnConverging(tcx.mk_nil()),
+        output: tcx.mk_nil(),
         variadic: false,
     };
     // Create a FnType for fn(*mut i8) and substitute the real type in
@@ -575,6 +575,10 @@ fn make_drop_glue<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, v0: ValueRef, g: DropGlueK
                 (ty::TraitDtor(false), false) => {
                     trans_struct_drop(bcx, t, v0)
                 }
+                (ty::NoDtor, _) | (_, true) if def.is_union() => {
+                    // The fields of a union are never dropped.
+                    bcx
+                }
                 (ty::NoDtor, _) | (_, true) => {
                     // No dtor? Just the default case
                     iter_structural_ty(bcx, v0, t, |bb, vv, tt| drop_ty(bb, vv, tt, DebugLoc::None))
@@ -53,6 +53,7 @@ extern crate rustc_platform_intrinsics as intrinsics;
 extern crate serialize;
 extern crate rustc_const_math;
 extern crate rustc_const_eval;
+extern crate rustc_i128;
 
 #[macro_use] extern crate log;
 #[macro_use] extern crate syntax;
@@ -122,11 +123,13 @@ mod meth;
 mod mir;
 mod monomorphize;
 mod partitioning;
+mod size_lints;
 mod symbol_names_test;
 mod trans_item;
 mod tvec;
 mod type_;
 mod type_of;
+mod type_sizes;
 mod value;
 
 #[derive(Copy, Clone)]
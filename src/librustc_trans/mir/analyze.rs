@@ -115,7 +115,9 @@ impl<'mir, 'bcx, 'tcx> Visitor<'tcx> for TempAnalyzer<'mir, 'bcx, 'tcx> {
                     LvalueContext::Call => {
                         self.mark_assigned(index as usize);
                     }
-                    LvalueContext::Consume => {
+                    LvalueContext::Consume |
+                    LvalueContext::StorageLive |
+                    LvalueContext::StorageDead => {
                     }
                     LvalueContext::Store |
                     LvalueContext::Drop |
@@ -322,7 +322,7 @@ impl<'bcx, 'tcx> MirContext<'bcx, 'tcx> {
                             // Make a fake operand for store_return
                             let op = OperandRef {
                                 val: OperandValue::Ref(dst),
-                                ty: sig.output.unwrap()
+                                ty: sig.output
                             };
                             self.store_return(&bcx, ret_dest, fn_ty.ret, op);
                         }
@@ -372,7 +372,7 @@ impl<'bcx, 'tcx> MirContext<'bcx, 'tcx> {
                             debug_loc.apply_to_bcx(ret_bcx);
                             let op = OperandRef {
                                 val: OperandValue::Immediate(invokeret),
-                                ty: sig.output.unwrap()
+                                ty: sig.output
                             };
                             self.store_return(&ret_bcx, ret_dest, fn_ty.ret, op);
                             for op in args {
@@ -386,7 +386,7 @@ impl<'bcx, 'tcx> MirContext<'bcx, 'tcx> {
                     if let Some((_, target)) = *destination {
                         let op = OperandRef {
                             val: OperandValue::Immediate(llret),
-                            ty: sig.output.unwrap()
+                            ty: sig.output
                         };
                         self.store_return(&bcx, ret_dest, fn_ty.ret, op);
                         for op in args {
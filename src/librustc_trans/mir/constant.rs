@@ -11,6 +11,7 @@
 use llvm::{self, ValueRef};
 use rustc::middle::const_val::ConstVal;
 use rustc_const_eval::ErrKind;
+use rustc_mir::interpret::{self, ValueKind};
 use rustc_const_math::ConstInt::*;
 use rustc::hir::def_id::DefId;
 use rustc::infer::TransNormalize;
@@ -23,7 +24,7 @@ use rustc::ty::subst::Substs;
 use {abi, adt, base, Disr};
 use callee::Callee;
 use common::{self, BlockAndBuilder, CrateContext, const_get_elt, val_ty};
-use common::{C_array, C_bool, C_bytes, C_floating_f64, C_integral};
+use common::{C_array, C_bool, C_bytes, C_floating_f64, C_integral, C_big_integral};
 use common::{C_null, C_struct, C_str_slice, C_undef, C_uint};
 use consts::{self, ConstEvalFailure, TrueConst, to_const_int};
 use monomorphize::{self, Instance};
@@ -33,6 +34,7 @@ use value::Value;
 
 use syntax::codemap::{Span, DUMMY_SP};
 
+use rustc_i128::u128;
 use std::ptr;
 
 use super::operand::{OperandRef, OperandValue};
@@ -68,6 +70,7 @@ impl<'tcx> Const<'tcx> {
             ConstVal::Integral(I16(v)) => C_integral(Type::i16(ccx), v as u64, true),
             ConstVal::Integral(I32(v)) => C_integral(Type::i32(ccx), v as u64, true),
             ConstVal::Integral(I64(v)) => C_integral(Type::i64(ccx), v as u64, true),
+            ConstVal::Integral(I128(v)) => C_big_integral(Type::i128(ccx), v as u128, true),
             ConstVal::Integral(Isize(v)) => {
                 let i = v.as_i64(ccx.tcx().sess.target.int_type);
                 C_integral(Type::int(ccx), i as u64, true)
@@ -76,12 +79,13 @@ impl<'tcx> Const<'tcx> {
             ConstVal::Integral(U16(v)) => C_integral(Type::i16(ccx), v as u64, false),
             ConstVal::Integral(U32(v)) => C_integral(Type::i32(ccx), v as u64, false),
             ConstVal::Integral(U64(v)) => C_integral(Type::i64(ccx), v, false),
+            ConstVal::Integral(U128(v)) => C_big_integral(Type::i128(ccx), v, false),
             ConstVal::Integral(Usize(v)) => {
                 let u = v.as_u64(ccx.tcx().sess.target.uint_type);
                 C_integral(Type::int(ccx), u, false)
             },
-            ConstVal::Integral(Infer(v)) => C_integral(llty, v as u64, false),
-            ConstVal::Integral(InferSigned(v)) => C_integral(llty, v as u64, true),
+            ConstVal::Integral(Infer(v)) => C_big_integral(llty, v, false),
+            ConstVal::Integral(InferSigned(v)) => C_big_integral(llty, v as u128, true),
             ConstVal::Str(ref v) => C_str_slice(ccx, v.clone()),
             ConstVal::ByteStr(ref v) => consts::addr_of(ccx, C_bytes(ccx, v), 1, "byte_str"),
             ConstVal::Struct(_) | ConstVal::Tuple(_) |
@@ -98,6 +102,50 @@ impl<'tcx> Const<'tcx> {
         Const::new(val, ty)
     }
 
+    /// Translate a value computed by the MIR interpreter.
+    fn from_miri_value<'a>(ccx: &CrateContext<'a, 'tcx>,
+                           value: interpret::Value<'tcx>)
+                           -> Const<'tcx> {
+        let ty = value.ty;
+        let llty = type_of::type_of(ccx, ty);
+        if common::type_is_zero_size(ccx, ty) {
+            return Const::new(C_null(llty), ty);
+        }
+
+        let val = match value.kind {
+            ValueKind::Bool(v) => C_bool(ccx, v),
+            ValueKind::Char(c) => C_integral(Type::char(ccx), c as u64, false),
+            ValueKind::Int(bits) => C_integral(llty, bits, false),
+            ValueKind::Float(v) => C_floating_f64(v, llty),
+            ValueKind::Aggregate { variant, fields } => {
+                let fields = fields.into_iter().map(|field| {
+                    Const::from_miri_value(ccx, field).llval
+                }).collect::<Vec<_>>();
+                match ty.sty {
+                    ty::TyStruct(adt_def, _) | ty::TyEnum(adt_def, _) => {
+                        let repr = adt::represent_type(ccx, ty);
+                        let disr = Disr::from(adt_def.variants[variant].disr_val);
+                        adt::trans_const(ccx, &repr, disr, &fields)
+                    }
+                    _ => C_struct(ccx, &fields, false)
+                }
+            }
+            ValueKind::Array(elems) => {
+                let elems = elems.into_iter().map(|elem| {
+                    Const::from_miri_value(ccx, elem).llval
+                }).collect::<Vec<_>>();
+                let llunitty = type_of::type_of(ccx, ty.builtin_index().unwrap());
+                // If the array contains enums, an LLVM array won't work.
+                if elems.iter().all(|&elem| val_ty(elem) == llunitty) {
+                    C_array(llunitty, &elems)
+                } else {
+                    C_struct(ccx, &elems, false)
+                }
+            }
+        };
+        Const::new(val, ty)
+    }
+
     fn get_fat_ptr(&self) -> (ValueRef, ValueRef) {
         (const_get_elt(self.llval, &[abi::FAT_PTR_ADDR as u32]),
          const_get_elt(self.llval, &[abi::FAT_PTR_EXTRA as u32]))
@@ -246,6 +294,22 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
             }
         }
 
+        if ccx.sess().opts.debugging_opts.miri && args.is_empty() {
+            match interpret::eval_item(ccx.tcx(), ccx.shared().mir_map(),
+                                       instance.def, instance.substs) {
+                Ok(value) => return Ok(Const::from_miri_value(ccx, value)),
+                Err(ref err) if err.kind.is_unimplemented() => {
+                    debug!("trans_def: falling back from miri for {}: {}",
+                           instance, err.kind);
+                }
+                Err(err) => {
+                    return consts::const_err(ccx, err.span,
+                                             Err(ErrKind::Miri(err.kind.to_string())),
+                                             TrueConst::Yes);
+                }
+            }
+        }
+
         let mir = ccx.get_mir(instance.def).unwrap_or_else(|| {
             bug!("missing constant MIR for {}", instance)
         });
@@ -273,6 +337,8 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
                         let value = self.const_rvalue(rvalue, ty, statement.span)?;
                         self.store(dest, value, statement.span);
                     }
+                    mir::StatementKind::StorageLive(_) |
+                    mir::StatementKind::StorageDead(_) => {}
                 }
             }
 
@@ -499,7 +565,7 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
                     closure::trans_closure_expr(closure::Dest::Ignore(self.ccx),
                                                 &hir::FnDecl {
                                                     inputs: P::new(),
-                                                    output: hir::NoReturn(DUMMY_SP),
+                                                    output: hir::DefaultReturn(DUMMY_SP),
                                                     variadic: false
                                                 },
                                                 &hir::Block {
@@ -118,8 +118,7 @@ impl<'bcx, 'tcx> MirContext<'bcx, 'tcx> {
                     // ReturnPointer LValue (i.e. store into it, load from it etc).
                     C_undef(fcx.fn_ty.ret.original_ty.ptr_to())
                 };
-                let fn_return_ty = bcx.monomorphize(&self.mir.return_ty);
-                let return_ty = fn_return_ty.unwrap();
+                let return_ty = bcx.monomorphize(&self.mir.return_ty);
                 LvalueRef::new_sized(llval, LvalueTy::from_ty(return_ty))
             },
             mir::Lvalue::Projection(ref projection) => {
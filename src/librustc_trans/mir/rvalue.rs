@@ -143,7 +143,7 @@ impl<'bcx, 'tcx> MirContext<'bcx, 'tcx> {
                             closure::trans_closure_expr(closure::Dest::Ignore(bcx.ccx()),
                                                         &hir::FnDecl {
                                                             inputs: P::new(),
-                                                            output: hir::NoReturn(DUMMY_SP),
+                                                            output: hir::DefaultReturn(DUMMY_SP),
                                                             variadic: false
                                                         },
                                                         &hir::Block {
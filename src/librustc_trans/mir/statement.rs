@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use rustc::mir::repr as mir;
+use base;
 use common::{self, BlockAndBuilder};
 use debuginfo::DebugLoc;
 
@@ -63,6 +64,35 @@ impl<'bcx, 'tcx> MirContext<'bcx, 'tcx> {
                     }
                 }
             }
+            mir::StatementKind::StorageLive(ref lvalue) => {
+                self.trans_storage_liveness(bcx, lvalue, base::Lifetime::Start)
+            }
+            mir::StatementKind::StorageDead(ref lvalue) => {
+                self.trans_storage_liveness(bcx, lvalue, base::Lifetime::End)
+            }
         }
     }
+
+    fn trans_storage_liveness(&self,
+                              bcx: BlockAndBuilder<'bcx, 'tcx>,
+                              lvalue: &mir::Lvalue<'tcx>,
+                              intrinsic: base::Lifetime)
+                              -> BlockAndBuilder<'bcx, 'tcx> {
+        let llval = match *lvalue {
+            mir::Lvalue::Var(index) => self.vars[index as usize].llval,
+            mir::Lvalue::Temp(index) => match self.temps[index as usize] {
+                TempRef::Lvalue(tr) => tr.llval,
+                // Operand temps live in SSA values, not on the stack.
+                TempRef::Operand(_) => return bcx,
+            },
+            _ => bug!("storage marker for {:?}, not a var or temp", lvalue),
+        };
+        bcx.with_block(|bcx| {
+            match intrinsic {
+                base::Lifetime::Start => base::call_lifetime_start(bcx, llval),
+                base::Lifetime::End => base::call_lifetime_end(bcx, llval),
+            }
+        });
+        bcx
+    }
 }
@@ -0,0 +1,172 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `large_stack_frames` and `large_move` lints.
+//!
+//! The sizes of the locals and operands of a generic function depend on
+//! how it is instantiated, so these lints check the MIR of each instance
+//! that the collector finds, against the limits set by the
+//! `#![stack_frame_size_limit]` and `#![move_size_limit]` attributes.
+
+use context::SharedCrateContext;
+use lint;
+use monomorphize::{self, Instance};
+use trans_item::TransItem;
+use util::nodemap::FnvHashSet;
+
+use rustc::mir::repr as mir;
+use rustc::mir::visit::Visitor;
+use rustc::traits::ProjectionMode;
+use rustc::ty::{Ty, TyCtxt};
+use syntax::codemap::{Span, DUMMY_SP};
+
+/// Checks each function instance among the translation items once.
+pub fn check_items<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>,
+                             items: &FnvHashSet<TransItem<'tcx>>) {
+    let tcx = scx.tcx();
+    if tcx.sess.stack_frame_size_limit.get().is_none() &&
+       tcx.sess.move_size_limit.get().is_none() {
+        return;
+    }
+
+    // Sorted, so that the lints are reported in the same order every time.
+    let mut instances: Vec<_> = items.iter().filter_map(|item| match *item {
+        TransItem::Fn(instance) => Some((instance.to_string(), instance)),
+        _ => None,
+    }).collect();
+    instances.sort_by(|&(ref a, _), &(ref b, _)| a.cmp(b));
+    for (_, instance) in instances {
+        check_instance(scx, instance);
+    }
+}
+
+fn check_instance<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>, instance: Instance<'tcx>) {
+    let tcx = scx.tcx();
+    let frame_limit = tcx.sess.stack_frame_size_limit.get();
+    let move_limit = tcx.sess.move_size_limit.get();
+
+    // Lint levels are only known for the functions of the local crate.
+    let id = match tcx.map.as_local_node_id(instance.def) {
+        Some(id) => id,
+        None => return,
+    };
+    let mir = match scx.get_mir(instance.def) {
+        Some(mir) => mir,
+        None => return,
+    };
+    let levels = tcx.node_lint_levels.borrow();
+    let level = |lint| levels.get(&(id, lint::LintId::of(lint))).cloned();
+
+    tcx.normalizing_infer_ctxt(ProjectionMode::Any).enter(|infcx| {
+        let size_of = |ty: Ty<'tcx>| {
+            let ty = monomorphize::apply_param_substs(tcx, instance.substs, &ty);
+            ty.layout(&infcx).ok().map(|layout| layout.size(&tcx.data_layout).bytes())
+        };
+
+        if let (Some(limit), Some(lvlsrc)) = (frame_limit,
+                                              level(lint::builtin::LARGE_STACK_FRAMES)) {
+            // Only the variables have spans to point at.
+            let locals = mir.arg_decls.iter().map(|decl| (decl.ty, None))
+                .chain(mir.var_decls.iter().map(|decl| (decl.ty, Some(decl.span))))
+                .chain(mir.temp_decls.iter().map(|decl| (decl.ty, None)));
+
+            let mut total = 0;
+            let mut largest = None;
+            for (ty, span) in locals {
+                let size = size_of(ty).unwrap_or(0);
+                total += size;
+                if let Some(span) = span {
+                    if largest.map_or(true, |(largest, _)| size > largest) {
+                        largest = Some((size, span));
+                    }
+                }
+            }
+
+            if total > limit {
+                let mut err = lint::raw_struct_lint(
+                    &tcx.sess,
+                    &tcx.sess.lint_store.borrow(),
+                    lint::builtin::LARGE_STACK_FRAMES,
+                    lvlsrc,
+                    Some(mir.span),
+                    &format!("the locals of `{}` take up {} bytes, more than the stack frame \
+                              size limit of {} bytes", instance, total, limit));
+                if let Some((size, span)) = largest {
+                    err.span_note(span, &format!("the largest variable takes up {} bytes", size));
+                }
+                err.emit();
+            }
+        }
+
+        if let (Some(limit), Some(lvlsrc)) = (move_limit, level(lint::builtin::LARGE_MOVE)) {
+            let mut checker = MoveChecker {
+                tcx: tcx,
+                mir: &mir,
+                size_of: &size_of,
+                limit: limit,
+                span: DUMMY_SP,
+                moves: vec![],
+            };
+            checker.visit_mir(&mir);
+
+            for (span, size) in checker.moves {
+                lint::raw_struct_lint(&tcx.sess,
+                                      &tcx.sess.lint_store.borrow(),
+                                      lint::builtin::LARGE_MOVE,
+                                      lvlsrc,
+                                      Some(span),
+                                      &format!("moving {} bytes in `{}`, more than the move \
+                                                size limit of {} bytes", size, instance, limit))
+                    .emit();
+            }
+        }
+    });
+}
+
+/// Collects the operands used by value whose size exceeds the limit. A
+/// value passed by value is usually moved into a temporary first, so moves
+/// within the span of an earlier one are not reported again.
+struct MoveChecker<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    mir: &'a mir::Mir<'tcx>,
+    size_of: &'a Fn(Ty<'tcx>) -> Option<u64>,
+    limit: u64,
+    /// The span of the statement or terminator being visited.
+    span: Span,
+    moves: Vec<(Span, u64)>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for MoveChecker<'a, 'tcx> {
+    fn visit_statement(&mut self, block: mir::BasicBlock, statement: &mir::Statement<'tcx>) {
+        self.span = statement.span;
+        self.super_statement(block, statement);
+    }
+
+    fn visit_terminator(&mut self, block: mir::BasicBlock, terminator: &mir::Terminator<'tcx>) {
+        self.span = terminator.span;
+        self.super_terminator(block, terminator);
+    }
+
+    fn visit_operand(&mut self, operand: &mir::Operand<'tcx>) {
+        if let mir::Operand::Consume(ref lvalue) = *operand {
+            let ty = self.mir.lvalue_ty(self.tcx, lvalue).to_ty(self.tcx);
+            if let Some(size) = (self.size_of)(ty) {
+                let span = self.span;
+                let reported = self.moves.iter().any(|&(s, _)| {
+                    s.contains(span) || span.contains(s)
+                });
+                if size > self.limit && !reported {
+                    self.moves.push((span, size));
+                }
+            }
+        }
+        self.super_operand(operand);
+    }
+}
@@ -75,16 +75,19 @@ pub fn push_unique_type_name<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         ty::TyBool              => output.push_str("bool"),
         ty::TyChar              => output.push_str("char"),
         ty::TyStr               => output.push_str("str"),
+        ty::TyNever             => output.push_str("!"),
         ty::TyInt(ast::IntTy::Is)    => output.push_str("isize"),
         ty::TyInt(ast::IntTy::I8)    => output.push_str("i8"),
         ty::TyInt(ast::IntTy::I16)   => output.push_str("i16"),
         ty::TyInt(ast::IntTy::I32)   => output.push_str("i32"),
         ty::TyInt(ast::IntTy::I64)   => output.push_str("i64"),
+        ty::TyInt(ast::IntTy::I128)  => output.push_str("i128"),
         ty::TyUint(ast::UintTy::Us)   => output.push_str("usize"),
         ty::TyUint(ast::UintTy::U8)   => output.push_str("u8"),
         ty::TyUint(ast::UintTy::U16)  => output.push_str("u16"),
         ty::TyUint(ast::UintTy::U32)  => output.push_str("u32"),
         ty::TyUint(ast::UintTy::U64)  => output.push_str("u64"),
+        ty::TyUint(ast::UintTy::U128) => output.push_str("u128"),
         ty::TyFloat(ast::FloatTy::F32) => output.push_str("f32"),
         ty::TyFloat(ast::FloatTy::F64) => output.push_str("f64"),
         ty::TyStruct(adt_def, substs) |
@@ -178,15 +181,9 @@ pub fn push_unique_type_name<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
             output.push(')');
 
-            match sig.output {
-                ty::FnConverging(result_type) if result_type.is_nil() => {}
-                ty::FnConverging(result_type) => {
-                    output.push_str(" -> ");
-                    push_unique_type_name(tcx, result_type, output);
-                }
-                ty::FnDiverging => {
-                    output.push_str(" -> !");
-                }
+            if !sig.output.is_nil() {
+                output.push_str(" -> ");
+                push_unique_type_name(tcx, sig.output, output);
             }
         },
         ty::TyClosure(def_id, ref closure_substs) => {
@@ -199,6 +196,7 @@ pub fn push_unique_type_name<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         ty::TyError |
         ty::TyInfer(_) |
         ty::TyProjection(..) |
+        ty::TyAnon(..) |
         ty::TyParam(_) => {
             bug!("debuginfo: Trying to create type name for \
                   unexpected type: {:?}", t);
@@ -0,0 +1,210 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `-Z print-type-sizes`: the size, alignment and field offsets of every
+//! struct, enum, tuple and closure type that was laid out during
+//! translation, i.e. of every monomorphized type that the crate uses by
+//! value, along with the padding between and after their fields.
+//!
+//! The layouts are those of `rustc::ty::layout`, which are cached in
+//! `tcx.layout_cache` as translation sizes the types.
+
+use rustc::infer::InferCtxt;
+use rustc::traits::ProjectionMode;
+use rustc::ty::layout::{Layout, Primitive, Struct};
+use rustc::ty::{self, Ty, TyCtxt, TypeFoldable};
+
+use std::cmp::Ordering;
+
+const PREFIX: &'static str = "print-type-size";
+
+pub fn print_type_sizes<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    // Computing the layouts of the fields adds to the cache, so it must
+    // not stay borrowed.
+    let types = tcx.layout_cache.borrow().iter().filter(|&(&ty, layout)| {
+        is_aggregate(ty) && !layout.is_unsized()
+    }).map(|(&ty, &layout)| (ty, layout)).collect::<Vec<_>>();
+
+    let mut reports = tcx.normalizing_infer_ctxt(ProjectionMode::Any).enter(|infcx| {
+        types.into_iter().filter_map(|(ty, layout)| {
+            let size = layout.size(&tcx.data_layout).bytes();
+            describe(&infcx, ty, layout).map(|lines| {
+                (size, ty.to_string(), layout.align(&tcx.data_layout).abi(), lines)
+            })
+        }).collect::<Vec<_>>()
+    });
+
+    // The largest types first, as those are the ones worth looking at.
+    reports.sort_by(|a, b| match b.0.cmp(&a.0) {
+        Ordering::Equal => a.1.cmp(&b.1),
+        ordering => ordering,
+    });
+
+    for (size, name, align, lines) in reports {
+        println!("{} type: `{}`: {} bytes, alignment: {} bytes", PREFIX, name, size, align);
+        for line in lines {
+            println!("{}     {}", PREFIX, line);
+        }
+    }
+}
+
+fn is_aggregate(ty: Ty) -> bool {
+    if ty.has_param_types() || ty.has_self_ty() || ty.has_projection_types() {
+        return false;
+    }
+    match ty.sty {
+        ty::TyStruct(..) => !ty.is_simd(),
+        ty::TyTuple(tys) => !tys.is_empty(),
+        ty::TyEnum(..) | ty::TyClosure(..) => true,
+        _ => false,
+    }
+}
+
+/// The lines describing the fields and variants of `ty`, or `None` if
+/// the layout of one of its fields is not known.
+fn describe<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx, 'tcx>,
+                      ty: Ty<'tcx>,
+                      layout: &Layout)
+                      -> Option<Vec<String>> {
+    let tcx = infcx.tcx;
+    let dl = &tcx.data_layout;
+    let size = layout.size(dl).bytes();
+
+    let mut lines = vec![];
+    match (layout, &ty.sty) {
+        (&Layout::Univariant { ref variant, .. }, &ty::TyEnum(def, substs)) => {
+            let fields = variant_fields(tcx, &def.variants[0], substs);
+            lines.push(format!("variant `{}`: {} bytes", def.variants[0].name,
+                               variant.min_size().bytes()));
+            let end = match struct_lines(infcx, variant, None, &fields, "    ", &mut lines) {
+                Some(end) => end,
+                None => return None,
+            };
+            end_padding(size, end, &mut lines);
+        }
+        (&Layout::Univariant { ref variant, .. }, _) => {
+            let fields = match ty.sty {
+                ty::TyStruct(def, substs) => variant_fields(tcx, def.struct_variant(), substs),
+                ty::TyClosure(_, ty::ClosureSubsts { upvar_tys: tys, .. }) |
+                ty::TyTuple(tys) => {
+                    tys.iter().enumerate().map(|(i, &ty)| (i.to_string(), ty)).collect()
+                }
+                _ => return None,
+            };
+            let end = match struct_lines(infcx, variant, None, &fields, "", &mut lines) {
+                Some(end) => end,
+                None => return None,
+            };
+            end_padding(size, end, &mut lines);
+        }
+        (&Layout::UntaggedUnion { .. }, &ty::TyStruct(def, substs)) => {
+            for (name, field_ty) in variant_fields(tcx, def.struct_variant(), substs) {
+                let field_size = match field_ty.layout(infcx) {
+                    Ok(layout) => layout.size(dl).bytes(),
+                    Err(_) => return None,
+                };
+                lines.push(format!("field `.{}`: {} bytes", name, field_size));
+            }
+        }
+        (&Layout::CEnum { discr, .. }, _) => {
+            lines.push(format!("discriminant: {} bytes", Primitive::Int(discr).size(dl).bytes()));
+        }
+        (&Layout::General { discr, ref variants, .. }, &ty::TyEnum(def, substs)) => {
+            let discr = Primitive::Int(discr).size(dl).bytes();
+            for (variant, st) in def.variants.iter().zip(variants) {
+                let fields = variant_fields(tcx, variant, substs);
+                lines.push(format!("variant `{}`: {} bytes", variant.name,
+                                   st.min_size().bytes()));
+                if struct_lines(infcx, st, Some(discr), &fields, "    ", &mut lines).is_none() {
+                    return None;
+                }
+            }
+        }
+        (&Layout::RawNullablePointer { nndiscr, value }, &ty::TyEnum(def, _)) => {
+            for (i, variant) in def.variants.iter().enumerate() {
+                let variant_size = if i as u64 == nndiscr { value.size(dl).bytes() } else { 0 };
+                lines.push(format!("variant `{}`: {} bytes", variant.name, variant_size));
+            }
+        }
+        (&Layout::StructWrappedNullablePointer { nndiscr, ref nonnull, .. },
+         &ty::TyEnum(def, substs)) => {
+            for (i, variant) in def.variants.iter().enumerate() {
+                if i as u64 != nndiscr {
+                    lines.push(format!("variant `{}`: 0 bytes", variant.name));
+                    continue;
+                }
+                let fields = variant_fields(tcx, variant, substs);
+                lines.push(format!("variant `{}`: {} bytes", variant.name,
+                                   nonnull.min_size().bytes()));
+                let end = match struct_lines(infcx, nonnull, None, &fields, "    ", &mut lines) {
+                    Some(end) => end,
+                    None => return None,
+                };
+                end_padding(size, end, &mut lines);
+            }
+        }
+        _ => {}
+    }
+    Some(lines)
+}
+
+fn variant_fields<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                            variant: &ty::VariantDefData<'tcx, 'static>,
+                            substs: &'tcx ty::subst::Substs<'tcx>)
+                            -> Vec<(String, Ty<'tcx>)> {
+    variant.fields.iter().map(|field| {
+        (field.name.to_string(), tcx.normalize_associated_type(&field.ty(tcx, substs)))
+    }).collect()
+}
+
+/// Adds the lines for the discriminant, fields and drop flag laid out in
+/// `st`, returning the offset after the last of them. The entries of `st`
+/// after the fields can only be the drop flag.
+fn struct_lines<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx, 'tcx>,
+                          st: &Struct,
+                          discr: Option<u64>,
+                          fields: &[(String, Ty<'tcx>)],
+                          indent: &str,
+                          lines: &mut Vec<String>)
+                          -> Option<u64> {
+    let dl = &infcx.tcx.data_layout;
+
+    let mut entries = vec![];
+    if let Some(discr) = discr {
+        entries.push(("discriminant".to_string(), discr));
+    }
+    for &(ref name, field_ty) in fields {
+        let field_size = match field_ty.layout(infcx) {
+            Ok(layout) => layout.size(dl).bytes(),
+            Err(_) => return None,
+        };
+        entries.push((format!("field `.{}`", name), field_size));
+    }
+    while entries.len() < st.offset_after_field.len() {
+        entries.push(("drop flag".to_string(), 1));
+    }
+
+    let mut end = 0;
+    for (i, (label, entry_size)) in entries.into_iter().enumerate() {
+        let offset = st.offset_of_field(i).bytes();
+        if offset > end {
+            lines.push(format!("{}padding: {} bytes", indent, offset - end));
+        }
+        lines.push(format!("{}{}: {} bytes, offset: {}", indent, label, entry_size, offset));
+        end = offset + entry_size;
+    }
+    Some(end)
+}
+
+fn end_padding(size: u64, end: u64, lines: &mut Vec<String>) {
+    if size > end {
+        lines.push(format!("end padding: {} bytes", size - end));
+    }
+}
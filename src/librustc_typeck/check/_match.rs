@@ -265,7 +265,8 @@ impl<'a, 'gcx, 'tcx> PatCtxt<'a, 'gcx, 'tcx> {
                 }
                 let max_len = cmp::max(expected_len, elements.len());
 
-                let element_tys: Vec<_> = (0 .. max_len).map(|_| self.next_ty_var()).collect();
+                let element_tys: Vec<_> = (0 .. max_len).map(|_| self.next_ty_var(pat.span))
+                                                          .collect();
                 let pat_ty = tcx.mk_tup(element_tys.clone());
                 self.write_ty(pat.id, pat_ty);
                 self.demand_eqtype(pat.span, expected, pat_ty);
@@ -274,7 +275,7 @@ impl<'a, 'gcx, 'tcx> PatCtxt<'a, 'gcx, 'tcx> {
                 }
             }
             PatKind::Box(ref inner) => {
-                let inner_ty = self.next_ty_var();
+                let inner_ty = self.next_ty_var(inner.span);
                 let uniq_ty = tcx.mk_box(inner_ty);
 
                 if self.check_dereferencable(pat.span, expected, &inner) {
@@ -305,7 +306,7 @@ impl<'a, 'gcx, 'tcx> PatCtxt<'a, 'gcx, 'tcx> {
                             (expected, mt.ty)
                         }
                         _ => {
-                            let inner_ty = self.next_ty_var();
+                            let inner_ty = self.next_ty_var(inner.span);
                             let mt = ty::TypeAndMut { ty: inner_ty, mutbl: mutbl };
                             let region = self.next_region_var(infer::PatternRegion(pat.span));
                             let rptr_ty = tcx.mk_ref(tcx.mk_region(region), mt);
@@ -323,7 +324,7 @@ impl<'a, 'gcx, 'tcx> PatCtxt<'a, 'gcx, 'tcx> {
             }
             PatKind::Vec(ref before, ref slice, ref after) => {
                 let expected_ty = self.structurally_resolved_type(pat.span, expected);
-                let inner_ty = self.next_ty_var();
+                let inner_ty = self.next_ty_var(pat.span);
                 let pat_ty = match expected_ty.sty {
                     ty::TyArray(_, size) => tcx.mk_array(inner_ty, {
                         let min_len = before.len() + after.len();
@@ -478,7 +479,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             // ...but otherwise we want to use any supertype of the
             // discriminant. This is sort of a workaround, see note (*) in
             // `check_pat` for some details.
-            discrim_ty = self.next_ty_var();
+            discrim_ty = self.next_ty_var(discrim.span);
             self.check_expr_has_type(discrim, discrim_ty);
         };
 
@@ -504,7 +505,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         // of execution reach it, we will panic, so bottom is an appropriate
         // type in that case)
         let expected = expected.adjust_for_branches(self);
-        let mut result_ty = self.next_diverging_ty_var();
+        let mut result_ty = self.next_diverging_ty_var(expr.span);
         let coerce_first = match expected {
             // We don't coerce to `()` so that if the match expression is a
             // statement it's branches can have any consistent type. That allows
@@ -598,12 +599,23 @@ impl<'a, 'gcx, 'tcx> PatCtxt<'a, 'gcx, 'tcx> {
         };
 
         let pat_ty = self.instantiate_type(def.def_id(), path);
-        let item_substs = match pat_ty.sty {
-            ty::TyStruct(_, substs) | ty::TyEnum(_, substs) => substs,
+        let (adt, item_substs) = match pat_ty.sty {
+            ty::TyStruct(adt, substs) | ty::TyEnum(adt, substs) => (adt, substs),
             _ => span_bug!(pat.span, "struct variant is not an ADT")
         };
         self.demand_eqtype(pat.span, expected, pat_ty);
-        self.check_struct_pat_fields(pat.span, fields, variant, &item_substs, etc);
+
+        // A union pattern matches on exactly one of its fields.
+        if adt.is_union() {
+            if fields.len() != 1 {
+                tcx.sess.span_err(pat.span, "union patterns should have exactly one field");
+            }
+            if etc {
+                tcx.sess.span_err(pat.span, "`..` cannot be used in union patterns");
+            }
+        }
+        self.check_struct_pat_fields(pat.span, fields, variant, &item_substs,
+                                     etc || adt.is_union());
 
         self.write_ty(pat.id, pat_ty);
         self.write_substs(pat.id, ty::ItemSubsts {
@@ -659,7 +671,7 @@ impl<'a, 'gcx, 'tcx> PatCtxt<'a, 'gcx, 'tcx> {
         let path_scheme = if ctor_scheme.ty.is_fn() {
             let fn_ret = tcx.no_late_bound_regions(&ctor_scheme.ty.fn_ret()).unwrap();
             ty::TypeScheme {
-                ty: fn_ret.unwrap(),
+                ty: fn_ret,
                 generics: ctor_scheme.generics,
             }
         } else {
@@ -62,7 +62,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         // types of upvars. These will be unified during the upvar
         // inference phase (`upvar.rs`).
         let num_upvars = self.tcx.with_freevars(expr.id, |fv| fv.len());
-        let upvar_tys = self.next_ty_vars(num_upvars);
+        let upvar_tys = self.next_ty_vars(num_upvars, expr.span);
 
         debug!("check_closure: expr.id={:?} upvar_tys={:?}",
                expr.id, upvar_tys);
@@ -221,7 +221,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
 
         let fn_sig = ty::FnSig {
             inputs: input_tys,
-            output: ty::FnConverging(ret_param_ty),
+            output: ret_param_ty,
             variadic: false
         };
         debug!("deduce_sig_from_projection: fn_sig {:?}", fn_sig);
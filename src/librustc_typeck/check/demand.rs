@@ -10,10 +10,12 @@
 
 
 use check::FnCtxt;
-use rustc::ty::Ty;
+use rustc::ty::{self, Ty};
 use rustc::infer::{InferOk, TypeOrigin};
 
-use syntax::codemap::Span;
+use syntax::codemap::{Span, NO_EXPANSION};
+use syntax::errors::{Applicability, DiagnosticBuilder};
+use syntax::parse::token;
 use rustc::hir;
 
 impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
@@ -51,7 +53,82 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         if let Err(e) = self.try_coerce(expr, expected) {
             let origin = TypeOrigin::Misc(expr.span);
             let expr_ty = self.resolve_type_vars_with_obligations(self.expr_ty(expr));
-            self.report_mismatched_types(origin, expected, expr_ty, e);
+            let mut err = self.struct_mismatched_types(origin, expected, expr_ty, e);
+            self.suggest_reference_fix(&mut err, expr, expected, expr_ty);
+            err.emit();
+        }
+    }
+
+    /// Suggests borrowing `expr`, or dereferencing or cloning it, when
+    /// that gives it the expected type.
+    fn suggest_reference_fix(&self,
+                             err: &mut DiagnosticBuilder,
+                             expr: &hir::Expr,
+                             expected: Ty<'tcx>,
+                             found: Ty<'tcx>) {
+        if expr.span.expn_id != NO_EXPANSION || expected.is_ty_var() || found.is_ty_var() {
+            return;
+        }
+        let codemap = self.tcx.sess.codemap();
+        let snippet = match codemap.span_to_snippet(expr.span) {
+            Ok(snippet) => snippet,
+            Err(_) => return,
+        };
+
+        match (&expected.sty, &found.sty) {
+            (&ty::TyRef(_, expected_mt), _)
+                    if self.can_sub_types(found, expected_mt.ty).is_ok() => {
+                let borrow = match expected_mt.mutbl {
+                    hir::MutMutable => "&mut ",
+                    hir::MutImmutable => "&",
+                };
+                let operand = if is_prefix_operand(expr) {
+                    snippet
+                } else {
+                    format!("({})", snippet)
+                };
+                err.span_suggestion_with_applicability(expr.span,
+                                                       "consider borrowing here",
+                                                       format!("{}{}", borrow, operand),
+                                                       Applicability::MachineApplicable);
+            }
+            (_, &ty::TyRef(_, found_mt))
+                    if self.can_sub_types(found_mt.ty, expected).is_ok() => {
+                if let hir::ExprAddrOf(_, ref inner) = expr.node {
+                    if let Ok(inner) = codemap.span_to_snippet(inner.span) {
+                        err.span_suggestion_with_applicability(expr.span,
+                                                               "consider removing the borrow",
+                                                               inner,
+                                                               Applicability::MachineApplicable);
+                    }
+                } else if !self.type_moves_by_default(expected, expr.span) {
+                    let operand = if is_prefix_operand(expr) {
+                        snippet
+                    } else {
+                        format!("({})", snippet)
+                    };
+                    err.span_suggestion_with_applicability(expr.span,
+                                                           "consider dereferencing here",
+                                                           format!("*{}", operand),
+                                                           Applicability::MachineApplicable);
+                } else if self.method_exists(expr.span,
+                                             token::intern("clone"),
+                                             expected,
+                                             expr.id,
+                                             false) {
+                    // Cloning may be expensive, or not what was meant.
+                    let operand = if is_postfix_operand(expr) {
+                        snippet
+                    } else {
+                        format!("({})", snippet)
+                    };
+                    err.span_suggestion_with_applicability(expr.span,
+                                                           "consider cloning the value here",
+                                                           format!("{}.clone()", operand),
+                                                           Applicability::MaybeIncorrect);
+                }
+            }
+            _ => {}
         }
     }
 
@@ -67,3 +144,29 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         }
     }
 }
+
+/// Whether `expr` can have a method called on it without being put in
+/// parentheses.
+fn is_postfix_operand(expr: &hir::Expr) -> bool {
+    match expr.node {
+        hir::ExprPath(..) |
+        hir::ExprLit(..) |
+        hir::ExprCall(..) |
+        hir::ExprMethodCall(..) |
+        hir::ExprField(..) |
+        hir::ExprTupField(..) |
+        hir::ExprIndex(..) |
+        hir::ExprTup(..) |
+        hir::ExprVec(..) => true,
+        _ => false,
+    }
+}
+
+/// Whether `expr` can be borrowed or dereferenced without being put in
+/// parentheses.
+fn is_prefix_operand(expr: &hir::Expr) -> bool {
+    match expr.node {
+        hir::ExprUnary(..) | hir::ExprAddrOf(..) => true,
+        _ => is_postfix_operand(expr),
+    }
+}
@@ -265,7 +265,7 @@ impl<'a, 'gcx, 'tcx> ConfirmContext<'a, 'gcx, 'tcx> {
                 // unify some of these variables.
                 self.fresh_substs_for_trait(self.span,
                                             &trait_def.generics,
-                                            self.next_ty_var())
+                                            self.next_ty_var(self.span))
             }
 
             probe::WhereClausePick(ref poly_trait_ref) => {
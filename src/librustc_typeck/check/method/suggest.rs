@@ -54,7 +54,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
 
                 self.autoderef(span, ty).any(|(ty, _)| self.probe(|_| {
                     let fn_once_substs =
-                        Substs::new_trait(vec![self.next_ty_var()], vec![], ty);
+                        Substs::new_trait(vec![self.next_ty_var(span)], vec![], ty);
                     let trait_ref =
                         ty::TraitRef::new(fn_once,
                                           tcx.mk_substs(fn_once_substs));
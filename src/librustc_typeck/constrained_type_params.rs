@@ -69,12 +69,14 @@ fn parameters_for_type_shallow<'tcx>(ty: Ty<'tcx>) -> Vec<Parameter> {
             parameters_for_regions_in_substs(&data.principal.skip_binder().substs),
         ty::TyProjection(ref pi) =>
             parameters_for_regions_in_substs(&pi.trait_ref.substs),
+        ty::TyAnon(_, substs) =>
+            parameters_for_regions_in_substs(substs),
         ty::TyBool | ty::TyChar | ty::TyInt(..) | ty::TyUint(..) |
         ty::TyFloat(..) | ty::TyBox(..) | ty::TyStr |
         ty::TyArray(..) | ty::TySlice(..) |
         ty::TyFnDef(..) | ty::TyFnPtr(_) |
         ty::TyTuple(..) | ty::TyRawPtr(..) |
-        ty::TyInfer(..) | ty::TyClosure(..) | ty::TyError =>
+        ty::TyInfer(..) | ty::TyClosure(..) | ty::TyError | ty::TyNever =>
             vec![]
     }
 }
@@ -82,7 +82,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ConstraintContext<'a, 'tcx> {
         debug!("visit_item item={}", tcx.map.node_to_string(item.id));
 
         match item.node {
-            hir::ItemEnum(..) | hir::ItemStruct(..) => {
+            hir::ItemEnum(..) | hir::ItemStruct(..) | hir::ItemUnion(..) => {
                 let scheme = tcx.lookup_item_type(did);
 
                 // Not entirely obvious: constraints on structs/enums do not
@@ -187,6 +187,7 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
                         hir::ItemTy(..) |
                         hir::ItemEnum(..) |
                         hir::ItemStruct(..) |
+                        hir::ItemUnion(..) |
                         hir::ItemTrait(..)   => is_inferred = true,
                         hir::ItemFn(..)      => is_inferred = false,
                         _                    => cannot_happen!(),
@@ -322,7 +323,7 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
         match ty.sty {
             ty::TyBool |
             ty::TyChar | ty::TyInt(_) | ty::TyUint(_) |
-            ty::TyFloat(_) | ty::TyStr => {
+            ty::TyFloat(_) | ty::TyStr | ty::TyNever => {
                 /* leaf type -- noop */
             }
 
@@ -394,6 +395,18 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
                     variance);
             }
 
+            ty::TyAnon(_, substs) => {
+                // The concrete type behind an anonymized type can use
+                // its parameters in any way, so they're all invariant.
+                let invar = self.invariant(variance);
+                for &ty in substs.types.as_slice() {
+                    self.add_constraints_from_ty(generics, ty, invar);
+                }
+                for &region in substs.regions.as_slice() {
+                    self.add_constraints_from_region(generics, region, invar);
+                }
+            }
+
             ty::TyTrait(ref data) => {
                 let poly_trait_ref =
                     data.principal_trait_ref_with_self_ty(self.tcx(),
@@ -490,9 +503,7 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
         for &input in &sig.0.inputs {
             self.add_constraints_from_ty(generics, input, contra);
         }
-        if let ty::FnConverging(result_type) = sig.0.output {
-            self.add_constraints_from_ty(generics, result_type, variance);
-        }
+        self.add_constraints_from_ty(generics, sig.0.output, variance);
     }
 
     /// Adds constraints appropriate for a region appearing in a
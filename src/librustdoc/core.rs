@@ -24,7 +24,6 @@ use rustc_metadata::cstore::CStore;
 use rustc_metadata::creader::read_local_crates;
 
 use syntax::{ast, codemap, errors};
-use syntax::errors::emitter::ColorConfig;
 use syntax::feature_gate::UnstableFeatures;
 use syntax::parse::token;
 
@@ -102,7 +101,8 @@ pub fn run_core(search_paths: SearchPaths,
                 cfgs: Vec<String>,
                 externs: Externs,
                 input: Input,
-                triple: Option<String>) -> (clean::Crate, RenderInfo)
+                triple: Option<String>,
+                error_format: config::ErrorOutputType) -> (clean::Crate, RenderInfo)
 {
     // Parse, resolve, and typecheck the given crate.
 
@@ -128,11 +128,10 @@ pub fn run_core(search_paths: SearchPaths,
     };
 
     let codemap = Rc::new(codemap::CodeMap::new());
-    let diagnostic_handler = errors::Handler::with_tty_emitter(ColorConfig::Auto,
-                                                               None,
-                                                               true,
-                                                               false,
-                                                               codemap.clone());
+    let diagnostic_handler =
+        errors::Handler::with_emitter(true,
+                                      false,
+                                      session::emitter(error_format, None, codemap.clone()));
 
     let dep_graph = DepGraph::new(false);
     let _ignore = dep_graph.in_ignore();
@@ -149,8 +148,9 @@ pub fn run_core(search_paths: SearchPaths,
     let name = link::find_crate_name(Some(&sess), &krate.attrs,
                                      &input);
 
-    let krate = driver::phase_2_configure_and_expand(&sess, &cstore, krate, &name, None)
-                    .expect("phase_2_configure_and_expand aborted in rustdoc!");
+    let (krate, macro_scopes) =
+        driver::phase_2_configure_and_expand(&sess, &cstore, krate, &name, None)
+            .expect("phase_2_configure_and_expand aborted in rustdoc!");
 
     let krate = driver::assign_node_ids(&sess, krate);
 
@@ -159,7 +159,7 @@ pub fn run_core(search_paths: SearchPaths,
 
     // Lower ast -> hir and resolve.
     let (analysis, resolutions, mut hir_forest) = {
-        driver::lower_and_resolve(&sess, &name, &mut defs, &krate,
+        driver::lower_and_resolve(&sess, &name, &mut defs, &krate, macro_scopes,
                                   &sess.dep_graph, resolve::MakeGlobMap::No)
     };
 
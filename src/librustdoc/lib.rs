@@ -58,6 +58,7 @@ use std::sync::mpsc::channel;
 use externalfiles::ExternalHtml;
 use rustc::session::search_paths::SearchPaths;
 use rustc::session::config::{ErrorOutputType, RustcOptGroup, nightly_options};
+use syntax::errors::emitter::ColorConfig;
 
 #[macro_use]
 pub mod externalfiles;
@@ -181,6 +182,8 @@ pub fn opts() -> Vec<RustcOptGroup> {
                          own theme", "PATH")),
         unstable(optmulti("Z", "",
                           "internal and debugging options (only on nightly build)", "FLAG")),
+        unstable(optopt("", "error-format",
+                        "how errors and other messages are produced", "human|json|short")),
     )
 }
 
@@ -277,12 +280,22 @@ pub fn main_args(args: &[String]) -> isize {
     };
     let crate_name = matches.opt_str("crate-name");
 
+    let error_format = match matches.opt_str("error-format").as_ref().map(|s| &**s) {
+        Some("human") | None => ErrorOutputType::default(),
+        Some("json") => ErrorOutputType::Json,
+        Some("short") => ErrorOutputType::Short(ColorConfig::Auto),
+        Some(s) => {
+            println!("unknown error format: {}", s);
+            return 1;
+        }
+    };
+
     match (should_test, markdown_input) {
         (true, true) => {
             return markdown::test(input, cfgs, libs, externs, test_args)
         }
         (true, false) => {
-            return test::run(input, cfgs, libs, externs, test_args, crate_name)
+            return test::run(input, cfgs, libs, externs, test_args, crate_name, error_format)
         }
         (false, true) => return markdown::render(input,
                                                  output.unwrap_or(PathBuf::from("doc")),
@@ -290,7 +303,7 @@ pub fn main_args(args: &[String]) -> isize {
                                                  !matches.opt_present("markdown-no-toc")),
         (false, false) => {}
     }
-    let out = match acquire_input(input, externs, &matches) {
+    let out = match acquire_input(input, externs, error_format, &matches) {
         Ok(out) => out,
         Err(s) => {
             println!("input error: {}", s);
@@ -321,12 +334,13 @@ pub fn main_args(args: &[String]) -> isize {
 /// and files and then generates the necessary rustdoc output for formatting.
 fn acquire_input(input: &str,
                  externs: core::Externs,
+                 error_format: ErrorOutputType,
                  matches: &getopts::Matches) -> Result<Output, String> {
     match matches.opt_str("r").as_ref().map(|s| &**s) {
-        Some("rust") => Ok(rust_input(input, externs, matches)),
+        Some("rust") => Ok(rust_input(input, externs, error_format, matches)),
         Some(s) => Err(format!("unknown input format: {}", s)),
         None => {
-            Ok(rust_input(input, externs, matches))
+            Ok(rust_input(input, externs, error_format, matches))
         }
     }
 }
@@ -353,7 +367,11 @@ fn parse_externs(matches: &getopts::Matches) -> Result<core::Externs, String> {
 /// generated from the cleaned AST of the crate.
 ///
 /// This form of input will run all of the plug/cleaning passes
-fn rust_input(cratefile: &str, externs: core::Externs, matches: &getopts::Matches) -> Output {
+fn rust_input(cratefile: &str,
+              externs: core::Externs,
+              error_format: ErrorOutputType,
+              matches: &getopts::Matches)
+              -> Output {
     let mut default_passes = !matches.opt_present("no-defaults");
     let mut passes = matches.opt_strs("passes");
     let mut plugins = matches.opt_strs("plugins");
@@ -374,7 +392,7 @@ fn rust_input(cratefile: &str, externs: core::Externs, matches: &getopts::Matche
         use rustc::session::config::Input;
 
         tx.send(core::run_core(paths, cfgs, externs, Input::File(cr),
-                               triple)).unwrap();
+                               triple, error_format)).unwrap();
     });
     let (mut krate, renderinfo) = rx.recv().unwrap();
     info!("finished with rustc");
@@ -35,7 +35,6 @@ use rustc_driver::{driver, Compilation};
 use rustc_metadata::cstore::CStore;
 use syntax::codemap::CodeMap;
 use syntax::errors;
-use syntax::errors::emitter::ColorConfig;
 use syntax::parse::token;
 
 use core;
@@ -57,7 +56,8 @@ pub fn run(input: &str,
            libs: SearchPaths,
            externs: core::Externs,
            mut test_args: Vec<String>,
-           crate_name: Option<String>)
+           crate_name: Option<String>,
+           error_format: config::ErrorOutputType)
            -> isize {
     let input_path = PathBuf::from(input);
     let input = config::Input::File(input_path.clone());
@@ -73,11 +73,10 @@ pub fn run(input: &str,
     };
 
     let codemap = Rc::new(CodeMap::new());
-    let diagnostic_handler = errors::Handler::with_tty_emitter(ColorConfig::Auto,
-                                                               None,
-                                                               true,
-                                                               false,
-                                                               codemap.clone());
+    let diagnostic_handler =
+        errors::Handler::with_emitter(true,
+                                      false,
+                                      session::emitter(error_format, None, codemap.clone()));
 
     let dep_graph = DepGraph::new(false);
     let _ignore = dep_graph.in_ignore();
@@ -93,8 +92,8 @@ pub fn run(input: &str,
     let mut cfg = config::build_configuration(&sess);
     cfg.extend(config::parse_cfgspecs(cfgs.clone()));
     let krate = panictry!(driver::phase_1_parse_input(&sess, cfg, &input));
-    let krate = driver::phase_2_configure_and_expand(&sess, &cstore, krate,
-                                                     "rustdoc-test", None)
+    let (krate, _) = driver::phase_2_configure_and_expand(&sess, &cstore, krate,
+                                                          "rustdoc-test", None)
         .expect("phase_2_configure_and_expand aborted in rustdoc!");
     let krate = driver::assign_node_ids(&sess, krate);
     let dep_graph = DepGraph::new(false);
@@ -85,7 +85,9 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                                               &krate.module,
                                               None);
         // attach the crate's exported macros to the top-level module:
+        // FIXME: document the macros of `macro` items in their modules.
         self.module.macros = krate.exported_macros.iter()
+            .filter(|def| def.decl.is_none())
             .map(|def| self.visit_macro(def)).collect();
         self.module.is_crate = true;
     }
@@ -241,7 +243,11 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             Some(tcx) => tcx,
             None => return false
         };
-        let def = tcx.def_map.borrow()[&id];
+        // Imports that only name macros resolve to nothing.
+        let def = match tcx.def_map.borrow().get(&id) {
+            Some(&def) => def,
+            None => return false,
+        };
         let def_did = def.def_id();
 
         let use_attrs = tcx.map.attrs(id).clean(self.cx);
@@ -364,6 +370,8 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                 om.enums.push(self.visit_enum_def(item, name, ed, gen)),
             hir::ItemStruct(ref sd, ref gen) =>
                 om.structs.push(self.visit_variant_data(item, name, sd, gen)),
+            // FIXME: unions are not documented yet.
+            hir::ItemUnion(..) => {}
             hir::ItemFn(ref fd, ref unsafety, constness, ref abi, ref gen, _) =>
                 om.fns.push(self.visit_fn(item, name, &**fd, unsafety,
                                           constness, abi, gen)),
@@ -318,6 +318,43 @@ impl Decodable for i64 {
     }
 }
 
+// The encoders have no 128-bit primitives, so these go out as a pair of
+// 64-bit halves, most significant half first.
+#[cfg(not(stage0))]
+impl Encodable for u128 {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        s.emit_tuple(2, |s| {
+            s.emit_tuple_arg(0, |s| s.emit_u64((*self >> 64) as u64))?;
+            s.emit_tuple_arg(1, |s| s.emit_u64(*self as u64))
+        })
+    }
+}
+
+#[cfg(not(stage0))]
+impl Decodable for u128 {
+    fn decode<D: Decoder>(d: &mut D) -> Result<u128, D::Error> {
+        d.read_tuple(2, |d| {
+            let hi = d.read_tuple_arg(0, |d| d.read_u64())?;
+            let lo = d.read_tuple_arg(1, |d| d.read_u64())?;
+            Ok(((hi as u128) << 64) | lo as u128)
+        })
+    }
+}
+
+#[cfg(not(stage0))]
+impl Encodable for i128 {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        (*self as u128).encode(s)
+    }
+}
+
+#[cfg(not(stage0))]
+impl Decodable for i128 {
+    fn decode<D: Decoder>(d: &mut D) -> Result<i128, D::Error> {
+        u128::decode(d).map(|v| v as i128)
+    }
+}
+
 impl Encodable for str {
     fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
         s.emit_str(self)
@@ -273,6 +273,7 @@
 #![feature(vec_push_all)]
 #![feature(zero_one)]
 #![feature(question_mark)]
+#![cfg_attr(not(stage0), feature(i128))]
 #![feature(try_from)]
 #![feature(needs_panic_runtime)]
 
@@ -400,6 +401,9 @@ pub use core::i16;
 pub use core::i32;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::i64;
+#[unstable(feature = "i128", issue = "35118")]
+#[cfg(not(stage0))]
+pub use core::i128;
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::usize;
@@ -411,6 +415,9 @@ pub use core::u16;
 pub use core::u32;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::u64;
+#[unstable(feature = "i128", issue = "35118")]
+#[cfg(not(stage0))]
+pub use core::u128;
 
 #[path = "num/f32.rs"]   pub mod f32;
 #[path = "num/f64.rs"]   pub mod f64;
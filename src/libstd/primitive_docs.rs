@@ -538,6 +538,14 @@ mod prim_i32 { }
 ///
 mod prim_i64 { }
 
+#[doc(primitive = "i128")]
+//
+/// The 128-bit signed integer type.
+///
+/// *[See also the `std::i128` module](i128/index.html).*
+///
+mod prim_i128 { }
+
 #[doc(primitive = "u8")]
 //
 /// The 8-bit unsigned integer type.
@@ -570,6 +578,14 @@ mod prim_u32 { }
 ///
 mod prim_u64 { }
 
+#[doc(primitive = "u128")]
+//
+/// The 128-bit unsigned integer type.
+///
+/// *[See also the `std::u128` module](u128/index.html).*
+///
+mod prim_u128 { }
+
 #[doc(primitive = "isize")]
 //
 /// The pointer-sized signed integer type.
@@ -32,6 +32,7 @@ use std::rc::Rc;
 use std::borrow::Cow;
 use std::hash::{Hash, Hasher};
 use serialize::{Encodable, Decodable, Encoder, Decoder};
+use rustc_i128::{i128, u128};
 
 /// A name is a part of an identifier, representing a string or gensym. It's
 /// the result of interning.
@@ -1143,7 +1144,8 @@ pub struct SequenceRepetition {
     pub tts: Vec<TokenTree>,
     /// The optional separator
     pub separator: Option<token::Token>,
-    /// Whether the sequence can be repeated zero (*), or one or more times (+)
+    /// Whether the sequence can be repeated zero or more (*), one or more (+),
+    /// or zero or one (?) times
     pub op: KleeneOp,
     /// The number of `MatchNt`s that appear in the sequence (and subsequences)
     pub num_captures: usize,
@@ -1155,6 +1157,7 @@ pub struct SequenceRepetition {
 pub enum KleeneOp {
     ZeroOrMore,
     OneOrMore,
+    ZeroOrOne,
 }
 
 /// When the main rust parser encounters a syntax-extension invocation, it
@@ -1334,7 +1337,7 @@ pub enum LitKind {
     /// A character literal (`'a'`)
     Char(char),
     /// An integer literal (`1`)
-    Int(u64, LitIntType),
+    Int(u128, LitIntType),
     /// A float literal (`1f64` or `1E10f64`)
     Float(InternedString, FloatTy),
     /// A float literal without a suffix (`1.0 or 1.0E10`)
@@ -1418,6 +1421,7 @@ pub enum IntTy {
     I16,
     I32,
     I64,
+    I128,
 }
 
 impl fmt::Debug for IntTy {
@@ -1439,23 +1443,25 @@ impl IntTy {
             IntTy::I8 => "i8",
             IntTy::I16 => "i16",
             IntTy::I32 => "i32",
-            IntTy::I64 => "i64"
+            IntTy::I64 => "i64",
+            IntTy::I128 => "i128",
         }
     }
 
-    pub fn val_to_string(&self, val: i64) -> String {
-        // cast to a u64 so we can correctly print INT64_MIN. All integral types
-        // are parsed as u64, so we wouldn't want to print an extra negative
+    pub fn val_to_string(&self, val: i128) -> String {
+        // cast to a u128 so we can correctly print INT128_MIN. All integral types
+        // are parsed as u128, so we wouldn't want to print an extra negative
         // sign.
-        format!("{}{}", val as u64, self.ty_to_string())
+        format!("{}{}", val as u128, self.ty_to_string())
     }
 
-    pub fn ty_max(&self) -> u64 {
+    pub fn ty_max(&self) -> u128 {
         match *self {
             IntTy::I8 => 0x80,
             IntTy::I16 => 0x8000,
             IntTy::Is | IntTy::I32 => 0x80000000, // FIXME: actually ni about Is
-            IntTy::I64 => 0x8000000000000000
+            IntTy::I64 => 0x8000000000000000,
+            IntTy::I128 => i128::min_value() as u128,
         }
     }
 
@@ -1466,6 +1472,7 @@ impl IntTy {
             IntTy::I16 => 16,
             IntTy::I32 => 32,
             IntTy::I64 => 64,
+            IntTy::I128 => 128,
         })
     }
 }
@@ -1477,6 +1484,7 @@ pub enum UintTy {
     U16,
     U32,
     U64,
+    U128,
 }
 
 impl UintTy {
@@ -1486,20 +1494,22 @@ impl UintTy {
             UintTy::U8 => "u8",
             UintTy::U16 => "u16",
             UintTy::U32 => "u32",
-            UintTy::U64 => "u64"
+            UintTy::U64 => "u64",
+            UintTy::U128 => "u128",
         }
     }
 
-    pub fn val_to_string(&self, val: u64) -> String {
+    pub fn val_to_string(&self, val: u128) -> String {
         format!("{}{}", val, self.ty_to_string())
     }
 
-    pub fn ty_max(&self) -> u64 {
+    pub fn ty_max(&self) -> u128 {
         match *self {
             UintTy::U8 => 0xff,
             UintTy::U16 => 0xffff,
             UintTy::Us | UintTy::U32 => 0xffffffff, // FIXME: actually ni about Us
-            UintTy::U64 => 0xffffffffffffffff
+            UintTy::U64 => 0xffffffffffffffff,
+            UintTy::U128 => u128::max_value(),
         }
     }
 
@@ -1510,6 +1520,7 @@ impl UintTy {
             UintTy::U16 => 16,
             UintTy::U32 => 32,
             UintTy::U64 => 64,
+            UintTy::U128 => 128,
         })
     }
 }
@@ -1602,6 +1613,8 @@ pub enum TyKind {
     Rptr(Option<Lifetime>, MutTy),
     /// A bare function (e.g. `fn(usize) -> bool`)
     BareFn(P<BareFnTy>),
+    /// The never type (`!`)
+    Never,
     /// A tuple (`(A, B, C, D,...)`)
     Tup(Vec<P<Ty>> ),
     /// A path (`module::module::...::Type`), optionally
@@ -1613,6 +1626,8 @@ pub enum TyKind {
     ObjectSum(P<Ty>, TyParamBounds),
     /// A type like `for<'a> Foo<&'a Bar>`
     PolyTraitRef(TyParamBounds),
+    /// An `impl TraitA+TraitB` type.
+    ImplTrait(TyParamBounds),
     /// No-op; kept solely so that we can pretty-print faithfully
     Paren(P<Ty>),
     /// Unused for now
@@ -1792,9 +1807,6 @@ impl fmt::Debug for ImplPolarity {
 
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub enum FunctionRetTy {
-    /// Functions with return type `!`that always
-    /// raise an error or exit (i.e. never return to the caller)
-    None(Span),
     /// Return type is not specified.
     ///
     /// Functions default to `()` and
@@ -1808,7 +1820,6 @@ pub enum FunctionRetTy {
 impl FunctionRetTy {
     pub fn span(&self) -> Span {
         match *self {
-            FunctionRetTy::None(span) => span,
             FunctionRetTy::Default(span) => span,
             FunctionRetTy::Ty(ref ty) => ty.span,
         }
@@ -2057,6 +2068,8 @@ pub enum ItemKind {
     Enum(EnumDef, Generics),
     /// A struct definition, e.g. `struct Foo<A> {x: A}`
     Struct(VariantData, Generics),
+    /// A union definition, e.g. `union Foo<A, B> {x: A, y: B}`
+    Union(VariantData, Generics),
     /// Represents a Trait Declaration
     Trait(Unsafety, Generics, TyParamBounds, Vec<TraitItem>),
 
@@ -2088,6 +2101,7 @@ impl ItemKind {
             ItemKind::Ty(..) => "type alias",
             ItemKind::Enum(..) => "enum",
             ItemKind::Struct(..) => "struct",
+            ItemKind::Union(..) => "union",
             ItemKind::Trait(..) => "trait",
             ItemKind::Mac(..) |
             ItemKind::Impl(..) |
@@ -2127,7 +2141,8 @@ impl ForeignItemKind {
 
 /// A macro definition, in this crate or imported from another.
 ///
-/// Not parsed directly, but created on macro import or `macro_rules!` expansion.
+/// Not parsed directly, but created on macro import, `macro_rules!` expansion
+/// or the expansion of a `macro` item.
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub struct MacroDef {
     pub ident: Ident,
@@ -2139,6 +2154,24 @@ pub struct MacroDef {
     pub use_locally: bool,
     pub allow_internal_unstable: bool,
     pub body: Vec<TokenTree>,
+    /// Where a `macro` item is named from and resolved in; `None` for
+    /// `macro_rules!` macros.
+    pub decl: Option<DeclMacro>,
+}
+
+/// The modules of a `macro` item, as paths from the roots of their crates.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub struct DeclMacro {
+    /// The name of the crate that exports the macro, or `None` in that crate.
+    pub krate: Option<Name>,
+    /// The module the macro is reachable through, by its name.
+    pub module: Vec<Name>,
+    /// The name of the crate that defines the macro, or `None` if that is
+    /// the crate that exports it.
+    pub def_site_crate: Option<Name>,
+    /// The module the macro is defined in, where the names in its body are
+    /// resolved.
+    pub def_site: Vec<Name>,
 }
 
 #[cfg(test)]
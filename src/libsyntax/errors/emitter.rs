@@ -15,6 +15,7 @@ use diagnostics;
 
 use errors::check_old_skool;
 use errors::{Level, RenderSpan, CodeSuggestion, DiagnosticBuilder};
+use errors::{DiagnosticStyledString, StringPart};
 use errors::RenderSpan::*;
 use errors::Level::*;
 use errors::snippet::{RenderedLineKind, SnippetData, Style};
@@ -22,6 +23,7 @@ use errors::snippet::{RenderedLineKind, SnippetData, Style};
 use std::{cmp, fmt};
 use std::io::prelude::*;
 use std::io;
+use std::iter::repeat;
 use std::rc::Rc;
 use term;
 
@@ -43,6 +45,17 @@ pub trait CoreEmitter {
                     lvl: Level,
                     is_header: bool,
                     show_snippet: bool);
+
+    /// Emits a sub-diagnostic whose message also comes with styled parts.
+    /// Emitters that do not style messages emit the plain `msg`.
+    fn emit_styled_message(&mut self,
+                           rsp: &RenderSpan,
+                           msg: &str,
+                           _styled: &DiagnosticStyledString,
+                           lvl: Level,
+                           show_snippet: bool) {
+        self.emit_message(rsp, msg, None, lvl, false, show_snippet);
+    }
 }
 
 impl<T: CoreEmitter> Emitter for T {
@@ -75,23 +88,45 @@ impl<T: CoreEmitter> Emitter for T {
                                        || FullSpan(child.span.clone()));
 
             if !old_school {
-                self.emit_message(&render_span,
-                                    &child.message,
-                                    None,
-                                    child.level,
-                                    false,
-                                    true);
+                match child.styled_message {
+                    Some(ref styled) => {
+                        self.emit_styled_message(&render_span,
+                                                 &child.message,
+                                                 styled,
+                                                 child.level,
+                                                 true);
+                    }
+                    None => {
+                        self.emit_message(&render_span,
+                                            &child.message,
+                                            None,
+                                            child.level,
+                                            false,
+                                            true);
+                    }
+                }
             } else {
                 let (render_span, show_snippet) = match render_span.span().primary_span() {
                     None => (db_span.clone(), false),
                     _ => (render_span, true)
                 };
-                self.emit_message(&render_span,
-                                    &child.message,
-                                    None,
-                                    child.level,
-                                    false,
-                                    show_snippet);
+                match child.styled_message {
+                    Some(ref styled) => {
+                        self.emit_styled_message(&render_span,
+                                                 &child.message,
+                                                 styled,
+                                                 child.level,
+                                                 show_snippet);
+                    }
+                    None => {
+                        self.emit_message(&render_span,
+                                            &child.message,
+                                            None,
+                                            child.level,
+                                            false,
+                                            show_snippet);
+                    }
+                }
             }
         }
     }
@@ -149,6 +184,71 @@ impl BasicEmitter {
     }
 }
 
+/// Prints each diagnostic on one line, as `file:line:col: level[code]:
+/// message`, leaving out the source snippets and the notes attached to
+/// it. Meant for editors and other tools that read the output line by
+/// line.
+pub struct ShortEmitter {
+    dst: Destination,
+    cm: Rc<codemap::CodeMap>,
+}
+
+impl Emitter for ShortEmitter {
+    fn emit(&mut self, msp: &MultiSpan, msg: &str, code: Option<&str>, lvl: Level) {
+        if let Err(e) = self.emit_short(msp, msg, code, lvl) {
+            panic!("failed to emit error: {}", e);
+        }
+    }
+
+    fn emit_struct(&mut self, db: &DiagnosticBuilder) {
+        self.emit(&db.span, &db.message, db.code.as_ref().map(|s| &**s), db.level);
+    }
+}
+
+impl ShortEmitter {
+    pub fn stderr(color_config: ColorConfig, code_map: Rc<codemap::CodeMap>) -> ShortEmitter {
+        let dst = if color_config.use_color() {
+            Destination::from_stderr()
+        } else {
+            Raw(Box::new(io::stderr()))
+        };
+        ShortEmitter { dst: dst, cm: code_map }
+    }
+
+    pub fn new(dst: Box<Write + Send>, code_map: Rc<codemap::CodeMap>) -> ShortEmitter {
+        ShortEmitter { dst: Raw(dst), cm: code_map }
+    }
+
+    fn emit_short(&mut self,
+                  msp: &MultiSpan,
+                  msg: &str,
+                  code: Option<&str>,
+                  lvl: Level)
+                  -> io::Result<()> {
+        if let Some(sp) = msp.primary_span() {
+            if sp != DUMMY_SP && sp != COMMAND_LINE_SP {
+                // Point at the code the user wrote, not inside a macro.
+                let loc = self.cm.lookup_char_pos(self.cm.source_callsite(sp).lo);
+                write!(self.dst, "{}:{}:{}: ", loc.file.name, loc.line, loc.col.0 + 1)?;
+            }
+        }
+        self.dst.start_attr(term::Attr::Bold)?;
+        self.dst.start_attr(term::Attr::ForegroundColor(lvl.color()))?;
+        write!(self.dst, "{}", lvl.to_string())?;
+        if let Some(code) = code {
+            write!(self.dst, "[{}]", code)?;
+        }
+        self.dst.reset_attrs()?;
+        write!(self.dst, ": ")?;
+        self.dst.start_attr(term::Attr::Bold)?;
+        // Keep the diagnostic on one line.
+        write!(self.dst, "{}", msg.lines().collect::<Vec<_>>().join(" "))?;
+        self.dst.reset_attrs()?;
+        write!(self.dst, "\n")?;
+        Ok(())
+    }
+}
+
 pub struct EmitterWriter {
     dst: Destination,
     registry: Option<diagnostics::registry::Registry>,
@@ -175,6 +275,27 @@ impl CoreEmitter for EmitterWriter {
             Err(e) => panic!("failed to emit error: {}", e)
         }
     }
+
+    fn emit_styled_message(&mut self,
+                           rsp: &RenderSpan,
+                           msg: &str,
+                           styled: &DiagnosticStyledString,
+                           lvl: Level,
+                           show_snippet: bool) {
+        if show_snippet && rsp.span().primary_span().is_some() {
+            // Only the messages without a snippet are styled for now.
+            return self.emit_message(rsp, msg, None, lvl, false, show_snippet);
+        }
+        let loc = match rsp.span().primary_span() {
+            Some(COMMAND_LINE_SP) | Some(DUMMY_SP) | None => "".to_string(),
+            Some(_) if !self.old_school => "".to_string(),
+            Some(ps) => self.cm.span_to_string(ps),
+        };
+        match print_styled_diagnostic(&mut self.dst, &loc, lvl, styled) {
+            Ok(()) => { }
+            Err(e) => panic!("failed to emit error: {}", e)
+        }
+    }
 }
 
 /// Do not use this for messages that end in `\n` – use `println_maybe_styled` instead. See
@@ -474,6 +595,48 @@ fn print_diagnostic(dst: &mut Destination,
     Ok(())
 }
 
+/// Like `print_diagnostic`, but renders the elided parts of `msg` as `_`,
+/// the highlighted ones in bold and its line breaks as lines indented from
+/// where the message starts.
+fn print_styled_diagnostic(dst: &mut Destination,
+                           topic: &str,
+                           lvl: Level,
+                           msg: &DiagnosticStyledString)
+                           -> io::Result<()> {
+    let mut header_width = 0;
+    if !topic.is_empty() {
+        write!(dst, "{} ", topic)?;
+        header_width += topic.chars().count() + 1;
+    }
+    dst.start_attr(term::Attr::Bold)?;
+    dst.start_attr(term::Attr::ForegroundColor(lvl.color()))?;
+    write!(dst, "{}", lvl.to_string())?;
+    dst.reset_attrs()?;
+    write!(dst, ": ")?;
+    header_width += lvl.to_string().len() + 2;
+    for (i, part) in msg.0.iter().enumerate() {
+        match *part {
+            StringPart::Normal(ref text) => {
+                match msg.0.get(i + 1) {
+                    Some(&StringPart::LineBreak(_)) => write!(dst, "{}", text.trim_right())?,
+                    _ => write!(dst, "{}", text)?,
+                }
+            }
+            StringPart::Elided(_) => write!(dst, "_")?,
+            StringPart::LineBreak(indent) => {
+                write!(dst, "\n{}", repeat(" ").take(header_width + indent).collect::<String>())?
+            }
+            StringPart::Highlighted(ref text) => {
+                dst.start_attr(term::Attr::Bold)?;
+                write!(dst, "{}", text)?;
+                dst.reset_attrs()?;
+            }
+        }
+    }
+    write!(dst, "\n")?;
+    Ok(())
+}
+
 #[cfg(unix)]
 fn stderr_isatty() -> bool {
     use libc;
@@ -621,7 +784,7 @@ impl Write for Destination {
 
 #[cfg(test)]
 mod test {
-    use errors::{Level, CodeSuggestion};
+    use errors::{Applicability, Level, CodeSuggestion, DiagnosticStyledString, Handler};
     use super::EmitterWriter;
     use codemap::{mk_sp, CodeMap, Span, MultiSpan, BytePos, NO_EXPANSION};
     use std::sync::{Arc, Mutex};
@@ -706,6 +869,7 @@ mod test {
         let suggest = CodeSuggestion {
             msp: msp,
             substitutes: vec![substitute],
+            applicability: Applicability::Unspecified,
         };
         assert_eq!(suggest.splice_lines(&cm), expected);
     }
@@ -726,7 +890,8 @@ mod test {
         let suggest = CodeSuggestion {
             msp: msp,
             substitutes: vec!["ZZZZZZ".to_owned(),
-                              "XYZ".to_owned()]
+                              "XYZ".to_owned()],
+            applicability: Applicability::Unspecified,
         };
 
         assert_eq!(suggest.splice_lines(&cm), expected);
@@ -868,4 +1033,85 @@ mod test {
             diag.highlight_lines(&msp, Level::Error).unwrap();
         });
     }
+
+    #[test]
+    fn test_styled_note() {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let cm = Rc::new(CodeMap::new());
+        let ew = EmitterWriter::new(Box::new(Sink(data.clone())), None, cm);
+        let handler = Handler::with_emitter(true, false, Box::new(ew));
+
+        let mut expected = DiagnosticStyledString::new();
+        expected.push_normal("Foo<");
+        expected.push_elided("String");
+        expected.push_normal(", ");
+        expected.push_highlighted("u8");
+        expected.push_normal(">");
+        let mut found = DiagnosticStyledString::new();
+        found.push_normal("Foo<");
+        found.push_elided("String");
+        found.push_normal(", ");
+        found.push_highlighted("i8");
+        found.push_normal(">");
+
+        handler.struct_err("mismatched types")
+               .note_expected_found_styled(&"type",
+                                           &"Foo<String, u8>",
+                                           &"Foo<String, i8>",
+                                           expected,
+                                           found)
+               .emit();
+
+        let vec = data.lock().unwrap().clone();
+        let actual = from_utf8(&vec[..]).unwrap();
+        assert_eq!(actual, "error: mismatched types\n\
+                            note: expected type `Foo<_, u8>`\n\
+                            note:    found type `Foo<_, i8>`\n");
+    }
+
+    #[test]
+    fn test_styled_note_tree() {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let cm = Rc::new(CodeMap::new());
+        let ew = EmitterWriter::new(Box::new(Sink(data.clone())), None, cm);
+        let handler = Handler::with_emitter(true, false, Box::new(ew));
+
+        let styled = |error: &str| {
+            let mut styled = DiagnosticStyledString::new();
+            styled.push_normal("std::collections::HashMap");
+            styled.push_normal("<");
+            styled.push_elided("std::string::String");
+            styled.push_normal(", ");
+            styled.push_normal("std::vec::Vec");
+            styled.push_normal("<");
+            styled.push_normal("std::result::Result");
+            styled.push_normal("<");
+            styled.push_elided("u8");
+            styled.push_normal(", ");
+            styled.push_highlighted(error);
+            styled.push_normal(">");
+            styled.push_normal(">");
+            styled.push_normal(">");
+            styled
+        };
+
+        handler.struct_err("mismatched types")
+               .note_expected_found_styled(&"type",
+                                           &"HashMap<String, Vec<Result<u8, io::Error>>>",
+                                           &"HashMap<String, Vec<Result<u8, fmt::Error>>>",
+                                           styled("std::io::Error"),
+                                           styled("std::fmt::Error"))
+               .emit();
+
+        let vec = data.lock().unwrap().clone();
+        let actual = from_utf8(&vec[..]).unwrap();
+        assert_eq!(actual, format!("error: mismatched types\n\
+                                    note: expected type `std::collections::HashMap<\n\
+                                    {0}_,\n\
+                                    {0}std::vec::Vec<std::result::Result<_, std::io::Error>>>`\n\
+                                    note:    found type `std::collections::HashMap<\n\
+                                    {0}_,\n\
+                                    {0}std::vec::Vec<std::result::Result<_, std::fmt::Error>>>`\n",
+                                   "                         "));
+    }
 }
@@ -23,6 +23,7 @@
 use codemap::{self, MacroBacktrace, Span, SpanLabel, MultiSpan, CodeMap};
 use diagnostics::registry::Registry;
 use errors::{Level, DiagnosticBuilder, SubDiagnostic, RenderSpan, CodeSuggestion};
+use errors::{Applicability, StringPart};
 use errors::emitter::Emitter;
 
 use std::rc::Rc;
@@ -78,6 +79,10 @@ struct Diagnostic<'a> {
     /// "error: internal compiler error", "error", "warning", "note", "help".
     level: &'static str,
     spans: Vec<DiagnosticSpan>,
+    /// The message split into parts, when rustc renders some of them
+    /// differently, e.g. the elided and highlighted parts of mismatched
+    /// types.
+    message_parts: Option<Vec<&'a StringPart>>,
     /// Associated diagnostic messages.
     children: Vec<Diagnostic<'a>>,
     /// The message as rustc would render it. Currently this is only
@@ -109,6 +114,10 @@ struct DiagnosticSpan {
     /// load the fully rendered version from the parent `Diagnostic`,
     /// however.
     suggested_replacement: Option<String>,
+    /// If the suggestion is approximate, or needs the user to fill in
+    /// some placeholders, this says so; only `MachineApplicable`
+    /// replacements should be applied without asking.
+    suggestion_applicability: Option<Applicability>,
     /// Macro invocations that created the code at this span, if any.
     expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
 }
@@ -157,6 +166,7 @@ impl<'a> Diagnostic<'a> {
             code: DiagnosticCode::map_opt_string(code.map(|c| c.to_owned()), je),
             level: level.to_str(),
             spans: DiagnosticSpan::from_multispan(msp, je),
+            message_parts: None,
             children: vec![],
             rendered: None,
         }
@@ -170,6 +180,7 @@ impl<'a> Diagnostic<'a> {
             code: DiagnosticCode::map_opt_string(db.code.clone(), je),
             level: db.level.to_str(),
             spans: DiagnosticSpan::from_multispan(&db.span, je),
+            message_parts: None,
             children: db.children.iter().map(|c| {
                 Diagnostic::from_sub_diagnostic(c, je)
             }).collect(),
@@ -185,6 +196,13 @@ impl<'a> Diagnostic<'a> {
             spans: db.render_span.as_ref()
                      .map(|sp| DiagnosticSpan::from_render_span(sp, je))
                      .unwrap_or_else(|| DiagnosticSpan::from_multispan(&db.span, je)),
+            // The line breaks only lay the message out for the terminal.
+            message_parts: db.styled_message.as_ref().map(|styled| {
+                styled.0.iter().filter(|part| match **part {
+                    StringPart::LineBreak(_) => false,
+                    _ => true,
+                }).collect()
+            }),
             children: vec![],
             rendered: db.render_span.as_ref()
                                     .and_then(|rsp| je.render(rsp)),
@@ -194,7 +212,7 @@ impl<'a> Diagnostic<'a> {
 
 impl DiagnosticSpan {
     fn from_span_label(span: SpanLabel,
-                       suggestion: Option<&String>,
+                       suggestion: Option<(&String, Applicability)>,
                        je: &JsonEmitter)
                        -> DiagnosticSpan {
         Self::from_span_etc(span.span,
@@ -207,7 +225,7 @@ impl DiagnosticSpan {
     fn from_span_etc(span: Span,
                      is_primary: bool,
                      label: Option<String>,
-                     suggestion: Option<&String>,
+                     suggestion: Option<(&String, Applicability)>,
                      je: &JsonEmitter)
                      -> DiagnosticSpan {
         // obtain the full backtrace from the `macro_backtrace`
@@ -227,7 +245,7 @@ impl DiagnosticSpan {
     fn from_span_full(span: Span,
                       is_primary: bool,
                       label: Option<String>,
-                      suggestion: Option<&String>,
+                      suggestion: Option<(&String, Applicability)>,
                       mut backtrace: vec::IntoIter<MacroBacktrace>,
                       je: &JsonEmitter)
                       -> DiagnosticSpan {
@@ -265,7 +283,8 @@ impl DiagnosticSpan {
             column_end: end.col.0 + 1,
             is_primary: is_primary,
             text: DiagnosticSpanLine::from_span(span, je),
-            suggested_replacement: suggestion.cloned(),
+            suggested_replacement: suggestion.map(|x| x.0.clone()),
+            suggestion_applicability: suggestion.map(|x| x.1),
             expansion: backtrace_step,
             label: label,
         }
@@ -284,9 +303,10 @@ impl DiagnosticSpan {
         suggestion.msp.span_labels()
                       .into_iter()
                       .zip(&suggestion.substitutes)
-                      .map(|(span_label, suggestion)| {
+                      .map(|(span_label, substitute)| {
                           DiagnosticSpan::from_span_label(span_label,
-                                                          Some(suggestion),
+                                                          Some((substitute,
+                                                                suggestion.applicability)),
                                                           je)
                       })
                       .collect()
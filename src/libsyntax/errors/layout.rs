@@ -0,0 +1,236 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Laying out the expected and found types of a mismatch as aligned trees.
+//!
+//! When the notes for two mismatched types would be too wide to compare at
+//! a glance, the generic parameters, tuple fields and the like of the types
+//! are broken onto lines of their own, indented by how deeply they are
+//! nested. Both types are broken at the same places, so that their
+//! components line up one below the other.
+
+use errors::{DiagnosticStyledString, StringPart};
+
+use std::cmp;
+use std::iter::Peekable;
+use std::mem;
+
+/// The number of columns, from the start of a message, that a type is laid
+/// out to fit in.
+const MAX_WIDTH: usize = 80;
+
+/// How much further the components of a broken group are indented.
+const INDENT: usize = 4;
+
+/// A component of a type as the human emitter renders it.
+enum Node {
+    Part(StringPart),
+    /// A bracketed, comma-separated list, such as the parameters of a
+    /// generic type.
+    Group(char, Vec<Vec<Node>>, char),
+}
+
+enum Piece {
+    Part(StringPart),
+    Open(char),
+    Separator,
+    Close(char),
+}
+
+/// Lays out `expected` and `found`, which start at column `col` of their
+/// messages, as aligned trees if either of them does not fit in
+/// `MAX_WIDTH` columns. They are left as they are if they do fit, or if
+/// they do not have the same shape.
+pub fn lay_out_types(expected: DiagnosticStyledString,
+                     found: DiagnosticStyledString,
+                     col: usize)
+                     -> (DiagnosticStyledString, DiagnosticStyledString) {
+    let width = cmp::max(parts_width(&expected.0), parts_width(&found.0));
+    if col + width <= MAX_WIDTH {
+        return (expected, found);
+    }
+    let (expected_nodes, found_nodes) = match (nodes(&expected.0), nodes(&found.0)) {
+        (Some(expected_nodes), Some(found_nodes)) => (expected_nodes, found_nodes),
+        _ => return (expected, found),
+    };
+
+    let mut styled = (DiagnosticStyledString::new(), DiagnosticStyledString::new());
+    let mut current_col = col;
+    if lay_out(&expected_nodes, &found_nodes, col, &mut current_col, &mut styled) {
+        styled
+    } else {
+        (expected, found)
+    }
+}
+
+/// Splits the parts of a type into the groups it is made of, or returns
+/// `None` if its brackets are not balanced.
+fn nodes(parts: &[StringPart]) -> Option<Vec<Node>> {
+    let mut pieces = pieces(parts).into_iter().peekable();
+    let nodes = parse(&mut pieces);
+    if pieces.next().is_some() {
+        return None;
+    }
+    nodes
+}
+
+/// Splits the normal parts of a type at its brackets and separators. The
+/// other parts are kept whole.
+fn pieces(parts: &[StringPart]) -> Vec<Piece> {
+    let mut pieces = vec![];
+    for part in parts {
+        let text = match *part {
+            StringPart::Normal(ref text) => text,
+            _ => {
+                pieces.push(Piece::Part(part.clone()));
+                continue;
+            }
+        };
+        let mut run = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let piece = match c {
+                '<' | '(' | '[' => Piece::Open(c),
+                '>' | ')' | ']' => Piece::Close(c),
+                ',' if chars.peek() == Some(&' ') => {
+                    chars.next();
+                    Piece::Separator
+                }
+                _ => {
+                    run.push(c);
+                    continue;
+                }
+            };
+            if !run.is_empty() {
+                pieces.push(Piece::Part(StringPart::Normal(mem::replace(&mut run, String::new()))));
+            }
+            pieces.push(piece);
+        }
+        if !run.is_empty() {
+            pieces.push(Piece::Part(StringPart::Normal(run)));
+        }
+    }
+    pieces
+}
+
+/// Parses the nodes up to the end of the type or of the enclosing group.
+fn parse<I: Iterator<Item=Piece>>(pieces: &mut Peekable<I>) -> Option<Vec<Node>> {
+    let mut nodes = vec![];
+    loop {
+        match pieces.peek() {
+            None | Some(&Piece::Separator) | Some(&Piece::Close(_)) => return Some(nodes),
+            Some(&Piece::Part(_)) | Some(&Piece::Open(_)) => {}
+        }
+        match pieces.next() {
+            Some(Piece::Part(part)) => nodes.push(Node::Part(part)),
+            Some(Piece::Open(open)) => {
+                let mut items = vec![];
+                loop {
+                    match parse(pieces) {
+                        Some(item) => items.push(item),
+                        None => return None,
+                    }
+                    match pieces.next() {
+                        Some(Piece::Separator) => {}
+                        Some(Piece::Close(close)) => {
+                            nodes.push(Node::Group(open, items, close));
+                            break;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Lays out `expected` and `found` from column `col` on, breaking the groups
+/// that do not fit in `MAX_WIDTH` columns in either of them onto lines
+/// indented by `indent` more than their own. Returns `false` if the two do
+/// not have the same shape.
+fn lay_out(expected: &[Node],
+           found: &[Node],
+           indent: usize,
+           col: &mut usize,
+           styled: &mut (DiagnosticStyledString, DiagnosticStyledString))
+           -> bool {
+    if expected.len() != found.len() {
+        return false;
+    }
+    for (expected, found) in expected.iter().zip(found) {
+        match (expected, found) {
+            (&Node::Part(ref expected_part), &Node::Part(ref found_part)) => {
+                *col += cmp::max(part_width(expected_part), part_width(found_part));
+                (styled.0).0.push(expected_part.clone());
+                (styled.1).0.push(found_part.clone());
+            }
+            (&Node::Group(open, ref expected_items, close),
+             &Node::Group(found_open, ref found_items, found_close))
+                    if open == found_open && close == found_close &&
+                       expected_items.len() == found_items.len() => {
+                let fits = *col + cmp::max(node_width(expected), node_width(found)) <= MAX_WIDTH;
+                push_both(styled, StringPart::Normal(open.to_string()));
+                *col += 1;
+                for (i, (expected_item, found_item)) in expected_items.iter()
+                                                                    .zip(found_items)
+                                                                    .enumerate() {
+                    if i > 0 {
+                        push_both(styled, StringPart::Normal(", ".to_string()));
+                        *col += 2;
+                    }
+                    if !fits {
+                        push_both(styled, StringPart::LineBreak(indent + INDENT));
+                        *col = indent + INDENT;
+                    }
+                    if !lay_out(expected_item, found_item, indent + INDENT, col, styled) {
+                        return false;
+                    }
+                }
+                push_both(styled, StringPart::Normal(close.to_string()));
+                *col += 1;
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn push_both(styled: &mut (DiagnosticStyledString, DiagnosticStyledString), part: StringPart) {
+    (styled.0).0.push(part.clone());
+    (styled.1).0.push(part);
+}
+
+fn part_width(part: &StringPart) -> usize {
+    match *part {
+        StringPart::Normal(ref text) | StringPart::Highlighted(ref text) => text.chars().count(),
+        StringPart::Elided(_) => 1,
+        StringPart::LineBreak(_) => 0,
+    }
+}
+
+fn parts_width(parts: &[StringPart]) -> usize {
+    parts.iter().map(part_width).sum()
+}
+
+/// The width of a node when it is not broken.
+fn node_width(node: &Node) -> usize {
+    match *node {
+        Node::Part(ref part) => part_width(part),
+        Node::Group(_, ref items, _) => {
+            let items_width: usize = items.iter().map(|item| nodes_width(item)).sum();
+            2 + items_width + 2 * (items.len() - 1)
+        }
+    }
+}
+
+fn nodes_width(nodes: &[Node]) -> usize {
+    nodes.iter().map(node_width).sum()
+}
@@ -18,7 +18,7 @@ use diagnostics;
 use errors::emitter::{Emitter, EmitterWriter};
 
 use std::cell::{RefCell, Cell};
-use std::{error, fmt};
+use std::{error, fmt, mem};
 use std::rc::Rc;
 use std::thread::panicking;
 use term;
@@ -26,6 +26,7 @@ use term;
 pub mod emitter;
 pub mod json;
 pub mod snippet;
+mod layout;
 
 #[derive(Clone)]
 pub enum RenderSpan {
@@ -45,6 +46,24 @@ pub enum RenderSpan {
 pub struct CodeSuggestion {
     msp: MultiSpan,
     substitutes: Vec<String>,
+    applicability: Applicability,
+}
+
+/// How sure we are that a suggestion is what the user meant, and so
+/// whether a tool may apply it without asking them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RustcEncodable)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended, and can be
+    /// applied automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but it is not
+    /// certain; it should result in valid code if applied.
+    MaybeIncorrect,
+    /// The suggestion contains placeholders like `(...)` that the user
+    /// has to fill in.
+    HasPlaceholders,
+    /// Nothing is known about how good the suggestion is.
+    Unspecified,
 }
 
 impl RenderSpan {
@@ -58,6 +77,17 @@ impl RenderSpan {
 }
 
 impl CodeSuggestion {
+    pub fn applicability(&self) -> Applicability {
+        self.applicability
+    }
+
+    /// The spans to replace, each with the text that replaces it.
+    pub fn substitution_parts(&self) -> Vec<(Span, &str)> {
+        self.msp.primary_spans().iter().cloned()
+            .zip(self.substitutes.iter().map(|s| &s[..]))
+            .collect()
+    }
+
     /// Returns the assembled code suggestion.
     pub fn splice_lines(&self, cm: &CodeMap) -> String {
         use codemap::{CharPos, Loc, Pos};
@@ -140,6 +170,43 @@ impl CodeSuggestion {
     }
 }
 
+/// A piece of a message that the human emitter may render differently
+/// from its text.
+#[derive(Clone, Debug, PartialEq, Eq, RustcEncodable)]
+pub enum StringPart {
+    Normal(String),
+    /// Text to draw the eye to, such as where two mismatched types differ.
+    Highlighted(String),
+    /// Text the human emitter abbreviates to `_`, such as a component that
+    /// two mismatched types have in common.
+    Elided(String),
+    /// Where the human emitter starts a new line, indented by this many
+    /// columns more than the first one of the message.
+    LineBreak(usize),
+}
+
+/// A message made of parts that the human emitter renders differently.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiagnosticStyledString(pub Vec<StringPart>);
+
+impl DiagnosticStyledString {
+    pub fn new() -> DiagnosticStyledString {
+        DiagnosticStyledString(vec![])
+    }
+
+    pub fn push_normal<S: Into<String>>(&mut self, text: S) {
+        self.0.push(StringPart::Normal(text.into()));
+    }
+
+    pub fn push_highlighted<S: Into<String>>(&mut self, text: S) {
+        self.0.push(StringPart::Highlighted(text.into()));
+    }
+
+    pub fn push_elided<S: Into<String>>(&mut self, text: S) {
+        self.0.push(StringPart::Elided(text.into()));
+    }
+}
+
 /// Used as a return value to signify a fatal error occurred. (It is also
 /// used as the argument to panic at the moment, but that will eventually
 /// not be true.)
@@ -193,6 +260,9 @@ pub struct DiagnosticBuilder<'a> {
 struct SubDiagnostic {
     level: Level,
     message: String,
+    /// The message split into parts, if the human emitter should render
+    /// some of them differently.
+    styled_message: Option<DiagnosticStyledString>,
     span: MultiSpan,
     render_span: Option<RenderSpan>,
 }
@@ -205,6 +275,7 @@ impl<'a> DiagnosticBuilder<'a> {
         }
 
         self.handler.emit.borrow_mut().emit_struct(&self);
+        self.handler.record_suggestions(&self);
         self.cancel();
         self.handler.panic_if_treat_err_as_bug();
 
@@ -254,6 +325,46 @@ impl<'a> DiagnosticBuilder<'a> {
         self
     }
 
+    /// Like `note_expected_found`, but the human emitter renders the
+    /// expected and found values as `expected_styled` and `found_styled`,
+    /// e.g. with what they have in common elided and their differences
+    /// highlighted. Values too wide for one line are laid out as trees,
+    /// aligned with each other. The other emitters use the plain values.
+    pub fn note_expected_found_styled(&mut self,
+                                      label: &fmt::Display,
+                                      expected: &fmt::Display,
+                                      found: &fmt::Display,
+                                      expected_styled: DiagnosticStyledString,
+                                      found_styled: DiagnosticStyledString)
+                                      -> &mut DiagnosticBuilder<'a>
+    {
+        let expected_prefix = format!("expected {} `", label);
+        let found_prefix = format!("   found {} `", label);
+        let (expected_styled, found_styled) =
+            layout::lay_out_types(expected_styled, found_styled, expected_prefix.len());
+        self.styled_note(&format!("expected {} `{}`", label, expected),
+                         &expected_prefix,
+                         expected_styled);
+        self.styled_note(&format!("   found {} `{}`", label, found),
+                         &found_prefix,
+                         found_styled);
+        self
+    }
+
+    fn styled_note(&mut self, message: &str, prefix: &str, value: DiagnosticStyledString) {
+        let mut styled = DiagnosticStyledString::new();
+        styled.push_normal(prefix);
+        styled.0.extend(value.0);
+        styled.push_normal("`");
+        self.children.push(SubDiagnostic {
+            level: Level::Note,
+            message: message.to_owned(),
+            styled_message: Some(styled),
+            span: MultiSpan::new(),
+            render_span: None,
+        });
+    }
+
     pub fn note(&mut self, msg: &str) -> &mut DiagnosticBuilder<'a> {
         self.sub(Level::Note, msg, MultiSpan::new(), None);
         self
@@ -295,9 +406,36 @@ impl<'a> DiagnosticBuilder<'a> {
                                                msg: &str,
                                                suggestion: String)
                                                -> &mut DiagnosticBuilder<'a> {
+        self.span_suggestion_with_applicability(sp, msg, suggestion, Applicability::Unspecified)
+    }
+    /// Like `span_suggestion`, but also says whether the edit can be
+    /// applied by a tool without the user looking at it.
+    pub fn span_suggestion_with_applicability<S: Into<MultiSpan>>(&mut self,
+                                                                  sp: S,
+                                                                  msg: &str,
+                                                                  suggestion: String,
+                                                                  applicability: Applicability)
+                                                                  -> &mut DiagnosticBuilder<'a> {
         self.sub(Level::Help, msg, MultiSpan::new(), Some(Suggestion(CodeSuggestion {
             msp: sp.into(),
             substitutes: vec![suggestion],
+            applicability: applicability,
+        })));
+        self
+    }
+    /// Suggests several edits which only make sense together, such as
+    /// removing the same `mut` from each alternative of a pattern. The
+    /// spans must be disjoint and in the same file.
+    pub fn multipart_suggestion(&mut self,
+                                msg: &str,
+                                parts: Vec<(Span, String)>,
+                                applicability: Applicability)
+                                -> &mut DiagnosticBuilder<'a> {
+        let (spans, substitutes) = parts.into_iter().unzip();
+        self.sub(Level::Help, msg, MultiSpan::new(), Some(Suggestion(CodeSuggestion {
+            msp: MultiSpan::from_spans(spans),
+            substitutes: substitutes,
+            applicability: applicability,
         })));
         self
     }
@@ -345,6 +483,7 @@ impl<'a> DiagnosticBuilder<'a> {
         let sub = SubDiagnostic {
             level: level,
             message: message.to_owned(),
+            styled_message: None,
             span: span,
             render_span: render_span,
         };
@@ -382,6 +521,9 @@ pub struct Handler {
     treat_err_as_bug: bool,
     continue_after_error: Cell<bool>,
     delayed_span_bug: RefCell<Option<(MultiSpan, String)>>,
+    /// The machine-applicable suggestions of the diagnostics emitted
+    /// so far, if `set_collect_suggestions` asked for them.
+    collected_suggestions: RefCell<Option<Vec<CodeSuggestion>>>,
 }
 
 impl Handler {
@@ -405,6 +547,7 @@ impl Handler {
             treat_err_as_bug: treat_err_as_bug,
             continue_after_error: Cell::new(true),
             delayed_span_bug: RefCell::new(None),
+            collected_suggestions: RefCell::new(None),
         }
     }
 
@@ -412,6 +555,31 @@ impl Handler {
         self.continue_after_error.set(continue_after_error);
     }
 
+    /// Keeps the machine-applicable suggestions of the diagnostics
+    /// emitted from now on, for `take_suggestions`.
+    pub fn set_collect_suggestions(&self, collect: bool) {
+        *self.collected_suggestions.borrow_mut() = if collect { Some(vec![]) } else { None };
+    }
+
+    pub fn take_suggestions(&self) -> Vec<CodeSuggestion> {
+        match *self.collected_suggestions.borrow_mut() {
+            Some(ref mut suggestions) => mem::replace(suggestions, vec![]),
+            None => vec![],
+        }
+    }
+
+    fn record_suggestions(&self, db: &DiagnosticBuilder) {
+        if let Some(ref mut suggestions) = *self.collected_suggestions.borrow_mut() {
+            for child in &db.children {
+                if let Some(Suggestion(ref suggestion)) = child.render_span {
+                    if suggestion.applicability == Applicability::MachineApplicable {
+                        suggestions.push(suggestion.clone());
+                    }
+                }
+            }
+        }
+    }
+
     pub fn struct_dummy<'a>(&'a self) -> DiagnosticBuilder<'a> {
         DiagnosticBuilder::new(self, Level::Cancelled, "")
     }
@@ -440,6 +608,14 @@ impl Handler {
         }
         result
     }
+    pub fn struct_span_note<'a, S: Into<MultiSpan>>(&'a self,
+                                                    sp: S,
+                                                    msg: &str)
+                                                    -> DiagnosticBuilder<'a> {
+        let mut result = DiagnosticBuilder::new(self, Level::Note, msg);
+        result.set_span(sp);
+        result
+    }
     pub fn struct_warn<'a>(&'a self, msg: &str) -> DiagnosticBuilder<'a> {
         let mut result = DiagnosticBuilder::new(self, Level::Warning, msg);
         if !self.can_emit_warnings {
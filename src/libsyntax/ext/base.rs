@@ -17,6 +17,7 @@ use codemap::{CodeMap, Span, ExpnId, ExpnInfo, NO_EXPANSION};
 use errors::DiagnosticBuilder;
 use ext;
 use ext::expand;
+use ext::macro_scope::{MacroResolver, ModulePath};
 use ext::tt::macro_rules;
 use feature_gate::GatedCfgAttr;
 use parse;
@@ -438,6 +439,14 @@ pub enum SyntaxExtension {
 
 pub type NamedSyntaxExtension = (Name, SyntaxExtension);
 
+/// A macro loaded from an external crate through `#[macro_use] extern crate`.
+pub enum LoadedMacro {
+    /// A `macro_rules!` definition exported by the crate.
+    Def(ast::MacroDef),
+    /// A `#[derive(Trait)]` mode defined by a `proc-macro` crate.
+    CustomDerive(String, Box<MultiItemDecorator + 'static>),
+}
+
 pub struct BlockInfo {
     /// Should macros escape from this scope?
     pub macros_escape: bool,
@@ -551,6 +560,10 @@ pub struct ExtCtxt<'a> {
     pub exported_macros: Vec<ast::MacroDef>,
 
     pub syntax_env: SyntaxEnv,
+    /// Names the macros of `macro` items, which are named by path.
+    pub macro_resolver: &'a mut MacroResolver,
+    /// Names of the derive modes loaded from `proc-macro` crates.
+    pub derive_modes: HashSet<Name>,
     pub recursion_count: usize,
 
     pub filename: Option<String>,
@@ -561,7 +574,8 @@ pub struct ExtCtxt<'a> {
 impl<'a> ExtCtxt<'a> {
     pub fn new(parse_sess: &'a parse::ParseSess, cfg: ast::CrateConfig,
                ecfg: expand::ExpansionConfig<'a>,
-               feature_gated_cfgs: &'a mut Vec<GatedCfgAttr>) -> ExtCtxt<'a> {
+               feature_gated_cfgs: &'a mut Vec<GatedCfgAttr>,
+               macro_resolver: &'a mut MacroResolver) -> ExtCtxt<'a> {
         let env = initial_syntax_expander_table(&ecfg);
         ExtCtxt {
             parse_sess: parse_sess,
@@ -573,6 +587,8 @@ impl<'a> ExtCtxt<'a> {
             feature_gated_cfgs: feature_gated_cfgs,
             exported_macros: Vec::new(),
             syntax_env: env,
+            macro_resolver: macro_resolver,
+            derive_modes: HashSet::new(),
             recursion_count: 0,
 
             filename: None,
@@ -678,6 +694,14 @@ impl<'a> ExtCtxt<'a> {
     }
 
     pub fn insert_macro(&mut self, def: ast::MacroDef) {
+        if def.decl.is_some() {
+            // A `macro` item of another crate, which is named by its path.
+            if def.use_locally {
+                let ext = Rc::new(macro_rules::compile(self, &def));
+                self.macro_resolver.define_imported(ext, def);
+            }
+            return;
+        }
         if def.export {
             self.exported_macros.push(def.clone());
         }
@@ -687,12 +711,83 @@ impl<'a> ExtCtxt<'a> {
         }
     }
 
+    /// The module being expanded.
+    pub fn current_module(&self) -> ModulePath {
+        ModulePath::local(self.mod_path.iter().map(|ident| ident.name).collect())
+    }
+
+    /// Finds the macro named by the path of an invocation, along with the
+    /// module that the names in its expansion are resolved in if it is the
+    /// macro of a `macro` item. Reports an error if there is no such macro.
+    ///
+    /// The path is resolved where the macro is defined if it comes from the
+    /// body of a `macro` item, and in the module being expanded otherwise.
+    /// A single name may also name a macro of a block being expanded or a
+    /// `macro_rules!` macro.
+    pub fn find_macro(&mut self, path: &ast::Path)
+                      -> Option<(Rc<SyntaxExtension>, Option<ModulePath>)> {
+        if path.segments.len() > 1 && !self.ecfg.enable_decl_macro() {
+            self.span_err(path.span, "expected macro name without module separators");
+            return None;
+        }
+
+        let ident = path.segments[0].identifier;
+        let def_site = mtwt::def_site(ident.ctxt);
+        if path.segments.len() == 1 && !path.global && def_site.is_none() {
+            if let Some(item) = self.macro_resolver.find_in_blocks(ident.name) {
+                return Some((item.ext.clone(), Some(item.def_site.clone())));
+            }
+        }
+
+        let module = def_site.unwrap_or_else(|| self.current_module());
+        match self.macro_resolver.resolve_macro(&module, path) {
+            Ok(item) => return Some((item.ext.clone(), Some(item.def_site.clone()))),
+            Err(Some(msg)) => {
+                self.span_err(path.span, &msg);
+                return None;
+            }
+            Err(None) => {}
+        }
+
+        if let Some(extension) = self.syntax_env.find(ident.name) {
+            return Some((extension, None));
+        }
+        let mut err = self.struct_span_err(path.span,
+                                           &format!("macro undefined: '{}!'", ident.name));
+        self.suggest_macro_name(&ident.name.as_str(), &mut err);
+        err.emit();
+        None
+    }
+
+    /// Registers a custom derive mode, making `#[derive(name)]` expand through
+    /// `ext`.
+    pub fn insert_custom_derive(&mut self, name: &str, ext: Box<MultiItemDecorator>) {
+        let ext_name = intern(&format!("derive_{}", name));
+        if self.syntax_env.find(ext_name).is_some() {
+            self.parse_sess.span_diagnostic.err(&format!("cannot shadow existing derive \
+                                                          mode `{}`", name));
+            return
+        }
+        self.derive_modes.insert(intern(name));
+        self.syntax_env.insert(ext_name, MultiDecorator(ext));
+    }
+
+    pub fn is_custom_derive(&self, name: &str) -> bool {
+        self.derive_modes.contains(&intern(name))
+    }
+
     pub fn struct_span_warn(&self,
                             sp: Span,
                             msg: &str)
                             -> DiagnosticBuilder<'a> {
         self.parse_sess.span_diagnostic.struct_span_warn(sp, msg)
     }
+    pub fn struct_span_note(&self,
+                            sp: Span,
+                            msg: &str)
+                            -> DiagnosticBuilder<'a> {
+        self.parse_sess.span_diagnostic.struct_span_note(sp, msg)
+    }
     pub fn struct_span_err(&self,
                            sp: Span,
                            msg: &str)
@@ -13,6 +13,7 @@ use ast::{Local, Ident, Mac_, Name, SpannedIdent};
 use ast::{MacStmtStyle, Mrk, Stmt, StmtKind, ItemKind};
 use ast::TokenTree;
 use ast;
+use ext::macro_scope;
 use ext::mtwt;
 use ext::build::AstBuilder;
 use attr;
@@ -21,7 +22,7 @@ use codemap;
 use codemap::{Span, Spanned, ExpnInfo, ExpnId, NameAndSpan, MacroBang, MacroAttribute};
 use config::StripUnconfigured;
 use ext::base::*;
-use feature_gate::{self, Features};
+use feature_gate::{self, Features, GateIssue};
 use fold;
 use fold::*;
 use util::move_map::MoveMap;
@@ -33,7 +34,7 @@ use visit;
 use visit::Visitor;
 use std_inject;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 // A trait for AST nodes and AST node lists into which macro invocations may expand.
 trait MacroGenerable: Sized {
@@ -202,20 +203,15 @@ fn expand_mac_invoc<T>(mac: ast::Mac, ident: Option<Ident>, attrs: Vec<ast::Attr
                                           &fld.cx.ecfg.features.unwrap());
         }
 
-        if path.segments.len() > 1 {
-            fld.cx.span_err(path.span, "expected macro name without module separators");
-            return None;
-        }
-
-        let extname = path.segments[0].identifier.name;
-        let extension = if let Some(extension) = fld.cx.syntax_env.find(extname) {
-            extension
-        } else {
-            let mut err = fld.cx.struct_span_err(path.span,
-                                                 &format!("macro undefined: '{}!'", &extname));
-            fld.cx.suggest_macro_name(&extname.as_str(), &mut err);
-            err.emit();
-            return None;
+        let extname = path.segments.last().unwrap().identifier.name;
+        let extension = match fld.cx.find_macro(path) {
+            Some((extension, def_site)) => {
+                if let Some(def_site) = def_site {
+                    mtwt::set_def_site(mark, def_site);
+                }
+                extension
+            }
+            None => return None,
         };
 
         let ident = ident.unwrap_or(keywords::Invalid.ident());
@@ -296,6 +292,7 @@ fn expand_mac_invoc<T>(mac: ast::Mac, ident: Option<Ident>, attrs: Vec<ast::Attr
                     export: attr::contains_name(&attrs, "macro_export"),
                     allow_internal_unstable: attr::contains_name(&attrs, "allow_internal_unstable"),
                     attrs: attrs,
+                    decl: None,
                 });
 
                 // macro_rules! has a side effect but expands to nothing.
@@ -320,7 +317,7 @@ fn expand_mac_invoc<T>(mac: ast::Mac, ident: Option<Ident>, attrs: Vec<ast::Attr
         expanded
     } else {
         let msg = format!("non-{kind} macro in {kind} position: {name}",
-                          name = path.segments[0].identifier.name, kind = T::kind_name());
+                          name = path, kind = T::kind_name());
         fld.cx.span_err(path.span, &msg);
         return T::dummy(span);
     };
@@ -329,6 +326,18 @@ fn expand_mac_invoc<T>(mac: ast::Mac, ident: Option<Ident>, attrs: Vec<ast::Attr
     let configured = marked.fold_with(&mut fld.strip_unconfigured());
     let fully_expanded = configured.fold_with(fld);
     fld.cx.bt_pop();
+
+    // The items that a `macro` item expands to are only visible to the rest
+    // of the expansion, unless their names were passed in by the invocation.
+    if mtwt::has_def_site(mark) {
+        let mut collector = ItemRenamer { mark: mark, renames: HashMap::new(), items: true };
+        let collected = fully_expanded.fold_with(&mut collector);
+        if collector.renames.is_empty() {
+            return collected;
+        }
+        let mut renamer = ItemRenamer { items: false, ..collector };
+        return collected.fold_with(&mut renamer);
+    }
     fully_expanded
 }
 
@@ -730,6 +739,10 @@ fn expand_annotatable(a: Annotatable,
 
     let mut new_items: SmallVector<Annotatable> = match a {
         Annotatable::Item(it) => match it.node {
+            ast::ItemKind::Mac(ref mac) if macro_scope::is_macro_item(mac) => {
+                expand_macro_item(&it, fld);
+                SmallVector::zero()
+            }
             ast::ItemKind::Mac(..) => {
                 let new_items: SmallVector<P<ast::Item>> = it.and_then(|it| match it.node {
                     ItemKind::Mac(mac) =>
@@ -743,6 +756,11 @@ fn expand_annotatable(a: Annotatable,
                 let valid_ident =
                     it.ident.name != keywords::Invalid.name();
 
+                if fld.cx.ecfg.enable_decl_macro() && !fld.cx.in_block {
+                    let module = fld.cx.current_module();
+                    fld.cx.macro_resolver.record_item(&module, &it);
+                }
+
                 if valid_ident {
                     fld.cx.mod_push(it.ident);
                 }
@@ -756,6 +774,14 @@ fn expand_annotatable(a: Annotatable,
                 result.into_iter().map(|i| Annotatable::Item(i)).collect()
             },
             _ => {
+                // Imports may name macros, which are resolved before the
+                // crate is.
+                if let ast::ItemKind::Use(..) = it.node {
+                    if fld.cx.ecfg.enable_decl_macro() && !fld.cx.in_block {
+                        let module = fld.cx.current_module();
+                        fld.cx.macro_resolver.record_item(&module, &it);
+                    }
+                }
                 let it = P(ast::Item {
                     attrs: new_attrs,
                     ..(*it).clone()
@@ -794,6 +820,18 @@ fn expand_annotatable(a: Annotatable,
     new_items
 }
 
+/// Defines the macro of a `macro` item, which expands to nothing.
+fn expand_macro_item(it: &ast::Item, fld: &mut MacroExpander) {
+    if !fld.cx.ecfg.enable_decl_macro() {
+        feature_gate::emit_feature_err(&fld.cx.parse_sess.span_diagnostic, "decl_macro", it.span,
+                                       GateIssue::Language, "`macro` items are experimental");
+        return;
+    }
+    let module = fld.cx.current_module();
+    let in_block = fld.cx.in_block;
+    macro_scope::define_macro_item(fld.cx, &module, in_block, it);
+}
+
 // Partition a set of attributes into one kind of attribute, and other kinds.
 macro_rules! partition {
     ($fn_name: ident, $variant: ident) => {
@@ -1055,7 +1093,9 @@ impl<'a, 'b> Folder for MacroExpander<'a, 'b> {
 
     fn fold_block(&mut self, block: P<Block>) -> P<Block> {
         let was_in_block = ::std::mem::replace(&mut self.cx.in_block, true);
+        self.cx.macro_resolver.push_block();
         let result = expand_block(block, self);
+        self.cx.macro_resolver.pop_block();
         self.cx.in_block = was_in_block;
         result
     }
@@ -1133,12 +1173,13 @@ impl<'feat> ExpansionConfig<'feat> {
         fn enable_allow_internal_unstable = allow_internal_unstable,
         fn enable_custom_derive = custom_derive,
         fn enable_pushpop_unsafe = pushpop_unsafe,
+        fn enable_decl_macro = decl_macro,
     }
 }
 
 pub fn expand_crate(mut cx: ExtCtxt,
                     // these are the macros being imported to this crate:
-                    imported_macros: Vec<ast::MacroDef>,
+                    imported_macros: Vec<LoadedMacro>,
                     user_exts: Vec<NamedSyntaxExtension>,
                     c: Crate) -> (Crate, HashSet<Name>) {
     if std_inject::no_core(&c) {
@@ -1152,7 +1193,12 @@ pub fn expand_crate(mut cx: ExtCtxt,
         let mut expander = MacroExpander::new(&mut cx);
 
         for def in imported_macros {
-            expander.cx.insert_macro(def);
+            match def {
+                LoadedMacro::Def(def) => expander.cx.insert_macro(def),
+                LoadedMacro::CustomDerive(name, ext) => {
+                    expander.cx.insert_custom_derive(&name, ext);
+                }
+            }
         }
 
         for (name, extension) in user_exts {
@@ -1160,8 +1206,10 @@ pub fn expand_crate(mut cx: ExtCtxt,
         }
 
         let err_count = cx.parse_sess.span_diagnostic.err_count();
+        macro_scope::collect(expander.cx, &c);
         let mut ret = expander.fold_crate(c);
         ret.exported_macros = expander.cx.exported_macros.clone();
+        ret.exported_macros.extend(expander.cx.macro_resolver.exports());
 
         if cx.parse_sess.span_diagnostic.err_count() > err_count {
             cx.parse_sess.span_diagnostic.abort_if_errors();
@@ -1207,6 +1255,84 @@ impl Folder for Marker {
     }
 }
 
+/// Gives the items that the expansion of a `macro` item defines with names
+/// from the body of the macro fresh names, and renames the paths and
+/// patterns of the expansion that name them, so that the items cannot be
+/// named from outside of the expansion. The first pass renames the items,
+/// the second the paths and patterns.
+struct ItemRenamer {
+    mark: Mrk,
+    /// The fresh names of the items, by their names in the body.
+    renames: HashMap<Name, Name>,
+    items: bool,
+}
+
+impl ItemRenamer {
+    fn rename(&self, ident: Ident) -> Ident {
+        match self.renames.get(&ident.name) {
+            Some(&name) if mtwt::outermost_mark(ident.ctxt) == Some(self.mark) => {
+                Ident::with_empty_ctxt(name)
+            }
+            _ => ident,
+        }
+    }
+}
+
+impl Folder for ItemRenamer {
+    fn fold_item(&mut self, item: P<ast::Item>) -> SmallVector<P<ast::Item>> {
+        if !self.items {
+            return noop_fold_item(item, self);
+        }
+        let from_body = match item.node {
+            ItemKind::Use(..) | ItemKind::ExternCrate(..) | ItemKind::Impl(..) |
+            ItemKind::DefaultImpl(..) | ItemKind::ForeignMod(..) | ItemKind::Mac(..) => false,
+            _ => mtwt::outermost_mark(item.ident.ctxt) == Some(self.mark),
+        };
+        let item = if from_body {
+            let name = fresh_name(item.ident);
+            self.renames.insert(item.ident.name, name);
+            item.map(|item| ast::Item { ident: Ident::with_empty_ctxt(name), ..item })
+        } else {
+            item
+        };
+        noop_fold_item(item, self)
+    }
+    fn fold_path(&mut self, path: ast::Path) -> ast::Path {
+        let path = noop_fold_path(path, self);
+        if self.items {
+            return path;
+        }
+        let ast::Path { span, global, segments } = path;
+        ast::Path {
+            span: span,
+            global: global,
+            segments: segments.into_iter().map(|segment| {
+                ast::PathSegment { identifier: self.rename(segment.identifier), ..segment }
+            }).collect(),
+        }
+    }
+    fn fold_pat(&mut self, pat: P<ast::Pat>) -> P<ast::Pat> {
+        let pat = noop_fold_pat(pat, self);
+        if self.items {
+            return pat;
+        }
+        pat.map(|ast::Pat {id, node, span}| match node {
+            PatKind::Ident(binding_mode, Spanned{span: sp, node: ident}, sub) => {
+                let ident = self.rename(ident);
+                ast::Pat {
+                    id: id,
+                    node: PatKind::Ident(binding_mode, Spanned{span: sp, node: ident}, sub),
+                    span: span,
+                }
+            }
+            node => ast::Pat { id: id, node: node, span: span },
+        })
+    }
+    fn fold_mac(&mut self, mac: ast::Mac) -> ast::Mac {
+        fold::noop_fold_mac(mac, self)
+    }
+}
+
 // apply a given mark to the given token trees. Used prior to expansion of a macro.
 fn mark_tts(tts: &[TokenTree], m: Mrk) -> Vec<TokenTree> {
     noop_fold_tts(tts, &mut Marker{mark:m, expn_id: None})
@@ -1239,6 +1365,7 @@ mod tests {
     use ast::Name;
     use codemap;
     use ext::base::ExtCtxt;
+    use ext::macro_scope::DummyMacroResolver;
     use ext::mtwt;
     use fold::Folder;
     use parse;
@@ -1310,7 +1437,8 @@ mod tests {
             Vec::new(), &sess).unwrap();
         // should fail:
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast);
     }
 
@@ -1325,7 +1453,8 @@ mod tests {
             src,
             Vec::new(), &sess).unwrap();
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast);
     }
 
@@ -1339,7 +1468,8 @@ mod tests {
             src,
             Vec::new(), &sess).unwrap();
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast);
     }
 
@@ -1348,7 +1478,8 @@ mod tests {
         let crate_ast = panictry!(string_to_parser(&ps, crate_str).parse_crate_mod());
         // the cfg argument actually does matter, here...
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&ps, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&ps, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast).0
     }
 
@@ -0,0 +1,228 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+//! The scopes that the macros of `macro` items are named in.
+//!
+//! A `macro_rules!` macro is in scope after its definition in the text of the
+//! crate. The macro of a `macro` item is instead an item of the module that
+//! defines it: it is named by path, subject to privacy, can be imported with
+//! `use`, and is exported to other crates if it is public and its module can
+//! be named from the crate root. Macros are named during expansion, long
+//! before the crate is resolved, so the paths of macro invocations are
+//! resolved by a `MacroResolver`, which the resolver of the crate provides.
+//! The modules, `macro` items, imports and `extern crate` items of the crate
+//! are handed to it before the crate is expanded.
+
+use ast::{self, DeclMacro, Name, Visibility};
+use attr;
+use codemap::Span;
+use ext::base::{ExtCtxt, SyntaxExtension};
+use ext::tt::macro_rules;
+use parse::token::keywords;
+
+use std::rc::Rc;
+
+/// A module, as the path to it from the root of its crate.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ModulePath {
+    /// The name of the crate of the module, or `None` for the crate being
+    /// compiled.
+    pub krate: Option<Name>,
+    pub path: Vec<Name>,
+}
+
+impl ModulePath {
+    pub fn local(path: Vec<Name>) -> ModulePath {
+        ModulePath { krate: None, path: path }
+    }
+
+    pub fn root(&self) -> ModulePath {
+        ModulePath { krate: self.krate, path: vec![] }
+    }
+
+    pub fn child(&self, name: Name) -> ModulePath {
+        let mut path = self.path.clone();
+        path.push(name);
+        ModulePath { krate: self.krate, path: path }
+    }
+
+    pub fn parent(&self) -> Option<ModulePath> {
+        self.path.split_last().map(|(_, path)| {
+            ModulePath { krate: self.krate, path: path.to_vec() }
+        })
+    }
+
+    /// Whether this is `module` or one of its descendants.
+    pub fn is_within(&self, module: &ModulePath) -> bool {
+        self.krate == module.krate && self.path.starts_with(&module.path)
+    }
+}
+
+/// The macro of a `macro` item.
+pub struct MacroItem {
+    pub ext: Rc<SyntaxExtension>,
+    /// The module the macro is defined in, where the names in its body are
+    /// resolved.
+    pub def_site: ModulePath,
+    pub vis: Visibility,
+    pub def: ast::MacroDef,
+}
+
+/// Names the macros of `macro` items during expansion.
+pub trait MacroResolver {
+    /// Records an item of `module` that the path of a macro may name or go
+    /// through.
+    fn record_item(&mut self, module: &ModulePath, item: &ast::Item);
+
+    /// Whether the macro named `name` of `module`, or of the innermost block
+    /// being expanded if `in_block`, is the one defined by the item at `span`.
+    fn is_defined_by(&self, module: &ModulePath, in_block: bool, name: Name, span: Span)
+                     -> bool;
+
+    /// Defines a macro in `module`, or in the innermost block being expanded
+    /// if `in_block`. Fails if there already is a macro with the same name.
+    fn define(&mut self, module: &ModulePath, in_block: bool, item: MacroItem)
+              -> Result<(), ()>;
+
+    /// Defines a public macro of another crate, in the module given by the
+    /// definition.
+    fn define_imported(&mut self, ext: Rc<SyntaxExtension>, def: ast::MacroDef);
+
+    fn push_block(&mut self);
+
+    fn pop_block(&mut self);
+
+    /// The macro named `name` in the blocks being expanded, if any.
+    fn find_in_blocks(&self, name: Name) -> Option<Rc<MacroItem>>;
+
+    /// Resolves the path of a macro invocation in `module`. Fails with `None`
+    /// if the path is a single name that does not name a macro there, as it
+    /// may still name a `macro_rules!` macro, or with the error to report.
+    fn resolve_macro(&self, module: &ModulePath, path: &ast::Path)
+                     -> Result<Rc<MacroItem>, Option<String>>;
+
+    /// The definitions of the macros that other crates can name, with the
+    /// modules that they can be named from.
+    fn exports(&self) -> Vec<ast::MacroDef>;
+}
+
+/// A `MacroResolver` that knows no macros, for expanding code that cannot
+/// use `macro` items.
+pub struct DummyMacroResolver;
+
+impl MacroResolver for DummyMacroResolver {
+    fn record_item(&mut self, _module: &ModulePath, _item: &ast::Item) {}
+
+    fn is_defined_by(&self, _module: &ModulePath, _in_block: bool, _name: Name, _span: Span)
+                     -> bool {
+        false
+    }
+
+    fn define(&mut self, _module: &ModulePath, _in_block: bool, _item: MacroItem)
+              -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn define_imported(&mut self, _ext: Rc<SyntaxExtension>, _def: ast::MacroDef) {}
+
+    fn push_block(&mut self) {}
+
+    fn pop_block(&mut self) {}
+
+    fn find_in_blocks(&self, _name: Name) -> Option<Rc<MacroItem>> {
+        None
+    }
+
+    fn resolve_macro(&self, _module: &ModulePath, _path: &ast::Path)
+                     -> Result<Rc<MacroItem>, Option<String>> {
+        Err(None)
+    }
+
+    fn exports(&self) -> Vec<ast::MacroDef> {
+        vec![]
+    }
+}
+
+/// Whether the macro invocation is a `macro` item, as those are parsed into
+/// invocations of `macro` with the rules of the macro.
+pub fn is_macro_item(mac: &ast::Mac) -> bool {
+    let path = &mac.node.path;
+    !path.global && path.segments.len() == 1 &&
+        path.segments[0].identifier.name == keywords::Macro.name()
+}
+
+/// Collects the modules, `macro` items, imports and `extern crate` items of
+/// the crate, so that macros can be named before the items that define them
+/// are expanded. Nothing is collected unless `macro` items are enabled.
+pub fn collect(cx: &mut ExtCtxt, krate: &ast::Crate) {
+    if cx.ecfg.enable_decl_macro() {
+        collect_module(cx, &ModulePath::local(vec![]), &krate.module);
+    }
+}
+
+fn collect_module(cx: &mut ExtCtxt, module: &ModulePath, m: &ast::Mod) {
+    for item in &m.items {
+        cx.macro_resolver.record_item(module, item);
+        match item.node {
+            ast::ItemKind::Mod(ref inner) => {
+                collect_module(cx, &module.child(item.ident.name), inner);
+            }
+            ast::ItemKind::Mac(ref mac) if is_macro_item(mac) => {
+                define_macro_item(cx, module, false, item);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Defines the macro of a `macro` item of `module`, or of the innermost
+/// block being expanded if `in_block`.
+pub fn define_macro_item(cx: &mut ExtCtxt, module: &ModulePath, in_block: bool, item: &ast::Item) {
+    let name = item.ident.name;
+    if cx.macro_resolver.is_defined_by(module, in_block, name, item.span) {
+        return;
+    }
+
+    let body = match item.node {
+        ast::ItemKind::Mac(ref mac) => mac.node.tts.clone(),
+        _ => return,
+    };
+    let def = ast::MacroDef {
+        ident: item.ident,
+        attrs: item.attrs.clone(),
+        id: ast::DUMMY_NODE_ID,
+        span: item.span,
+        imported_from: None,
+        export: false,
+        use_locally: true,
+        allow_internal_unstable: attr::contains_name(&item.attrs, "allow_internal_unstable"),
+        body: body,
+        decl: Some(DeclMacro {
+            krate: None,
+            module: module.path.clone(),
+            def_site_crate: None,
+            def_site: module.path.clone(),
+        }),
+    };
+    let ext = Rc::new(macro_rules::compile(cx, &def));
+
+    let macro_item = MacroItem {
+        ext: ext,
+        def_site: module.clone(),
+        vis: item.vis.clone(),
+        def: def,
+    };
+    if cx.macro_resolver.define(module, in_block, macro_item).is_err() {
+        let scope = if in_block { "block" } else { "module" };
+        cx.span_err(item.span, &format!("a macro named `{}` has already been defined in this {}",
+                                        name, scope));
+    }
+}
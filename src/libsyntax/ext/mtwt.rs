@@ -18,6 +18,8 @@
 pub use self::SyntaxContext_::*;
 
 use ast::{Ident, Mrk, Name, SyntaxContext};
+use ext::macro_scope::ModulePath;
+use parse::token::keywords;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -135,6 +137,7 @@ pub fn clear_tables() {
         *table.rename_memo.borrow_mut() = HashMap::new();
     });
     with_resolve_table_mut(|table| *table = HashMap::new());
+    with_def_sites_mut(|def_sites| *def_sites = HashMap::new());
 }
 
 /// Reset the tables to their initial state
@@ -145,6 +148,7 @@ pub fn reset_tables() {
         *table.rename_memo.borrow_mut() = HashMap::new();
     });
     with_resolve_table_mut(|table| *table = HashMap::new());
+    with_def_sites_mut(|def_sites| *def_sites = HashMap::new());
 }
 
 /// Add a value to the end of a vec, return its index
@@ -256,6 +260,50 @@ fn marksof_internal(ctxt: SyntaxContext,
     }
 }
 
+/// Return the outermost mark of a context, if it has any marks that are not
+/// cancelled out.
+pub fn outermost_mark(ctxt: SyntaxContext) -> Option<Mrk> {
+    marksof(ctxt, keywords::Invalid.name()).first().cloned()
+}
+
+type DefSiteTable = HashMap<Mrk, ModulePath>;
+
+fn with_def_sites_mut<T, F>(op: F) -> T where
+    F: FnOnce(&mut DefSiteTable) -> T,
+{
+    thread_local!(static DEF_SITES_KEY: RefCell<DefSiteTable> = {
+        RefCell::new(HashMap::new())
+    });
+
+    DEF_SITES_KEY.with(move |slot| op(&mut *slot.borrow_mut()))
+}
+
+/// Record that the expansion with the given mark is of a `macro` item that
+/// was defined in the given module.
+pub fn set_def_site(mark: Mrk, module: ModulePath) {
+    with_def_sites_mut(|def_sites| def_sites.insert(mark, module));
+}
+
+/// Whether the expansion with the given mark is of a `macro` item.
+pub fn has_def_site(mark: Mrk) -> bool {
+    with_def_sites_mut(|def_sites| def_sites.contains_key(&mark))
+}
+
+/// The module that the names with the given context are resolved in, if
+/// they come from the body of a `macro` item: the module the macro was
+/// defined in. Names that other macros were given as arguments keep the
+/// context they were written in, and so are resolved where they were
+/// written.
+pub fn def_site(ctxt: SyntaxContext) -> Option<ModulePath> {
+    if with_def_sites_mut(|def_sites| def_sites.is_empty()) {
+        return None;
+    }
+    let marks = marksof(ctxt, keywords::Invalid.name());
+    with_def_sites_mut(|def_sites| {
+        marks.iter().filter_map(|mark| def_sites.get(mark)).next().cloned()
+    })
+}
+
 /// Return the outer mark for a context with a mark at the outside.
 /// FAILS when outside is not a mark.
 pub fn outer_mark(ctxt: SyntaxContext) -> Mrk {
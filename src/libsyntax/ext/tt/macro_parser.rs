@@ -134,6 +134,8 @@ pub struct MatcherPos {
     stack: Vec<MatcherTtFrame>,
     top_elts: TokenTreeOrTokenTreeVec,
     sep: Option<Token>,
+    /// The repetition operator of the sequence being matched, if any
+    seq_op: Option<ast::KleeneOp>,
     idx: usize,
     up: Option<Box<MatcherPos>>,
     matches: Vec<Vec<Rc<NamedMatch>>>,
@@ -168,6 +170,7 @@ pub fn initial_matcher_pos(ms: Rc<Vec<TokenTree>>, sep: Option<Token>, lo: ByteP
         stack: vec![],
         top_elts: TtSeq(ms),
         sep: sep,
+        seq_op: None,
         idx: 0,
         up: None,
         matches: matches,
@@ -272,9 +275,21 @@ pub fn token_name_eq(t1 : &Token, t2 : &Token) -> bool {
 
 pub fn parse(sess: &ParseSess,
              cfg: ast::CrateConfig,
-             mut rdr: TtReader,
+             rdr: TtReader,
              ms: &[TokenTree])
              -> NamedParseResult {
+    parse_with_expected(sess, cfg, rdr, ms, &mut vec![])
+}
+
+/// Like `parse`, but on a `Failure`, `expected` is left with what the
+/// matcher could have matched instead of the token it failed at, e.g.
+/// `` `,` `` or `` `$e:expr` ``.
+pub fn parse_with_expected(sess: &ParseSess,
+                           cfg: ast::CrateConfig,
+                           mut rdr: TtReader,
+                           ms: &[TokenTree],
+                           expected: &mut Vec<String>)
+                           -> NamedParseResult {
     let mut cur_eis = Vec::new();
     cur_eis.push(initial_matcher_pos(Rc::new(ms.iter()
                                                 .cloned()
@@ -288,6 +303,7 @@ pub fn parse(sess: &ParseSess,
         let mut eof_eis = Vec::new();
 
         let TokenAndSpan { tok, sp } = rdr.peek();
+        expected.clear();
 
         /* we append new items to this while we go */
         loop {
@@ -349,6 +365,8 @@ pub fn parse(sess: &ParseSess,
 
                     // the *_t vars are workarounds for the lack of unary move
                     match ei.sep {
+                        // `?` matches at most once
+                        _ if ei.seq_op == Some(ast::KleeneOp::ZeroOrOne) => {}
                         Some(ref t) if idx == len => { // we need a separator
                             // i'm conflicted about whether this should be hygienic....
                             // though in this case, if the separators are never legal
@@ -358,6 +376,8 @@ pub fn parse(sess: &ParseSess,
                                 // ei_t.match_cur = ei_t.match_lo;
                                 ei_t.idx += 1;
                                 next_eis.push(ei_t);
+                            } else {
+                                expected.push(format!("`{}`", pprust::token_to_string(t)));
                             }
                         }
                         _ => { // we don't need a separator
@@ -374,7 +394,7 @@ pub fn parse(sess: &ParseSess,
                 match ei.top_elts.get_tt(idx) {
                     /* need to descend into sequence */
                     TokenTree::Sequence(sp, seq) => {
-                        if seq.op == ast::KleeneOp::ZeroOrMore {
+                        if seq.op != ast::KleeneOp::OneOrMore {
                             let mut new_ei = ei.clone();
                             new_ei.match_cur += seq.num_captures;
                             new_ei.idx += 1;
@@ -392,6 +412,7 @@ pub fn parse(sess: &ParseSess,
                         cur_eis.push(Box::new(MatcherPos {
                             stack: vec![],
                             sep: seq.separator.clone(),
+                            seq_op: Some(seq.op),
                             idx: 0,
                             matches: matches,
                             match_lo: ei_t.match_cur,
@@ -402,12 +423,21 @@ pub fn parse(sess: &ParseSess,
                             top_elts: Tt(TokenTree::Sequence(sp, seq)),
                         }));
                     }
-                    TokenTree::Token(_, MatchNt(..)) => {
-                        // Built-in nonterminals never start with these tokens,
-                        // so we can eliminate them from consideration.
-                        match tok {
-                            token::CloseDelim(_) => {},
-                            _ => bb_eis.push(ei),
+                    TokenTree::Token(_, MatchNt(bind, name)) => {
+                        let name = name.name.as_str();
+                        if name == "vis" && !may_begin_visibility(&tok) {
+                            // A visibility may be empty, which is matched
+                            // without consuming the token.
+                            let match_cur = ei.match_cur;
+                            (&mut ei.matches[match_cur]).push(Rc::new(MatchedNonterminal(
+                                token::NtVis(ast::Visibility::Inherited))));
+                            ei.idx += 1;
+                            ei.match_cur += 1;
+                            cur_eis.push(ei);
+                        } else if may_begin_with(&name, &tok, &rdr) {
+                            bb_eis.push(ei);
+                        } else {
+                            expected.push(format!("`${}:{}`", bind, name));
                         }
                     }
                     TokenTree::Token(sp, SubstNt(..)) => {
@@ -428,6 +458,8 @@ pub fn parse(sess: &ParseSess,
                         if token_name_eq(t,&tok) {
                             ei_t.idx += 1;
                             next_eis.push(ei_t);
+                        } else {
+                            expected.push(format!("`{}`", pprust::token_to_string(t)));
                         }
                     }
                 }
@@ -445,6 +477,11 @@ pub fn parse(sess: &ParseSess,
             } else if eof_eis.len() > 1 {
                 return Error(sp, "ambiguity: multiple successful parses".to_string());
             } else {
+                for ei in &bb_eis {
+                    if let TokenTree::Token(_, MatchNt(bind, name)) = ei.top_elts.get_tt(ei.idx) {
+                        expected.push(format!("`${}:{}`", bind, name));
+                    }
+                }
                 return Failure(sp, "unexpected end of macro invocation".to_string());
             }
         } else {
@@ -466,6 +503,9 @@ pub fn parse(sess: &ParseSess,
                     }
                 ))
             } else if bb_eis.is_empty() && next_eis.is_empty() {
+                if !eof_eis.is_empty() {
+                    expected.push("the end of the macro invocation".to_string());
+                }
                 return Failure(sp, format!("no rules expected the token `{}`",
                             pprust::token_to_string(&tok)));
             } else if !next_eis.is_empty() {
@@ -500,6 +540,45 @@ pub fn parse(sess: &ParseSess,
     }
 }
 
+fn may_begin_visibility(tok: &Token) -> bool {
+    match *tok {
+        token::Interpolated(token::NtVis(..)) => true,
+        _ => tok.is_keyword(token::keywords::Pub),
+    }
+}
+
+/// Whether a fragment of type `name` can start with `tok`, which `rdr` is
+/// at. Checking this before black-box parsing keeps the fragments made of a
+/// single kind of token from being ambiguous with the other options.
+fn may_begin_with(name: &str, tok: &Token, rdr: &TtReader) -> bool {
+    match name {
+        "vis" => may_begin_visibility(tok),
+        "lifetime" => tok.is_lifetime(),
+        "literal" => match *tok {
+            token::Literal(..) => true,
+            // Only a negative number is a literal, so that e.g. `-x` is left
+            // to an `expr` fragment.
+            token::BinOp(token::Minus) => {
+                let mut rdr = rdr.clone();
+                rdr.next_token();
+                match rdr.peek().tok {
+                    token::Literal(token::Integer(..), _) |
+                    token::Literal(token::Float(..), _) => true,
+                    _ => false,
+                }
+            }
+            token::Interpolated(token::NtLiteral(..)) => true,
+            _ => tok.is_keyword(token::keywords::True) || tok.is_keyword(token::keywords::False),
+        },
+        // Built-in nonterminals never start with these tokens,
+        // so we can eliminate them from consideration.
+        _ => match *tok {
+            token::CloseDelim(_) => false,
+            _ => true,
+        },
+    }
+}
+
 pub fn parse_nt<'a>(p: &mut Parser<'a>, sp: Span, name: &str) -> Nonterminal {
     match name {
         "tt" => {
@@ -549,6 +628,21 @@ pub fn parse_nt<'a>(p: &mut Parser<'a>, sp: Span, name: &str) -> Nonterminal {
             token::NtPath(Box::new(panictry!(p.parse_path(PathStyle::Type))))
         },
         "meta" => token::NtMeta(panictry!(p.parse_meta_item())),
+        "vis" => token::NtVis(panictry!(p.parse_visibility(true))),
+        // like idents, lifetimes are single tokens
+        "lifetime" => match p.token {
+            token::Lifetime(sn) => {
+                p.bump();
+                token::NtLifetime(Box::new(Spanned::<Ident>{node: sn, span: p.last_span}))
+            }
+            _ => {
+                let token_str = pprust::token_to_string(&p.token);
+                p.fatal(&format!("expected a lifetime, found {}",
+                                 &token_str[..])).emit();
+                panic!(FatalError)
+            }
+        },
+        "literal" => token::NtLiteral(panictry!(p.parse_pat_literal_maybe_minus())),
         // this is not supposed to happen, since it has been checked
         // when compiling the macro.
         _ => p.span_bug(sp, "invalid fragment specifier")
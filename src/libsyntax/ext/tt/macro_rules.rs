@@ -10,12 +10,14 @@
 
 use ast::{self, TokenTree};
 use codemap::{Span, DUMMY_SP};
+use errors::{DiagnosticBuilder, FatalError};
 use ext::base::{DummyResult, ExtCtxt, MacResult, SyntaxExtension};
 use ext::base::{NormalTT, TTMacroExpander};
 use ext::tt::macro_parser::{Success, Error, Failure};
-use ext::tt::macro_parser::{MatchedSeq, MatchedNonterminal};
-use ext::tt::macro_parser::parse;
-use parse::lexer::new_tt_reader;
+use ext::tt::macro_parser::{MatchedSeq, MatchedNonterminal, NamedMatch};
+use ext::tt::macro_parser::{parse, parse_with_expected};
+use feature_gate::{self, GateIssue};
+use parse::lexer::{new_tt_reader, new_tt_reader_with_doc_flag};
 use parse::parser::{Parser, Restrictions};
 use parse::token::{self, gensym_ident, NtTT, Token};
 use parse::token::Token::*;
@@ -166,15 +168,12 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
                           lhses: &[TokenTree],
                           rhses: &[TokenTree])
                           -> Box<MacResult+'cx> {
-    if cx.trace_macros() {
-        println!("{}! {{ {} }}",
-                 name,
-                 print::pprust::tts_to_string(arg));
-    }
-
     // Which arm's failure should we report? (the one furthest along)
     let mut best_fail_spot = DUMMY_SP;
     let mut best_fail_msg = "internal error: ran no matchers".to_string();
+    let mut best_fail_arm = None;
+    // What each arm expected at the token it failed to match.
+    let mut expected = Vec::with_capacity(lhses.len());
 
     for (i, lhs) in lhses.iter().enumerate() { // try each arm's matchers
         let lhs_tt = match *lhs {
@@ -182,19 +181,57 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
             _ => cx.span_fatal(sp, "malformed macro lhs")
         };
 
-        match TokenTree::parse(cx, lhs_tt, arg) {
+        // `None` is because we're not interpolating
+        let arg_rdr = new_tt_reader_with_doc_flag(&cx.parse_sess().span_diagnostic,
+                                                  None,
+                                                  None,
+                                                  arg.to_vec(),
+                                                  true);
+        let mut arm_expected = vec![];
+        match parse_with_expected(cx.parse_sess(), cx.cfg(), arg_rdr, lhs_tt, &mut arm_expected) {
             Success(named_matches) => {
                 let rhs = match rhses[i] {
                     // ignore delimiters
                     TokenTree::Delimited(_, ref delimed) => delimed.tts.clone(),
                     _ => cx.span_fatal(sp, "malformed macro rhs"),
                 };
+                let mut note = None;
+                if cx.trace_macros() {
+                    let mut trace = trace_note(cx, sp, name, arg);
+                    trace.note(&format!("matched arm {} of {}", i + 1, lhses.len()));
+                    for (bind_name, kind) in match_names(lhs_tt) {
+                        let named_match = &named_matches[&bind_name.name];
+                        trace.note(&format!("binding `${}:{}` to {}",
+                                            bind_name,
+                                            kind,
+                                            named_match_to_string(named_match)));
+                    }
+                    note = Some(trace);
+                }
                 // rhs has holes ( `$id` and `$(...)` that need filled)
                 let trncbr = new_tt_reader(&cx.parse_sess().span_diagnostic,
                                            Some(named_matches),
                                            imported_from,
                                            rhs);
                 let mut p = Parser::new(cx.parse_sess(), cx.cfg(), Box::new(trncbr));
+                if let Some(mut note) = note {
+                    // Transcribe the whole expansion up front to report it,
+                    // and parse the expansion from the same tokens.
+                    let tts = p.parse_all_token_trees();
+                    if let Ok(ref tts) = tts {
+                        note.note(&format!("to `{}`", print::pprust::tts_to_string(tts)));
+                    }
+                    note.emit();
+                    let tts = match tts {
+                        Ok(tts) => tts,
+                        Err(mut err) => {
+                            err.emit();
+                            return DummyResult::any(sp);
+                        }
+                    };
+                    let rdr = new_tt_reader(&cx.parse_sess().span_diagnostic, None, None, tts);
+                    p = Parser::new(cx.parse_sess(), cx.cfg(), Box::new(rdr));
+                }
                 p.filename = cx.filename.clone();
                 p.mod_path_stack = cx.mod_path_stack.clone();
                 p.restrictions = match cx.in_block {
@@ -214,17 +251,115 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
                     macro_ident: name
                 })
             }
-            Failure(sp, ref msg) => if sp.lo >= best_fail_spot.lo {
-                best_fail_spot = sp;
-                best_fail_msg = (*msg).clone();
-            },
+            Failure(sp, ref msg) => {
+                if sp.lo >= best_fail_spot.lo {
+                    best_fail_spot = sp;
+                    best_fail_msg = (*msg).clone();
+                    best_fail_arm = Some(i);
+                }
+                expected.push(describe_expected(arm_expected));
+            }
             Error(err_sp, ref msg) => {
+                if cx.trace_macros() {
+                    trace_note(cx, sp, name, arg).emit();
+                }
                 cx.span_fatal(err_sp.substitute_dummy(sp), &msg[..])
             }
         }
     }
 
-     cx.span_fatal(best_fail_spot.substitute_dummy(sp), &best_fail_msg[..]);
+    if cx.trace_macros() {
+        trace_note(cx, sp, name, arg).emit();
+    }
+
+    // Say what the arm that got the furthest expected where it failed, and
+    // what each of the arms expected where they did.
+    let best_fail_spot = best_fail_spot.substitute_dummy(sp);
+    let mut err = cx.struct_span_fatal(best_fail_spot, &best_fail_msg[..]);
+    if let Some(arm) = best_fail_arm {
+        if !expected[arm].is_empty() {
+            err.span_label(best_fail_spot,
+                           &format!("arm {} of `{}!` got the furthest, expecting {}",
+                                    arm + 1,
+                                    name,
+                                    expected[arm]));
+        }
+    }
+    for (i, lhs) in lhses.iter().enumerate() {
+        if lhs.get_span() == DUMMY_SP {
+            continue;
+        }
+        let msg = if expected[i].is_empty() {
+            format!("arm {} was tried", i + 1)
+        } else {
+            format!("arm {} expected {}", i + 1, expected[i])
+        };
+        err.span_note(lhs.get_span(), &msg);
+    }
+    err.emit();
+    panic!(FatalError);
+}
+
+/// Describes what the matcher of an arm expected at the token of the
+/// invocation that it failed to match, e.g. `` `$e:expr` or `,` ``, or
+/// nothing if it did not fail to match a token.
+fn describe_expected(expected: Vec<String>) -> String {
+    let mut unique: Vec<String> = vec![];
+    for e in expected {
+        if !unique.contains(&e) {
+            unique.push(e);
+        }
+    }
+    match unique.len() {
+        0 => String::new(),
+        1 => unique.pop().unwrap(),
+        n => format!("{} or {}", unique[..n - 1].join(", "), unique[n - 1]),
+    }
+}
+
+/// Starts the note that `-Z trace-macros` reports an expansion with. The
+/// notes for the arm that matched, its bindings and the tokens it expands
+/// to are added when the invocation matches.
+fn trace_note<'cx>(cx: &'cx ExtCtxt,
+                   sp: Span,
+                   name: ast::Ident,
+                   arg: &[TokenTree])
+                   -> DiagnosticBuilder<'cx> {
+    let mut note = cx.struct_span_note(sp, "trace_macro");
+    note.note(&format!("expanding `{}! {{ {} }}`", name, print::pprust::tts_to_string(arg)));
+    note
+}
+
+/// The names and fragment specifiers bound by a matcher, in order.
+fn match_names(matcher: &[TokenTree]) -> Vec<(ast::Ident, ast::Ident)> {
+    fn collect(tts: &[TokenTree], names: &mut Vec<(ast::Ident, ast::Ident)>) {
+        for tt in tts {
+            match *tt {
+                TokenTree::Token(_, MatchNt(name, kind)) => names.push((name, kind)),
+                TokenTree::Delimited(_, ref delim) => collect(&delim.tts, names),
+                TokenTree::Sequence(_, ref seq) => collect(&seq.tts, names),
+                TokenTree::Token(..) => {}
+            }
+        }
+    }
+
+    let mut names = vec![];
+    collect(matcher, &mut names);
+    names
+}
+
+fn named_match_to_string(named_match: &NamedMatch) -> String {
+    match *named_match {
+        MatchedNonterminal(ref nt) => {
+            format!("`{}`", print::pprust::token_to_string(&token::Interpolated(nt.clone())))
+        }
+        MatchedSeq(ref named_matches, _) => {
+            let matches = named_matches.iter()
+                                       .map(|named_match| named_match_to_string(named_match))
+                                       .collect::<Vec<_>>();
+            format!("[{}]", matches.join(", "))
+        }
+    }
 }
 
 // Note that macro-by-example's input is also matched against a token tree:
@@ -315,6 +450,19 @@ pub fn compile<'cx>(cx: &'cx mut ExtCtxt,
         valid &= check_rhs(cx, rhs);
     }
 
+    // Imported macros were already checked in the crate that defined them.
+    if def.imported_from.is_none() &&
+       !cx.ecfg.features.map_or(true, |features| features.macro_at_most_once_rep) {
+        let mut at_most_once = lhses.iter().chain(&rhses).filter_map(find_at_most_once_rep);
+        if let Some(sp) = at_most_once.next() {
+            feature_gate::emit_feature_err(&cx.parse_sess.span_diagnostic,
+                                           "macro_at_most_once_rep",
+                                           sp,
+                                           GateIssue::Language,
+                                           "the `?` macro repetition operator is experimental");
+        }
+    }
+
     let exp: Box<_> = Box::new(MacroRulesMacroExpander {
         name: def.ident,
         imported_from: def.imported_from,
@@ -326,6 +474,23 @@ pub fn compile<'cx>(cx: &'cx mut ExtCtxt,
     NormalTT(exp, Some(def.span), def.allow_internal_unstable)
 }
 
+/// The span of the first `$(...)?` sequence in `tt`, if any.
+fn find_at_most_once_rep(tt: &TokenTree) -> Option<Span> {
+    match *tt {
+        TokenTree::Token(..) => None,
+        TokenTree::Delimited(_, ref delimed) => {
+            delimed.tts.iter().filter_map(find_at_most_once_rep).next()
+        }
+        TokenTree::Sequence(sp, ref seq) => {
+            if seq.op == ast::KleeneOp::ZeroOrOne {
+                Some(sp)
+            } else {
+                seq.tts.iter().filter_map(find_at_most_once_rep).next()
+            }
+        }
+    }
+}
+
 // why is this here? because of https://github.com/rust-lang/rust/issues/27774
 fn ref_slice<A>(s: &A) -> &[A] { use std::slice::from_raw_parts; unsafe { from_raw_parts(s, 1) } }
 
@@ -360,6 +525,7 @@ fn check_rhs(cx: &mut ExtCtxt, rhs: &TokenTree) -> bool {
 #[derive(Debug)]
 struct OnFail {
     saw_failure: bool,
+    saw_error: bool,
     action: OnFailAction,
 }
 
@@ -367,9 +533,12 @@ struct OnFail {
 enum OnFailAction { Warn, Error, DoNothing }
 
 impl OnFail {
-    fn warn() -> OnFail { OnFail { saw_failure: false, action: OnFailAction::Warn } }
-    fn error() -> OnFail { OnFail { saw_failure: false, action: OnFailAction::Error } }
-    fn do_nothing() -> OnFail { OnFail { saw_failure: false, action: OnFailAction::DoNothing } }
+    fn new(action: OnFailAction) -> OnFail {
+        OnFail { saw_failure: false, saw_error: false, action: action }
+    }
+    fn warn() -> OnFail { OnFail::new(OnFailAction::Warn) }
+    fn error() -> OnFail { OnFail::new(OnFailAction::Error) }
+    fn do_nothing() -> OnFail { OnFail::new(OnFailAction::DoNothing) }
     fn react(&mut self, cx: &mut ExtCtxt, sp: Span, msg: &str, help: Option<&str>) {
         match self.action {
             OnFailAction::DoNothing => {}
@@ -377,6 +546,7 @@ impl OnFail {
                 let mut err = cx.struct_span_err(sp, msg);
                 if let Some(msg) = help { err.span_help(sp, msg); }
                 err.emit();
+                self.saw_error = true;
             }
             OnFailAction::Warn => {
                 let mut warn = cx.struct_span_warn(sp, msg);
@@ -387,6 +557,17 @@ impl OnFail {
         };
         self.saw_failure = true;
     }
+    /// Reports a failure that is an error even during the warning cycle,
+    /// for the fragments that were added after it started.
+    fn react_with_error(&mut self, cx: &mut ExtCtxt, sp: Span, msg: &str, help: Option<&str>) {
+        if self.action == OnFailAction::Warn {
+            self.action = OnFailAction::Error;
+            self.react(cx, sp, msg, help);
+            self.action = OnFailAction::Warn;
+        } else {
+            self.react(cx, sp, msg, help);
+        }
+    }
 }
 
 fn check_matcher(cx: &mut ExtCtxt, matcher: &[TokenTree]) -> bool {
@@ -406,7 +587,7 @@ fn check_matcher(cx: &mut ExtCtxt, matcher: &[TokenTree]) -> bool {
     check_matcher_new(cx, matcher, &mut on_fail);
     // matcher is valid if the new pass didn't see any error,
     // or if errors were considered warnings
-    on_fail.action != OnFailAction::Error || !on_fail.saw_failure
+    !on_fail.saw_error
 }
 
 // returns the last token that was checked, for TokenTree::Sequence.
@@ -515,7 +696,7 @@ fn check_matcher_old<'a, I>(cx: &mut ExtCtxt, matcher: I, follow: &Token, on_fai
                             None => last,
                         }
                     },
-                    // If T has the form $(...)+ or $(...)*, run the algorithm
+                    // If T has the form $(...)+, $(...)* or $(...)?, run the algorithm
                     // on the contents with F set to the token following the
                     // sequence. If it accepts, continue, else, reject.
                     None => {
@@ -623,7 +804,7 @@ impl FirstSets {
                         }
 
                         // Reverse scan: Sequence comes before `first`.
-                        if subfirst.maybe_empty || seq_rep.op == ast::KleeneOp::ZeroOrMore {
+                        if subfirst.maybe_empty || seq_rep.op != ast::KleeneOp::OneOrMore {
                             // If sequence is potentially empty, then
                             // union them (preserving first emptiness).
                             first.add_all(&TokenSet { maybe_empty: true, ..subfirst });
@@ -670,7 +851,7 @@ impl FirstSets {
 
                             assert!(first.maybe_empty);
                             first.add_all(subfirst);
-                            if subfirst.maybe_empty || seq_rep.op == ast::KleeneOp::ZeroOrMore {
+                            if subfirst.maybe_empty || seq_rep.op != ast::KleeneOp::OneOrMore {
                                 // continue scanning for more first
                                 // tokens, but also make sure we
                                 // restore empty-tracking state
@@ -823,8 +1004,8 @@ fn check_matcher_core(cx: &mut ExtCtxt,
                     on_fail.react(cx, sp,
                                   &format!("invalid fragment specifier `{}`", bad_frag),
                                   Some("valid fragment specifiers are `ident`, `block`, \
-                                        `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt` \
-                                        and `item`"));
+                                        `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt`, \
+                                        `item`, `vis`, `lifetime` and `literal`"));
                     // (This eliminates false positives and duplicates
                     // from error messages.)
                     can_be_followed_by_any = true;
@@ -914,16 +1095,19 @@ fn check_matcher_core(cx: &mut ExtCtxt,
                                 "may be"
                             };
 
-                            on_fail.react(
-                                cx, sp,
-                                &format!("`${name}:{frag}` {may_be} followed by `{next}`, which \
-                                          is not allowed for `{frag}` fragments",
-                                         name=name,
-                                         frag=frag_spec,
-                                         next=token_to_string(next_token),
-                                         may_be=may_be),
-                                None
-                            );
+                            let msg = format!("`${name}:{frag}` {may_be} followed by `{next}`, \
+                                               which is not allowed for `{frag}` fragments",
+                                              name=name,
+                                              frag=frag_spec,
+                                              next=token_to_string(next_token),
+                                              may_be=may_be);
+                            // `vis` fragments are newer than the warning
+                            // cycle of issue 30450.
+                            if &*frag_spec.name.as_str() == "vis" {
+                                on_fail.react_with_error(cx, sp, &msg, None);
+                            } else {
+                                on_fail.react(cx, sp, &msg, None);
+                            }
                         }
                     }
                 }
@@ -952,11 +1136,13 @@ fn token_can_be_followed_by_any(tok: &Token) -> bool {
 /// ANYTHING without fear of future compatibility hazards).
 fn frag_can_be_followed_by_any(frag: &str) -> bool {
     match frag {
-        "item" |  // always terminated by `}` or `;`
-        "block" | // exactly one token tree
-        "ident" | // exactly one token tree
-        "meta" |  // exactly one token tree
-        "tt" =>    // exactly one token tree
+        "item" |     // always terminated by `}` or `;`
+        "block" |    // exactly one token tree
+        "ident" |    // exactly one token tree
+        "lifetime" | // exactly one token tree
+        "literal" |  // one token, or two for a negative literal
+        "meta" |     // exactly one token tree
+        "tt" =>       // exactly one token tree
             true,
 
         _ =>
@@ -974,11 +1160,13 @@ fn frag_can_be_followed_by_any(frag: &str) -> bool {
 /// ANYTHING without fear of future compatibility hazards).
 fn can_be_followed_by_any(frag: &str) -> bool {
     match frag {
-        "item" |  // always terminated by `}` or `;`
-        "block" | // exactly one token tree
-        "ident" | // exactly one token tree
-        "meta" |  // exactly one token tree
-        "tt" =>    // exactly one token tree
+        "item" |     // always terminated by `}` or `;`
+        "block" |    // exactly one token tree
+        "ident" |    // exactly one token tree
+        "lifetime" | // exactly one token tree
+        "literal" |  // one token, or two for a negative literal
+        "meta" |     // exactly one token tree
+        "tt" =>       // exactly one token tree
             true,
 
         _ =>
@@ -1034,10 +1222,25 @@ fn is_in_follow(_: &ExtCtxt, tok: &Token, frag: &str) -> Result<bool, (String, &
                     _ => Ok(false)
                 }
             },
-            "ident" => {
-                // being a single token, idents are harmless
+            "ident" | "lifetime" | "literal" => {
+                // being a single token (or two, for a negative literal),
+                // these are harmless
                 Ok(true)
             },
+            "vis" => {
+                // `pub(...)` would be ambiguous with a following `(`, and
+                // `priv` is kept out on the off chance it comes back.
+                match *tok {
+                    Comma | ModSep | Not | Lt | AndAnd | BinOp(token::And) |
+                    BinOp(token::Star) | OpenDelim(token::DelimToken::Bracket) => Ok(true),
+                    Ident(i) => Ok(i.name.as_str() != "priv"),
+                    MatchNt(_, ref frag) => {
+                        let frag = frag.name.as_str();
+                        Ok(frag == "ident" || frag == "ty" || frag == "path")
+                    }
+                    _ => Ok(false)
+                }
+            },
             "meta" | "tt" => {
                 // being either a single token or a delimited sequence, tt is
                 // harmless
@@ -1045,8 +1248,8 @@ fn is_in_follow(_: &ExtCtxt, tok: &Token, frag: &str) -> Result<bool, (String, &
             },
             _ => Err((format!("invalid fragment specifier `{}`", frag),
                      "valid fragment specifiers are `ident`, `block`, \
-                      `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt` \
-                      and `item`"))
+                      `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt`, \
+                      `item`, `vis`, `lifetime` and `literal`"))
         }
     }
 }
@@ -1065,7 +1268,8 @@ fn has_legal_fragment_specifier(tok: &Token) -> Result<(), String> {
 fn is_legal_fragment_specifier(frag: &str) -> bool {
     match frag {
         "item" | "block" | "stmt" | "expr" | "pat" |
-        "path" | "ty" | "ident" | "meta" | "tt" => true,
+        "path" | "ty" | "ident" | "meta" | "tt" |
+        "vis" | "lifetime" | "literal" => true,
         _ => false,
     }
 }
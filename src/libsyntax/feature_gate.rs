@@ -277,7 +277,32 @@ declare_features! (
     (active, cfg_target_has_atomic, "1.9.0", Some(32976)),
 
     // Allows `..` in tuple (struct) patterns
-    (active, dotdot_in_tuple_patterns, "1.10.0", Some(33627))
+    (active, dotdot_in_tuple_patterns, "1.10.0", Some(33627)),
+
+    // The `proc-macro` crate type and `#[proc_macro_derive]`
+    (active, proc_macro, "1.11.0", Some(35900)),
+
+    // Allows `impl Trait` in function return types.
+    (active, conservative_impl_trait, "1.11.0", Some(34511)),
+
+    // The `i128` and `u128` types
+    (active, i128_type, "1.11.0", Some(35118)),
+
+    // Allows `union` items
+    (active, untagged_unions, "1.11.0", Some(32836)),
+
+    // Allows using `!` as a type outside of function return types
+    (active, never_type, "1.11.0", Some(35121)),
+
+    // The `#![stack_frame_size_limit]` and `#![move_size_limit]` attributes
+    (active, size_limits, "1.11.0", None),
+
+    // Allows the `$(...)?` zero-or-one macro repetition operator
+    (active, macro_at_most_once_rep, "1.11.0", None),
+
+    // Allows `macro` items: macros that are named by path and resolve the
+    // names in their bodies where they are defined
+    (active, decl_macro, "1.11.0", None)
 );
 
 declare_features! (
@@ -376,6 +401,10 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
     ("macro_use", Normal, Ungated),
     ("macro_export", Normal, Ungated),
     ("plugin_registrar", Normal, Ungated),
+    ("proc_macro_derive", Normal, Gated("proc_macro",
+                                        "the `#[proc_macro_derive]` attribute \
+                                         is an experimental feature",
+                                        cfg_fn!(proc_macro))),
 
     ("cfg", Normal, Ungated),
     ("cfg_attr", Normal, Ungated),
@@ -497,6 +526,11 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
                                       is just used for rustc unit tests \
                                       and will never be stable",
                                      cfg_fn!(rustc_attrs))),
+    ("rustc_derive_registrar", Normal, Gated("rustc_attrs",
+                                             "the `#[rustc_derive_registrar]` attribute \
+                                              is used internally by the compiler \
+                                              and will never be stable",
+                                             cfg_fn!(rustc_attrs))),
     ("rustc_no_mir", Whitelisted, Gated("rustc_attrs",
                                         "the `#[rustc_no_mir]` attribute \
                                          is just used to make tests pass \
@@ -585,6 +619,14 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
     ("no_main", CrateLevel, Ungated),
     ("no_builtins", CrateLevel, Ungated),
     ("recursion_limit", CrateLevel, Ungated),
+    ("stack_frame_size_limit", CrateLevel, Gated("size_limits",
+                                                 "the `#![stack_frame_size_limit]` attribute \
+                                                  is experimental",
+                                                 cfg_fn!(size_limits))),
+    ("move_size_limit", CrateLevel, Gated("size_limits",
+                                          "the `#![move_size_limit]` attribute \
+                                           is experimental",
+                                          cfg_fn!(size_limits))),
 ];
 
 // cfg(...)'s that are feature gated
@@ -927,6 +969,24 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
                 }
             }
 
+            ast::ItemKind::Union(..) => {
+                gate_feature_post!(&self, untagged_unions, i.span,
+                                   "unions are unstable and possibly buggy");
+            }
+
+            ast::ItemKind::Enum(..) => {
+                for attr in &i.attrs {
+                    if attr.name() == "repr" {
+                        for item in attr.meta_item_list().unwrap_or(&[]) {
+                            if item.name() == "i128" || item.name() == "u128" {
+                                gate_feature_post!(&self, i128_type, i.span,
+                                                   "128-bit type is unstable");
+                            }
+                        }
+                    }
+                }
+            }
+
             ast::ItemKind::DefaultImpl(..) => {
                 gate_feature_post!(&self, optin_builtin_traits,
                                    i.span,
@@ -1002,6 +1062,18 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
             ast::ExprKind::InPlace(..) => {
                 gate_feature_post!(&self, placement_in_syntax, e.span, EXPLAIN_PLACEMENT_IN);
             }
+            ast::ExprKind::Lit(ref lit) => {
+                if let ast::LitKind::Int(_, ref ty) = lit.node {
+                    match *ty {
+                        ast::LitIntType::Signed(ast::IntTy::I128) |
+                        ast::LitIntType::Unsigned(ast::UintTy::U128) => {
+                            gate_feature_post!(&self, i128_type, e.span,
+                                               "128-bit integers are not stable");
+                        }
+                        _ => {}
+                    }
+                }
+            }
             _ => {}
         }
         visit::walk_expr(self, e);
@@ -1049,6 +1121,31 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
         visit::walk_pat(self, pattern)
     }
 
+    fn visit_ty(&mut self, ty: &ast::Ty) {
+        match ty.node {
+            ast::TyKind::ImplTrait(..) => {
+                gate_feature_post!(&self, conservative_impl_trait, ty.span,
+                                   "`impl Trait` is experimental");
+            }
+            ast::TyKind::Never => {
+                gate_feature_post!(&self, never_type, ty.span,
+                                   "The `!` type is experimental");
+            }
+            _ => {}
+        }
+        visit::walk_ty(self, ty)
+    }
+
+    fn visit_fn_ret_ty(&mut self, ret_ty: &ast::FunctionRetTy) {
+        if let ast::FunctionRetTy::Ty(ref output_ty) = *ret_ty {
+            // `-> !` has always been allowed for diverging functions.
+            if let ast::TyKind::Never = output_ty.node {
+                return;
+            }
+            self.visit_ty(output_ty)
+        }
+    }
+
     fn visit_fn(&mut self,
                 fn_kind: FnKind<'v>,
                 fn_decl: &'v ast::FnDecl,
@@ -41,6 +41,7 @@ extern crate libc;
 #[macro_use] extern crate log;
 #[macro_use] #[no_link] extern crate rustc_bitflags;
 extern crate rustc_unicode;
+extern crate rustc_i128;
 
 extern crate serialize as rustc_serialize; // used by deriving
 
@@ -115,6 +116,7 @@ pub mod ext {
     pub mod base;
     pub mod build;
     pub mod expand;
+    pub mod macro_scope;
     pub mod mtwt;
     pub mod quote;
     pub mod source_util;
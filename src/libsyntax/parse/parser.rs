@@ -62,6 +62,8 @@ use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::slice;
 
+use rustc_i128::u128;
+
 bitflags! {
     flags Restrictions: u8 {
         const RESTRICTION_STMT_EXPR         = 1 << 0,
@@ -109,7 +111,8 @@ macro_rules! maybe_whole_expr {
     ($p:expr) => (
         {
             let found = match $p.token {
-                token::Interpolated(token::NtExpr(ref e)) => {
+                token::Interpolated(token::NtExpr(ref e)) |
+                token::Interpolated(token::NtLiteral(ref e)) => {
                     Some((*e).clone())
                 }
                 token::Interpolated(token::NtPath(_)) => {
@@ -1350,11 +1353,7 @@ impl<'a> Parser<'a> {
     /// Parse optional return type [ -> TY ] in function decl
     pub fn parse_ret_ty(&mut self) -> PResult<'a, FunctionRetTy> {
         if self.eat(&token::RArrow) {
-            if self.eat(&token::Not) {
-                Ok(FunctionRetTy::None(self.last_span))
-            } else {
-                Ok(FunctionRetTy::Ty(self.parse_ty()?))
-            }
+            Ok(FunctionRetTy::Ty(self.parse_ty()?))
         } else {
             let pos = self.span.lo;
             Ok(FunctionRetTy::Default(mk_sp(pos, pos)))
@@ -1417,6 +1416,8 @@ impl<'a> Parser<'a> {
             } else {
                 TyKind::Tup(ts)
             }
+        } else if self.eat(&token::Not) {
+            TyKind::Never
         } else if self.check(&token::BinOp(token::Star)) {
             // STAR POINTER (bare pointer?)
             self.bump();
@@ -1441,6 +1442,9 @@ impl<'a> Parser<'a> {
             self.parse_borrowed_pointee()?
         } else if self.check_keyword(keywords::For) {
             self.parse_for_in_type()?
+        } else if self.eat_keyword(keywords::Impl) {
+            // IMPL TRAIT
+            TyKind::ImplTrait(self.parse_ty_param_bounds(BoundParsingMode::Bare)?)
         } else if self.token_is_bare_fn_keyword() {
             // BARE FUNCTION
             self.parse_ty_bare_fn(Vec::new())?
@@ -1596,7 +1600,8 @@ impl<'a> Parser<'a> {
     /// Matches token_lit = LIT_INTEGER | ...
     pub fn parse_lit_token(&mut self) -> PResult<'a, LitKind> {
         let out = match self.token {
-            token::Interpolated(token::NtExpr(ref v)) => {
+            token::Interpolated(token::NtExpr(ref v)) |
+            token::Interpolated(token::NtLiteral(ref v)) => {
                 match v.node {
                     ExprKind::Lit(ref lit) => { lit.node.clone() }
                     _ => { return self.unexpected_last(&self.token); }
@@ -1671,6 +1676,8 @@ impl<'a> Parser<'a> {
 
     /// matches '-' lit | lit
     pub fn parse_pat_literal_maybe_minus(&mut self) -> PResult<'a, P<Expr>> {
+        maybe_whole!(self, NtLiteral);
+
         let minus_lo = self.span.lo;
         let minus_present = self.eat(&token::BinOp(token::Minus));
         let lo = self.span.lo;
@@ -2114,7 +2121,7 @@ impl<'a> Parser<'a> {
     pub fn mk_lit_u32(&mut self, i: u32, attrs: ThinAttributes) -> P<Expr> {
         let span = &self.span;
         let lv_lit = P(codemap::Spanned {
-            node: LitKind::Int(i as u64, ast::LitIntType::Unsigned(UintTy::U32)),
+            node: LitKind::Int(i as u128, ast::LitIntType::Unsigned(UintTy::U32)),
             span: *span
         });
 
@@ -2731,6 +2738,13 @@ impl<'a> Parser<'a> {
                     parser.bump();
                     Ok(Some(ast::KleeneOp::OneOrMore))
                 },
+                // `?` is the separator in `$(...)?*` and `$(...)?+`
+                token::Question if !parser.look_ahead(1, |t| {
+                    *t == token::BinOp(token::Star) || *t == token::BinOp(token::Plus)
+                }) => {
+                    parser.bump();
+                    Ok(Some(ast::KleeneOp::ZeroOrOne))
+                },
                 _ => Ok(None)
             }
         };
@@ -2742,8 +2756,13 @@ impl<'a> Parser<'a> {
 
         let separator = self.bump_and_get();
         match parse_kleene_op(self)? {
+            Some(ast::KleeneOp::ZeroOrOne) => {
+                let span = self.last_span;
+                Err(self.span_fatal(span, "the `?` macro repetition operator does not take \
+                                           a separator"))
+            }
             Some(zerok) => Ok((Some(separator), zerok)),
-            None => return Err(self.fatal("expected `*` or `+`"))
+            None => return Err(self.fatal("expected one of `*`, `+` or `?`"))
         }
     }
 
@@ -5114,6 +5133,25 @@ impl<'a> Parser<'a> {
         Ok((class_name, ItemKind::Struct(vdata, generics), None))
     }
 
+    /// Parse union Foo { ... }
+    fn parse_item_union(&mut self) -> PResult<'a, ItemInfo> {
+        let union_name = self.parse_ident()?;
+        let mut generics = self.parse_generics()?;
+
+        let vdata = if self.token.is_keyword(keywords::Where) {
+            generics.where_clause = self.parse_where_clause()?;
+            VariantData::Struct(self.parse_record_struct_body()?, ast::DUMMY_NODE_ID)
+        } else if self.token == token::OpenDelim(token::Brace) {
+            VariantData::Struct(self.parse_record_struct_body()?, ast::DUMMY_NODE_ID)
+        } else {
+            let token_str = self.this_token_to_string();
+            return Err(self.fatal(&format!("expected `where` or `{{` after union \
+                                            name, found `{}`", token_str)))
+        };
+
+        Ok((union_name, ItemKind::Union(vdata, generics), None))
+    }
+
     pub fn parse_record_struct_body(&mut self) -> PResult<'a, Vec<StructField>> {
         let mut fields = Vec::new();
         if self.eat(&token::OpenDelim(token::Brace)) {
@@ -5205,7 +5243,9 @@ impl<'a> Parser<'a> {
     }
 
     // If `allow_path` is false, just parse the `pub` in `pub(path)` (but still parse `pub(crate)`)
-    fn parse_visibility(&mut self, allow_path: bool) -> PResult<'a, Visibility> {
+    pub fn parse_visibility(&mut self, allow_path: bool) -> PResult<'a, Visibility> {
+        maybe_whole!(self, NtVis);
+
         let pub_crate = |this: &mut Self| {
             let span = this.last_span;
             this.expect(&token::CloseDelim(token::Paren))?;
@@ -5936,9 +5976,66 @@ impl<'a> Parser<'a> {
                                     maybe_append(attrs, extra_attrs));
             return Ok(Some(item));
         }
+        if self.check_contextual_keyword(keywords::Union.ident()) &&
+           self.look_ahead(1, |t| t.is_ident() && !t.is_any_keyword()) {
+            // UNION ITEM
+            self.bump();
+            let (ident, item_, extra_attrs) = self.parse_item_union()?;
+            let last_span = self.last_span;
+            let item = self.mk_item(lo,
+                                    last_span.hi,
+                                    ident,
+                                    item_,
+                                    visibility,
+                                    maybe_append(attrs, extra_attrs));
+            return Ok(Some(item));
+        }
+        if self.eat_keyword(keywords::Macro) {
+            // MACRO ITEM
+            return Ok(Some(self.parse_item_decl_macro(lo, visibility, attrs)?));
+        }
         self.parse_macro_use_or_failure(attrs,macros_allowed,attributes_allowed,lo,visibility)
     }
 
+    /// Parse a `macro` item, either `macro NAME(MATCHER) { BODY }` or
+    /// `macro NAME { (MATCHER) => { BODY }, ... }`.
+    ///
+    /// The item is kept as an invocation of `macro`, with the rules of the
+    /// equivalent `macro_rules!` macro as its token trees, until expansion.
+    fn parse_item_decl_macro(&mut self, lo: BytePos, visibility: Visibility,
+                             attrs: Vec<Attribute>) -> PResult<'a, P<Item>> {
+        let keyword_span = self.last_span;
+        let ident = self.parse_ident()?;
+        let tts = if self.check(&token::OpenDelim(token::Paren)) {
+            let matcher = self.parse_token_tree()?;
+            if !self.check(&token::OpenDelim(token::Brace)) {
+                return self.unexpected();
+            }
+            let arrow = TokenTree::Token(self.span, token::FatArrow);
+            let body = self.parse_token_tree()?;
+            vec![matcher, arrow, body]
+        } else {
+            self.expect(&token::OpenDelim(token::Brace))?;
+            let rules = self.parse_seq_to_end(&token::CloseDelim(token::Brace),
+                                              SeqSep::none(),
+                                              |p| p.parse_token_tree())?;
+            // The rules may be separated by commas as well as semicolons.
+            rules.into_iter().map(|tt| match tt {
+                TokenTree::Token(sp, token::Comma) => TokenTree::Token(sp, token::Semi),
+                tt => tt,
+            }).collect()
+        };
+
+        let hi = self.last_span.hi;
+        let m = Mac_ {
+            path: ast::Path::from_ident(keyword_span, keywords::Macro.ident()),
+            tts: tts,
+            ctxt: EMPTY_CTXT,
+        };
+        let m: ast::Mac = codemap::Spanned { node: m, span: mk_sp(lo, hi) };
+        Ok(self.mk_item(lo, hi, ident, ItemKind::Mac(m), visibility, attrs))
+    }
+
     /// Parse a foreign item.
     fn parse_foreign_item(&mut self) -> PResult<'a, Option<ForeignItem>> {
         let attrs = self.parse_outer_attributes()?;
@@ -33,6 +33,8 @@ use std::ascii;
 use std::io::{self, Write, Read};
 use std::iter;
 
+use rustc_i128::i128;
+
 pub enum AnnNode<'a> {
     NodeIdent(&'a ast::Ident),
     NodeName(&'a ast::Name),
@@ -296,6 +298,9 @@ pub fn token_to_string(tok: &Token) -> String {
             token::NtPat(ref e)         => pat_to_string(&e),
             token::NtIdent(ref e)       => ident_to_string(e.node),
             token::NtTT(ref e)          => tt_to_string(&e),
+            token::NtVis(ref e)         => visibility_qualified(&e, "").trim().to_string(),
+            token::NtLifetime(ref e)    => ident_to_string(e.node),
+            token::NtLiteral(ref e)     => expr_to_string(&e),
             token::NtArm(ref e)         => arm_to_string(&e),
             token::NtImplItem(ref e)    => impl_item_to_string(&e),
             token::NtTraitItem(ref e)   => trait_item_to_string(&e),
@@ -644,7 +649,7 @@ pub trait PrintState<'a> {
                 match t {
                     ast::LitIntType::Signed(st) => {
                         word(self.writer(),
-                             &st.val_to_string(i as i64))
+                             &st.val_to_string(i as i128))
                     }
                     ast::LitIntType::Unsigned(ut) => {
                         word(self.writer(), &ut.val_to_string(i))
@@ -973,6 +978,9 @@ impl<'a> State<'a> {
                 self.print_opt_lifetime(lifetime)?;
                 self.print_mt(mt)?;
             }
+            ast::TyKind::Never => {
+                word(&mut self.s, "!")?;
+            }
             ast::TyKind::Tup(ref elts) => {
                 self.popen()?;
                 self.commasep(Inconsistent, &elts[..],
@@ -1015,6 +1023,9 @@ impl<'a> State<'a> {
             ast::TyKind::PolyTraitRef(ref bounds) => {
                 self.print_bounds("", &bounds[..])?;
             }
+            ast::TyKind::ImplTrait(ref bounds) => {
+                self.print_bounds("impl", &bounds[..])?;
+            }
             ast::TyKind::FixedLengthVec(ref ty, ref v) => {
                 word(&mut self.s, "[")?;
                 self.print_type(&ty)?;
@@ -1227,6 +1238,10 @@ impl<'a> State<'a> {
                 self.head(&visibility_qualified(&item.vis, "struct"))?;
                 self.print_struct(&struct_def, generics, item.ident, item.span, true)?;
             }
+            ast::ItemKind::Union(ref struct_def, ref generics) => {
+                self.head(&visibility_qualified(&item.vis, "union"))?;
+                self.print_struct(&struct_def, generics, item.ident, item.span, true)?;
+            }
 
             ast::ItemKind::DefaultImpl(unsafety, ref trait_ref) => {
                 self.head("")?;
@@ -1309,6 +1324,19 @@ impl<'a> State<'a> {
                 }
                 self.bclose(item.span)?;
             }
+            ast::ItemKind::Mac(codemap::Spanned { ref node, .. })
+                    if node.path.segments.len() == 1 &&
+                       node.path.segments[0].identifier.name == keywords::Macro.name() => {
+                // A `macro` item, with the rules it was parsed into.
+                self.print_visibility(&item.vis)?;
+                self.word_nbsp("macro")?;
+                self.print_ident(item.ident)?;
+                self.cbox(INDENT_UNIT)?;
+                word(&mut self.s, " {")?;
+                self.print_tts(&node.tts[..])?;
+                word(&mut self.s, "}")?;
+                self.end()?;
+            }
             ast::ItemKind::Mac(codemap::Spanned { ref node, .. }) => {
                 self.print_visibility(&item.vis)?;
                 self.print_path(&node.path, false, 0)?;
@@ -1477,6 +1505,7 @@ impl<'a> State<'a> {
                 match seq.op {
                     ast::KleeneOp::ZeroOrMore => word(&mut self.s, "*"),
                     ast::KleeneOp::OneOrMore => word(&mut self.s, "+"),
+                    ast::KleeneOp::ZeroOrOne => word(&mut self.s, "?"),
                 }
             }
         }
@@ -2693,10 +2722,6 @@ impl<'a> State<'a> {
                 self.maybe_print_comment(ty.span.lo)
             }
             ast::FunctionRetTy::Default(..) => unreachable!(),
-            ast::FunctionRetTy::None(span) => {
-                self.word_nbsp("!")?;
-                self.maybe_print_comment(span.lo)
-            }
         }
     }
 
@@ -2957,8 +2982,6 @@ impl<'a> State<'a> {
         self.ibox(INDENT_UNIT)?;
         self.word_space("->")?;
         match decl.output {
-            ast::FunctionRetTy::None(_) =>
-                self.word_nbsp("!")?,
             ast::FunctionRetTy::Default(..) => unreachable!(),
             ast::FunctionRetTy::Ty(ref ty) =>
                 self.print_type(&ty)?
@@ -56,6 +56,15 @@ pub fn expand_deriving_clone(cx: &mut ExtCtxt,
                     }));
                 }
 
+                // Unions can only be cloned by copying them bitwise.
+                ItemKind::Union(..) => {
+                    bounds = vec![Literal(path_std!(cx, core::marker::Copy))];
+                    unify_fieldless_variants = false;
+                    substructure = combine_substructure(Box::new(|c, s, sub| {
+                        cs_clone("Clone", c, s, sub, Mode::Shallow)
+                    }));
+                }
+
                 _ => {
                     bounds = vec![];
                     unify_fieldless_variants = false;
@@ -78,6 +87,7 @@ pub fn expand_deriving_clone(cx: &mut ExtCtxt,
         additional_bounds: bounds,
         generics: LifetimeBounds::empty(),
         is_unsafe: false,
+        supports_unions: true,
         methods: vec!(
             MethodDef {
                 name: "clone",
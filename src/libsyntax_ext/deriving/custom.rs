@@ -0,0 +1,114 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Custom derive modes defined in `proc-macro` crates.
+
+use std::panic;
+
+use proc_macro::TokenStream;
+use proc_macro::__internal;
+use syntax::ast::{self, MetaItem};
+use syntax::attr::AttrMetaMethods;
+use syntax::codemap::Span;
+use syntax::ext::base::*;
+use syntax::fold::{self, Folder};
+use syntax::errors::FatalError;
+
+/// A `#[derive(Trait)]` mode loaded from a `#[proc_macro_derive(Trait)]`
+/// function of a `proc-macro` crate.
+pub struct CustomDerive {
+    inner: fn(TokenStream) -> TokenStream,
+}
+
+impl CustomDerive {
+    pub fn new(inner: fn(TokenStream) -> TokenStream) -> CustomDerive {
+        CustomDerive { inner: inner }
+    }
+}
+
+impl MultiItemDecorator for CustomDerive {
+    fn expand(&self,
+              cx: &mut ExtCtxt,
+              span: Span,
+              _meta_item: &MetaItem,
+              item: &Annotatable,
+              push: &mut FnMut(Annotatable)) {
+        let item = match *item {
+            Annotatable::Item(ref item) => item.clone(),
+            _ => {
+                cx.span_err(span, "custom derive attributes may only be \
+                                   applied to struct/enum items");
+                return
+            }
+        };
+        match item.node {
+            ast::ItemKind::Struct(..) |
+            ast::ItemKind::Enum(..) => {}
+            _ => {
+                cx.span_err(span, "custom derive attributes may only be \
+                                   applied to struct/enum items");
+                return
+            }
+        }
+
+        // The `#[derive_Foo]` markers and `#[structural_match]` are compiler
+        // internals, so the macro only gets to see the item as it was written.
+        let item = item.map(|mut i| {
+            i.attrs.retain(|a| {
+                let name = a.name();
+                !name.starts_with("derive_") && name != "structural_match"
+            });
+            i
+        });
+
+        let input = __internal::new_token_stream(item);
+        let inner = self.inner;
+        let res = __internal::set_parse_sess(cx.parse_sess, || {
+            panic::catch_unwind(panic::AssertUnwindSafe(|| inner(input)))
+        });
+        let new_items = match res {
+            Ok(stream) => __internal::token_stream_items(stream),
+            Err(e) => {
+                let mut err = cx.struct_span_err(span,
+                                                 "custom derive attribute panicked");
+                if let Some(s) = e.downcast_ref::<String>() {
+                    err.help(&format!("message: {}", s));
+                }
+                if let Some(s) = e.downcast_ref::<&'static str>() {
+                    err.help(&format!("message: {}", s));
+                }
+
+                err.emit();
+                panic!(FatalError);
+            }
+        };
+
+        // The generated items were parsed from a string, so their spans point
+        // nowhere useful; attribute them all to the derive instead.
+        let mut folder = ChangeSpan { span: span };
+        for item in new_items {
+            for item in folder.fold_item(item) {
+                push(Annotatable::Item(item));
+            }
+        }
+    }
+}
+
+struct ChangeSpan { span: Span }
+
+impl Folder for ChangeSpan {
+    fn new_span(&mut self, _sp: Span) -> Span {
+        self.span
+    }
+
+    fn fold_mac(&mut self, mac: ast::Mac) -> ast::Mac {
+        fold::noop_fold_mac(mac, self)
+    }
+}
@@ -229,6 +229,9 @@ pub struct TraitDef<'a> {
     /// Is it an `unsafe` trait?
     pub is_unsafe: bool,
 
+    /// Can this trait be derived for unions?
+    pub supports_unions: bool,
+
     pub methods: Vec<MethodDef<'a>>,
 
     pub associated_types: Vec<(ast::Ident, Ty<'a>)>,
@@ -397,7 +400,19 @@ impl<'a> TraitDef<'a> {
                         self.expand_struct_def(cx,
                                                &struct_def,
                                                item.ident,
-                                               generics)
+                                               generics,
+                                               false)
+                    }
+                    ast::ItemKind::Union(ref struct_def, ref generics) => {
+                        if !self.supports_unions {
+                            cx.span_err(mitem.span, "this trait cannot be derived for unions");
+                            return;
+                        }
+                        self.expand_struct_def(cx,
+                                               &struct_def,
+                                               item.ident,
+                                               generics,
+                                               true)
                     }
                     ast::ItemKind::Enum(ref enum_def, ref generics) => {
                         self.expand_enum_def(cx,
@@ -651,7 +666,8 @@ impl<'a> TraitDef<'a> {
                          cx: &mut ExtCtxt,
                          struct_def: &'a VariantData,
                          type_ident: Ident,
-                         generics: &Generics) -> P<ast::Item> {
+                         generics: &Generics,
+                         is_union: bool) -> P<ast::Item> {
         let field_tys: Vec<P<ast::Ty>> = struct_def.fields().iter()
             .map(|field| field.ty.clone())
             .collect();
@@ -675,7 +691,8 @@ impl<'a> TraitDef<'a> {
                                                      struct_def,
                                                      type_ident,
                                                      &self_args[..],
-                                                     &nonself_args[..])
+                                                     &nonself_args[..],
+                                                     is_union)
             };
 
             method_def.create_method(cx,
@@ -755,12 +772,14 @@ fn find_repr_type_name(diagnostic: &Handler,
                 attr::ReprInt(_, attr::SignedInt(ast::IntTy::I16)) => "i16",
                 attr::ReprInt(_, attr::SignedInt(ast::IntTy::I32)) => "i32",
                 attr::ReprInt(_, attr::SignedInt(ast::IntTy::I64)) => "i64",
+                attr::ReprInt(_, attr::SignedInt(ast::IntTy::I128)) => "i128",
 
                 attr::ReprInt(_, attr::UnsignedInt(ast::UintTy::Us)) => "usize",
                 attr::ReprInt(_, attr::UnsignedInt(ast::UintTy::U8)) => "u8",
                 attr::ReprInt(_, attr::UnsignedInt(ast::UintTy::U16)) => "u16",
                 attr::ReprInt(_, attr::UnsignedInt(ast::UintTy::U32)) => "u32",
                 attr::ReprInt(_, attr::UnsignedInt(ast::UintTy::U64)) => "u64",
+                attr::ReprInt(_, attr::UnsignedInt(ast::UintTy::U128)) => "u128",
             }
         }
     }
@@ -924,9 +943,21 @@ impl<'a> MethodDef<'a> {
                                  struct_def: &'b VariantData,
                                  type_ident: Ident,
                                  self_args: &[P<Expr>],
-                                 nonself_args: &[P<Expr>])
+                                 nonself_args: &[P<Expr>],
+                                 is_union: bool)
         -> P<Expr> {
 
+        // Union fields can't be destructured safely, so the traits that
+        // support unions only get to see `self` as a whole.
+        if is_union {
+            return self.call_substructure_method(cx,
+                                                 trait_,
+                                                 type_ident,
+                                                 self_args,
+                                                 nonself_args,
+                                                 &Struct(struct_def, Vec::new()));
+        }
+
         let mut raw_fields = Vec::new(); // Vec<[fields of self],
                                  // [fields of next Self arg], [etc]>
         let mut patterns = Vec::new();
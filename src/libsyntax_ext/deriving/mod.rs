@@ -56,6 +56,7 @@ macro_rules! path_std {
 
 pub mod bounds;
 pub mod clone;
+pub mod custom;
 pub mod encodable;
 pub mod decodable;
 pub mod hash;
@@ -105,7 +106,10 @@ fn expand_derive(cx: &mut ExtCtxt,
                     }
                 };
 
-                if !(is_builtin_trait(tname) || cx.ecfg.enable_custom_derive()) {
+                // Derive modes loaded from `proc-macro` crates are not gated
+                // behind `custom_derive`, only their definition is.
+                if !(is_builtin_trait(tname) || cx.is_custom_derive(tname) ||
+                     cx.ecfg.enable_custom_derive()) {
                     feature_gate::emit_feature_err(&cx.parse_sess.span_diagnostic,
                                                    "custom_derive",
                                                    titem.span,
@@ -274,7 +278,8 @@ fn hygienic_type_parameter(item: &Annotatable, base: &str) -> String {
     if let Annotatable::Item(ref item) = *item {
         match item.node {
             ast::ItemKind::Struct(_, ast::Generics { ref ty_params, .. }) |
-                ast::ItemKind::Enum(_, ast::Generics { ref ty_params, .. }) => {
+                ast::ItemKind::Enum(_, ast::Generics { ref ty_params, .. }) |
+                ast::ItemKind::Union(_, ast::Generics { ref ty_params, .. }) => {
 
                 for ty in ty_params.iter() {
                     typaram.push_str(&ty.ident.name.as_str());
@@ -19,6 +19,8 @@
        html_root_url = "https://doc.rust-lang.org/nightly/")]
 #![cfg_attr(not(stage0), deny(warnings))]
 
+#![feature(proc_macro_internals)]
+#![feature(proc_macro_lib)]
 #![feature(rustc_private)]
 #![feature(staged_api)]
 
@@ -26,6 +28,7 @@ extern crate fmt_macros;
 #[macro_use] extern crate log;
 #[macro_use]
 extern crate syntax;
+extern crate proc_macro;
 
 use syntax::ext::base::{MacroExpanderFn, NormalTT};
 use syntax::ext::base::{SyntaxEnv, SyntaxExtension};
@@ -44,6 +47,8 @@ mod trace_macros;
 // for custom_derive
 pub mod deriving;
 
+pub mod proc_macro_registrar;
+
 pub fn register_builtins(env: &mut SyntaxEnv) {
     // utility function to simplify creating NormalTT syntax extensions
     fn builtin_normal_expander(f: MacroExpanderFn) -> SyntaxExtension {
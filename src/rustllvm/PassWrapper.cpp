@@ -166,6 +166,78 @@ LLVMRustHasFeature(LLVMTargetMachineRef TM,
     return (Bits & FeatureEntry->Value) == FeatureEntry->Value;
 }
 
+#if LLVM_VERSION_MINOR >= 7
+// `MCSubtargetInfo` keeps the CPU and feature tables of its target to
+// itself. Access is not checked for the names used in an explicit
+// instantiation, so instantiating `SubtargetTable` with a pointer to one
+// of those members defines a `get` that returns it.
+template <typename Tag, typename Tag::type Member>
+struct SubtargetTable {
+    friend typename Tag::type get(Tag) { return Member; }
+};
+
+struct ProcDescTag {
+    typedef ArrayRef<SubtargetFeatureKV> MCSubtargetInfo::*type;
+    friend type get(ProcDescTag);
+};
+
+struct ProcFeaturesTag {
+    typedef ArrayRef<SubtargetFeatureKV> MCSubtargetInfo::*type;
+    friend type get(ProcFeaturesTag);
+};
+
+template struct SubtargetTable<ProcDescTag, &MCSubtargetInfo::ProcDesc>;
+template struct SubtargetTable<ProcFeaturesTag, &MCSubtargetInfo::ProcFeatures>;
+
+static ArrayRef<SubtargetFeatureKV>
+GetCPUTable(LLVMTargetMachineRef TM) {
+    return unwrap(TM)->getMCSubtargetInfo()->*get(ProcDescTag());
+}
+
+static ArrayRef<SubtargetFeatureKV>
+GetFeatureTable(LLVMTargetMachineRef TM) {
+    return unwrap(TM)->getMCSubtargetInfo()->*get(ProcFeaturesTag());
+}
+#else
+// The tables are not kept as `ArrayRef`s before LLVM 3.7, and are not
+// listed.
+static ArrayRef<SubtargetFeatureKV>
+GetCPUTable(LLVMTargetMachineRef) {
+    return ArrayRef<SubtargetFeatureKV>();
+}
+
+static ArrayRef<SubtargetFeatureKV>
+GetFeatureTable(LLVMTargetMachineRef) {
+    return ArrayRef<SubtargetFeatureKV>();
+}
+#endif
+
+extern "C" size_t
+LLVMRustGetTargetCPUsCount(LLVMTargetMachineRef TM) {
+    return GetCPUTable(TM).size();
+}
+
+extern "C" void
+LLVMRustGetTargetCPU(LLVMTargetMachineRef TM, size_t index,
+                     const char **name, const char **desc) {
+    const SubtargetFeatureKV &CPU = GetCPUTable(TM)[index];
+    *name = CPU.Key;
+    *desc = CPU.Desc;
+}
+
+extern "C" size_t
+LLVMRustGetTargetFeaturesCount(LLVMTargetMachineRef TM) {
+    return GetFeatureTable(TM).size();
+}
+
+extern "C" void
+LLVMRustGetTargetFeature(LLVMTargetMachineRef TM, size_t index,
+                         const char **name, const char **desc) {
+    const SubtargetFeatureKV &Feature = GetFeatureTable(TM)[index];
+    *name = Feature.Key;
+    *desc = Feature.Desc;
+}
+
 extern "C" LLVMTargetMachineRef
 LLVMRustCreateTargetMachine(const char *triple,
                             const char *cpu,
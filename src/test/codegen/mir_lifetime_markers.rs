@@ -0,0 +1,39 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O -C no-prepopulate-passes
+
+#![feature(rustc_attrs)]
+#![crate_type = "lib"]
+
+extern {
+    fn consume(x: &mut [u8; 64]);
+}
+
+// The storage of each buffer ends with its scope, so LLVM may let the
+// two share a stack slot.
+
+// CHECK-LABEL: @scoped_buffers
+#[no_mangle]
+#[rustc_mir]
+pub fn scoped_buffers() {
+    {
+        // CHECK: call void @llvm.lifetime.start
+        let mut a = [0u8; 64];
+        unsafe { consume(&mut a) };
+        // CHECK: call void @llvm.lifetime.end
+    }
+    {
+        // CHECK: call void @llvm.lifetime.start
+        let mut b = [1u8; 64];
+        unsafe { consume(&mut b) };
+        // CHECK: call void @llvm.lifetime.end
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -Z mir-opt-level=2 -Z force-overflow-checks=off
+
+#![feature(rustc_attrs)]
+#![crate_type = "lib"]
+
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[inline(never)]
+fn never(a: i32) -> i32 {
+    a * 2
+}
+
+// CHECK-LABEL: @calls
+#[no_mangle]
+#[rustc_mir]
+pub fn calls(x: i32) -> i32 {
+    // CHECK-NOT: call{{.*}}3add
+    // CHECK: call{{.*}}5never
+    // CHECK-NOT: call{{.*}}3add
+    // CHECK: ret
+    add(add(x, 1), never(x))
+}
@@ -0,0 +1,41 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -Z mir-opt-level=2 -Z force-overflow-checks=off
+
+#![feature(rustc_attrs)]
+#![crate_type = "lib"]
+
+// The arithmetic on constants is folded, and the temps that held its
+// intermediate results are removed.
+// CHECK-LABEL: @folded
+#[no_mangle]
+#[rustc_mir]
+pub fn folded() -> i32 {
+    // CHECK-NOT: mul
+    // CHECK-NOT: icmp
+    // CHECK: ret
+    let a = 2i32;
+    let b = a * 3;
+    let c = -b + 10;
+    if c == 4 { c + b } else { 0 }
+}
+
+// Arithmetic on an argument is left alone.
+// CHECK-LABEL: @not_folded
+#[no_mangle]
+#[rustc_mir]
+pub fn not_folded(a: i32) -> i32 {
+    // CHECK: mul
+    // CHECK: icmp
+    let b = a * 3;
+    let c = -b + 10;
+    if c == 4 { c + b } else { 0 }
+}
@@ -10,8 +10,8 @@
 
 // Tests that a function with a ! annotation always actually fails
 
-fn bad_bang(i: usize) -> ! { //~ ERROR computation may converge in a function marked as diverging
-    if i < 0 { } else { panic!(); }
+fn bad_bang(i: usize) -> ! {
+    if i < 0 { } else { panic!(); } //~ ERROR mismatched types
 }
 
 fn main() { bad_bang(5); }
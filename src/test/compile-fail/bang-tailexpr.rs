@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-fn f() -> ! { //~ ERROR computation may converge in a function marked as diverging
-    3
+fn f() -> ! {
+    3 //~ ERROR mismatched types
 }
 fn main() { }
@@ -0,0 +1,84 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z borrowck-mir
+
+// Errors that both borrow checkers report. The MIR borrowck marks its
+// own with `(Mir)`.
+
+fn mutable_twice() {
+    let mut x = 0;
+    let a = &mut x;
+    let b = &mut x; //~ ERROR cannot borrow `x` as mutable more than once at a time (Mir)
+                    //~| ERROR cannot borrow `x` as mutable more than once at a time
+    *a += 1;
+    *b += 1;
+}
+
+fn assign_while_borrowed() {
+    let mut x = 0;
+    let r = &x;
+    x = 1; //~ ERROR cannot assign to `x` because it is borrowed (Mir)
+           //~| ERROR cannot assign to `x` because it is borrowed
+    drop(r);
+}
+
+fn move_while_borrowed() {
+    let s = String::new();
+    let r = &s;
+    let t = s; //~ ERROR cannot move out of `s` because it is borrowed (Mir)
+               //~| ERROR cannot move out of `s` because it is borrowed
+    drop(r);
+    drop(t);
+}
+
+fn use_after_move() {
+    let s = String::new();
+    drop(s);
+    drop(s); //~ ERROR use of moved value: `s` (Mir)
+             //~| ERROR use of moved value: `s`
+}
+
+fn use_uninit() {
+    let x: i32;
+    let y = x; //~ ERROR use of possibly uninitialized variable: `x` (Mir)
+               //~| ERROR use of possibly uninitialized variable: `x`
+    drop(y);
+}
+
+fn reassign_immutable() {
+    let x;
+    x = 1;
+    x = 2; //~ ERROR re-assignment of immutable variable `x` (Mir)
+           //~| ERROR re-assignment of immutable variable `x`
+    drop(x);
+}
+
+fn dropped_while_borrowed() {
+    let r;
+    {
+        let s = String::new();
+        r = &s; //~ ERROR `s` does not live long enough (Mir)
+                //~| ERROR `s` does not live long enough
+    }
+    drop(r);
+}
+
+fn storage_dead_while_borrowed() {
+    let r;
+    {
+        let x = 0;
+        r = &x; //~ ERROR `x` does not live long enough (Mir)
+                //~| ERROR `x` does not live long enough
+    }
+    drop(r);
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z borrowck-mir
+
+// The MIR borrowck ends a borrow at the last use of the reference
+// rather than at the end of its lexical scope, so it accepts all of
+// these; only the AST borrowck reports errors.
+
+fn borrow_after_last_use() {
+    let mut x = 0;
+    let r = &mut x;
+    *r += 1;
+    let y = &x; //~ ERROR cannot borrow `x` as immutable because it is also borrowed as mutable
+    drop(y);
+}
+
+fn assign_after_last_use() {
+    let mut x = 5;
+    let r = &x;
+    let y = *r;
+    x = y + 1; //~ ERROR cannot assign to `x` because it is borrowed
+    drop(x);
+}
+
+// The borrow taken by `first` only needs to last as long as the
+// returned reference on the path that returns it.
+fn first_or_push(v: &mut Vec<i32>) -> &i32 {
+    if let Some(x) = v.first() {
+        return x;
+    }
+    v.push(0); //~ ERROR cannot borrow `*v` as mutable because it is also borrowed as immutable
+    &v[0]
+}
+
+fn main() {}
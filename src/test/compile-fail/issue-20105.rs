@@ -13,5 +13,5 @@ fn f<F, R>(_: F) where F: Fn() -> R {
 
 fn main() {
     f(|| -> ! { () });
-//~^ ERROR: computation may converge in a function marked as diverging [E0270]
+//~^ ERROR: mismatched types
 }
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 mod mul1 {
+//~^ HELP `use std::ops::Div;`
     pub trait Mul {}
 }
 
@@ -88,7 +89,6 @@ impl ThisTraitReallyDoesntExistInAnyModuleReally for Foo {
 // Let's also test what happens if there's just one alternative:
 impl Div for Foo {
 //~^ ERROR trait `Div` is not in scope
-//~| HELP `use std::ops::Div;`
 }
 
 fn main() {
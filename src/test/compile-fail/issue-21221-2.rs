@@ -9,6 +9,7 @@
 // except according to those terms.
 
 pub mod foo {
+//~^ HELP you can import it into scope: `use foo::bar::T;`.
     pub mod bar {
         // note: trait T is not public, but being in the current
         // crate, it's fine to show it, since the programmer can
@@ -27,4 +28,3 @@ pub mod baz {
 struct Foo;
 impl T for Foo { }
 //~^ ERROR trait `T` is not in scope
-//~| HELP you can import it into scope: `use foo::bar::T;`.
@@ -14,6 +14,7 @@
 // aux-build:issue-21221-3.rs
 
 extern crate issue_21221_3;
+//~^ HELP you can import it into scope: `use issue_21221_3::outer::OuterTrait;`.
 
 struct Foo;
 
@@ -24,7 +25,6 @@ struct Foo;
 // are hidden from the view.
 impl OuterTrait for Foo {}
 //~^ ERROR trait `OuterTrait` is not in scope
-//~| HELP you can import it into scope: `use issue_21221_3::outer::OuterTrait;`.
 fn main() {
     println!("Hello, world!");
 }
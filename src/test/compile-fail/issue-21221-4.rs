@@ -14,12 +14,12 @@
 // aux-build:issue-21221-4.rs
 
 extern crate issue_21221_4;
+//~^ HELP you can import it into scope: `use issue_21221_4::T;`.
 
 struct Foo;
 
 impl T for Foo {}
 //~^ ERROR trait `T` is not in scope
-//~| HELP you can import it into scope: `use issue_21221_4::T;`.
 
 fn main() {
     println!("Hello, world!");
@@ -12,12 +12,12 @@
 
 fn g() -> ! { panic!(); }
 fn f() -> ! {
-    return g(); //~ ERROR `return` in a function declared as diverging
-    g();
+    return g();
+    g(); //~ ERROR unreachable statement
 }
 fn h() -> ! {
     loop {}
-    g();
+    g(); //~ ERROR unreachable statement
 }
 
 fn main() { f() }
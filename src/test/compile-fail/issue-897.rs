@@ -11,9 +11,8 @@
 #![deny(unreachable_code)]
 
 fn f() -> ! {
-    return panic!(); //~ ERROR `return` in a function declared as diverging
-    panic!(); // the unreachable statement error is in <std macro>, at this line, there
-             // only is a note
+    return panic!();
+    loop {} //~ ERROR unreachable statement
 }
 
 fn main() { f() }
@@ -0,0 +1,32 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(size_limits)]
+#![move_size_limit = "256"]
+#![deny(large_move)]
+
+fn consume(buf: [u8; 512]) -> u8 {
+    buf[0]
+}
+
+fn identity<T>(value: T) -> T {
+    value //~ ERROR moving 512 bytes in `identity::<[u8; 512]>`
+}
+
+fn main() {
+    let small = [0u8; 128];
+    let buf = [0u8; 512];
+    let copy = small;
+    consume(buf); //~ ERROR moving 512 bytes in `main`
+    identity(copy);
+    identity(buf); //~ ERROR moving 512 bytes in `main`
+    let by_ref = &buf;
+    println!("{}", by_ref[0]);
+}
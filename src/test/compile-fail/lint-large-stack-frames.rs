@@ -0,0 +1,52 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(size_limits)]
+#![stack_frame_size_limit = "1024"]
+#![deny(large_stack_frames)]
+
+fn small() -> u8 {
+    let buf = [0u8; 512];
+    buf[0]
+}
+
+fn big() -> u8 { //~ ERROR the locals of `big` take up
+    let buf = [0u8; 2048]; //~ NOTE the largest variable takes up 2048 bytes
+    buf[0]
+}
+
+// Only the instances whose frame exceeds the limit are reported.
+fn generic<T: Default>() -> u8 { //~ ERROR the locals of `generic::<Big>` take up
+    let value = T::default(); //~ NOTE the largest variable takes up 2048 bytes
+    drop(value);
+    0
+}
+
+#[allow(large_stack_frames)]
+fn allowed() -> u8 {
+    let buf = [0u8; 4096];
+    buf[0]
+}
+
+struct Big([u8; 2048]);
+
+impl Default for Big {
+    fn default() -> Big {
+        Big([0; 2048]) //~^ ERROR more than the stack frame size limit of 1024 bytes
+    }
+}
+
+fn main() {
+    small();
+    big();
+    allowed();
+    generic::<u8>();
+    generic::<Big>();
+}
@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(i128_type)]
+#![deny(overflowing_literals)]
+
+#[allow(unused_variables)]
+fn main() {
+    let x = 170141183460469231731687303715884105727_i128; // should be OK
+    let x = -170141183460469231731687303715884105728_i128; // should be OK
+    let x = 170141183460469231731687303715884105728_i128; //~ error: literal out of range for i128
+    let x: i128 = -170141183460469231731687303715884105729;
+    //~^ error: literal out of range for i128
+
+    let x = 340282366920938463463374607431768211455_u128; // should be OK
+    let x: u128 = 0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff; // should be OK
+    let x = 18446744073709551616_u64; //~ error: literal out of range for u64
+    let x: i64 = 18446744073709551616; //~ error: literal out of range for i64
+}
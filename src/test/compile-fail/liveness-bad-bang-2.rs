@@ -10,7 +10,7 @@
 
 // Tests that a function with a ! annotation always actually fails
 
-fn bad_bang(i: usize) -> ! { //~ ERROR computation may converge in a function marked as diverging
+fn bad_bang(i: usize) -> ! { //~ ERROR mismatched types
     println!("{}", 3);
 }
 
@@ -14,7 +14,7 @@ fn forever() -> ! {
   loop {
     break;
   }
-  return 42; //~ ERROR `return` in a function declared as diverging
+  return 42; //~ ERROR mismatched types
 }
 
 fn main() {
@@ -8,8 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-fn forever2() -> ! { //~ ERROR computation may converge in a function marked as diverging
-  loop { break }
+fn forever2() -> ! {
+  loop { break } //~ ERROR mismatched types
 }
 
 fn main() {}
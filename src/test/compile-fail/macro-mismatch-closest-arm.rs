@@ -0,0 +1,21 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+macro_rules! pair {
+    ($a:expr, $b:expr) => { ($a, $b) }; //~ NOTE arm 1 expected `,`
+    ($a:ident) => { $a }; //~ NOTE arm 2 expected the end of the macro invocation
+}
+
+fn main() {
+    let x = 1;
+    pair!(x; 2);
+    //~^ ERROR no rules expected the token `;`
+    //~| NOTE arm 2 of `pair!` got the furthest, expecting the end of the macro invocation
+}
@@ -0,0 +1,20 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+macro_rules! add {
+    ($a:expr, $b:expr) => { $a + $b }; //~ NOTE arm 1 expected `$b:expr`
+    (double $a:expr) => { $a * 2 }; //~ NOTE arm 2 expected `double`
+}
+
+fn main() {
+    add!(1,);
+    //~^ ERROR unexpected end of macro invocation
+    //~| NOTE arm 1 of `add!` got the furthest, expecting `$b:expr`
+}
@@ -0,0 +1,33 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An empty `vis` fragment leaves the item private.
+
+macro_rules! item {
+    ($v:vis fn $name:ident) => { $v fn $name() {} }
+}
+
+macro_rules! follow_vis {
+    ($v:vis ()) => {}; //~ ERROR `$v:vis` is followed by `(`
+    ($v:vis $e:expr) => {}; //~ ERROR `$v:vis` is followed by `$e:expr`
+    ($v:vis priv) => {}; //~ ERROR `$v:vis` is followed by `priv`
+    ($v:vis, $i:ident) => {};
+    ($v:vis $t:ty) => {};
+}
+
+mod a {
+    item!(pub fn public);
+    item!(fn private);
+}
+
+fn main() {
+    a::public();
+    a::private(); //~ ERROR function `private` is private
+}
@@ -0,0 +1,47 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z orbit -Z miri
+
+// The MIR interpreter lifts the restrictions on control flow in constants
+// and `const fn`s, but not those on calls and statics.
+
+#![feature(const_fn)]
+
+static LIMIT: u32 = 10;
+
+fn plain(x: u32) -> u32 { x }
+
+const fn count_to(n: u32) -> u32 {
+    let mut i = 0;
+    while i < n {
+        i = plain(i + 1); //~ ERROR calls in constant functions are limited
+    }
+    i
+}
+
+const fn limited() -> u32 {
+    let mut i = 0;
+    while i < LIMIT {
+        //~^ ERROR constant functions cannot refer to statics
+        //~| ERROR cannot refer to statics by value
+        i += 1;
+    }
+    i
+}
+
+const COUNT: u32 = plain(3); //~ ERROR calls in constants are limited
+const LIMIT_COPY: u32 = LIMIT;
+//~^ ERROR constants cannot refer to statics
+//~| ERROR cannot refer to statics by value
+
+fn main() {
+    println!("{} {} {} {}", count_to(3), limited(), COUNT, LIMIT_COPY);
+}
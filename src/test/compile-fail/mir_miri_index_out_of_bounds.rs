@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z orbit -Z miri
+
+#![feature(const_fn)]
+#![allow(const_err)]
+
+const fn lookup(index: usize) -> u32 {
+    let table = [1, 2, 3];
+    table[index] //~ ERROR array index out of bounds: the len is 3 but the index is 3
+}
+
+static LOOKUP: u32 = lookup(3);
+//~^ ERROR array index out of bounds: the len is 3 but the index is 3
+
+fn main() {
+    println!("{}", LOOKUP);
+}
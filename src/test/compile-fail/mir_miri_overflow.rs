@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z orbit -Z miri
+
+// Errors found by the MIR interpreter point at the offending statement,
+// even when it is inside a `const fn`.
+
+#![feature(const_fn)]
+#![allow(const_err)]
+
+const fn sum_to(n: u8) -> u8 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < n {
+        i += 1;
+        sum += i; //~ ERROR attempted to add with overflow
+    }
+    sum
+}
+
+static SUM: u8 = sum_to(30);
+//~^ ERROR attempted to add with overflow
+
+fn main() {
+    println!("{}", SUM);
+}
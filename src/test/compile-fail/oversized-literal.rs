@@ -9,5 +9,5 @@
 // except according to those terms.
 
 fn main() {
-    println!("{}", 18446744073709551616u64);  //~ error: int literal is too large
+    println!("{}", 340282366920938463463374607431768211456u64);  //~ error: int literal is too large
 }
@@ -0,0 +1,27 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z type-trace
+
+// Check that `-Z type-trace` explains where the types of a mismatch
+// were inferred.
+
+fn main() {
+    let mut x = None;
+    //~^ NOTE the type `std::option::Option<u32>` was inferred for this
+    //~| NOTE the type `u32` was inferred for this
+    x = Some(1u32);
+    //~^ NOTE ...because it was required to be `u32` here
+    let _s: Option<String> = x;
+    //~^ ERROR mismatched types
+    //~| expected type `std::option::Option<std::string::String>`
+    //~| found type `std::option::Option<u32>`
+    //~| NOTE expected struct `std::string::String`, found u32
+}
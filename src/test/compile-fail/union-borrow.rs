@@ -0,0 +1,41 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(untagged_unions)]
+
+#[derive(Clone, Copy)]
+union U {
+    a: u8,
+    b: u64,
+}
+
+fn main() {
+    unsafe {
+        let mut u = U { b: 0 };
+        // Mutable borrow of one field conflicts with borrows of every other field
+        {
+            let ra = &mut u.a;
+            let rb = &u.b; //~ ERROR cannot borrow `u.b` as immutable
+        }
+        {
+            let ra = &u.a;
+            let rb = &mut u.b; //~ ERROR cannot borrow `u.b` as mutable
+        }
+        {
+            let ra = &u.a;
+            let rb = &u.b; // OK
+        }
+        // Assigning to any field is forbidden while a field is borrowed
+        {
+            let ra = &u.a;
+            u.b = 1; //~ ERROR cannot assign to `u.b`
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(untagged_unions)]
+#![deny(improper_ctypes)]
+
+union W {
+    a: u8,
+    b: u32,
+}
+
+#[repr(C)]
+union U {
+    a: u8,
+    b: u32,
+}
+
+#[repr(C)]
+union BadField {
+    a: u8,
+    b: char,
+}
+
+extern {
+    fn take_w(w: W); //~ ERROR found union without foreign-function-safe
+    fn take_u(u: U); // OK
+    fn take_bad(b: BadField); //~ ERROR found non-foreign-function-safe member in union
+}
+
+fn main() {}
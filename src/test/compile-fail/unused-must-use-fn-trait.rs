@@ -0,0 +1,90 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `#[must_use]` on functions, methods and traits.
+
+#![feature(conservative_impl_trait)]
+#![deny(unused_must_use)]
+#![allow(dead_code)]
+
+#[must_use]
+fn checked(x: u32) -> Option<u32> { Some(x) }
+
+#[must_use = "the sum is returned"]
+fn add(x: u32, y: u32) -> u32 { x + y }
+
+#[must_use]
+fn is_even(x: u32) -> bool { x % 2 == 0 }
+
+fn plain() -> u32 { 0 }
+
+// Warns once, for the function rather than for `Result`.
+#[must_use]
+fn parse(x: &str) -> Result<u32, ()> { x.parse().map_err(|_| ()) }
+
+struct Builder;
+
+impl Builder {
+    #[must_use = "builders do nothing unless built"]
+    fn name(self, _: &str) -> Builder { self }
+}
+
+#[must_use = "futures do nothing unless polled"]
+trait Future {
+    fn poll(&mut self);
+}
+
+impl Future for () {
+    fn poll(&mut self) {}
+}
+
+fn boxed() -> Box<Future> { Box::new(()) }
+
+fn borrowed(f: &mut ()) -> &mut Future { f }
+
+trait Parse {
+    fn parse_next(&mut self) -> u32;
+}
+
+struct Tokens;
+
+impl Parse for Tokens {
+    #[must_use = "the token is consumed"]
+    fn parse_next(&mut self) -> u32 { 0 }
+}
+
+fn parse_generic<P: Parse>(p: &mut P) {
+    p.parse_next();
+}
+
+fn anon() -> impl Future { () }
+
+fn main() {
+    checked(1); //~ ERROR unused return value of `checked` which must be used
+    add(1, 2); //~ ERROR unused return value of `add` which must be used: the sum is returned
+    is_even(2); //~ ERROR unused return value of `is_even` which must be used
+    plain();
+    parse("1"); //~ ERROR unused return value of `parse` which must be used
+
+    Builder.name("a"); //~ ERROR unused return value of `Builder::name` which must be used
+    Builder::name(Builder, "b"); //~ ERROR unused return value of `Builder::name`
+
+    boxed(); //~ ERROR unused boxed `Future` trait object which must be used: futures do nothing
+    borrowed(&mut ()); //~ ERROR unused borrowed `Future` trait object which must be used
+    anon(); //~ ERROR unused implementer of `Future` which must be used: futures do nothing
+
+    Tokens.parse_next(); //~ ERROR which must be used: the token is consumed
+    Parse::parse_next(&mut Tokens); //~ ERROR which must be used: the token is consumed
+    parse_generic(&mut Tokens);
+
+    let _ = checked(1);
+    let _ = boxed();
+    boxed().poll();
+}
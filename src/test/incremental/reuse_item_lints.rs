@@ -0,0 +1,49 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that an item whose results are reused still gets the warnings
+// that analyzing it again would report, and that the `mut` bindings
+// that borrow checking found to be used are not reported as unused.
+
+// revisions: rpass1 rpass2
+
+#![feature(rustc_attrs)]
+
+fn main() {
+    assert_eq!(x::x(), 22);
+    assert_eq!(y::y(), 23);
+}
+
+mod x {
+    #[cfg(rpass1)]
+    pub fn x() -> u32 {
+        22
+    }
+
+    #[cfg(rpass2)]
+    pub fn x() -> u32 {
+        11 * 2
+    }
+}
+
+mod y {
+    use x;
+
+    #[rustc_clean(label="TypeckItemBody", cfg="rpass2")]
+    #[rustc_clean(label="BorrowCheck", cfg="rpass2")]
+    pub fn y() -> u32 {
+        let mut y = x::x();
+        y += 1;
+        return y;
+        y + 1
+        //[rpass1]~^ WARN unreachable expression
+        //[rpass2]~^^ WARN unreachable expression
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the type-checking results and MIR of an item whose inputs
+// are unchanged are reused, and that those of a changed item are not.
+
+// revisions: rpass1 rpass2
+
+#![allow(warnings)]
+#![feature(rustc_attrs)]
+
+fn main() {
+    assert_eq!(x::x(), 22);
+    assert_eq!(y::y(), 23);
+    assert_eq!(z::z(), 3);
+}
+
+mod x {
+    #[cfg(rpass1)]
+    pub fn x() -> u32 {
+        22
+    }
+
+    #[cfg(rpass2)]
+    #[rustc_dirty(label="TypeckItemBody", cfg="rpass2")]
+    #[rustc_dirty(label="MirMapConstruction", cfg="rpass2")]
+    pub fn x() -> u32 {
+        11 * 2
+    }
+}
+
+mod y {
+    use x;
+
+    #[rustc_clean(label="TypeckItemBody", cfg="rpass2")]
+    #[rustc_clean(label="MirMapConstruction", cfg="rpass2")]
+    #[rustc_clean(label="BorrowCheck", cfg="rpass2")]
+    pub fn y() -> u32 {
+        x::x() + 1
+    }
+}
+
+mod z {
+    #[rustc_clean(label="TypeckItemBody", cfg="rpass2")]
+    #[rustc_clean(label="MirMapConstruction", cfg="rpass2")]
+    #[rustc_clean(label="BorrowCheck", cfg="rpass2")]
+    pub fn z() -> u32 {
+        let v = vec![1, 2];
+        v.iter().fold(0, |a, b| a + b)
+    }
+}
@@ -11,6 +11,6 @@
 // compile-flags: -Z parse-only
 
 fn main() {
-    let __isize = 0xff_ffff_ffff_ffff_ffff;
+    let __isize = 0xff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff;
     //~^ ERROR int literal is too large
 }
@@ -24,10 +24,12 @@ use syntax::print::pprust;
 fn main() {
     let ps = syntax::parse::ParseSess::new();
     let mut feature_gated_cfgs = vec![];
+    let mut resolver = syntax::ext::macro_scope::DummyMacroResolver;
     let mut cx = syntax::ext::base::ExtCtxt::new(
         &ps, vec![],
         syntax::ext::expand::ExpansionConfig::default("qquote".to_string()),
-        &mut feature_gated_cfgs);
+        &mut feature_gated_cfgs,
+        &mut resolver);
     cx.bt_push(syntax::codemap::ExpnInfo {
         call_site: DUMMY_SP,
         callee: syntax::codemap::NameAndSpan {
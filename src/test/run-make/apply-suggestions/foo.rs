@@ -0,0 +1,22 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn square(x: &u32) -> u32 {
+    x * x
+}
+
+fn main() {
+    let mut a = 3;
+    let b: u32 = &a;
+    let c = square(b);
+    if (c == 9) {
+        println!("{}", c);
+    }
+}
@@ -238,14 +238,15 @@ fn compile_program(input: &str, sysroot: PathBuf)
 
         let krate = panictry!(driver::phase_1_parse_input(&sess, cfg, &input));
 
-        let krate = driver::phase_2_configure_and_expand(&sess, &cstore, krate, &id, None)
-            .expect("phase_2 returned `None`");
+        let (krate, macro_scopes) =
+            driver::phase_2_configure_and_expand(&sess, &cstore, krate, &id, None)
+                .expect("phase_2 returned `None`");
 
         let krate = driver::assign_node_ids(&sess, krate);
         let mut defs = ast_map::collect_definitions(&krate);
         read_local_crates(&sess, &cstore, &defs, &krate, &id, &dep_graph);
         let (analysis, resolutions, mut hir_forest) = {
-            driver::lower_and_resolve(&sess, &id, &mut defs, &krate,
+            driver::lower_and_resolve(&sess, &id, &mut defs, &krate, macro_scopes,
                                       &sess.dep_graph, MakeGlobMap::No)
         };
 
@@ -0,0 +1,25 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub struct Padded {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+pub struct Wrapper<T> {
+    inner: T,
+}
+
+fn main() {
+    let p = Padded { a: 1, b: 2, c: 3 };
+    let w = Wrapper { inner: 4u16 };
+    println!("{} {} {} {}", p.a, p.b, p.c, w.inner);
+}
@@ -0,0 +1,39 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(decl_macro)]
+
+pub mod math {
+    pub fn double(x: u32) -> u32 {
+        x * 2
+    }
+
+    pub macro quadruple($x:expr) {
+        double(double($x))
+    }
+}
+
+pub mod reexports {
+    pub use math::*;
+}
+
+mod detail {
+    pub fn triple(x: u32) -> u32 {
+        x * 3
+    }
+}
+
+pub macro some($x:expr) {
+    Some($x)
+}
+
+pub macro triple($x:expr) {
+    detail::triple($x)
+}
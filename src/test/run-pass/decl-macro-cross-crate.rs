@@ -0,0 +1,25 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:decl_macro.rs
+
+#![feature(decl_macro)]
+
+extern crate decl_macro;
+
+use decl_macro::math::quadruple;
+
+fn main() {
+    assert_eq!(decl_macro::math::quadruple!(1), 4);
+    assert_eq!(quadruple!(2), 8);
+    assert_eq!(decl_macro::some!(1), Some(1));
+    assert_eq!(decl_macro::reexports::quadruple!(3), 12);
+    assert_eq!(decl_macro::triple!(2), 6);
+}
@@ -0,0 +1,64 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(decl_macro)]
+
+mod math {
+    fn double(x: u32) -> u32 {
+        x * 2
+    }
+
+    // Resolved in `math`, so the private `double` can be called from anywhere.
+    pub macro quadruple($x:expr) {
+        double(double($x))
+    }
+
+    pub mod nested {
+        pub macro octuple($x:expr) {
+            super::quadruple!(super::quadruple!($x) / 2)
+        }
+    }
+}
+
+use math::quadruple;
+
+macro min {
+    ($x:expr) => { $x },
+    ($x:expr, $($y:expr),+) => { ::std::cmp::min($x, min!($($y),+)) }
+}
+
+// The items defined by a `macro` item do not clash with those of the call site.
+macro define_helper() {
+    fn helper() -> u32 { 1 }
+
+    pub fn call_helper() -> u32 { helper() }
+}
+
+fn helper() -> u32 { 2 }
+
+mod helpers {
+    define_helper!();
+}
+
+// The names passed to the macro are those of the call site.
+macro define_fn($name:ident) {
+    fn $name() -> u32 { 3 }
+}
+
+define_fn!(three);
+
+fn main() {
+    assert_eq!(math::quadruple!(1), 4);
+    assert_eq!(quadruple!(2), 8);
+    assert_eq!(math::nested::octuple!(1), 8);
+    assert_eq!(min!(3, 1, 2), 1);
+    assert_eq!(helper(), 2);
+    assert_eq!(three(), 3);
+}
@@ -0,0 +1,66 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![feature(i128_type)]
+
+const SHIFTED: i128 = -(1 << 100);
+const DIVIDED: i128 = SHIFTED / -(1 << 90);
+const MIN: i128 = -0x8000_0000_0000_0000_0000_0000_0000_0000;
+
+fn main() {
+    let x: i128 = -1;
+    assert_eq!(0, !x);
+    let y: i128 = -2;
+    assert_eq!(!1, y);
+    let z: i128 = 0xABCD_EF;
+    assert_eq!(z * z, 0x734C_C2F2_A521);
+    assert_eq!(z * z * z * z, 0x33EE_0E2A_54E2_59DA_A0E7_8E41);
+    assert_eq!(-z * -z, 0x734C_C2F2_A521);
+    assert_eq!(-z * -z * -z * -z, 0x33EE_0E2A_54E2_59DA_A0E7_8E41);
+    assert_eq!(-z + -z + -z + -z, -0x2AF3_7BC);
+    let k: i128 = -0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210;
+    assert_eq!(k as u128, 0xFEDC_BA98_7654_3210_0123_4567_89AB_CDF0);
+    assert_eq!(k + k, -0x0246_8ACF_1357_9BDF_FDB9_7530_ECA8_6420);
+    assert_eq!(0, k - k);
+    assert_eq!(k / 42, -0x0006_EF5D_E4CD_3BC2_AAA3_BB4C_C5DD_6EE8);
+    assert_eq!(0, k % 42);
+    assert_eq!(-k, k / -1);
+    assert_eq!(-0x91_A2B3_C4D5_E6F8, k >> 65);
+    assert_eq!(0x0246_8ACF_1357_9BE0_0000_0000_0000_0000, k << 65);
+    assert!(k < z);
+    assert!(y > k);
+    assert!(y < x);
+    assert_eq!(x as i64, -1);
+    assert_eq!(z as i64, 0xABCD_EF);
+    assert_eq!(k as i64, 0x0123_4567_89AB_CDF0);
+    assert_eq!((-z as f64) as i128, -z);
+    assert_eq!((-z as f32) as i128, -z);
+    assert_eq!((-z as f64 * 16.0) as i128, -z * 16);
+    assert_eq!((-z as f32 * 16.0) as i128, -z * 16);
+    assert_eq!(SHIFTED >> 100, -1);
+    assert_eq!(DIVIDED, 1 << 10);
+    assert_eq!(i128::min_value().checked_div(-1), None);
+    assert_eq!(i128::max_value().checked_mul(2), None);
+    assert_eq!(i128::min_value().wrapping_sub(1), i128::max_value());
+    assert_eq!(MIN, i128::min_value());
+    assert_eq!(-170141183460469231731687303715884105728, i128::min_value());
+    assert_eq!(170141183460469231731687303715884105727, i128::max_value());
+    // formatting
+    let j: i128 = -(1 << 67);
+    assert_eq!("-147573952589676412928", format!("{}", j));
+    assert_eq!("fffffffffffffff80000000000000000", format!("{:x}", j));
+    assert_eq!("3777777777777777777760000000000000000000000", format!("{:o}", j));
+    assert_eq!("-147573952589676412928", format!("{:?}", j));
+    assert_eq!("-170141183460469231731687303715884105728",
+               format!("{}", i128::min_value()));
+    assert_eq!("-147573952589676412928".parse::<i128>(), Ok(j));
+    // common traits
+    assert_eq!(x.clone(), x);
+    assert_eq!(i128::default(), 0);
+}
@@ -0,0 +1,28 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(conservative_impl_trait)]
+
+use std::thread;
+
+// Auto traits like `Send` are not declared on `impl Trait`,
+// they leak through from the concrete type.
+fn numbers() -> impl Iterator<Item=u32> {
+    vec![1, 2, 3].into_iter()
+}
+
+fn is_send<T: Send>(_: &T) {}
+
+fn main() {
+    let it = numbers();
+    is_send(&it);
+    let sum = thread::spawn(move || it.sum::<u32>()).join().unwrap();
+    assert_eq!(sum, 6);
+}
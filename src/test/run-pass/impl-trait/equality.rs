@@ -0,0 +1,47 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(conservative_impl_trait)]
+
+trait Foo: std::fmt::Debug + Eq {}
+
+impl<T: std::fmt::Debug + Eq> Foo for T {}
+
+fn hide<T: Foo>(x: T) -> impl Foo {
+    x
+}
+
+fn evens(limit: u32) -> impl Iterator<Item=u32> {
+    (0..limit).filter(|x| x % 2 == 0)
+}
+
+fn adder(a: u32) -> impl Fn(u32) -> u32 {
+    move |b| a + b
+}
+
+fn first<'a>(xs: &'a [u32]) -> impl Iterator<Item=&'a u32> + 'a {
+    xs.iter().take(1)
+}
+
+struct Counter(u32);
+
+impl Counter {
+    fn upto(&self) -> impl Iterator<Item=u32> {
+        0..self.0
+    }
+}
+
+fn main() {
+    assert_eq!(hide(5), hide(5));
+    assert_eq!(evens(10).collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+    assert_eq!(adder(2)(3), 5);
+    assert_eq!(first(&[7, 8, 9]).cloned().collect::<Vec<_>>(), [7]);
+    assert_eq!(Counter(3).upto().sum::<u32>(), 3);
+}
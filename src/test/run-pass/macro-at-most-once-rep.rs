@@ -0,0 +1,48 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(macro_at_most_once_rep)]
+
+// An optional trailing comma.
+macro_rules! sum {
+    ($($x:expr),* $(,)?) => (0 $(+ $x)*);
+}
+
+// An optional clause, transcribed only if it was matched.
+macro_rules! make_fn {
+    ($name:ident $(-> $ret:ty)? { $body:expr }) => {
+        fn $name() $(-> $ret)? { $body }
+    };
+}
+
+// `?` followed by `*` or `+` is still a separator.
+macro_rules! count {
+    ($($x:ident)?*) => (0 $(+ { let _ = stringify!($x); 1 })*);
+}
+
+macro_rules! is_some {
+    ($($x:ident)?) => (false $(|| { let _ = stringify!($x); true })?);
+}
+
+make_fn!(unit { () });
+make_fn!(answer -> u32 { 42 });
+
+fn main() {
+    assert_eq!(sum!(1, 2, 3), 6);
+    assert_eq!(sum!(1, 2, 3,), 6);
+    assert_eq!(sum!(), 0);
+    assert_eq!(unit(), ());
+    assert_eq!(answer(), 42);
+    assert_eq!(count!(a ? b ? c), 3);
+    assert_eq!(count!(), 0);
+    assert!(is_some!(x));
+    assert!(!is_some!());
+}
@@ -0,0 +1,37 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+macro_rules! first {
+    ($lt:lifetime) => {
+        fn first<$lt>(s: &$lt str) -> &$lt str {
+            s.split(' ').next().unwrap()
+        }
+    }
+}
+
+macro_rules! wrapper {
+    ($name:ident<$lt:lifetime> $($rest:tt)*) => {
+        struct $name<$lt> { inner: &$lt str }
+        impl<$lt> $name<$lt> {
+            fn get(&self) -> &$lt str { self.inner }
+        }
+        $($rest)*
+    }
+}
+
+first!('a);
+wrapper!(Wrapper<'b> fn unused() {});
+
+fn main() {
+    let s = String::from("hello world");
+    assert_eq!(first(&s), "hello");
+    let w = Wrapper { inner: first(&s) };
+    assert_eq!(w.get(), "hello");
+}
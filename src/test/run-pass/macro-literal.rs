@@ -0,0 +1,67 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+macro_rules! lit {
+    ($l:literal) => { $l }
+}
+
+macro_rules! classify {
+    ($($l:literal => $name:expr),*) => {
+        fn classify(x: i32) -> &'static str {
+            match x {
+                $($l => $name,)*
+                _ => "other",
+            }
+        }
+    }
+}
+
+macro_rules! documented {
+    ($doc:literal $name:ident) => {
+        #[doc = $doc]
+        fn $name() -> &'static str { $doc }
+    }
+}
+
+macro_rules! abs {
+    ($l:literal) => { $l.abs() }
+}
+
+macro_rules! describe {
+    ($l:literal) => { "a literal" };
+    ($e:expr) => { "an expression" };
+}
+
+classify!(-1 => "minus one", 0 => "zero", 1 => "one");
+documented!("Returns its own documentation." doc);
+
+fn main() {
+    assert_eq!(lit!(1u8), 1);
+    assert_eq!(lit!(-2), -2);
+    assert_eq!(lit!(-2.5f64), -2.5);
+    assert_eq!(lit!("str"), "str");
+    assert_eq!(lit!(b'x'), b'x');
+    assert_eq!(lit!(true), true);
+
+    // A negative literal is a single operand.
+    assert_eq!(abs!(-2i32), 2);
+    assert_eq!(abs!(-2.5f64), 2.5);
+
+    assert_eq!(classify(-1), "minus one");
+    assert_eq!(classify(1), "one");
+    assert_eq!(classify(7), "other");
+
+    assert_eq!(doc(), "Returns its own documentation.");
+
+    assert_eq!(describe!(42), "a literal");
+    assert_eq!(describe!(-42), "a literal");
+    assert_eq!(describe!(x), "an expression");
+    assert_eq!(describe!(-x), "an expression");
+}
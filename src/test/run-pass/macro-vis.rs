@@ -0,0 +1,56 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_restricted)]
+
+macro_rules! getter {
+    ($v:vis fn $name:ident -> $t:ty = $e:expr) => {
+        $v fn $name() -> $t { $e }
+    }
+}
+
+macro_rules! record {
+    ($v:vis struct $name:ident { $($fv:vis $field:ident: $t:ty),* }) => {
+        $v struct $name { $($fv $field: $t),* }
+    }
+}
+
+macro_rules! forward {
+    ($v:vis $name:ident) => { getter!($v fn $name -> u32 = 3); }
+}
+
+macro_rules! vis_str {
+    ($v:vis) => { stringify!($v) }
+}
+
+mod a {
+    getter!(pub fn one -> u32 = 1);
+    getter!(pub(crate) fn two -> u32 = 2);
+    getter!(fn private -> u32 = 0);
+    forward!(pub three);
+
+    record!(pub struct Point { pub x: i32, pub(crate) y: i32, z: i32 });
+
+    pub fn point() -> Point {
+        Point { x: 1, y: 2, z: private() as i32 }
+    }
+}
+
+fn main() {
+    assert_eq!(a::one(), 1);
+    assert_eq!(a::two(), 2);
+    assert_eq!(a::three(), 3);
+
+    let p = a::point();
+    assert_eq!((p.x, p.y), (1, 2));
+
+    assert_eq!(vis_str!(pub), "pub");
+    assert_eq!(vis_str!(), "");
+}
@@ -0,0 +1,87 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z orbit -Z miri
+
+// Test constants and statics whose `const fn`s use loops, matches,
+// mutable locals and struct construction, none of which the HIR-based
+// constant evaluator supports.
+
+#![feature(const_fn)]
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Shape {
+    Square(u32),
+    Rect { w: u32, h: u32 },
+    Empty,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Stats {
+    count: u32,
+    sum: u64,
+}
+
+const fn area(shape: Shape) -> u32 {
+    match shape {
+        Shape::Square(side) => side * side,
+        Shape::Rect { w, h } => w * h,
+        Shape::Empty => 0,
+    }
+}
+
+const fn crc8(byte: u8) -> u8 {
+    let mut crc = byte;
+    let mut i = 0;
+    while i < 8 {
+        crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        i += 1;
+    }
+    crc
+}
+
+const fn crc8_table() -> [u8; 16] {
+    let mut table = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        table[i] = crc8(i as u8);
+        i += 1;
+    }
+    table
+}
+
+const fn stats(n: u32) -> Stats {
+    let mut stats = Stats { count: 0, sum: 0 };
+    while stats.count < n {
+        stats.count += 1;
+        stats.sum += stats.count as u64;
+    }
+    stats
+}
+
+const fn fib(n: u64) -> u64 {
+    if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+}
+
+const AREAS: [u32; 3] = [area(Shape::Square(3)), area(Shape::Rect { w: 2, h: 5 }),
+                         area(Shape::Empty)];
+const STATS: Stats = stats(10);
+const FIB: u64 = fib(20);
+static TABLE: [u8; 16] = crc8_table();
+
+fn main() {
+    assert_eq!(AREAS, [9, 10, 0]);
+    assert_eq!(STATS, Stats { count: 10, sum: 55 });
+    assert_eq!(FIB, 6765);
+    for i in 0..16 {
+        assert_eq!(TABLE[i], crc8(i as u8));
+    }
+    assert_eq!(TABLE[1], 0x07);
+}
@@ -0,0 +1,91 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test MIR inlining of small functions.
+
+// compile-flags: -Z mir-opt-level=2
+// ignore-emscripten no threads support
+
+#![feature(rustc_attrs)]
+
+use std::sync::atomic::{AtomicUsize, ATOMIC_USIZE_INIT, Ordering};
+use std::thread;
+
+static DROPS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn fact(n: u32) -> u32 {
+    if n == 0 { 1 } else { n * fact(n - 1) }
+}
+
+#[inline(never)]
+fn never(a: i32) -> i32 {
+    a * 2
+}
+
+fn swap(pair: &mut (i32, i32)) {
+    let t = pair.0;
+    pair.0 = pair.1;
+    pair.1 = t;
+}
+
+struct Noisy;
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn consume(noisy: Noisy, fail: bool) -> usize {
+    if fail {
+        panic!("failing");
+    }
+    drop(noisy);
+    DROPS.load(Ordering::SeqCst)
+}
+
+#[rustc_mir]
+fn calls(x: i32) -> i32 {
+    add(add(x, 1), never(x))
+}
+
+#[rustc_mir]
+fn recursion(n: u32) -> u32 {
+    fact(n) + fact(1)
+}
+
+#[rustc_mir]
+fn mutation() -> (i32, i32) {
+    let mut pair = (1, 2);
+    swap(&mut pair);
+    pair
+}
+
+#[rustc_mir]
+fn drops(fail: bool) -> usize {
+    let _outer = Noisy;
+    consume(Noisy, fail)
+}
+
+fn main() {
+    assert_eq!(calls(3), 10);
+    assert_eq!(recursion(4), 25);
+    assert_eq!(mutation(), (2, 1));
+
+    assert_eq!(drops(false), 1);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+    // Both values are dropped when unwinding out of the inlined body.
+    assert!(thread::spawn(|| drops(true)).join().is_err());
+    assert_eq!(DROPS.load(Ordering::SeqCst), 4);
+}
@@ -0,0 +1,64 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test constant and copy propagation and dead store elimination.
+
+// compile-flags: -Z mir-opt-level=2
+
+#![feature(rustc_attrs)]
+
+#[rustc_mir]
+fn folded() -> i32 {
+    let a = 2i32;
+    let b = a * 3;
+    let c = -b + 10;
+    if c == 4 { c + b } else { 0 }
+}
+
+#[rustc_mir]
+fn not_folded(x: u8) -> u8 {
+    // Overflow is left to happen at runtime.
+    let a = 200u8;
+    a.wrapping_add(x) / (x - 1)
+}
+
+#[rustc_mir]
+fn reassigned(x: i32) -> i32 {
+    let mut a = 1;
+    if x > 0 {
+        a = x;
+    }
+    a + 1
+}
+
+#[rustc_mir]
+fn copies(x: (i32, i32), mut y: i32) -> i32 {
+    let t = y;
+    y = 5;
+    let r = &mut y;
+    *r += x.0;
+    t + x.1 + *r
+}
+
+#[rustc_mir]
+fn strings(s: String) -> usize {
+    let t = s;
+    let u = t;
+    u.len()
+}
+
+fn main() {
+    assert_eq!(folded(), 10);
+    assert_eq!(not_folded(2), 202);
+    assert_eq!(reassigned(0), 2);
+    assert_eq!(reassigned(5), 6);
+    assert_eq!(copies((1, 2), 3), 11);
+    assert_eq!(strings(String::from("abc")), 3);
+}
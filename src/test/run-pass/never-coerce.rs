@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a diverging expression can be coerced to any type, including
+// in positions where the type is inferred.
+
+#![feature(never_type)]
+
+fn get<T>() -> T {
+    panic!("no value")
+}
+
+fn main() {
+    let x: Result<u8, String> = Ok(1);
+    let y = match x {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    assert_eq!(y, 1);
+
+    let _: &str = if y == 1 { "one" } else { panic!() };
+
+    if y == 0 {
+        let z: ! = get();
+        let _: u64 = z;
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that we can use ! as an associated type, and as a type argument.
+
+#![feature(never_type)]
+
+trait Foo {
+    type Wow;
+
+    fn smeg(&self) -> Self::Wow;
+}
+
+struct Blah;
+impl Foo for Blah {
+    type Wow = !;
+    fn smeg(&self) -> ! {
+        panic!("kapow!");
+    }
+}
+
+fn never_direct(x: !) -> u32 {
+    x
+}
+
+fn never_ref(x: &!) -> u32 {
+    *x
+}
+
+fn never_result(r: Result<u32, !>) -> u32 {
+    match r {
+        Ok(x) => x,
+        Err(e) => e,
+    }
+}
+
+fn main() {
+    assert_eq!(never_result(Ok(23)), 23);
+
+    let v: Vec<!> = Vec::new();
+    assert_eq!(v.len(), 0);
+    assert_eq!(::std::mem::size_of::<!>(), 0);
+
+    if false {
+        never_direct(Blah.smeg());
+        never_ref(&Blah.smeg());
+    }
+
+    let x: u32 = if true {
+        5
+    } else {
+        return;
+    };
+    assert_eq!(x, 5);
+}
@@ -0,0 +1,74 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![feature(i128_type)]
+
+const SHIFTED: u128 = 3 << 100;
+const DIVIDED: u128 = SHIFTED / (1 << 90);
+const MAX: u128 = 0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF;
+
+fn main() {
+    let x: u128 = 0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF;
+    assert_eq!(x, u128::max_value());
+    assert_eq!(MAX, x);
+    assert_eq!(340282366920938463463374607431768211455, x);
+    assert_eq!(0, !x);
+    let y: u128 = 0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFE;
+    assert_eq!(!1, y);
+    assert_eq!(x, y | 1);
+    let z: u128 = 0xABCD_EF;
+    assert_eq!(z * z, 0x734C_C2F2_A521);
+    assert_eq!(z * z * z * z, 0x33EE_0E2A_54E2_59DA_A0E7_8E41);
+    assert_eq!(z + z + z + z, 0x2AF3_7BC);
+    let k: u128 = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210;
+    assert_eq!(k + k, 0x0246_8ACF_1357_9BDF_FDB9_7530_ECA8_6420);
+    assert_eq!(0, k - k);
+    assert_eq!(0x0123_4567_89AB_CDEF_FEDC_BA98_75A8_6421, k - z);
+    assert_eq!(k.wrapping_mul(z), 0xAAAA_AAAA_AA9D_AF96_5555_5555_5561_8CF0);
+    assert_eq!(0x0006_EF5D_E4CD_3BC2_AAA3_BB4C_C5DD_6EE8, k / 42);
+    assert_eq!(0, k % 42);
+    assert_eq!(15, z % 42);
+    assert_eq!(0x16_9DA8_020C_EC18, k % 0x3A_CBFE_49FF_24AC);
+    assert_eq!(0x91_A2B3_C4D5_E6F7, k >> 65);
+    assert_eq!(0xFDB9_7530_ECA8_6420_0000_0000_0000_0000, k << 65);
+    assert!(k > z);
+    assert!(y > k);
+    assert!(y < x);
+    assert_eq!(x as u64, !0);
+    assert_eq!(z as u64, 0xABCD_EF);
+    assert_eq!(k as u64, 0xFEDC_BA98_7654_3210);
+    assert_eq!((z as f64) as u128, z);
+    assert_eq!((z as f32) as u128, z);
+    assert_eq!((z as f64 * 16.0) as u128, z * 16);
+    assert_eq!((z as f32 * 16.0) as u128, z * 16);
+    let l: u128 = 432 << 100;
+    assert_eq!((l as f32) as u128, l);
+    assert_eq!((l as f64) as u128, l);
+    assert_eq!(SHIFTED >> 100, 3);
+    assert_eq!(DIVIDED, 3 << 10);
+    assert_eq!(x.checked_add(1), None);
+    assert_eq!(k.checked_mul(1 << 64), None);
+    assert_eq!(x.wrapping_add(1), 0);
+    assert_eq!(k.leading_zeros(), 7);
+    assert_eq!((1u128 << 100).trailing_zeros(), 100);
+    // formatting
+    let j: u128 = 1 << 67;
+    assert_eq!("147573952589676412928", format!("{}", j));
+    assert_eq!("80000000000000000", format!("{:x}", j));
+    assert_eq!("20000000000000000000000", format!("{:o}", j));
+    assert_eq!("10000000000000000000000000000000000000000000000000000000000000000000",
+               format!("{:b}", j));
+    assert_eq!("340282366920938463463374607431768211455",
+               format!("{}", u128::max_value()));
+    assert_eq!("147573952589676412928", format!("{:?}", j));
+    assert_eq!("340282366920938463463374607431768211455".parse::<u128>(), Ok(x));
+    // common traits
+    assert_eq!(x.clone(), x);
+    assert_eq!(u128::default(), 0);
+}
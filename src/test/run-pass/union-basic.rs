@@ -0,0 +1,94 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(untagged_unions)]
+
+use std::mem::{size_of, size_of_val, align_of, align_of_val};
+
+#[derive(Clone, Copy)]
+union U {
+    a: u8,
+}
+
+#[derive(Clone, Copy)]
+union U64 {
+    a: u64,
+}
+
+#[derive(Clone, Copy)]
+union W {
+    a: u8,
+    b: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+union Y {
+    f1: u16,
+    f2: [u8; 4],
+}
+
+static mut Z: W = W { b: 0xffff_ffff_ffff_ffff };
+const C: U64 = U64 { a: 11 };
+
+fn main() {
+    assert_eq!(size_of::<U>(), 1);
+    assert_eq!(size_of::<U64>(), 8);
+    assert_eq!(size_of::<W>(), 8);
+    assert_eq!(align_of::<U>(), 1);
+    assert_eq!(align_of::<U64>(), align_of::<u64>());
+    assert_eq!(align_of::<W>(), align_of::<u64>());
+    assert_eq!(size_of::<Y>(), 4);
+    assert_eq!(align_of::<Y>(), 2);
+
+    let u = U { a: 10 };
+    assert_eq!(size_of_val(&u), 1);
+    assert_eq!(align_of_val(&u), 1);
+    unsafe {
+        assert_eq!(u.a, 10);
+        let U { a } = u;
+        assert_eq!(a, 10);
+    }
+
+    let mut w = W { b: 0 };
+    unsafe {
+        assert_eq!(w.a, 0);
+        assert_eq!(w.b, 0);
+        w.a = 1;
+        assert_eq!(w.a, 1);
+        assert_eq!(w.b.to_le(), 1);
+        let W { a } = w;
+        assert_eq!(a, 1);
+        let W { b } = w;
+        assert_eq!(b.to_le(), 1);
+        match w {
+            W { a: 0 } => panic!(),
+            W { b: 1 } => {}
+            W { a: _ } => panic!(),
+        }
+    }
+
+    let y = Y { f1: 0x1234 };
+    unsafe {
+        assert_eq!(y.f1, 0x1234);
+        if cfg!(target_endian = "little") {
+            assert_eq!(&y.f2[..2], &[0x34, 0x12]);
+        } else {
+            assert_eq!(&y.f2[..2], &[0x12, 0x34]);
+        }
+    }
+
+    unsafe {
+        assert_eq!(Z.a, 0xff);
+        Z.a = 0;
+        assert_eq!(Z.b.to_le(), 0xffff_ffff_ffff_ff00);
+        assert_eq!(C.a, 11);
+    }
+}
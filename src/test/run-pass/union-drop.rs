@@ -0,0 +1,47 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Union fields are never dropped, no matter which one was written last.
+
+#![feature(untagged_unions)]
+
+static mut DROPPED: bool = false;
+
+struct S;
+
+impl Drop for S {
+    fn drop(&mut self) {
+        unsafe { DROPPED = true; }
+    }
+}
+
+union U {
+    a: u8,
+    s: S,
+}
+
+fn main() {
+    unsafe {
+        {
+            let u = U { s: S };
+        }
+        assert!(!DROPPED);
+        {
+            let mut u = U { a: 0 };
+            u.s = S;
+        }
+        assert!(!DROPPED);
+        {
+            let u = U { s: S };
+            let s = u.s;
+        }
+        assert!(DROPPED);
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(untagged_unions)]
+
+use std::mem::size_of;
+
+#[derive(Clone, Copy)]
+union MaybeUninit<T: Copy> {
+    uninit: (),
+    value: T,
+}
+
+impl<T: Copy> MaybeUninit<T> {
+    fn new(value: T) -> Self {
+        MaybeUninit { value: value }
+    }
+
+    fn empty() -> Self {
+        MaybeUninit { uninit: () }
+    }
+
+    unsafe fn get(&self) -> T {
+        self.value
+    }
+}
+
+fn main() {
+    assert_eq!(size_of::<MaybeUninit<u32>>(), 4);
+    assert_eq!(size_of::<MaybeUninit<(u8, u64)>>(), size_of::<(u8, u64)>());
+
+    let _ = MaybeUninit::<u64>::empty();
+    let x = MaybeUninit::new((1u8, 2u64));
+    unsafe {
+        assert_eq!(x.get(), (1, 2));
+    }
+}